@@ -0,0 +1,606 @@
+use std::fs;
+use std::fs::{File, OpenOptions, read_to_string};
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use serde_json::Value;
+use crate::cache::{CacheEntry, CacheMetadata, CacheStore, CacheValidators};
+
+/// Suffix of the file next to each entry recording when it was written, e.g.
+/// `steampowered/appdetails/0/730/730.json.meta`.
+pub const METADATA_FILE_SUFFIX: &str = ".meta";
+/// Suffix of the lock file held while an entry is written, e.g. `ISteamApps-GetAppList-v2.json.lock`.
+pub const LOCK_FILE_SUFFIX: &str = ".lock";
+/// Suffix of the temporary files entries are written to before being renamed into place.
+pub const TEMP_FILE_SUFFIX: &str = ".tmp";
+/// Suffix of entries compressed with `CacheCompression::Gzip`, e.g. `ISteamApps-GetAppList-v2.json.gz`.
+pub const GZIP_FILE_SUFFIX: &str = ".gz";
+/// Suffix of entries compressed with `CacheCompression::Zstd`, e.g. `ISteamApps-GetAppList-v2.json.zst`.
+pub const ZSTD_FILE_SUFFIX: &str = ".zst";
+/// How long a write waits for another writer's lock before giving up.
+pub const DEFAULT_LOCK_TIMEOUT: Duration = Duration::from_secs(10);
+/// Age after which a lock is considered left behind by a crashed writer and removed. Shorter than
+/// `DEFAULT_LOCK_TIMEOUT`, so a write waiting on such a lock takes it over instead of failing.
+pub const STALE_LOCK_AGE: Duration = Duration::from_secs(5);
+
+const LOCK_RETRY_INTERVAL: Duration = Duration::from_millis(10);
+
+static TEMP_FILE_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// Suffixes an entry may be stored under, uncompressed first.
+const COMPRESSION_SUFFIXES: [&str; 3] = ["", ZSTD_FILE_SUFFIX, GZIP_FILE_SUFFIX];
+
+/// How `FilesystemCache` compresses the entries it writes. Entries are read whatever their
+/// compression, so a cache directory may mix plain `.json` files, such as the prebuilt appdetails
+/// cache, with compressed ones.
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default)]
+pub enum CacheCompression {
+    /// Plain files, the layout the SDK has always used.
+    #[default]
+    None,
+    /// `<key>.gz` files, available with the `gzip` feature.
+    #[cfg(feature = "gzip")]
+    Gzip,
+    /// `<key>.zst` files, available with the `zstd` feature. Smaller and faster than gzip.
+    #[cfg(feature = "zstd")]
+    Zstd,
+}
+
+impl CacheCompression {
+    /// Suffix appended to the key of entries compressed this way.
+    pub fn get_file_suffix(&self) -> &'static str {
+        match self {
+            CacheCompression::None => "",
+            #[cfg(feature = "gzip")]
+            CacheCompression::Gzip => GZIP_FILE_SUFFIX,
+            #[cfg(feature = "zstd")]
+            CacheCompression::Zstd => ZSTD_FILE_SUFFIX,
+        }
+    }
+
+    fn compress(&self, bytes: &[u8]) -> Result<Vec<u8>, String> {
+        match self {
+            CacheCompression::None => Ok(bytes.to_vec()),
+            #[cfg(feature = "gzip")]
+            CacheCompression::Gzip => {
+                let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+                let boxed_write = encoder.write_all(bytes);
+                if boxed_write.is_err() {
+                    return Err(boxed_write.err().unwrap().to_string());
+                }
+                encoder.finish().map_err(|error| error.to_string())
+            },
+            #[cfg(feature = "zstd")]
+            CacheCompression::Zstd => zstd::encode_all(bytes, 0).map_err(|error| error.to_string()),
+        }
+    }
+}
+
+/// Stores each entry as a file named by its key under the root directory, the layout the SDK has
+/// always used: `steam-webapi-cache/ISteamApps-GetAppList-v2.json`,
+/// `steam-webapi-cache/steampowered/appdetails/0/730/730.json` and so on. Existing cache
+/// directories, including the prebuilt appdetails archive, are read as is.
+///
+/// The time each entry was written, along with the `ETag`/`Last-Modified` validators of the
+/// response, is kept in a small `.meta` file next to it, so copying the cache directory around does
+/// not make old responses look fresh. Entries without one fall back to the modification time of
+/// the file.
+///
+/// Writes are crash-safe: the body goes to a temporary file which is synced and then renamed over
+/// the entry, so readers see either the old or the new body, never a truncated one. Writers of the
+/// same key, in this or other processes, take turns through an advisory `.lock` file next to the
+/// entry. Entries that are not valid UTF-8 are removed when read. When and with which validators an
+/// entry was written is kept in a `.meta` file, written after the body; a `.meta` file older than
+/// the body is ignored, so an interrupted write never pairs a new body with the old validators.
+///
+/// With `with_compression` entries are written gzip or zstd compressed, as `<key>.gz` or
+/// `<key>.zst`. Keys stay the same and plain files are still read, so compression can be turned on
+/// for an existing cache; each entry is compressed the next time it is written.
+///
+/// # Examples
+///
+/// ```
+/// use steam_webapi_rust_sdk::cache::{CacheStore, FilesystemCache};
+///
+/// let cache_dir = std::env::temp_dir().join("steam-webapi-rust-sdk-filesystem-cache-example");
+/// let cache = FilesystemCache::new(cache_dir.to_str().unwrap());
+///
+/// cache.put("steampowered/appdetails/0/730/730.json", "{}").unwrap();
+///
+/// let entry = cache.get("steampowered/appdetails/0/730/730.json").unwrap().unwrap();
+/// assert_eq!("{}", entry.body);
+/// assert!(cache_dir.join("steampowered/appdetails/0/730/730.json").is_file());
+/// ```
+#[derive(Clone, Debug)]
+pub struct FilesystemCache {
+    root: PathBuf,
+    lock_timeout: Duration,
+    compression: CacheCompression,
+}
+
+/// Advisory lock on a cache entry, released when dropped.
+#[derive(Debug)]
+struct FilesystemCacheLock {
+    path: PathBuf,
+}
+
+impl Drop for FilesystemCacheLock {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(self.path.as_path());
+    }
+}
+
+impl FilesystemCache {
+    pub fn new(root: &str) -> FilesystemCache {
+        FilesystemCache {
+            root: PathBuf::from(root),
+            lock_timeout: DEFAULT_LOCK_TIMEOUT,
+            compression: CacheCompression::None,
+        }
+    }
+
+    /// Sets how long a write waits for another writer of the same entry.
+    pub fn with_lock_timeout(mut self, lock_timeout: Duration) -> FilesystemCache {
+        self.lock_timeout = lock_timeout;
+        self
+    }
+
+    /// Sets how entries are compressed when written.
+    pub fn with_compression(mut self, compression: CacheCompression) -> FilesystemCache {
+        self.compression = compression;
+        self
+    }
+
+    pub fn get_root(&self) -> &Path {
+        self.root.as_path()
+    }
+
+    pub fn get_lock_timeout(&self) -> Duration {
+        self.lock_timeout
+    }
+
+    pub fn get_compression(&self) -> CacheCompression {
+        self.compression
+    }
+
+    fn lock(&self, key: &str) -> Result<FilesystemCacheLock, String> {
+        let path = self.root.join([key, LOCK_FILE_SUFFIX].join(""));
+        let started_at = Instant::now();
+
+        loop {
+            let boxed_lock_file = OpenOptions::new().write(true).create_new(true).open(path.as_path());
+            if let Ok(mut lock_file) = boxed_lock_file {
+                let _ = lock_file.write_all(std::process::id().to_string().as_bytes());
+                return Ok(FilesystemCacheLock { path });
+            }
+
+            let error = boxed_lock_file.err().unwrap();
+            if error.kind() != ErrorKind::AlreadyExists {
+                return Err(error.to_string());
+            }
+
+            if break_stale_lock(path.as_path(), SystemTime::now()) {
+                continue;
+            }
+
+            if started_at.elapsed() >= self.lock_timeout {
+                return Err(["timed out waiting for the lock ", path.to_string_lossy().as_ref()].join(""));
+            }
+            thread::sleep(LOCK_RETRY_INTERVAL);
+        }
+    }
+
+    fn get_filepath(&self, key: &str, compression_suffix: &str) -> PathBuf {
+        self.root.join([key, compression_suffix].join(""))
+    }
+
+    /// Finds the file holding the entry and the compression suffix it was stored with, trying the
+    /// configured compression first.
+    fn find_filepath(&self, key: &str) -> Option<(PathBuf, &'static str)> {
+        let own_suffix = self.compression.get_file_suffix();
+        let suffixes = std::iter::once(own_suffix)
+            .chain(COMPRESSION_SUFFIXES.iter().copied().filter(|suffix| *suffix != own_suffix));
+
+        for suffix in suffixes {
+            let filepath = self.get_filepath(key, suffix);
+            if filepath.is_file() {
+                return Some((filepath, suffix));
+            }
+        }
+        None
+    }
+
+    fn get_metadata_filepath(&self, key: &str) -> PathBuf {
+        self.root.join([key, METADATA_FILE_SUFFIX].join(""))
+    }
+
+    /// Reads the `.meta` file of the entry: when it was written, if recorded, and its validators.
+    /// The body is renamed into place before its `.meta` file is written, so a `.meta` file older
+    /// than the body, last modified at `body_modified`, was left behind by a write that didn't
+    /// finish and belongs to the previous body; it's treated as absent.
+    fn read_metadata_file(&self, key: &str, body_modified: Option<SystemTime>) -> (Option<SystemTime>, CacheValidators) {
+        let metadata_filepath = self.get_metadata_filepath(key);
+        if let Some(body_modified) = body_modified {
+            let boxed_modified = fs::metadata(metadata_filepath.as_path()).and_then(|metadata| metadata.modified());
+            if boxed_modified.is_err() || boxed_modified.unwrap() < body_modified {
+                return (None, CacheValidators::default());
+            }
+        }
+
+        let boxed_read = read_to_string(metadata_filepath);
+        if boxed_read.is_err() {
+            return (None, CacheValidators::default());
+        }
+
+        let boxed_json = serde_json::from_str::<Value>(boxed_read.unwrap().as_str());
+        if boxed_json.is_err() {
+            return (None, CacheValidators::default());
+        }
+        let json = boxed_json.unwrap();
+
+        let written_at = json["written_at_millis"].as_u64()
+            .map(|written_at_millis| UNIX_EPOCH + Duration::from_millis(written_at_millis));
+        let validators = CacheValidators {
+            etag: json["etag"].as_str().map(|etag| etag.to_string()),
+            last_modified: json["last_modified"].as_str().map(|last_modified| last_modified.to_string()),
+        };
+        (written_at, validators)
+    }
+
+    fn write_metadata_file(&self, key: &str, written_at: SystemTime, validators: &CacheValidators) -> Result<(), String> {
+        let written_at_millis = written_at.duration_since(UNIX_EPOCH).unwrap_or_default().as_millis() as u64;
+        let mut metadata = serde_json::json!({ "written_at_millis": written_at_millis });
+        if let Some(etag) = validators.etag.as_ref() {
+            metadata["etag"] = Value::from(etag.as_str());
+        }
+        if let Some(last_modified) = validators.last_modified.as_ref() {
+            metadata["last_modified"] = Value::from(last_modified.as_str());
+        }
+
+        write_atomically(self.get_metadata_filepath(key).as_path(), metadata.to_string().as_bytes())
+    }
+
+    fn write(&self, key: &str, body: &str, written_at: SystemTime, validators: &CacheValidators) -> Result<(), String> {
+        let compression_suffix = self.compression.get_file_suffix();
+        let filepath = self.get_filepath(key, compression_suffix);
+
+        if let Some(parent) = filepath.parent() {
+            if !parent.is_dir() {
+                let boxed_create_dir = fs::create_dir_all(parent);
+                if boxed_create_dir.is_err() {
+                    return Err(boxed_create_dir.err().unwrap().to_string());
+                }
+            }
+        }
+
+        let bytes = self.compression.compress(body.as_bytes())?;
+
+        let _lock = self.lock(key)?;
+
+        write_atomically(filepath.as_path(), bytes.as_slice())?;
+        for suffix in COMPRESSION_SUFFIXES.iter().filter(|suffix| **suffix != compression_suffix) {
+            let _ = fs::remove_file(self.get_filepath(key, suffix));
+        }
+        self.write_metadata_file(key, written_at, validators)
+    }
+
+    fn read_metadata(&self, key: &str, path: &Path) -> Result<CacheMetadata, String> {
+        let boxed_metadata = fs::metadata(path);
+        if boxed_metadata.is_err() {
+            return Err(boxed_metadata.err().unwrap().to_string());
+        }
+        let metadata = boxed_metadata.unwrap();
+
+        let boxed_modified = metadata.modified();
+        if boxed_modified.is_err() {
+            return Err(boxed_modified.err().unwrap().to_string());
+        }
+
+        let modified = boxed_modified.unwrap();
+        let (written_at, validators) = self.read_metadata_file(key, Some(modified));
+        Ok(CacheMetadata {
+            key: key.to_string(),
+            size: metadata.len(),
+            written_at: written_at.unwrap_or(modified),
+            validators,
+        })
+    }
+
+    fn collect_keys(&self, dir: &Path, keys: &mut Vec<String>) -> Result<(), String> {
+        let boxed_read_dir = fs::read_dir(dir);
+        if boxed_read_dir.is_err() {
+            let error = boxed_read_dir.err().unwrap();
+            if error.kind() == ErrorKind::NotFound {
+                return Ok(());
+            }
+            return Err(error.to_string());
+        }
+
+        for boxed_dir_entry in boxed_read_dir.unwrap() {
+            if boxed_dir_entry.is_err() {
+                return Err(boxed_dir_entry.err().unwrap().to_string());
+            }
+            let path = boxed_dir_entry.unwrap().path();
+
+            if path.is_dir() {
+                self.collect_keys(path.as_path(), keys)?;
+            } else if path.is_file() {
+                if let Ok(relative_path) = path.strip_prefix(self.root.as_path()) {
+                    let key = relative_path.components()
+                        .map(|component| component.as_os_str().to_string_lossy().to_string())
+                        .collect::<Vec<String>>()
+                        .join("/");
+                    if !is_internal_file(key.as_str()) {
+                        keys.push(strip_compression_suffix(key.as_str()).to_string());
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl CacheStore for FilesystemCache {
+    fn get(&self, key: &str) -> Result<Option<CacheEntry>, String> {
+        let boxed_filepath = self.find_filepath(key);
+        if boxed_filepath.is_none() {
+            return Ok(None);
+        }
+        let (filepath, compression_suffix) = boxed_filepath.unwrap();
+
+        let boxed_read = fs::read(filepath.as_path());
+        if boxed_read.is_err() {
+            let error = boxed_read.err().unwrap();
+            if error.kind() == ErrorKind::NotFound {
+                return Ok(None);
+            }
+            return Err(error.to_string());
+        }
+
+        let boxed_body = decode_body(compression_suffix, boxed_read.unwrap())?;
+        if boxed_body.is_none() {
+            // not UTF-8 or not decompressable, most likely written half way by a crashed process
+            let _ = self.delete(key);
+            return Ok(None);
+        }
+        let body = boxed_body.unwrap();
+
+        let boxed_metadata = self.read_metadata(key, filepath.as_path());
+        if boxed_metadata.is_err() {
+            return Err(boxed_metadata.err().unwrap());
+        }
+
+        Ok(Some(CacheEntry {
+            metadata: boxed_metadata.unwrap(),
+            body,
+        }))
+    }
+
+    fn put(&self, key: &str, body: &str) -> Result<(), String> {
+        self.put_with_written_at(key, body, SystemTime::now())
+    }
+
+    fn put_with_written_at(&self, key: &str, body: &str, written_at: SystemTime) -> Result<(), String> {
+        self.write(key, body, written_at, &CacheValidators::default())
+    }
+
+    fn put_with_validators(&self, key: &str, body: &str, validators: &CacheValidators) -> Result<(), String> {
+        self.write(key, body, SystemTime::now(), validators)
+    }
+
+    fn touch(&self, key: &str, written_at: SystemTime) -> Result<(), String> {
+        let boxed_filepath = self.find_filepath(key);
+        if boxed_filepath.is_none() {
+            return Ok(());
+        }
+        let (filepath, _) = boxed_filepath.unwrap();
+        let _lock = self.lock(key)?;
+
+        let body_modified = fs::metadata(filepath).and_then(|metadata| metadata.modified()).ok();
+        let (_, validators) = self.read_metadata_file(key, body_modified);
+        self.write_metadata_file(key, written_at, &validators)
+    }
+
+    fn delete(&self, key: &str) -> Result<(), String> {
+        if self.find_filepath(key).is_none() {
+            return Ok(());
+        }
+        let _lock = self.lock(key)?;
+
+        let _ = fs::remove_file(self.get_metadata_filepath(key));
+
+        for suffix in COMPRESSION_SUFFIXES.iter() {
+            let boxed_remove = fs::remove_file(self.get_filepath(key, suffix));
+            if boxed_remove.is_err() {
+                let error = boxed_remove.err().unwrap();
+                if error.kind() != ErrorKind::NotFound {
+                    return Err(error.to_string());
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn list(&self, prefix: &str) -> Result<Vec<CacheMetadata>, String> {
+        let mut keys = vec![];
+        let boxed_collect = self.collect_keys(self.root.as_path(), &mut keys);
+        if boxed_collect.is_err() {
+            return Err(boxed_collect.err().unwrap());
+        }
+        keys.sort();
+        keys.dedup();
+
+        let mut entries = vec![];
+        for key in keys.iter().filter(|key| key.starts_with(prefix)) {
+            let boxed_filepath = self.find_filepath(key);
+            if boxed_filepath.is_none() {
+                // removed in the meantime
+                continue;
+            }
+            let (filepath, _) = boxed_filepath.unwrap();
+
+            let boxed_metadata = self.read_metadata(key, filepath.as_path());
+            if boxed_metadata.is_err() {
+                return Err(boxed_metadata.err().unwrap());
+            }
+            entries.push(boxed_metadata.unwrap());
+        }
+
+        Ok(entries)
+    }
+
+    /// Returns the filepath the entry is written to, with the suffix of the configured compression.
+    fn get_location(&self, key: &str) -> String {
+        [self.root.to_string_lossy().to_string(), "/".to_string(), key.to_string(), self.compression.get_file_suffix().to_string()].join("")
+    }
+}
+
+pub(crate) fn is_internal_file(key: &str) -> bool {
+    key.ends_with(METADATA_FILE_SUFFIX) || key.ends_with(LOCK_FILE_SUFFIX) || key.ends_with(TEMP_FILE_SUFFIX)
+}
+
+pub(crate) fn strip_compression_suffix(filename: &str) -> &str {
+    filename.strip_suffix(ZSTD_FILE_SUFFIX)
+        .or_else(|| filename.strip_suffix(GZIP_FILE_SUFFIX))
+        .unwrap_or(filename)
+}
+
+/// Decompresses and decodes a stored body, `Ok(None)` if it is corrupt.
+pub(crate) fn decode_body(compression_suffix: &str, bytes: Vec<u8>) -> Result<Option<String>, String> {
+    let boxed_bytes = if compression_suffix == GZIP_FILE_SUFFIX {
+        gunzip(bytes.as_slice())?
+    } else if compression_suffix == ZSTD_FILE_SUFFIX {
+        unzstd(bytes.as_slice())?
+    } else {
+        Some(bytes)
+    };
+
+    Ok(boxed_bytes.and_then(|bytes| String::from_utf8(bytes).ok()))
+}
+
+#[cfg(feature = "gzip")]
+fn gunzip(bytes: &[u8]) -> Result<Option<Vec<u8>>, String> {
+    use std::io::Read;
+
+    let mut decompressed = vec![];
+    let boxed_read = flate2::read::GzDecoder::new(bytes).read_to_end(&mut decompressed);
+    Ok(boxed_read.ok().map(|_| decompressed))
+}
+
+#[cfg(not(feature = "gzip"))]
+fn gunzip(_bytes: &[u8]) -> Result<Option<Vec<u8>>, String> {
+    Err("the entry is gzip-compressed, reading it needs the `gzip` feature".to_string())
+}
+
+#[cfg(feature = "zstd")]
+fn unzstd(bytes: &[u8]) -> Result<Option<Vec<u8>>, String> {
+    use std::io::Read;
+
+    let mut decompressed = vec![];
+    let boxed_read = zstd::stream::read::Decoder::new(bytes).and_then(|mut decoder| decoder.read_to_end(&mut decompressed));
+    Ok(boxed_read.ok().map(|_| decompressed))
+}
+
+#[cfg(not(feature = "zstd"))]
+fn unzstd(_bytes: &[u8]) -> Result<Option<Vec<u8>>, String> {
+    Err("the entry is zstd-compressed, reading it needs the `zstd` feature".to_string())
+}
+
+fn is_stale_lock(path: &Path, now: SystemTime) -> bool {
+    let boxed_modified = fs::metadata(path).and_then(|metadata| metadata.modified());
+    if boxed_modified.is_err() {
+        return false;
+    }
+
+    let boxed_age = now.duration_since(boxed_modified.unwrap());
+    boxed_age.is_ok() && boxed_age.unwrap() > STALE_LOCK_AGE
+}
+
+/// Removes the lock at `path` if it was older than `STALE_LOCK_AGE` at `now`. Another writer may
+/// break the same lock and take a new one in the meantime, so the lock is renamed to a name of
+/// its own first and only deleted if the renamed file is still stale; a fresh lock is put back.
+pub(crate) fn break_stale_lock(path: &Path, now: SystemTime) -> bool {
+    if !is_stale_lock(path, now) {
+        return false;
+    }
+
+    let broken_path = get_temp_path(path);
+    if fs::rename(path, broken_path.as_path()).is_err() {
+        return false;
+    }
+
+    let is_stale = is_stale_lock(broken_path.as_path(), now);
+    if !is_stale {
+        let _ = fs::hard_link(broken_path.as_path(), path);
+    }
+    let _ = fs::remove_file(broken_path.as_path());
+    is_stale
+}
+
+/// Returns a path next to `path` no other write uses, ending in `TEMP_FILE_SUFFIX`.
+fn get_temp_path(path: &Path) -> PathBuf {
+    let temp_file_name = [
+        path.file_name().unwrap_or_default().to_string_lossy().as_ref(),
+        ".",
+        std::process::id().to_string().as_str(),
+        "-",
+        TEMP_FILE_COUNTER.fetch_add(1, Ordering::Relaxed).to_string().as_str(),
+        TEMP_FILE_SUFFIX,
+    ].join("");
+    path.with_file_name(temp_file_name)
+}
+
+/// Writes `bytes` to a temporary file next to `path`, syncs it to disk and renames it over `path`.
+fn write_atomically(path: &Path, bytes: &[u8]) -> Result<(), String> {
+    let temp_path = get_temp_path(path);
+
+    let boxed_write = write_and_sync(temp_path.as_path(), bytes);
+    if boxed_write.is_err() {
+        let _ = fs::remove_file(temp_path.as_path());
+        return boxed_write;
+    }
+
+    let boxed_rename = fs::rename(temp_path.as_path(), path);
+    if boxed_rename.is_err() {
+        let _ = fs::remove_file(temp_path.as_path());
+        return Err(boxed_rename.err().unwrap().to_string());
+    }
+
+    sync_parent_dir(path);
+    Ok(())
+}
+
+fn write_and_sync(path: &Path, bytes: &[u8]) -> Result<(), String> {
+    let boxed_file = File::create(path);
+    if boxed_file.is_err() {
+        return Err(boxed_file.err().unwrap().to_string());
+    }
+    let mut file = boxed_file.unwrap();
+
+    let boxed_write = file.write_all(bytes);
+    if boxed_write.is_err() {
+        return Err(boxed_write.err().unwrap().to_string());
+    }
+
+    let boxed_sync = file.sync_all();
+    if boxed_sync.is_err() {
+        return Err(boxed_sync.err().unwrap().to_string());
+    }
+    Ok(())
+}
+
+/// Makes the rename itself durable. Only possible, and needed, on Unix.
+#[cfg(unix)]
+fn sync_parent_dir(path: &Path) {
+    if let Some(parent) = path.parent() {
+        if let Ok(dir) = File::open(parent) {
+            let _ = dir.sync_all();
+        }
+    }
+}
+
+#[cfg(not(unix))]
+fn sync_parent_dir(_path: &Path) {}
@@ -0,0 +1,547 @@
+//! Keeps a cache in check: what is in it (`list_entries`, `summarize`), removing old entries
+//! (`prune`), checking entries still parse (`verify`) and, with the `archive` feature, moving a
+//! whole cache around as one `.tar.gz` file (`export_archive`, `import_archive`).
+//!
+//! Every function works on any `CacheStore`, e.g. the one of a client:
+//!
+//! ```
+//! use std::time::Duration;
+//! use steam_webapi_rust_sdk::cache::maintenance::{prune, summarize, PruneOptions};
+//! use steam_webapi_rust_sdk::client::SteamClient;
+//!
+//! let cache_dir = std::env::temp_dir().join("steam-webapi-rust-sdk-maintenance-example");
+//! let client = SteamClient::builder()
+//!     .cache_dir(cache_dir.to_str().unwrap())
+//!     .build();
+//!
+//! for summary in summarize(client.cache_store()).unwrap() {
+//!     println!("{}: {} entries, {} bytes", summary.endpoint, summary.entries, summary.size);
+//! }
+//!
+//! let report = prune(client.cache_store(), &PruneOptions::default()
+//!     .with_endpoint("steampowered/appdetails")
+//!     .with_max_size(10 * 1024 * 1024 * 1024)).unwrap();
+//! println!("freed {} bytes", report.removed_size);
+//! ```
+
+use std::collections::BTreeMap;
+use std::time::{Duration, SystemTime};
+use crate::cache::{CacheMetadata, CacheStore};
+use crate::error::SteamApiError;
+use crate::idota2match_570::{get_heroes, get_league_listing, get_live_league_games, get_match_details, get_match_history, get_match_history_by_sequence_num, get_team_info_by_team_id};
+use crate::iecon_dota2_570::get_game_items;
+use crate::iplayer_service::{get_badges, get_owned_games, get_recently_played_games, get_steam_level};
+use crate::isteam_apps::get_app_list;
+use crate::isteam_news::get_news_for_app;
+use crate::isteam_user::{get_friend_list, get_player_bans, get_player_summaries, resolve_vanity_url};
+use crate::isteam_user_stats::{get_global_achievement_percentages_for_app, get_number_of_current_players, get_player_achievements, get_schema_for_game, get_user_stats_for_game};
+use crate::store_steampowered_com::appdetails;
+
+#[cfg(feature = "archive")]
+use std::fs::File;
+#[cfg(feature = "archive")]
+use std::io::Read;
+#[cfg(feature = "archive")]
+use std::path::Component;
+#[cfg(feature = "archive")]
+use std::time::UNIX_EPOCH;
+#[cfg(feature = "archive")]
+use crate::cache::filesystem::{decode_body, is_internal_file, strip_compression_suffix};
+
+/// A cached entry along with the endpoint it belongs to and how old it is.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct CacheEntryInfo {
+    /// Endpoint of the entry as returned by the module's `get_endpoint`, e.g. `"steampowered/appdetails"`.
+    pub endpoint: String,
+    pub metadata: CacheMetadata,
+    pub age: Duration,
+}
+
+/// Number, total size and age range of the cached entries of one endpoint.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct EndpointCacheSummary {
+    pub endpoint: String,
+    pub entries: u64,
+    /// Total size of the bodies in bytes.
+    pub size: u64,
+    pub oldest_age: Duration,
+    pub newest_age: Duration,
+}
+
+/// What `prune` removes. Without a maximum age or size nothing is removed.
+#[derive(PartialEq, Eq, Clone, Debug, Default)]
+pub struct PruneOptions {
+    endpoint: Option<String>,
+    max_age: Option<Duration>,
+    max_size: Option<u64>,
+}
+
+impl PruneOptions {
+    /// Only prunes entries of `endpoint`, e.g. `"steampowered/appdetails"`. The size budget then
+    /// applies to that endpoint alone.
+    pub fn with_endpoint(mut self, endpoint: &str) -> PruneOptions {
+        self.endpoint = Some(endpoint.to_string());
+        self
+    }
+
+    /// Removes entries older than `max_age`.
+    pub fn with_max_age(mut self, max_age: Duration) -> PruneOptions {
+        self.max_age = Some(max_age);
+        self
+    }
+
+    /// Removes the oldest entries until the rest take up at most `max_size` bytes.
+    pub fn with_max_size(mut self, max_size: u64) -> PruneOptions {
+        self.max_size = Some(max_size);
+        self
+    }
+
+    pub fn get_endpoint(&self) -> Option<&str> {
+        self.endpoint.as_deref()
+    }
+
+    pub fn get_max_age(&self) -> Option<Duration> {
+        self.max_age
+    }
+
+    pub fn get_max_size(&self) -> Option<u64> {
+        self.max_size
+    }
+}
+
+/// Entries removed by `prune`.
+#[derive(PartialEq, Eq, Clone, Debug, Default)]
+pub struct PruneReport {
+    pub removed: Vec<CacheMetadata>,
+    /// Total size of the removed bodies in bytes.
+    pub removed_size: u64,
+}
+
+/// A cached entry that no longer parses.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct VerifyFailure {
+    pub key: String,
+    pub endpoint: String,
+    pub error: SteamApiError,
+}
+
+/// Outcome of `verify`.
+#[derive(PartialEq, Eq, Clone, Debug, Default)]
+pub struct VerifyReport {
+    /// Number of entries that parsed.
+    pub verified: u64,
+    /// Keys of entries that don't belong to a known endpoint and were not checked.
+    pub skipped: Vec<String>,
+    pub failures: Vec<VerifyFailure>,
+}
+
+impl VerifyReport {
+    pub fn is_ok(&self) -> bool {
+        self.failures.is_empty()
+    }
+}
+
+/// Outcome of `import_archive`.
+#[derive(PartialEq, Eq, Clone, Debug, Default)]
+pub struct ImportReport {
+    /// Number of entries stored.
+    pub imported: u64,
+    /// Paths of archived files that are not a readable entry, e.g. compressed with a codec whose
+    /// feature is disabled, or not UTF-8.
+    pub skipped: Vec<String>,
+}
+
+/// Returns the endpoint a cache key belongs to: `"steampowered/appdetails"` for
+/// `steampowered/appdetails/0/730/730.json`, `"IDOTA2Match_570/GetHeroes"` for
+/// `IDOTA2Match_570-GetHeroes-v1-language=en.json`. Keys not following either layout are
+/// returned as is.
+///
+/// # Examples
+///
+/// ```
+/// use steam_webapi_rust_sdk::cache::maintenance::get_endpoint_of_key;
+///
+/// assert_eq!("ISteamApps/GetAppList", get_endpoint_of_key("ISteamApps-GetAppList-v2.json"));
+/// assert_eq!("steampowered/appdetails", get_endpoint_of_key("steampowered/appdetails/0/730/730.json"));
+/// ```
+pub fn get_endpoint_of_key(key: &str) -> String {
+    let appdetails_endpoint = appdetails::get_endpoint();
+    if key.starts_with(get_key_prefix(appdetails_endpoint.as_str()).as_str()) {
+        return appdetails_endpoint;
+    }
+
+    let mut parts = key.splitn(3, '-');
+    match (parts.next(), parts.next()) {
+        (Some(interface), Some(method)) => [interface, "/", method].join(""),
+        _ => key.to_string(),
+    }
+}
+
+/// Common beginning of the keys of `endpoint`, narrowing down `CacheStore::list`.
+fn get_key_prefix(endpoint: &str) -> String {
+    if endpoint == appdetails::get_endpoint() {
+        return [endpoint, "/"].join("");
+    }
+    [endpoint.replace('/', "-").as_str(), "-"].join("")
+}
+
+fn get_age(written_at: SystemTime) -> Duration {
+    SystemTime::now().duration_since(written_at).unwrap_or_default()
+}
+
+/// Lists the cached entries, of `endpoint` only if given, sorted by key.
+pub fn list_entries(store: &dyn CacheStore, endpoint: Option<&str>) -> Result<Vec<CacheEntryInfo>, String> {
+    let prefix = endpoint.map(get_key_prefix).unwrap_or_default();
+    let boxed_list = store.list(prefix.as_str());
+    if boxed_list.is_err() {
+        return Err(boxed_list.err().unwrap());
+    }
+
+    let entries = boxed_list.unwrap().into_iter()
+        .map(|metadata| CacheEntryInfo {
+            endpoint: get_endpoint_of_key(metadata.key.as_str()),
+            age: get_age(metadata.written_at),
+            metadata,
+        })
+        .filter(|entry| endpoint.is_none() || endpoint == Some(entry.endpoint.as_str()))
+        .collect();
+    Ok(entries)
+}
+
+/// Sums up the cached entries per endpoint, sorted by endpoint.
+pub fn summarize(store: &dyn CacheStore) -> Result<Vec<EndpointCacheSummary>, String> {
+    let entries = list_entries(store, None)?;
+
+    let mut summaries : BTreeMap<String, EndpointCacheSummary> = BTreeMap::new();
+    for entry in entries {
+        let summary = summaries.entry(entry.endpoint.clone()).or_insert(EndpointCacheSummary {
+            endpoint: entry.endpoint.clone(),
+            entries: 0,
+            size: 0,
+            oldest_age: entry.age,
+            newest_age: entry.age,
+        });
+        summary.entries += 1;
+        summary.size += entry.metadata.size;
+        summary.oldest_age = summary.oldest_age.max(entry.age);
+        summary.newest_age = summary.newest_age.min(entry.age);
+    }
+
+    Ok(summaries.into_values().collect())
+}
+
+/// Removes entries older than the maximum age, then the oldest remaining ones until the size
+/// budget is met, as set in `options`.
+pub fn prune(store: &dyn CacheStore, options: &PruneOptions) -> Result<PruneReport, String> {
+    let mut entries = list_entries(store, options.get_endpoint())?;
+    entries.sort_by(|a, b| b.age.cmp(&a.age).then(a.metadata.key.cmp(&b.metadata.key)));
+
+    let mut total_size : u64 = entries.iter().map(|entry| entry.metadata.size).sum();
+    let mut report = PruneReport::default();
+    for entry in entries {
+        let is_expired = options.get_max_age().map_or(false, |max_age| entry.age > max_age);
+        let is_over_budget = options.get_max_size().map_or(false, |max_size| total_size > max_size);
+        if !is_expired && !is_over_budget {
+            continue;
+        }
+
+        store.delete(entry.metadata.key.as_str())?;
+        total_size -= entry.metadata.size;
+        report.removed_size += entry.metadata.size;
+        report.removed.push(entry.metadata);
+    }
+
+    Ok(report)
+}
+
+/// Parses every cached entry, of `endpoint` only if given, with the parser of its endpoint module
+/// (`parse_response`, `parse_api_call_result`), reporting the ones that fail. Nothing is removed;
+/// delete the failures from the store to have them fetched again.
+///
+/// # Examples
+///
+/// ```
+/// use steam_webapi_rust_sdk::cache::{CacheStore, MemoryCache};
+/// use steam_webapi_rust_sdk::cache::maintenance::verify;
+///
+/// let cache = MemoryCache::default();
+/// cache.put("ISteamUser-GetPlayerBans-v1-steamids=76561197960435530.json", "{\"players\":").unwrap();
+///
+/// let report = verify(&cache, None).unwrap();
+/// for failure in report.failures.iter() {
+///     cache.delete(failure.key.as_str()).unwrap();
+/// }
+/// assert!(cache.is_empty());
+/// ```
+pub fn verify(store: &dyn CacheStore, endpoint: Option<&str>) -> Result<VerifyReport, String> {
+    let entries = list_entries(store, endpoint)?;
+
+    let mut report = VerifyReport::default();
+    for entry in entries {
+        let key = entry.metadata.key;
+
+        let boxed_cached = store.get(key.as_str());
+        if boxed_cached.is_err() {
+            return Err(boxed_cached.err().unwrap());
+        }
+        let boxed_entry = boxed_cached.unwrap();
+        if boxed_entry.is_none() {
+            // removed in the meantime
+            continue;
+        }
+        let body = boxed_entry.unwrap().body;
+
+        let boxed_parse = parse_cached_body(entry.endpoint.as_str(), key.as_str(), body);
+        if boxed_parse.is_none() {
+            report.skipped.push(key);
+            continue;
+        }
+
+        let boxed_result = boxed_parse.unwrap();
+        if boxed_result.is_err() {
+            report.failures.push(VerifyFailure {
+                key,
+                endpoint: entry.endpoint,
+                error: boxed_result.err().unwrap(),
+            });
+            continue;
+        }
+        report.verified += 1;
+    }
+
+    Ok(report)
+}
+
+/// Runs `body` through the parser of `endpoint`, `None` if the endpoint is unknown.
+fn parse_cached_body(endpoint: &str, key: &str, body: String) -> Option<Result<(), SteamApiError>> {
+    let result = if endpoint == get_app_list::get_endpoint() {
+        get_app_list::parse_api_call_result(body).map(|_| ())
+    } else if endpoint == appdetails::get_endpoint() {
+        let boxed_app_id = get_key_id(key);
+        if boxed_app_id.is_none() {
+            return Some(Err(SteamApiError::malformed_response(endpoint, "cache key does not end with an app id")));
+        }
+        appdetails::parse_api_call_result(body, boxed_app_id.unwrap() as i64).map(|_| ())
+    } else if endpoint == get_match_details::get_endpoint() {
+        let boxed_match_id = get_key_id(key);
+        if boxed_match_id.is_none() {
+            return Some(Err(SteamApiError::malformed_response(endpoint, "cache key does not end with a match id")));
+        }
+        get_match_details::parse_response(body, boxed_match_id.unwrap()).map(|_| ())
+    } else if endpoint == get_match_history::get_endpoint() {
+        get_match_history::parse_response(body).map(|_| ())
+    } else if endpoint == get_match_history_by_sequence_num::get_endpoint() {
+        get_match_history_by_sequence_num::parse_response(body).map(|_| ())
+    } else if endpoint == get_heroes::get_endpoint() {
+        get_heroes::parse_response(body).map(|_| ())
+    } else if endpoint == get_game_items::get_endpoint() {
+        get_game_items::parse_response(body).map(|_| ())
+    } else if endpoint == get_league_listing::get_endpoint() {
+        get_league_listing::parse_response(body).map(|_| ())
+    } else if endpoint == get_live_league_games::get_endpoint() {
+        get_live_league_games::parse_response(body).map(|_| ())
+    } else if endpoint == get_team_info_by_team_id::get_endpoint() {
+        get_team_info_by_team_id::parse_response(body).map(|_| ())
+    } else if endpoint == get_player_summaries::get_endpoint() {
+        get_player_summaries::parse_response(body).map(|_| ())
+    } else if endpoint == get_friend_list::get_endpoint() {
+        get_friend_list::parse_response(body).map(|_| ())
+    } else if endpoint == get_player_bans::get_endpoint() {
+        get_player_bans::parse_response(body).map(|_| ())
+    } else if endpoint == resolve_vanity_url::get_endpoint() {
+        resolve_vanity_url::parse_response(body).map(|_| ())
+    } else if endpoint == get_player_achievements::get_endpoint() {
+        get_player_achievements::parse_response(body).map(|_| ())
+    } else if endpoint == get_schema_for_game::get_endpoint() {
+        get_schema_for_game::parse_response(body).map(|_| ())
+    } else if endpoint == get_number_of_current_players::get_endpoint() {
+        get_number_of_current_players::parse_response(body).map(|_| ())
+    } else if endpoint == get_user_stats_for_game::get_endpoint() {
+        get_user_stats_for_game::parse_response(body).map(|_| ())
+    } else if endpoint == get_global_achievement_percentages_for_app::get_endpoint() {
+        get_global_achievement_percentages_for_app::parse_response(body).map(|_| ())
+    } else if endpoint == get_news_for_app::get_endpoint() {
+        get_news_for_app::parse_response(body).map(|_| ())
+    } else if endpoint == get_owned_games::get_endpoint() {
+        get_owned_games::parse_response(body).map(|_| ())
+    } else if endpoint == get_recently_played_games::get_endpoint() {
+        get_recently_played_games::parse_response(body).map(|_| ())
+    } else if endpoint == get_steam_level::get_endpoint() {
+        get_steam_level::parse_response(body).map(|_| ())
+    } else if endpoint == get_badges::get_endpoint() {
+        get_badges::parse_response(body).map(|_| ())
+    } else {
+        return None;
+    };
+
+    Some(result)
+}
+
+/// Reads the id keys such as `steampowered/appdetails/0/730/730.json` and
+/// `IDOTA2Match_570-GetMatchDetails-v1-6000000000.json` end with.
+fn get_key_id(key: &str) -> Option<u64> {
+    let filename = key.rsplit(['/', '-']).next()?;
+    let id = filename.strip_suffix(".json")?;
+    id.parse::<u64>().ok()
+}
+
+/// Writes every cached entry to a gzip-compressed tar archive at `path`, named by its key and
+/// dated by when it was written, and returns the number of entries. Available with the `archive`
+/// feature.
+///
+/// # Examples
+///
+/// ```
+/// use steam_webapi_rust_sdk::cache::{CacheStore, FilesystemCache, MemoryCache};
+/// use steam_webapi_rust_sdk::cache::maintenance::{export_archive, import_archive};
+///
+/// let cache = MemoryCache::default();
+/// cache.put("ISteamApps-GetAppList-v2.json", "{\"applist\":{\"apps\":[]}}").unwrap();
+///
+/// let archive_path = std::env::temp_dir().join("steam-webapi-rust-sdk-export-example.tar.gz");
+/// assert_eq!(1, export_archive(&cache, archive_path.to_str().unwrap()).unwrap());
+///
+/// let cache_dir = std::env::temp_dir().join("steam-webapi-rust-sdk-import-example");
+/// let filesystem_cache = FilesystemCache::new(cache_dir.to_str().unwrap());
+/// assert_eq!(1, import_archive(&filesystem_cache, archive_path.to_str().unwrap()).unwrap().imported);
+/// ```
+#[cfg(feature = "archive")]
+pub fn export_archive(store: &dyn CacheStore, path: &str) -> Result<u64, String> {
+    let boxed_file = File::create(path);
+    if boxed_file.is_err() {
+        return Err(boxed_file.err().unwrap().to_string());
+    }
+    let encoder = flate2::write::GzEncoder::new(boxed_file.unwrap(), flate2::Compression::default());
+    let mut builder = tar::Builder::new(encoder);
+
+    let mut exported = 0;
+    for metadata in store.list("")? {
+        let boxed_entry = store.get(metadata.key.as_str())?;
+        if boxed_entry.is_none() {
+            continue;
+        }
+        let entry = boxed_entry.unwrap();
+
+        let mut header = tar::Header::new_gnu();
+        header.set_entry_type(tar::EntryType::Regular);
+        header.set_size(entry.body.len() as u64);
+        header.set_mode(0o644);
+        header.set_mtime(entry.metadata.written_at.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs());
+
+        let boxed_append = builder.append_data(&mut header, entry.metadata.key.as_str(), entry.body.as_bytes());
+        if boxed_append.is_err() {
+            return Err([entry.metadata.key.as_str(), ": ", boxed_append.err().unwrap().to_string().as_str()].join(""));
+        }
+        exported += 1;
+    }
+
+    let boxed_encoder = builder.into_inner();
+    if boxed_encoder.is_err() {
+        return Err(boxed_encoder.err().unwrap().to_string());
+    }
+    let boxed_file = boxed_encoder.unwrap().finish();
+    if boxed_file.is_err() {
+        return Err(boxed_file.err().unwrap().to_string());
+    }
+    let boxed_sync = boxed_file.unwrap().sync_all();
+    if boxed_sync.is_err() {
+        return Err(boxed_sync.err().unwrap().to_string());
+    }
+
+    Ok(exported)
+}
+
+/// Stores every file of the gzip-compressed tar archive at `path` under its path in the archive,
+/// dated by its modification time. Reads archives written by `export_archive` as well as a
+/// `tar czf` of a cache directory, whose name is dropped from the keys; `.meta`, `.lock` and `.tmp`
+/// files are ignored. Entries a `FilesystemCache` stored compressed are decompressed and stored
+/// under their key; files that can't be read as an entry are skipped and listed in the report.
+/// Available with the `archive` feature.
+#[cfg(feature = "archive")]
+pub fn import_archive(store: &dyn CacheStore, path: &str) -> Result<ImportReport, String> {
+    let boxed_file = File::open(path);
+    if boxed_file.is_err() {
+        return Err(boxed_file.err().unwrap().to_string());
+    }
+    let mut archive = tar::Archive::new(flate2::read::GzDecoder::new(boxed_file.unwrap()));
+
+    let boxed_entries = archive.entries();
+    if boxed_entries.is_err() {
+        return Err(boxed_entries.err().unwrap().to_string());
+    }
+
+    let mut report = ImportReport::default();
+    for boxed_entry in boxed_entries.unwrap() {
+        if boxed_entry.is_err() {
+            return Err(boxed_entry.err().unwrap().to_string());
+        }
+        let mut entry = boxed_entry.unwrap();
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+
+        let boxed_path = entry.path();
+        if boxed_path.is_err() {
+            return Err(boxed_path.err().unwrap().to_string());
+        }
+        let boxed_filename = get_archived_key(boxed_path.unwrap().components());
+        if boxed_filename.is_none() {
+            return Err("archive contains a path outside of the cache directory".to_string());
+        }
+        let filename = boxed_filename.unwrap();
+        if is_internal_file(filename.as_str()) {
+            continue;
+        }
+
+        let mut bytes = vec![];
+        let boxed_read = entry.read_to_end(&mut bytes);
+        if boxed_read.is_err() {
+            return Err([filename.as_str(), ": ", boxed_read.err().unwrap().to_string().as_str()].join(""));
+        }
+
+        let key = strip_compression_suffix(filename.as_str());
+        let boxed_body = decode_body(&filename[key.len()..], bytes);
+        if !matches!(boxed_body, Ok(Some(_))) {
+            report.skipped.push(filename);
+            continue;
+        }
+
+        let written_at = UNIX_EPOCH + Duration::from_secs(entry.header().mtime().unwrap_or(0));
+        store.put_with_written_at(key, boxed_body.unwrap().unwrap().as_str(), written_at)?;
+        report.imported += 1;
+    }
+
+    Ok(report)
+}
+
+/// Turns the path of an archived file into a cache key, `None` if it would escape the cache
+/// directory. A leading directory other than the ones keys are stored in, such as the
+/// `steam-webapi-cache` of `tar czf cache.tar.gz steam-webapi-cache/`, is the archived cache
+/// directory itself and dropped.
+#[cfg(feature = "archive")]
+fn get_archived_key<'a>(components: impl Iterator<Item = Component<'a>>) -> Option<String> {
+    let mut parts = vec![];
+    for component in components {
+        match component {
+            Component::Normal(part) => parts.push(part.to_string_lossy().to_string()),
+            Component::CurDir => {},
+            _ => return None,
+        }
+    }
+
+    if parts.len() > 1 && !is_key_directory(parts[0].as_str()) {
+        parts.remove(0);
+    }
+
+    if parts.is_empty() {
+        return None;
+    }
+    Some(parts.join("/"))
+}
+
+/// Whether keys are stored under the directory `name`, like `steampowered` of
+/// `steampowered/appdetails/0/730/730.json`.
+#[cfg(feature = "archive")]
+fn is_key_directory(name: &str) -> bool {
+    get_key_prefix(appdetails::get_endpoint().as_str()).split('/').next() == Some(name)
+}
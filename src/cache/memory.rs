@@ -0,0 +1,167 @@
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Mutex;
+use std::time::SystemTime;
+use crate::cache::{CacheEntry, CacheMetadata, CacheStore, CacheValidators};
+
+/// Default number of entries kept by `MemoryCache`.
+pub const DEFAULT_MEMORY_CACHE_CAPACITY: usize = 10_000;
+
+/// In-process cache keeping up to `capacity` entries, evicting the least recently used one when
+/// full. Nothing touches the filesystem, which suits read-only containers. Entries are lost when
+/// the process exits.
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::Arc;
+/// use steam_webapi_rust_sdk::cache::MemoryCache;
+/// use steam_webapi_rust_sdk::client::SteamClient;
+///
+/// let client = SteamClient::builder()
+///     .cache_store(Arc::new(MemoryCache::new(1000)))
+///     .build();
+/// ```
+#[derive(Debug)]
+pub struct MemoryCache {
+    capacity: usize,
+    state: Mutex<MemoryCacheState>,
+}
+
+#[derive(Debug, Default)]
+struct MemoryCacheState {
+    entries: HashMap<String, (CacheEntry, u64)>,
+    recently_used: BTreeMap<u64, String>,
+    tick: u64,
+}
+
+impl MemoryCacheState {
+    fn touch(&mut self, key: &str) {
+        self.tick += 1;
+        let tick = self.tick;
+        let boxed_entry = self.entries.get_mut(key);
+        if let Some((_, last_used)) = boxed_entry {
+            self.recently_used.remove(last_used);
+            *last_used = tick;
+            self.recently_used.insert(tick, key.to_string());
+        }
+    }
+}
+
+impl Default for MemoryCache {
+    fn default() -> MemoryCache {
+        MemoryCache::new(DEFAULT_MEMORY_CACHE_CAPACITY)
+    }
+}
+
+impl MemoryCache {
+    pub fn new(capacity: usize) -> MemoryCache {
+        MemoryCache {
+            capacity,
+            state: Mutex::new(MemoryCacheState::default()),
+        }
+    }
+
+    pub fn get_capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn len(&self) -> usize {
+        let state = self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        state.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn insert(&self, key: &str, body: &str, written_at: SystemTime, validators: CacheValidators) -> Result<(), String> {
+        if self.capacity == 0 {
+            return Ok(());
+        }
+
+        let mut state = self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        let entry = CacheEntry {
+            metadata: CacheMetadata {
+                key: key.to_string(),
+                size: body.len() as u64,
+                written_at,
+                validators,
+            },
+            body: body.to_string(),
+        };
+
+        let boxed_previous = state.entries.insert(key.to_string(), (entry, 0));
+        if let Some((_, last_used)) = boxed_previous {
+            state.recently_used.remove(&last_used);
+        }
+        state.touch(key);
+
+        while state.entries.len() > self.capacity {
+            let boxed_least_recently_used = state.recently_used.iter().next().map(|(tick, key)| (*tick, key.to_string()));
+            if boxed_least_recently_used.is_none() {
+                break;
+            }
+            let (tick, least_recently_used_key) = boxed_least_recently_used.unwrap();
+            state.recently_used.remove(&tick);
+            state.entries.remove(least_recently_used_key.as_str());
+        }
+
+        Ok(())
+    }
+}
+
+impl CacheStore for MemoryCache {
+    fn get(&self, key: &str) -> Result<Option<CacheEntry>, String> {
+        let mut state = self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        if !state.entries.contains_key(key) {
+            return Ok(None);
+        }
+
+        state.touch(key);
+        Ok(state.entries.get(key).map(|(entry, _)| entry.clone()))
+    }
+
+    fn put(&self, key: &str, body: &str) -> Result<(), String> {
+        self.put_with_written_at(key, body, SystemTime::now())
+    }
+
+    fn put_with_written_at(&self, key: &str, body: &str, written_at: SystemTime) -> Result<(), String> {
+        self.insert(key, body, written_at, CacheValidators::default())
+    }
+
+    fn put_with_validators(&self, key: &str, body: &str, validators: &CacheValidators) -> Result<(), String> {
+        self.insert(key, body, SystemTime::now(), validators.clone())
+    }
+
+    fn touch(&self, key: &str, written_at: SystemTime) -> Result<(), String> {
+        let mut state = self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Some((entry, _)) = state.entries.get_mut(key) {
+            entry.metadata.written_at = written_at;
+        }
+        Ok(())
+    }
+
+    fn delete(&self, key: &str) -> Result<(), String> {
+        let mut state = self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let boxed_removed = state.entries.remove(key);
+        if let Some((_, last_used)) = boxed_removed {
+            state.recently_used.remove(&last_used);
+        }
+        Ok(())
+    }
+
+    fn list(&self, prefix: &str) -> Result<Vec<CacheMetadata>, String> {
+        let state = self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let mut entries : Vec<CacheMetadata> = state.entries.values()
+            .filter(|(entry, _)| entry.metadata.key.starts_with(prefix))
+            .map(|(entry, _)| entry.metadata.clone())
+            .collect();
+        entries.sort_by(|a, b| a.key.cmp(&b.key));
+        Ok(entries)
+    }
+
+    fn get_location(&self, key: &str) -> String {
+        ["memory:", key].join("")
+    }
+}
@@ -0,0 +1,102 @@
+use std::fmt::Debug;
+use std::time::SystemTime;
+
+pub mod filesystem;
+pub mod maintenance;
+pub mod memory;
+pub mod policy;
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
+
+pub use crate::cache::filesystem::{CacheCompression, FilesystemCache};
+pub use crate::cache::memory::MemoryCache;
+pub use crate::cache::policy::{CacheMode, CachePolicy};
+#[cfg(feature = "sqlite")]
+pub use crate::cache::sqlite::SqliteCache;
+
+#[cfg(test)]
+mod tests;
+
+/// Describes a cached response without its body.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct CacheMetadata {
+    /// Key of the entry, the path of the resource relative to the cache root, e.g.
+    /// `"steampowered/appdetails/0/730/730.json"` or `"ISteamApps-GetAppList-v2.json"`.
+    pub key: String,
+    /// Size of the body in bytes as stored, i.e. compressed for compressed entries.
+    pub size: u64,
+    /// When the entry was written.
+    pub written_at: SystemTime,
+    /// Validators of the response the entry holds, empty if Steam sent none or the store doesn't
+    /// keep them.
+    pub validators: CacheValidators,
+}
+
+/// Validators Steam sent along with a response, its `ETag` and `Last-Modified` headers. They are
+/// sent back as `If-None-Match` and `If-Modified-Since` when the response is refreshed, so an
+/// unchanged resource costs a `304 Not Modified` rather than the whole body.
+#[derive(PartialEq, Eq, Clone, Debug, Default)]
+pub struct CacheValidators {
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+}
+
+impl CacheValidators {
+    pub fn is_empty(&self) -> bool {
+        self.etag.is_none() && self.last_modified.is_none()
+    }
+}
+
+/// Cached response body along with its metadata.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct CacheEntry {
+    pub metadata: CacheMetadata,
+    pub body: String,
+}
+
+/// Storage for cached responses, keyed by the resource path relative to the cache root (see the
+/// `get_cache_key` function of caching endpoints). `SteamClient` reads and writes through its
+/// store, `FilesystemCache` by default.
+///
+/// Errors are reported as messages, mirroring `transport::Transport`; a missing key is not an
+/// error.
+pub trait CacheStore: Send + Sync + Debug {
+    /// Returns the entry stored under `key`, if any.
+    fn get(&self, key: &str) -> Result<Option<CacheEntry>, String>;
+
+    /// Stores `body` under `key`, replacing the previous entry.
+    fn put(&self, key: &str, body: &str) -> Result<(), String>;
+
+    /// Stores `body` under `key` as if it had been written at `written_at`, e.g. when importing an
+    /// archived cache. Stores that can't keep the timestamp fall back to `put`.
+    fn put_with_written_at(&self, key: &str, body: &str, written_at: SystemTime) -> Result<(), String> {
+        let _ = written_at;
+        self.put(key, body)
+    }
+
+    /// Stores `body` under `key` along with the validators of the response it came from. Stores
+    /// that can't keep validators fall back to `put`.
+    fn put_with_validators(&self, key: &str, body: &str, validators: &CacheValidators) -> Result<(), String> {
+        let _ = validators;
+        self.put(key, body)
+    }
+
+    /// Marks the entry stored under `key` as written at `written_at` without replacing its body,
+    /// e.g. after Steam answered `304 Not Modified`. Touching a missing key is not an error.
+    fn touch(&self, key: &str, written_at: SystemTime) -> Result<(), String> {
+        let boxed_entry = self.get(key)?;
+        if let Some(entry) = boxed_entry {
+            return self.put_with_written_at(key, entry.body.as_str(), written_at);
+        }
+        Ok(())
+    }
+
+    /// Removes the entry stored under `key`. Removing a missing key is not an error.
+    fn delete(&self, key: &str) -> Result<(), String>;
+
+    /// Returns metadata of every entry whose key starts with `prefix`, sorted by key.
+    fn list(&self, prefix: &str) -> Result<Vec<CacheMetadata>, String>;
+
+    /// Describes where the entry lives, for error messages: a filepath for `FilesystemCache`.
+    fn get_location(&self, key: &str) -> String;
+}
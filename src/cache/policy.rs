@@ -0,0 +1,120 @@
+use std::collections::HashMap;
+use std::time::{Duration, SystemTime};
+
+/// Time to live of `ISteamApps/GetAppList`, new apps show up daily.
+pub const DEFAULT_APP_LIST_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+/// Time to live of `steampowered/appdetails`, prices and descriptions change every now and then.
+pub const DEFAULT_APP_DETAILS_TTL: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+/// Time to live of endpoints reporting what happens right now: live league games and the number of
+/// current players.
+pub const DEFAULT_LIVE_TTL: Duration = Duration::from_secs(60);
+/// Time to live of player summaries (online status) and match history (new matches keep coming).
+pub const DEFAULT_PLAYER_ACTIVITY_TTL: Duration = Duration::from_secs(5 * 60);
+/// Time to live of endpoints without a specific one, e.g. achievement schemas and hero lists.
+pub const DEFAULT_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// How the `get_cached` functions combine the cache and the network.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum CacheMode {
+    /// Only read the cache, however old the entry. A missing entry is a `SteamApiError::CacheMiss`.
+    CacheOnly,
+    /// Always call Steam, the response is still written to the cache.
+    NetworkOnly,
+    /// Serve a fresh cache entry, call Steam if it is missing or stale.
+    CacheThenNetworkIfStale,
+    /// Like `CacheThenNetworkIfStale`, but if calling Steam fails serve the stale entry rather than
+    /// the error.
+    StaleWhileError,
+}
+
+/// Decides when cached responses are fresh enough to serve. Each endpoint (as returned by its
+/// `get_endpoint` function) may have its own time to live; `None` means entries never go stale,
+/// which suits immutable resources such as finished Dota 2 matches.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use steam_webapi_rust_sdk::cache::{CacheMode, CachePolicy};
+/// use steam_webapi_rust_sdk::client::SteamClient;
+///
+/// let cache_policy = CachePolicy::default()
+///     .with_mode(CacheMode::StaleWhileError)
+///     .with_ttl("steampowered/appdetails", Some(Duration::from_secs(60 * 60)));
+///
+/// let client = SteamClient::builder()
+///     .cache_policy(cache_policy)
+///     .build();
+/// ```
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct CachePolicy {
+    mode: CacheMode,
+    default_ttl: Option<Duration>,
+    ttls: HashMap<String, Option<Duration>>,
+}
+
+impl Default for CachePolicy {
+    /// `CacheThenNetworkIfStale` with a day for the app list, a week for appdetails, forever for
+    /// match details, a minute for live data, five minutes for player summaries and match history
+    /// and a day for everything else.
+    fn default() -> CachePolicy {
+        CachePolicy {
+            mode: CacheMode::CacheThenNetworkIfStale,
+            default_ttl: Some(DEFAULT_TTL),
+            ttls: HashMap::new(),
+        }
+            .with_ttl("ISteamApps/GetAppList", Some(DEFAULT_APP_LIST_TTL))
+            .with_ttl("steampowered/appdetails", Some(DEFAULT_APP_DETAILS_TTL))
+            .with_ttl("IDOTA2Match_570/GetMatchDetails", None)
+            .with_ttl("IDOTA2Match_570/GetLiveLeagueGames", Some(DEFAULT_LIVE_TTL))
+            .with_ttl("ISteamUserStats/GetNumberOfCurrentPlayers", Some(DEFAULT_LIVE_TTL))
+            .with_ttl("ISteamUser/GetPlayerSummaries", Some(DEFAULT_PLAYER_ACTIVITY_TTL))
+            .with_ttl("IDOTA2Match_570/GetMatchHistory", Some(DEFAULT_PLAYER_ACTIVITY_TTL))
+    }
+}
+
+impl CachePolicy {
+    pub fn with_mode(mut self, mode: CacheMode) -> CachePolicy {
+        self.mode = mode;
+        self
+    }
+
+    /// Sets the time to live of endpoints without a specific one.
+    pub fn with_default_ttl(mut self, ttl: Option<Duration>) -> CachePolicy {
+        self.default_ttl = ttl;
+        self
+    }
+
+    /// Sets the time to live of `endpoint`, e.g. `"steampowered/appdetails"`.
+    pub fn with_ttl(mut self, endpoint: &str, ttl: Option<Duration>) -> CachePolicy {
+        self.ttls.insert(endpoint.to_string(), ttl);
+        self
+    }
+
+    pub fn get_mode(&self) -> CacheMode {
+        self.mode
+    }
+
+    pub fn get_ttl(&self, endpoint: &str) -> Option<Duration> {
+        let boxed_ttl = self.ttls.get(endpoint);
+        if boxed_ttl.is_none() {
+            return self.default_ttl;
+        }
+        *boxed_ttl.unwrap()
+    }
+
+    /// Whether an entry of `endpoint` written at `written_at` may still be served.
+    pub fn is_fresh(&self, endpoint: &str, written_at: SystemTime) -> bool {
+        let boxed_ttl = self.get_ttl(endpoint);
+        if boxed_ttl.is_none() {
+            return true;
+        }
+
+        let boxed_age = SystemTime::now().duration_since(written_at);
+        if boxed_age.is_err() {
+            // written in the future as far as this clock is concerned
+            return true;
+        }
+        boxed_age.unwrap() <= boxed_ttl.unwrap()
+    }
+}
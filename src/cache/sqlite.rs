@@ -0,0 +1,212 @@
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use rusqlite::{params, Connection, OptionalExtension};
+use crate::cache::{CacheEntry, CacheMetadata, CacheStore, CacheValidators};
+
+/// Keeps every entry as a row of a single SQLite database file, available with the `sqlite`
+/// feature. Handy when millions of appdetails files are too much for the filesystem.
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::Arc;
+/// use steam_webapi_rust_sdk::cache::{CacheStore, SqliteCache};
+/// use steam_webapi_rust_sdk::client::SteamClient;
+///
+/// let cache = SqliteCache::open_in_memory().unwrap();
+/// cache.put("ISteamApps-GetAppList-v2.json", "{}").unwrap();
+/// assert_eq!(1, cache.list("").unwrap().len());
+///
+/// let client = SteamClient::builder()
+///     .cache_store(Arc::new(cache))
+///     .build();
+/// ```
+#[derive(Debug)]
+pub struct SqliteCache {
+    location: String,
+    connection: Mutex<Connection>,
+}
+
+impl SqliteCache {
+    /// Opens (or creates) the database at `path`.
+    pub fn open(path: &str) -> Result<SqliteCache, String> {
+        let boxed_connection = Connection::open(path);
+        if boxed_connection.is_err() {
+            return Err(boxed_connection.err().unwrap().to_string());
+        }
+        SqliteCache::init(path, boxed_connection.unwrap())
+    }
+
+    /// Opens a database living in memory only.
+    pub fn open_in_memory() -> Result<SqliteCache, String> {
+        let boxed_connection = Connection::open_in_memory();
+        if boxed_connection.is_err() {
+            return Err(boxed_connection.err().unwrap().to_string());
+        }
+        SqliteCache::init(":memory:", boxed_connection.unwrap())
+    }
+
+    fn insert(&self, key: &str, body: &str, written_at: SystemTime, validators: &CacheValidators) -> Result<(), String> {
+        let connection = self.connection.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let boxed_insert = connection.execute(
+            "INSERT OR REPLACE INTO cache_entries (key, body, size, written_at_millis, etag, last_modified) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![key, body, body.len() as i64, to_millis(written_at), validators.etag, validators.last_modified],
+        );
+        if boxed_insert.is_err() {
+            return Err(boxed_insert.err().unwrap().to_string());
+        }
+        Ok(())
+    }
+
+    fn init(location: &str, connection: Connection) -> Result<SqliteCache, String> {
+        let boxed_create = connection.execute(
+            "CREATE TABLE IF NOT EXISTS cache_entries (
+                key TEXT PRIMARY KEY NOT NULL,
+                body TEXT NOT NULL,
+                size INTEGER NOT NULL,
+                written_at_millis INTEGER NOT NULL,
+                etag TEXT,
+                last_modified TEXT
+            )",
+            [],
+        );
+        if boxed_create.is_err() {
+            return Err(boxed_create.err().unwrap().to_string());
+        }
+
+        // databases created before validators were kept lack their columns
+        let boxed_validator_columns = connection.query_row(
+            "SELECT COUNT(*) FROM pragma_table_info('cache_entries') WHERE name IN ('etag', 'last_modified')",
+            [],
+            |row| row.get::<usize, i64>(0),
+        );
+        if boxed_validator_columns.is_err() {
+            return Err(boxed_validator_columns.err().unwrap().to_string());
+        }
+        if boxed_validator_columns.unwrap() == 0 {
+            let boxed_alter = connection.execute_batch(
+                "ALTER TABLE cache_entries ADD COLUMN etag TEXT;
+                ALTER TABLE cache_entries ADD COLUMN last_modified TEXT;",
+            );
+            if boxed_alter.is_err() {
+                return Err(boxed_alter.err().unwrap().to_string());
+            }
+        }
+
+        Ok(SqliteCache {
+            location: location.to_string(),
+            connection: Mutex::new(connection),
+        })
+    }
+}
+
+fn to_millis(system_time: SystemTime) -> i64 {
+    system_time.duration_since(UNIX_EPOCH).unwrap_or_default().as_millis() as i64
+}
+
+fn from_millis(millis: i64) -> SystemTime {
+    UNIX_EPOCH + Duration::from_millis(millis.max(0) as u64)
+}
+
+impl CacheStore for SqliteCache {
+    fn get(&self, key: &str) -> Result<Option<CacheEntry>, String> {
+        let connection = self.connection.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let boxed_row = connection.query_row(
+            "SELECT body, size, written_at_millis, etag, last_modified FROM cache_entries WHERE key = ?1",
+            params![key],
+            |row| {
+                let body : String = row.get(0)?;
+                let size : i64 = row.get(1)?;
+                let written_at_millis : i64 = row.get(2)?;
+                Ok(CacheEntry {
+                    metadata: CacheMetadata {
+                        key: key.to_string(),
+                        size: size as u64,
+                        written_at: from_millis(written_at_millis),
+                        validators: CacheValidators {
+                            etag: row.get(3)?,
+                            last_modified: row.get(4)?,
+                        },
+                    },
+                    body,
+                })
+            },
+        ).optional();
+        if boxed_row.is_err() {
+            return Err(boxed_row.err().unwrap().to_string());
+        }
+        Ok(boxed_row.unwrap())
+    }
+
+    fn put(&self, key: &str, body: &str) -> Result<(), String> {
+        self.put_with_written_at(key, body, SystemTime::now())
+    }
+
+    fn put_with_written_at(&self, key: &str, body: &str, written_at: SystemTime) -> Result<(), String> {
+        self.insert(key, body, written_at, &CacheValidators::default())
+    }
+
+    fn put_with_validators(&self, key: &str, body: &str, validators: &CacheValidators) -> Result<(), String> {
+        self.insert(key, body, SystemTime::now(), validators)
+    }
+
+    fn touch(&self, key: &str, written_at: SystemTime) -> Result<(), String> {
+        let connection = self.connection.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let boxed_update = connection.execute(
+            "UPDATE cache_entries SET written_at_millis = ?2 WHERE key = ?1",
+            params![key, to_millis(written_at)],
+        );
+        if boxed_update.is_err() {
+            return Err(boxed_update.err().unwrap().to_string());
+        }
+        Ok(())
+    }
+
+    fn delete(&self, key: &str) -> Result<(), String> {
+        let connection = self.connection.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let boxed_delete = connection.execute("DELETE FROM cache_entries WHERE key = ?1", params![key]);
+        if boxed_delete.is_err() {
+            return Err(boxed_delete.err().unwrap().to_string());
+        }
+        Ok(())
+    }
+
+    fn list(&self, prefix: &str) -> Result<Vec<CacheMetadata>, String> {
+        let connection = self.connection.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let boxed_statement = connection.prepare(
+            "SELECT key, size, written_at_millis, etag, last_modified FROM cache_entries WHERE substr(key, 1, length(?1)) = ?1 ORDER BY key",
+        );
+        if boxed_statement.is_err() {
+            return Err(boxed_statement.err().unwrap().to_string());
+        }
+        let mut statement = boxed_statement.unwrap();
+
+        let boxed_rows = statement.query_map(params![prefix], |row| {
+            let key : String = row.get(0)?;
+            let size : i64 = row.get(1)?;
+            let written_at_millis : i64 = row.get(2)?;
+            Ok(CacheMetadata {
+                key,
+                size: size as u64,
+                written_at: from_millis(written_at_millis),
+                validators: CacheValidators {
+                    etag: row.get(3)?,
+                    last_modified: row.get(4)?,
+                },
+            })
+        });
+        if boxed_rows.is_err() {
+            return Err(boxed_rows.err().unwrap().to_string());
+        }
+
+        let boxed_entries : Result<Vec<CacheMetadata>, rusqlite::Error> = boxed_rows.unwrap().collect();
+        if boxed_entries.is_err() {
+            return Err(boxed_entries.err().unwrap().to_string());
+        }
+        Ok(boxed_entries.unwrap())
+    }
+
+    fn get_location(&self, key: &str) -> String {
+        ["sqlite:", self.location.as_str(), "#", key].join("")
+    }
+}
@@ -0,0 +1,822 @@
+use std::fs::read_to_string;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use crate::cache::{CacheMode, CachePolicy, CacheStore, CacheValidators, FilesystemCache, MemoryCache};
+use crate::cache::filesystem::{break_stale_lock, DEFAULT_LOCK_TIMEOUT, STALE_LOCK_AGE};
+use crate::cache::maintenance::{list_entries, prune, summarize, verify, PruneOptions};
+use crate::client::SteamClient;
+use crate::retry::RetryPolicy;
+use crate::store_steampowered_com::appdetails;
+use crate::transport::{FakeTransport, HttpResponse};
+use crate::util::build_cache_key;
+
+fn get_temp_cache_dir(test_name: &str) -> PathBuf {
+    let cache_dir = std::env::temp_dir()
+        .join(["steam-webapi-rust-sdk-", test_name, "-", std::process::id().to_string().as_str()].join(""));
+    let _ = std::fs::remove_dir_all(cache_dir.as_path());
+    cache_dir
+}
+
+fn get_app_list_client(cache: Arc<MemoryCache>, transport: Arc<FakeTransport>, cache_policy: CachePolicy) -> SteamClient {
+    SteamClient::builder()
+        .api_key("KEY")
+        .transport(transport)
+        .cache_store(cache)
+        .cache_policy(cache_policy)
+        .retry_policy(RetryPolicy::none())
+        .build()
+}
+
+fn assert_store_roundtrip(cache: &dyn CacheStore) {
+    assert_eq!(None, cache.get("ISteamApps-GetAppList-v2.json").unwrap());
+
+    cache.put("ISteamApps-GetAppList-v2.json", "{\"applist\":{}}").unwrap();
+    cache.put("steampowered/appdetails/0/730/730.json", "{\"730\":{}}").unwrap();
+    cache.put("steampowered/appdetails/1/10010/10010.json", "{\"10010\":{}}").unwrap();
+
+    let entry = cache.get("ISteamApps-GetAppList-v2.json").unwrap().unwrap();
+    assert_eq!("{\"applist\":{}}", entry.body);
+    assert_eq!("ISteamApps-GetAppList-v2.json", entry.metadata.key);
+    assert_eq!(14, entry.metadata.size);
+
+    cache.put("ISteamApps-GetAppList-v2.json", "{}").unwrap();
+    assert_eq!("{}", cache.get("ISteamApps-GetAppList-v2.json").unwrap().unwrap().body);
+
+    let keys : Vec<String> = cache.list("").unwrap().into_iter().map(|metadata| metadata.key).collect();
+    assert_eq!(vec![
+        "ISteamApps-GetAppList-v2.json".to_string(),
+        "steampowered/appdetails/0/730/730.json".to_string(),
+        "steampowered/appdetails/1/10010/10010.json".to_string(),
+    ], keys);
+
+    let keys : Vec<String> = cache.list("steampowered/appdetails/").unwrap().into_iter().map(|metadata| metadata.key).collect();
+    assert_eq!(2, keys.len());
+
+    cache.delete("steampowered/appdetails/0/730/730.json").unwrap();
+    cache.delete("steampowered/appdetails/0/730/730.json").unwrap();
+    assert_eq!(None, cache.get("steampowered/appdetails/0/730/730.json").unwrap());
+    assert_eq!(1, cache.list("steampowered/").unwrap().len());
+}
+
+#[test]
+fn filesystem_roundtrip() {
+    let cache_dir = get_temp_cache_dir("filesystem_roundtrip");
+    let cache = FilesystemCache::new(cache_dir.to_str().unwrap());
+
+    assert_store_roundtrip(&cache);
+}
+
+#[test]
+fn filesystem_uses_existing_layout() {
+    let cache_dir = get_temp_cache_dir("filesystem_uses_existing_layout");
+    let cache = FilesystemCache::new(cache_dir.to_str().unwrap());
+
+    cache.put(appdetails::get_cache_key(730).as_str(), "{}").unwrap();
+    cache.put(appdetails::get_cache_key(1070410).as_str(), "{}").unwrap();
+
+    assert!(cache_dir.join("steampowered/appdetails/0/730/730.json").is_file());
+    assert!(cache_dir.join("steampowered/appdetails/107/1070410/1070410.json").is_file());
+    assert_eq!(
+        [cache_dir.to_str().unwrap(), "/steampowered/appdetails/0/730/730.json"].join(""),
+        cache.get_location(appdetails::get_cache_key(730).as_str())
+    );
+}
+
+#[test]
+fn filesystem_reads_files_written_before() {
+    let cache_dir = get_temp_cache_dir("filesystem_reads_files_written_before");
+    std::fs::create_dir_all(cache_dir.as_path()).unwrap();
+    let response = read_to_string("test/idota2match_570/get_match_details/1461414523.json").unwrap();
+    std::fs::write(cache_dir.join("IDOTA2Match_570-GetMatchDetails-v1-1461414523.json"), response.as_str()).unwrap();
+
+    let cache = FilesystemCache::new(cache_dir.to_str().unwrap());
+
+    let entry = cache.get("IDOTA2Match_570-GetMatchDetails-v1-1461414523.json").unwrap().unwrap();
+    assert_eq!(response, entry.body);
+}
+
+#[cfg(any(feature = "gzip", feature = "zstd"))]
+fn assert_compressed_roundtrip(test_name: &str, compression: crate::cache::CacheCompression, suffix: &str) {
+    let cache_dir = get_temp_cache_dir(test_name);
+    std::fs::create_dir_all(cache_dir.as_path()).unwrap();
+    let response = read_to_string("test/idota2match_570/get_match_details/1461414523.json").unwrap();
+    std::fs::write(cache_dir.join("IDOTA2Match_570-GetMatchDetails-v1-1461414523.json"), response.as_str()).unwrap();
+
+    let cache = FilesystemCache::new(cache_dir.to_str().unwrap()).with_compression(compression);
+
+    // plain files written before are still read
+    assert_eq!(response, cache.get("IDOTA2Match_570-GetMatchDetails-v1-1461414523.json").unwrap().unwrap().body);
+
+    cache.put("IDOTA2Match_570-GetMatchDetails-v1-1461414523.json", response.as_str()).unwrap();
+    let compressed_filepath = cache_dir.join(["IDOTA2Match_570-GetMatchDetails-v1-1461414523.json", suffix].join(""));
+    assert!(compressed_filepath.is_file());
+    assert!(!cache_dir.join("IDOTA2Match_570-GetMatchDetails-v1-1461414523.json").exists());
+    assert_eq!(compressed_filepath.to_str().unwrap(), cache.get_location("IDOTA2Match_570-GetMatchDetails-v1-1461414523.json"));
+
+    let entry = cache.get("IDOTA2Match_570-GetMatchDetails-v1-1461414523.json").unwrap().unwrap();
+    assert_eq!(response, entry.body);
+    assert!(entry.metadata.size < response.len() as u64);
+
+    cache.put("ISteamApps-GetAppList-v2.json", "{}").unwrap();
+    let keys : Vec<String> = cache.list("").unwrap().into_iter().map(|metadata| metadata.key).collect();
+    assert_eq!(vec!["IDOTA2Match_570-GetMatchDetails-v1-1461414523.json".to_string(), "ISteamApps-GetAppList-v2.json".to_string()], keys);
+
+    // an uncompressing cache reads compressed entries too, and replaces them with plain ones
+    let plain_cache = FilesystemCache::new(cache_dir.to_str().unwrap());
+    assert_eq!(response, plain_cache.get("IDOTA2Match_570-GetMatchDetails-v1-1461414523.json").unwrap().unwrap().body);
+    plain_cache.put("IDOTA2Match_570-GetMatchDetails-v1-1461414523.json", "{}").unwrap();
+    assert!(!compressed_filepath.exists());
+    assert_eq!("{}", cache.get("IDOTA2Match_570-GetMatchDetails-v1-1461414523.json").unwrap().unwrap().body);
+
+    cache.delete("ISteamApps-GetAppList-v2.json").unwrap();
+    assert_eq!(None, cache.get("ISteamApps-GetAppList-v2.json").unwrap());
+    assert_eq!(1, cache.list("").unwrap().len());
+
+    // corrupt compressed entries are evicted like corrupt plain ones
+    std::fs::write(cache_dir.join(["ISteamApps-GetAppList-v2.json", suffix].join("")), "{}").unwrap();
+    assert_eq!(None, cache.get("ISteamApps-GetAppList-v2.json").unwrap());
+    assert!(!cache_dir.join(["ISteamApps-GetAppList-v2.json", suffix].join("")).exists());
+}
+
+#[cfg(feature = "gzip")]
+#[test]
+fn filesystem_gzip_roundtrip() {
+    assert_compressed_roundtrip("filesystem_gzip_roundtrip", crate::cache::CacheCompression::Gzip, ".gz");
+}
+
+#[cfg(feature = "zstd")]
+#[test]
+fn filesystem_zstd_roundtrip() {
+    assert_compressed_roundtrip("filesystem_zstd_roundtrip", crate::cache::CacheCompression::Zstd, ".zst");
+}
+
+#[cfg(not(feature = "zstd"))]
+#[test]
+fn filesystem_needs_feature_to_read_compressed_entries() {
+    let cache_dir = get_temp_cache_dir("filesystem_needs_feature_to_read_compressed_entries");
+    std::fs::create_dir_all(cache_dir.as_path()).unwrap();
+    std::fs::write(cache_dir.join("ISteamApps-GetAppList-v2.json.zst"), [0x28, 0xb5, 0x2f, 0xfd]).unwrap();
+
+    let cache = FilesystemCache::new(cache_dir.to_str().unwrap());
+
+    assert!(cache.get("ISteamApps-GetAppList-v2.json").err().unwrap().contains("`zstd` feature"));
+    assert!(cache_dir.join("ISteamApps-GetAppList-v2.json.zst").is_file());
+    assert_eq!("ISteamApps-GetAppList-v2.json", cache.list("").unwrap()[0].key);
+}
+
+#[test]
+fn memory_roundtrip() {
+    let cache = MemoryCache::new(10);
+
+    assert_store_roundtrip(&cache);
+}
+
+#[test]
+fn memory_evicts_least_recently_used() {
+    let cache = MemoryCache::new(2);
+
+    cache.put("a.json", "a").unwrap();
+    cache.put("b.json", "b").unwrap();
+    cache.get("a.json").unwrap();
+    cache.put("c.json", "c").unwrap();
+
+    assert_eq!(2, cache.len());
+    assert!(cache.get("a.json").unwrap().is_some());
+    assert!(cache.get("b.json").unwrap().is_none());
+    assert!(cache.get("c.json").unwrap().is_some());
+
+    cache.put("a.json", "a2").unwrap();
+    cache.put("d.json", "d").unwrap();
+    assert!(cache.get("c.json").unwrap().is_none());
+    assert_eq!("a2", cache.get("a.json").unwrap().unwrap().body);
+}
+
+#[cfg(feature = "sqlite")]
+#[test]
+fn sqlite_roundtrip() {
+    let cache = crate::cache::SqliteCache::open_in_memory().unwrap();
+
+    assert_store_roundtrip(&cache);
+}
+
+#[cfg(feature = "sqlite")]
+#[test]
+fn sqlite_persists_to_file() {
+    let cache_dir = get_temp_cache_dir("sqlite_persists_to_file");
+    std::fs::create_dir_all(cache_dir.as_path()).unwrap();
+    let database = cache_dir.join("cache.sqlite");
+
+    let cache = crate::cache::SqliteCache::open(database.to_str().unwrap()).unwrap();
+    cache.put("ISteamApps-GetAppList-v2.json", "{}").unwrap();
+    drop(cache);
+
+    let cache = crate::cache::SqliteCache::open(database.to_str().unwrap()).unwrap();
+    assert_eq!("{}", cache.get("ISteamApps-GetAppList-v2.json").unwrap().unwrap().body);
+}
+
+#[test]
+fn client_reads_and_writes_through_store() {
+    let transport = Arc::new(FakeTransport::new());
+    let app_details = read_to_string("test/store_steampowered_com/appdetails/730.json").unwrap();
+    transport.respond_with("https://store.steampowered.com/api/appdetails?appids=730&lang=en", 200, app_details.as_str());
+
+    let cache = Arc::new(MemoryCache::new(10));
+    let client = SteamClient::builder()
+        .transport(transport.clone())
+        .cache_store(cache.clone())
+        .cache_policy(CachePolicy::default().with_mode(CacheMode::CacheOnly))
+        .build();
+
+    assert!(client.get_cached_app_details(730).is_err());
+
+    client.get_app_details(730).unwrap();
+    assert_eq!(1, cache.len());
+
+    let app = client.get_cached_app_details(730).unwrap();
+    assert_eq!("Counter-Strike: Global Offensive", app.name);
+    assert_eq!(1, transport.requests().len());
+    assert_eq!("memory:steampowered/appdetails/0/730/730.json", client.get_resource_filepath(appdetails::get_cache_key(730).as_str()));
+}
+
+#[test]
+fn policy_defaults() {
+    let cache_policy = CachePolicy::default();
+
+    assert_eq!(CacheMode::CacheThenNetworkIfStale, cache_policy.get_mode());
+    assert_eq!(Some(Duration::from_secs(24 * 60 * 60)), cache_policy.get_ttl("ISteamApps/GetAppList"));
+    assert_eq!(Some(Duration::from_secs(7 * 24 * 60 * 60)), cache_policy.get_ttl("steampowered/appdetails"));
+    assert_eq!(None, cache_policy.get_ttl("IDOTA2Match_570/GetMatchDetails"));
+    assert_eq!(Some(Duration::from_secs(24 * 60 * 60)), cache_policy.get_ttl("IDOTA2Match_570/GetHeroes"));
+}
+
+#[test]
+fn policy_freshness() {
+    let cache_policy = CachePolicy::default()
+        .with_ttl("steampowered/appdetails", Some(Duration::from_secs(60)));
+
+    let now = SystemTime::now();
+    let two_minutes_ago = now - Duration::from_secs(120);
+    let ten_years_ago = now - Duration::from_secs(10 * 365 * 24 * 60 * 60);
+
+    assert!(cache_policy.is_fresh("steampowered/appdetails", now));
+    assert!(!cache_policy.is_fresh("steampowered/appdetails", two_minutes_ago));
+    assert!(cache_policy.is_fresh("IDOTA2Match_570/GetMatchDetails", ten_years_ago));
+    assert!(cache_policy.is_fresh("steampowered/appdetails", now + Duration::from_secs(60)));
+}
+
+#[test]
+fn filesystem_stores_write_timestamp() {
+    let cache_dir = get_temp_cache_dir("filesystem_stores_write_timestamp");
+    let cache = FilesystemCache::new(cache_dir.to_str().unwrap());
+
+    let before = SystemTime::now() - Duration::from_secs(1);
+    cache.put("ISteamApps-GetAppList-v2.json", "{}").unwrap();
+    assert!(cache_dir.join("ISteamApps-GetAppList-v2.json.meta").is_file());
+
+    let metadata = cache.get("ISteamApps-GetAppList-v2.json").unwrap().unwrap().metadata;
+    assert!(metadata.written_at >= before);
+    assert_eq!(vec![metadata], cache.list("").unwrap());
+
+    let written_at_millis = 1_500_000_000_000u64;
+    std::fs::write(
+        cache_dir.join("ISteamApps-GetAppList-v2.json.meta"),
+        ["{\"written_at_millis\":", written_at_millis.to_string().as_str(), "}"].join(""),
+    ).unwrap();
+    let metadata = cache.get("ISteamApps-GetAppList-v2.json").unwrap().unwrap().metadata;
+    assert_eq!(UNIX_EPOCH + Duration::from_millis(written_at_millis), metadata.written_at);
+
+    cache.delete("ISteamApps-GetAppList-v2.json").unwrap();
+    assert!(!cache_dir.join("ISteamApps-GetAppList-v2.json.meta").exists());
+}
+
+#[test]
+fn filesystem_ignores_metadata_older_than_body() {
+    let cache_dir = get_temp_cache_dir("filesystem_ignores_metadata_older_than_body");
+    let cache = FilesystemCache::new(cache_dir.to_str().unwrap());
+    let validators = CacheValidators { etag: Some("\"v1\"".to_string()), last_modified: None };
+    cache.put_with_validators("ISteamApps-GetAppList-v2.json", "{}", &validators).unwrap();
+    assert_eq!(validators, cache.get("ISteamApps-GetAppList-v2.json").unwrap().unwrap().metadata.validators);
+
+    // a write that renamed the new body into place and crashed before writing its .meta file
+    thread::sleep(Duration::from_millis(10));
+    std::fs::write(cache_dir.join("ISteamApps-GetAppList-v2.json"), "{\"applist\":{}}").unwrap();
+    let body_modified = std::fs::metadata(cache_dir.join("ISteamApps-GetAppList-v2.json")).unwrap().modified().unwrap();
+
+    let entry = cache.get("ISteamApps-GetAppList-v2.json").unwrap().unwrap();
+    assert_eq!("{\"applist\":{}}", entry.body);
+    assert!(entry.metadata.validators.is_empty());
+    assert_eq!(body_modified, entry.metadata.written_at);
+}
+
+#[test]
+fn client_refreshes_stale_entries() {
+    let transport = Arc::new(FakeTransport::new());
+    transport.respond_with("https://api.steampowered.com/ISteamApps/GetAppList/v2?key=KEY", 200, r#"{"applist":{"apps":[{"appid":570,"name":"Dota 2"}]}}"#);
+    let cache = Arc::new(MemoryCache::new(10));
+    cache.put("ISteamApps-GetAppList-v2.json", r#"{"applist":{"apps":[{"appid":10,"name":"Counter-Strike"}]}}"#).unwrap();
+
+    let fresh_client = get_app_list_client(cache.clone(), transport.clone(), CachePolicy::default());
+    assert_eq!("Counter-Strike", fresh_client.get_cached_app_list().unwrap()[0].name);
+    assert_eq!(0, transport.requests().len());
+
+    thread::sleep(Duration::from_millis(10));
+    let cache_policy = CachePolicy::default().with_ttl("ISteamApps/GetAppList", Some(Duration::from_millis(1)));
+    let stale_client = get_app_list_client(cache.clone(), transport.clone(), cache_policy);
+    assert_eq!("Dota 2", stale_client.get_cached_app_list().unwrap()[0].name);
+    assert_eq!(1, transport.requests().len());
+
+    assert_eq!("Dota 2", fresh_client.get_cached_app_list().unwrap()[0].name);
+    assert_eq!(1, transport.requests().len());
+}
+
+#[test]
+fn client_read_modes() {
+    let transport = Arc::new(FakeTransport::new());
+    transport.respond_with("https://api.steampowered.com/ISteamApps/GetAppList/v2?key=KEY", 503, "");
+    let cache = Arc::new(MemoryCache::new(10));
+    let stale_policy = CachePolicy::default().with_ttl("ISteamApps/GetAppList", Some(Duration::from_millis(1)));
+
+    let cache_only_client = get_app_list_client(cache.clone(), transport.clone(), stale_policy.clone().with_mode(CacheMode::CacheOnly));
+    let error = cache_only_client.get_cached_app_list().err().unwrap();
+    assert!(matches!(error, crate::SteamApiError::CacheMiss { .. }));
+    assert_eq!(0, transport.requests().len());
+
+    cache.put("ISteamApps-GetAppList-v2.json", r#"{"applist":{"apps":[{"appid":10,"name":"Counter-Strike"}]}}"#).unwrap();
+    thread::sleep(Duration::from_millis(10));
+
+    assert_eq!("Counter-Strike", cache_only_client.get_cached_app_list().unwrap()[0].name);
+    assert_eq!(0, transport.requests().len());
+
+    let network_only_client = get_app_list_client(cache.clone(), transport.clone(), CachePolicy::default().with_mode(CacheMode::NetworkOnly));
+    assert_eq!(Some(503), network_only_client.get_cached_app_list().err().unwrap().http_status_code());
+    assert_eq!(1, transport.requests().len());
+
+    let if_stale_client = get_app_list_client(cache.clone(), transport.clone(), stale_policy.clone());
+    assert_eq!(Some(503), if_stale_client.get_cached_app_list().err().unwrap().http_status_code());
+    assert_eq!(2, transport.requests().len());
+
+    let stale_while_error_client = get_app_list_client(cache.clone(), transport.clone(), stale_policy.with_mode(CacheMode::StaleWhileError));
+    assert_eq!("Counter-Strike", stale_while_error_client.get_cached_app_list().unwrap()[0].name);
+    assert_eq!(3, transport.requests().len());
+}
+
+#[test]
+fn client_caches_every_endpoint() {
+    let transport = Arc::new(FakeTransport::new());
+    transport.respond_with(
+        "https://api.steampowered.com/IDOTA2Match_570/GetHeroes/v1?key=KEY&language=en",
+        200,
+        r#"{"result":{"heroes":[{"name":"npc_dota_hero_antimage","id":1}],"count":1}}"#,
+    );
+    let cache = Arc::new(MemoryCache::new(10));
+    let client = get_app_list_client(cache.clone(), transport.clone(), CachePolicy::default());
+
+    let heroes = client.get_cached_dota2_heroes(Some("en".to_string())).unwrap();
+    assert_eq!("npc_dota_hero_antimage", heroes[0].name);
+    assert!(cache.get("IDOTA2Match_570-GetHeroes-v1-language=en.json").unwrap().is_some());
+
+    let heroes = client.get_cached_dota2_heroes(Some("en".to_string())).unwrap();
+    assert_eq!(1, heroes[0].id);
+    assert_eq!(1, transport.requests().len());
+
+    assert!(client.get_cached_dota2_heroes(None).is_err());
+    assert_eq!(2, transport.requests().len());
+}
+
+#[test]
+fn cache_key_hashes_long_parameters() {
+    let steamids = (0..100u64).map(|id| (76561197960265728 + id).to_string()).collect::<Vec<String>>().join(",");
+    let cache_key = build_cache_key("ISteamUser", "GetPlayerSummaries", "v2", vec![("steamids", steamids.clone())]);
+
+    assert!(cache_key.starts_with("ISteamUser-GetPlayerSummaries-v2-"));
+    assert!(cache_key.ends_with(".json"));
+    assert!(cache_key.len() < 64);
+    assert_eq!(cache_key, build_cache_key("ISteamUser", "GetPlayerSummaries", "v2", vec![("steamids", steamids)]));
+    assert_ne!(cache_key, build_cache_key("ISteamUser", "GetPlayerBans", "v1", vec![("steamids", "1".repeat(200))]));
+}
+
+#[test]
+fn filesystem_leaves_no_temp_or_lock_files() {
+    let cache_dir = get_temp_cache_dir("filesystem_leaves_no_temp_or_lock_files");
+    let cache = FilesystemCache::new(cache_dir.to_str().unwrap());
+
+    cache.put("steampowered/appdetails/0/730/730.json", "{}").unwrap();
+    cache.put("steampowered/appdetails/0/730/730.json", "{\"730\":{}}").unwrap();
+
+    let mut filenames : Vec<String> = std::fs::read_dir(cache_dir.join("steampowered/appdetails/0/730")).unwrap()
+        .map(|dir_entry| dir_entry.unwrap().file_name().to_string_lossy().to_string())
+        .collect();
+    filenames.sort();
+    assert_eq!(vec!["730.json".to_string(), "730.json.meta".to_string()], filenames);
+
+    std::fs::write(cache_dir.join("steampowered/appdetails/0/730/730.json.1234-0.tmp"), "{").unwrap();
+    assert_eq!(1, cache.list("").unwrap().len());
+}
+
+#[test]
+fn filesystem_concurrent_writers() {
+    let cache_dir = get_temp_cache_dir("filesystem_concurrent_writers");
+    let cache = Arc::new(FilesystemCache::new(cache_dir.to_str().unwrap()));
+    let bodies : Vec<String> = (0..8)
+        .map(|writer| ["{\"writer\":", writer.to_string().as_str(), ",\"padding\":\"", "x".repeat(64 * 1024).as_str(), "\"}"].join(""))
+        .collect();
+
+    let handles : Vec<thread::JoinHandle<()>> = bodies.iter()
+        .map(|body| {
+            let cache = cache.clone();
+            let body = body.clone();
+            thread::spawn(move || {
+                for _ in 0..10 {
+                    cache.put("ISteamApps-GetAppList-v2.json", body.as_str()).unwrap();
+                    let entry = cache.get("ISteamApps-GetAppList-v2.json").unwrap().unwrap();
+                    assert!(serde_json::from_str::<serde_json::Value>(entry.body.as_str()).is_ok());
+                }
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    let entry = cache.get("ISteamApps-GetAppList-v2.json").unwrap().unwrap();
+    assert!(bodies.contains(&entry.body));
+    assert!(!cache_dir.join("ISteamApps-GetAppList-v2.json.lock").exists());
+}
+
+#[test]
+fn filesystem_waits_for_lock() {
+    let cache_dir = get_temp_cache_dir("filesystem_waits_for_lock");
+    std::fs::create_dir_all(cache_dir.as_path()).unwrap();
+    let cache = FilesystemCache::new(cache_dir.to_str().unwrap())
+        .with_lock_timeout(Duration::from_millis(50));
+
+    std::fs::write(cache_dir.join("ISteamApps-GetAppList-v2.json.lock"), "1").unwrap();
+    assert!(cache.put("ISteamApps-GetAppList-v2.json", "{}").is_err());
+    assert!(cache.get("ISteamApps-GetAppList-v2.json").unwrap().is_none());
+
+    std::fs::remove_file(cache_dir.join("ISteamApps-GetAppList-v2.json.lock")).unwrap();
+    cache.put("ISteamApps-GetAppList-v2.json", "{}").unwrap();
+    assert_eq!("{}", cache.get("ISteamApps-GetAppList-v2.json").unwrap().unwrap().body);
+}
+
+#[test]
+fn filesystem_breaks_stale_locks() {
+    let cache_dir = get_temp_cache_dir("filesystem_breaks_stale_locks");
+    std::fs::create_dir_all(cache_dir.as_path()).unwrap();
+    let lock_path = cache_dir.join("ISteamApps-GetAppList-v2.json.lock");
+    std::fs::write(lock_path.as_path(), "1").unwrap();
+
+    assert!(STALE_LOCK_AGE < DEFAULT_LOCK_TIMEOUT);
+    assert!(!break_stale_lock(lock_path.as_path(), SystemTime::now()));
+    assert!(lock_path.exists());
+
+    assert!(break_stale_lock(lock_path.as_path(), SystemTime::now() + STALE_LOCK_AGE + Duration::from_secs(1)));
+    assert_eq!(0, std::fs::read_dir(cache_dir.as_path()).unwrap().count());
+}
+
+#[test]
+fn filesystem_evicts_invalid_utf8() {
+    let cache_dir = get_temp_cache_dir("filesystem_evicts_invalid_utf8");
+    std::fs::create_dir_all(cache_dir.as_path()).unwrap();
+    std::fs::write(cache_dir.join("ISteamApps-GetAppList-v2.json"), [b'{', 0xff, 0xfe]).unwrap();
+
+    let cache = FilesystemCache::new(cache_dir.to_str().unwrap());
+
+    assert_eq!(None, cache.get("ISteamApps-GetAppList-v2.json").unwrap());
+    assert!(!cache_dir.join("ISteamApps-GetAppList-v2.json").exists());
+}
+
+#[test]
+fn client_evicts_corrupt_entries() {
+    let transport = Arc::new(FakeTransport::new());
+    transport.respond_with("https://api.steampowered.com/ISteamApps/GetAppList/v2?key=KEY", 200, r#"{"applist":{"apps":[{"appid":570,"name":"Dota 2"}]}}"#);
+    let cache = Arc::new(MemoryCache::new(10));
+
+    cache.put("ISteamApps-GetAppList-v2.json", r#"{"applist":{"apps":[{"appid":5"#).unwrap();
+    let cache_only_client = get_app_list_client(cache.clone(), transport.clone(), CachePolicy::default().with_mode(CacheMode::CacheOnly));
+    let error = cache_only_client.get_cached_app_list().err().unwrap();
+    assert!(matches!(error, crate::SteamApiError::CacheMiss { .. }));
+    assert!(cache.is_empty());
+
+    cache.put("ISteamApps-GetAppList-v2.json", r#"{"applist":{"apps":[{"appid":5"#).unwrap();
+    let client = get_app_list_client(cache.clone(), transport.clone(), CachePolicy::default());
+    assert_eq!("Dota 2", client.get_cached_app_list().unwrap()[0].name);
+    assert_eq!(1, transport.requests().len());
+
+    assert_eq!("Dota 2", cache_only_client.get_cached_app_list().unwrap()[0].name);
+}
+
+fn get_aged_cache() -> MemoryCache {
+    let cache = MemoryCache::new(10);
+    let now = SystemTime::now();
+    cache.put_with_written_at("ISteamApps-GetAppList-v2.json", "0123456789", now - Duration::from_secs(60)).unwrap();
+    cache.put_with_written_at("steampowered/appdetails/0/570/570.json", "01234", now - Duration::from_secs(3 * 24 * 60 * 60)).unwrap();
+    cache.put_with_written_at("steampowered/appdetails/0/730/730.json", "012", now - Duration::from_secs(2 * 24 * 60 * 60)).unwrap();
+    cache.put_with_written_at("steampowered/appdetails/0/440/440.json", "01", now - Duration::from_secs(24 * 60 * 60)).unwrap();
+    cache
+}
+
+#[test]
+fn maintenance_lists_and_summarizes() {
+    let cache = get_aged_cache();
+
+    let entries = list_entries(&cache, Some("steampowered/appdetails")).unwrap();
+    let keys : Vec<&str> = entries.iter().map(|entry| entry.metadata.key.as_str()).collect();
+    assert_eq!(vec![
+        "steampowered/appdetails/0/440/440.json",
+        "steampowered/appdetails/0/570/570.json",
+        "steampowered/appdetails/0/730/730.json",
+    ], keys);
+    assert!(entries[1].age >= Duration::from_secs(3 * 24 * 60 * 60));
+    assert_eq!(4, list_entries(&cache, None).unwrap().len());
+
+    let summaries = summarize(&cache).unwrap();
+    assert_eq!(2, summaries.len());
+    assert_eq!("ISteamApps/GetAppList", summaries[0].endpoint);
+    assert_eq!(1, summaries[0].entries);
+    assert_eq!("steampowered/appdetails", summaries[1].endpoint);
+    assert_eq!(3, summaries[1].entries);
+    assert_eq!(10, summaries[1].size);
+    assert!(summaries[1].oldest_age >= Duration::from_secs(3 * 24 * 60 * 60));
+    assert!(summaries[1].newest_age < Duration::from_secs(2 * 24 * 60 * 60));
+}
+
+#[test]
+fn maintenance_prunes_by_age_and_size() {
+    let cache = get_aged_cache();
+    let report = prune(&cache, &PruneOptions::default()).unwrap();
+    assert!(report.removed.is_empty());
+
+    let report = prune(&cache, &PruneOptions::default().with_max_age(Duration::from_secs(36 * 60 * 60))).unwrap();
+    let keys : Vec<&str> = report.removed.iter().map(|metadata| metadata.key.as_str()).collect();
+    assert_eq!(vec!["steampowered/appdetails/0/570/570.json", "steampowered/appdetails/0/730/730.json"], keys);
+    assert_eq!(8, report.removed_size);
+    assert_eq!(2, cache.len());
+
+    let cache = get_aged_cache();
+    let report = prune(&cache, &PruneOptions::default()
+        .with_endpoint("steampowered/appdetails")
+        .with_max_size(4)).unwrap();
+    assert_eq!(2, report.removed.len());
+    assert_eq!(8, report.removed_size);
+    assert!(cache.get("steampowered/appdetails/0/440/440.json").unwrap().is_some());
+    assert!(cache.get("ISteamApps-GetAppList-v2.json").unwrap().is_some());
+
+    let report = prune(&cache, &PruneOptions::default().with_max_size(0)).unwrap();
+    assert_eq!(2, report.removed.len());
+    assert!(cache.is_empty());
+}
+
+#[test]
+fn maintenance_verifies_entries() {
+    let cache = MemoryCache::new(10);
+    cache.put("steampowered/appdetails/0/570/570.json", read_to_string("test/store_steampowered_com/appdetails/570.json").unwrap().as_str()).unwrap();
+    cache.put("IDOTA2Match_570-GetMatchDetails-v1-1461414523.json", read_to_string("test/idota2match_570/get_match_details/1461414523.json").unwrap().as_str()).unwrap();
+    cache.put("ISteamApps-GetAppList-v2.json", read_to_string("test/isteam_apps/get_app_list/applist.json").unwrap().as_str()).unwrap();
+    cache.put("IDOTA2Match_570-GetHeroes-v1-language=en.json", r#"{"result":{"heroes":[{"id":1,"#).unwrap();
+    cache.put("notes.txt", "not a response").unwrap();
+
+    let report = verify(&cache, None).unwrap();
+    assert!(!report.is_ok());
+    assert_eq!(3, report.verified);
+    assert_eq!(vec!["notes.txt".to_string()], report.skipped);
+    assert_eq!(1, report.failures.len());
+    assert_eq!("IDOTA2Match_570-GetHeroes-v1-language=en.json", report.failures[0].key);
+    assert_eq!("IDOTA2Match_570/GetHeroes", report.failures[0].endpoint);
+    assert!(matches!(report.failures[0].error, crate::SteamApiError::MalformedResponse { .. }));
+
+    let report = verify(&cache, Some("steampowered/appdetails")).unwrap();
+    assert!(report.is_ok());
+    assert_eq!(1, report.verified);
+}
+
+#[cfg(feature = "archive")]
+#[test]
+fn maintenance_exports_and_imports_archives() {
+    use crate::cache::maintenance::{export_archive, import_archive};
+
+    let cache_dir = get_temp_cache_dir("maintenance_exports_and_imports_archives");
+    std::fs::create_dir_all(cache_dir.as_path()).unwrap();
+    let archive_path = cache_dir.join("cache.tar.gz");
+
+    let cache = get_aged_cache();
+    assert_eq!(4, export_archive(&cache, archive_path.to_str().unwrap()).unwrap());
+
+    let filesystem_cache = FilesystemCache::new(cache_dir.join("imported").to_str().unwrap());
+    assert_eq!(4, import_archive(&filesystem_cache, archive_path.to_str().unwrap()).unwrap().imported);
+
+    let exported = cache.list("").unwrap();
+    let imported = filesystem_cache.list("").unwrap();
+    assert_eq!(exported.len(), imported.len());
+    for (exported, imported) in exported.iter().zip(imported.iter()) {
+        assert_eq!(exported.key, imported.key);
+        assert_eq!(exported.size, imported.size);
+        assert_eq!(
+            exported.written_at.duration_since(UNIX_EPOCH).unwrap().as_secs(),
+            imported.written_at.duration_since(UNIX_EPOCH).unwrap().as_secs(),
+        );
+    }
+    assert_eq!("012", filesystem_cache.get("steampowered/appdetails/0/730/730.json").unwrap().unwrap().body);
+
+    // a cache directory archived with `tar czf`, .meta files included
+    let directory_archive_path = cache_dir.join("directory.tar.gz");
+    let encoder = flate2::write::GzEncoder::new(std::fs::File::create(directory_archive_path.as_path()).unwrap(), flate2::Compression::default());
+    let mut builder = tar::Builder::new(encoder);
+    builder.append_dir_all(".", cache_dir.join("imported")).unwrap();
+    builder.into_inner().unwrap().finish().unwrap();
+
+    let memory_cache = MemoryCache::new(10);
+    assert_eq!(4, import_archive(&memory_cache, directory_archive_path.to_str().unwrap()).unwrap().imported);
+    assert_eq!("01", memory_cache.get("steampowered/appdetails/0/440/440.json").unwrap().unwrap().body);
+}
+
+#[cfg(feature = "archive")]
+fn append_archived_file(builder: &mut tar::Builder<flate2::write::GzEncoder<std::fs::File>>, path: &str, bytes: &[u8]) {
+    let mut header = tar::Header::new_gnu();
+    header.set_entry_type(tar::EntryType::Regular);
+    header.set_size(bytes.len() as u64);
+    header.set_mode(0o644);
+    header.set_mtime(1_500_000_000);
+    builder.append_data(&mut header, path, bytes).unwrap();
+}
+
+#[cfg(feature = "archive")]
+#[test]
+fn maintenance_imports_archived_cache_directory() {
+    use crate::cache::maintenance::import_archive;
+
+    let cache_dir = get_temp_cache_dir("maintenance_imports_archived_cache_directory");
+    std::fs::create_dir_all(cache_dir.as_path()).unwrap();
+    let filesystem_cache = FilesystemCache::new(cache_dir.join("steam-webapi-cache").to_str().unwrap());
+    for metadata in get_aged_cache().list("").unwrap() {
+        filesystem_cache.put(metadata.key.as_str(), "{}").unwrap();
+    }
+
+    // `tar czf cache.tar.gz steam-webapi-cache/`
+    let archive_path = cache_dir.join("cache.tar.gz");
+    let encoder = flate2::write::GzEncoder::new(std::fs::File::create(archive_path.as_path()).unwrap(), flate2::Compression::default());
+    let mut builder = tar::Builder::new(encoder);
+    builder.append_dir_all("steam-webapi-cache", cache_dir.join("steam-webapi-cache")).unwrap();
+    builder.into_inner().unwrap().finish().unwrap();
+
+    let memory_cache = MemoryCache::new(10);
+    let report = import_archive(&memory_cache, archive_path.to_str().unwrap()).unwrap();
+    assert_eq!(4, report.imported);
+    assert!(report.skipped.is_empty());
+
+    let keys: Vec<String> = memory_cache.list("").unwrap().into_iter().map(|metadata| metadata.key).collect();
+    assert_eq!(vec![
+        "ISteamApps-GetAppList-v2.json",
+        "steampowered/appdetails/0/440/440.json",
+        "steampowered/appdetails/0/570/570.json",
+        "steampowered/appdetails/0/730/730.json",
+    ], keys);
+}
+
+#[cfg(all(feature = "archive", feature = "gzip"))]
+#[test]
+fn maintenance_imports_compressed_archive_entries() {
+    use std::io::Write;
+    use crate::cache::maintenance::import_archive;
+
+    let cache_dir = get_temp_cache_dir("maintenance_imports_compressed_archive_entries");
+    std::fs::create_dir_all(cache_dir.as_path()).unwrap();
+
+    let mut gzip_encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+    gzip_encoder.write_all(b"{\"applist\":{\"apps\":[]}}").unwrap();
+    let gzipped = gzip_encoder.finish().unwrap();
+
+    let archive_path = cache_dir.join("cache.tar.gz");
+    let encoder = flate2::write::GzEncoder::new(std::fs::File::create(archive_path.as_path()).unwrap(), flate2::Compression::default());
+    let mut builder = tar::Builder::new(encoder);
+    append_archived_file(&mut builder, "steam-webapi-cache/ISteamApps-GetAppList-v2.json.gz", gzipped.as_slice());
+    append_archived_file(&mut builder, "steam-webapi-cache/steampowered/appdetails/0/570/570.json.zst", &[0xff, 0x00, 0xff]);
+    append_archived_file(&mut builder, "steam-webapi-cache/steampowered/appdetails/0/730/730.json", &[0xff, 0xfe]);
+    append_archived_file(&mut builder, "steam-webapi-cache/steampowered/appdetails/0/440/440.json", b"{}");
+    builder.into_inner().unwrap().finish().unwrap();
+
+    let memory_cache = MemoryCache::new(10);
+    let report = import_archive(&memory_cache, archive_path.to_str().unwrap()).unwrap();
+
+    assert_eq!(2, report.imported);
+    assert_eq!(vec![
+        "steampowered/appdetails/0/570/570.json.zst".to_string(),
+        "steampowered/appdetails/0/730/730.json".to_string(),
+    ], report.skipped);
+    let entry = memory_cache.get("ISteamApps-GetAppList-v2.json").unwrap().unwrap();
+    assert_eq!("{\"applist\":{\"apps\":[]}}", entry.body);
+    assert_eq!(UNIX_EPOCH + Duration::from_secs(1_500_000_000), entry.metadata.written_at);
+    assert_eq!("{}", memory_cache.get("steampowered/appdetails/0/440/440.json").unwrap().unwrap().body);
+}
+
+fn assert_store_keeps_validators(cache: &dyn CacheStore) {
+    let validators = CacheValidators {
+        etag: Some("\"v1\"".to_string()),
+        last_modified: Some("Wed, 21 Oct 2015 07:28:00 GMT".to_string()),
+    };
+    cache.put_with_validators("ISteamApps-GetAppList-v2.json", "{}", &validators).unwrap();
+    assert_eq!(validators, cache.get("ISteamApps-GetAppList-v2.json").unwrap().unwrap().metadata.validators);
+
+    let written_at = UNIX_EPOCH + Duration::from_millis(1_500_000_000_000);
+    cache.touch("ISteamApps-GetAppList-v2.json", written_at).unwrap();
+    let metadata = cache.get("ISteamApps-GetAppList-v2.json").unwrap().unwrap().metadata;
+    assert_eq!(written_at, metadata.written_at);
+    assert_eq!(validators, metadata.validators);
+    assert_eq!(vec![metadata], cache.list("").unwrap());
+
+    cache.put("ISteamApps-GetAppList-v2.json", "{}").unwrap();
+    assert!(cache.get("ISteamApps-GetAppList-v2.json").unwrap().unwrap().metadata.validators.is_empty());
+
+    cache.touch("steampowered/appdetails/0/730/730.json", written_at).unwrap();
+    assert!(cache.get("steampowered/appdetails/0/730/730.json").unwrap().is_none());
+}
+
+#[test]
+fn stores_keep_validators() {
+    let cache_dir = get_temp_cache_dir("stores_keep_validators");
+    assert_store_keeps_validators(&FilesystemCache::new(cache_dir.to_str().unwrap()));
+    assert_store_keeps_validators(&MemoryCache::new(10));
+}
+
+#[cfg(feature = "sqlite")]
+#[test]
+fn sqlite_keeps_validators() {
+    assert_store_keeps_validators(&crate::cache::SqliteCache::open_in_memory().unwrap());
+
+    let cache_dir = get_temp_cache_dir("sqlite_keeps_validators");
+    std::fs::create_dir_all(cache_dir.as_path()).unwrap();
+    let database = cache_dir.join("cache.sqlite");
+    let connection = rusqlite::Connection::open(database.as_path()).unwrap();
+    connection.execute_batch(
+        "CREATE TABLE cache_entries (key TEXT PRIMARY KEY NOT NULL, body TEXT NOT NULL, size INTEGER NOT NULL, written_at_millis INTEGER NOT NULL);
+        INSERT INTO cache_entries VALUES ('ISteamApps-GetAppList-v2.json', '{}', 2, 1500000000000);",
+    ).unwrap();
+    drop(connection);
+
+    let cache = crate::cache::SqliteCache::open(database.to_str().unwrap()).unwrap();
+    let entry = cache.get("ISteamApps-GetAppList-v2.json").unwrap().unwrap();
+    assert_eq!("{}", entry.body);
+    assert!(entry.metadata.validators.is_empty());
+    assert_store_keeps_validators(&cache);
+}
+
+#[test]
+fn client_revalidates_stale_entries() {
+    let url = "https://api.steampowered.com/ISteamApps/GetAppList/v2?key=KEY";
+    let transport = Arc::new(FakeTransport::new());
+    transport.insert(url, HttpResponse::new(200, r#"{"applist":{"apps":[{"appid":570,"name":"Dota 2"}]}}"#)
+        .with_header("ETag", "\"v1\"")
+        .with_header("Last-Modified", "Wed, 21 Oct 2015 07:28:00 GMT"));
+    let cache = Arc::new(MemoryCache::new(10));
+    let cache_policy = CachePolicy::default().with_ttl("ISteamApps/GetAppList", Some(Duration::from_millis(1)));
+    let client = get_app_list_client(cache.clone(), transport.clone(), cache_policy);
+
+    assert_eq!("Dota 2", client.get_cached_app_list().unwrap()[0].name);
+    let request = transport.requests().remove(0);
+    assert_eq!(None, request.header("If-None-Match"));
+    let metadata = cache.get("ISteamApps-GetAppList-v2.json").unwrap().unwrap().metadata;
+    assert_eq!(Some("\"v1\"".to_string()), metadata.validators.etag);
+
+    thread::sleep(Duration::from_millis(10));
+    transport.enqueue(url, HttpResponse::new(304, ""));
+    assert_eq!("Dota 2", client.get_cached_app_list().unwrap()[0].name);
+    let request = transport.requests().remove(1);
+    assert_eq!(Some("\"v1\""), request.header("If-None-Match"));
+    assert_eq!(Some("Wed, 21 Oct 2015 07:28:00 GMT"), request.header("If-Modified-Since"));
+    let revalidated_metadata = cache.get("ISteamApps-GetAppList-v2.json").unwrap().unwrap().metadata;
+    assert!(revalidated_metadata.written_at > metadata.written_at);
+    assert_eq!(metadata.validators, revalidated_metadata.validators);
+
+    let uncached_client = SteamClient::builder()
+        .api_key("KEY")
+        .transport(transport.clone())
+        .cache_store(cache.clone())
+        .cache_enabled(false)
+        .build();
+    uncached_client.get_app_list().unwrap();
+    assert_eq!(None, transport.requests().remove(2).header("If-None-Match"));
+}
+
+#[test]
+fn client_refetches_when_revalidated_entry_is_corrupt() {
+    let url = "https://api.steampowered.com/ISteamApps/GetAppList/v2?key=KEY";
+    let transport = Arc::new(FakeTransport::new());
+    transport.respond_with(url, 200, r#"{"applist":{"apps":[{"appid":570,"name":"Dota 2"}]}}"#);
+    transport.enqueue(url, HttpResponse::new(304, ""));
+    let cache = Arc::new(MemoryCache::new(10));
+    let validators = CacheValidators {
+        etag: Some("\"v1\"".to_string()),
+        last_modified: None,
+    };
+    cache.put_with_validators("ISteamApps-GetAppList-v2.json", r#"{"applist":{"apps":[{"appid":5"#, &validators).unwrap();
+    let client = get_app_list_client(cache.clone(), transport.clone(), CachePolicy::default().with_mode(CacheMode::NetworkOnly));
+
+    assert_eq!("Dota 2", client.get_cached_app_list().unwrap()[0].name);
+    let requests = transport.requests();
+    assert_eq!(2, requests.len());
+    assert_eq!(Some("\"v1\""), requests[0].header("If-None-Match"));
+    assert_eq!(None, requests[1].header("If-None-Match"));
+    assert!(cache.get("ISteamApps-GetAppList-v2.json").unwrap().unwrap().metadata.validators.is_empty());
+}
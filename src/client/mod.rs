@@ -0,0 +1,1344 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, SystemTime};
+use url_build_parse::{build_url, parse_url, UrlComponents};
+use crate::cache::{CacheCompression, CacheEntry, CacheMode, CachePolicy, CacheStore, CacheValidators, FilesystemCache};
+use crate::error::SteamApiError;
+use crate::steam_id::SteamId;
+use crate::rate_limit::{get_shared_rate_limiter, RateLimiter};
+use crate::retry::{parse_retry_after, RetryPolicy};
+use crate::transport::{HttpRequest, HttpResponse, MinreqTransport, Transport};
+#[cfg(feature = "async")]
+use crate::transport::{AsyncTransport, ReqwestTransport};
+use crate::{get_host, idota2match_570, iecon_dota2_570, iplayer_service, isteam_apps, isteam_news, isteam_user, isteam_user_stats, store_steampowered_com};
+use crate::idota2match_570::get_heroes::Hero;
+use crate::idota2match_570::get_league_listing::League;
+use crate::idota2match_570::get_live_league_games::LiveLeagueGame;
+use crate::idota2match_570::get_match_details::MatchResult;
+use crate::idota2match_570::get_match_history::{MatchHistoryFilter, MatchHistoryPages, ResponseMatchHistory};
+use crate::idota2match_570::get_team_info_by_team_id::TeamInfo;
+use crate::idota2match_570::types::{GameMode, Skill};
+use crate::iecon_dota2_570::get_game_items::GameItem;
+use crate::iplayer_service::get_badges::Badges;
+use crate::iplayer_service::get_owned_games::OwnedGames;
+use crate::iplayer_service::get_recently_played_games::RecentlyPlayedGames;
+use crate::isteam_apps::get_app_list::SteamApp;
+use crate::isteam_news::get_news_for_app::NewsItem;
+use crate::isteam_user::get_friend_list::Friend;
+use crate::isteam_user::get_player_bans::PlayerBans;
+use crate::isteam_user::get_player_summaries::PlayerSummary;
+use crate::isteam_user::resolve_vanity_url::{SteamIdResolution, VanityUrlResolution};
+use crate::isteam_user_stats::get_global_achievement_percentages_for_app::AchievementPercentage;
+use crate::isteam_user_stats::get_player_achievements::PlayerAchievements;
+use crate::isteam_user_stats::get_schema_for_game::GameSchema;
+use crate::isteam_user_stats::get_user_stats_for_game::UserStatsForGame;
+use crate::store_steampowered_com::appdetails::SteamAppDetails;
+use crate::util::{get_cache_dir_path, get_steam_web_api_key};
+
+#[cfg(test)]
+mod tests;
+
+pub const DEFAULT_API_BASE_URL: &str = "https://api.steampowered.com";
+pub const DEFAULT_STORE_BASE_URL: &str = "https://store.steampowered.com";
+pub const DEFAULT_COMMUNITY_BASE_URL: &str = "https://steamcommunity.com";
+
+const STORE_HOST: &str = "store.steampowered.com";
+
+/// Holds everything needed to talk to Steam: the Web API key, the base urls of the Web API, store
+/// and community sites, transport settings and cache settings. Every endpoint of the SDK is
+/// available as a method, so several clients with different keys can live in one process.
+///
+/// The free functions in the crate root use a default client built from the `STEAM_WEBAPI_KEY`
+/// environment variable, see `get_default_client` and `set_default_client`.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use steam_webapi_rust_sdk::client::SteamClient;
+///
+/// let client = SteamClient::builder()
+///     .api_key("YOUR_STEAM_WEBAPI_KEY")
+///     .timeout(Duration::from_secs(10))
+///     .user_agent("my-app/1.0")
+///     .cache_dir("/tmp/steam-webapi-cache")
+///     .build();
+///
+/// assert_eq!("YOUR_STEAM_WEBAPI_KEY", client.api_key());
+/// assert_eq!("https://api.steampowered.com", client.api_base_url());
+/// ```
+#[derive(Clone, Debug)]
+pub struct SteamClient {
+    api_key: String,
+    api_base_url: String,
+    store_base_url: String,
+    community_base_url: String,
+    timeout: Option<Duration>,
+    user_agent: String,
+    cache_enabled: bool,
+    cache_dir: String,
+    transport: Arc<dyn Transport>,
+    #[cfg(feature = "async")]
+    async_transport: Arc<dyn AsyncTransport>,
+    rate_limiter: Option<Arc<RateLimiter>>,
+    retry_policy: RetryPolicy,
+    cache_store: Arc<dyn CacheStore>,
+    cache_policy: CachePolicy,
+}
+
+/// Builder for `SteamClient`. Every setting is optional; unset ones fall back to the defaults
+/// used by the free functions of the crate.
+#[derive(Clone, Debug, Default)]
+pub struct SteamClientBuilder {
+    api_key: Option<String>,
+    api_base_url: Option<String>,
+    store_base_url: Option<String>,
+    community_base_url: Option<String>,
+    timeout: Option<Duration>,
+    user_agent: Option<String>,
+    cache_enabled: Option<bool>,
+    cache_dir: Option<String>,
+    cache_compression: Option<CacheCompression>,
+    transport: Option<Arc<dyn Transport>>,
+    #[cfg(feature = "async")]
+    async_transport: Option<Arc<dyn AsyncTransport>>,
+    rate_limiter: Option<Arc<RateLimiter>>,
+    rate_limiter_enabled: Option<bool>,
+    retry_policy: Option<RetryPolicy>,
+    cache_store: Option<Arc<dyn CacheStore>>,
+    cache_policy: Option<CachePolicy>,
+}
+
+impl SteamClientBuilder {
+    pub fn api_key(mut self, api_key: &str) -> SteamClientBuilder {
+        self.api_key = Some(api_key.to_string());
+        self
+    }
+
+    /// Base url for `api.steampowered.com` endpoints, e.g. `http://127.0.0.1:8080` for a local stand-in server.
+    pub fn api_base_url(mut self, api_base_url: &str) -> SteamClientBuilder {
+        self.api_base_url = Some(api_base_url.to_string());
+        self
+    }
+
+    /// Base url for `store.steampowered.com` endpoints.
+    pub fn store_base_url(mut self, store_base_url: &str) -> SteamClientBuilder {
+        self.store_base_url = Some(store_base_url.to_string());
+        self
+    }
+
+    /// Base url for `steamcommunity.com` pages.
+    pub fn community_base_url(mut self, community_base_url: &str) -> SteamClientBuilder {
+        self.community_base_url = Some(community_base_url.to_string());
+        self
+    }
+
+    /// Timeout for a single request. Requests don't time out unless this is set. The default
+    /// blocking transport only supports whole seconds and rounds the timeout up.
+    pub fn timeout(mut self, timeout: Duration) -> SteamClientBuilder {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    pub fn user_agent(mut self, user_agent: &str) -> SteamClientBuilder {
+        self.user_agent = Some(user_agent.to_string());
+        self
+    }
+
+    /// Whether responses of caching endpoints are written to the cache store. Enabled by default.
+    pub fn cache_enabled(mut self, cache_enabled: bool) -> SteamClientBuilder {
+        self.cache_enabled = Some(cache_enabled);
+        self
+    }
+
+    /// Root directory of the default `FilesystemCache`. Defaults to `util::get_cache_dir_path`:
+    /// the `STEAM_WEBAPI_CACHE_DIR` environment variable, an existing `./steam-webapi-cache` or
+    /// the user cache directory.
+    pub fn cache_dir(mut self, cache_dir: &str) -> SteamClientBuilder {
+        self.cache_dir = Some(cache_dir.to_string());
+        self
+    }
+
+    /// How the default `FilesystemCache` compresses the entries it writes, `CacheCompression::None`
+    /// by default. Entries already in the cache directory are read whatever their compression.
+    pub fn cache_compression(mut self, cache_compression: CacheCompression) -> SteamClientBuilder {
+        self.cache_compression = Some(cache_compression);
+        self
+    }
+
+    /// Replaces the transport requests are sent through, `MinreqTransport` by default.
+    pub fn transport(mut self, transport: Arc<dyn Transport>) -> SteamClientBuilder {
+        self.transport = Some(transport);
+        self
+    }
+
+    /// Replaces the transport async requests are sent through, `ReqwestTransport` by default.
+    #[cfg(feature = "async")]
+    pub fn async_transport(mut self, async_transport: Arc<dyn AsyncTransport>) -> SteamClientBuilder {
+        self.async_transport = Some(async_transport);
+        self
+    }
+
+    /// Replaces the rate limiter, by default `rate_limit::get_shared_rate_limiter()`, which every
+    /// client of the process shares. Give a client its own `Arc` to give it a budget of its own.
+    pub fn rate_limiter(mut self, rate_limiter: Arc<RateLimiter>) -> SteamClientBuilder {
+        self.rate_limiter = Some(rate_limiter);
+        self
+    }
+
+    pub fn rate_limiter_enabled(mut self, rate_limiter_enabled: bool) -> SteamClientBuilder {
+        self.rate_limiter_enabled = Some(rate_limiter_enabled);
+        self
+    }
+
+    /// Replaces the retry policy, `RetryPolicy::default()` by default. Use `RetryPolicy::none()`
+    /// to disable retries.
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> SteamClientBuilder {
+        self.retry_policy = Some(retry_policy);
+        self
+    }
+
+    /// Replaces the store cached responses are kept in, a `FilesystemCache` rooted at `cache_dir`
+    /// by default.
+    pub fn cache_store(mut self, cache_store: Arc<dyn CacheStore>) -> SteamClientBuilder {
+        self.cache_store = Some(cache_store);
+        self
+    }
+
+    /// Decides how the `get_cached` functions mix the cache and the network and when cached
+    /// responses go stale, see `CachePolicy::default` for the defaults.
+    pub fn cache_policy(mut self, cache_policy: CachePolicy) -> SteamClientBuilder {
+        self.cache_policy = Some(cache_policy);
+        self
+    }
+
+    pub fn build(self) -> SteamClient {
+        let cache_dir = self.cache_dir.unwrap_or(get_cache_dir_path());
+        let cache_store = self.cache_store.unwrap_or(Arc::new(
+            FilesystemCache::new(cache_dir.as_str()).with_compression(self.cache_compression.unwrap_or_default())
+        ));
+
+        let rate_limiter = if self.rate_limiter_enabled.unwrap_or(true) {
+            Some(self.rate_limiter.unwrap_or_else(get_shared_rate_limiter))
+        } else {
+            None
+        };
+
+
+        SteamClient {
+            api_key: self.api_key.unwrap_or_default(),
+            api_base_url: self.api_base_url.unwrap_or(DEFAULT_API_BASE_URL.to_string()),
+            store_base_url: self.store_base_url.unwrap_or(DEFAULT_STORE_BASE_URL.to_string()),
+            community_base_url: self.community_base_url.unwrap_or(DEFAULT_COMMUNITY_BASE_URL.to_string()),
+            timeout: self.timeout,
+            user_agent: self.user_agent.unwrap_or(get_default_user_agent()),
+            cache_enabled: self.cache_enabled.unwrap_or(true),
+            cache_dir: cache_dir.to_string(),
+            transport: self.transport.unwrap_or(Arc::new(MinreqTransport)),
+            #[cfg(feature = "async")]
+            async_transport: self.async_transport.unwrap_or(Arc::new(ReqwestTransport::new())),
+            rate_limiter,
+            retry_policy: self.retry_policy.unwrap_or_default(),
+            cache_store,
+            cache_policy: self.cache_policy.unwrap_or_default(),
+        }
+    }
+}
+
+pub fn get_default_user_agent() -> String {
+    ["steam-webapi-rust-sdk/", env!("CARGO_PKG_VERSION")].join("")
+}
+
+/// Cache lookup result as seen through the client's `CachePolicy`.
+enum CachedResponse {
+    Fresh(String),
+    Stale(String),
+    Missing(SteamApiError),
+}
+
+/// What `get_cached_or_fetch` does after looking at the cache.
+enum CacheDecision<T> {
+    Serve(Result<T, SteamApiError>),
+    Fetch,
+    FetchOrServeStale(String),
+}
+
+/// Outcome of a request, conditional if the cached response had validators.
+enum ApiResponse {
+    Modified(String, CacheValidators),
+    NotModified,
+}
+
+fn get_retry_after(boxed_response: &Result<HttpResponse, String>) -> Option<Duration> {
+    let response = boxed_response.as_ref().ok()?;
+    let retry_after = response.header("Retry-After")?;
+    parse_retry_after(retry_after)
+}
+
+/// Turns the transport outcome into the response body, shared by the blocking and async calls.
+fn read_response(endpoint: &str, url: &str, boxed_response: Result<HttpResponse, String>) -> Result<String, SteamApiError> {
+    if boxed_response.is_err() {
+        let error_message = boxed_response.err().unwrap();
+        return Err(SteamApiError::transport(endpoint, url, error_message.as_str()));
+    }
+
+    let response = boxed_response.unwrap();
+    let status_code = response.status;
+    let raw_response : Vec<u8> = response.body;
+
+    if !(200..300).contains(&status_code) {
+        let body = String::from_utf8_lossy(&raw_response).to_string();
+        return Err(SteamApiError::http_status(endpoint, url, status_code, body.as_str()));
+    }
+
+    let response_string_boxed = String::from_utf8(raw_response);
+    if response_string_boxed.is_err() {
+        let error_message = response_string_boxed.err().unwrap().to_string();
+        if error_message == "invalid utf-8 sequence of 1 bytes from index 1" {
+            return Err(SteamApiError::malformed_response(endpoint, "no response from API").with_url(url));
+        }
+        return Err(SteamApiError::malformed_response(endpoint, "invalid utf-8 sequence").with_url(url));
+    }
+    let response_string: String = response_string_boxed.unwrap();
+
+    Ok(response_string)
+}
+
+/// Like `read_response`, but a conditional request may be answered with `304 Not Modified`, and
+/// the validators of the response are kept for the cache.
+fn read_api_response(endpoint: &str, url: &str, boxed_response: Result<HttpResponse, String>, is_conditional: bool) -> Result<ApiResponse, SteamApiError> {
+    if let (true, Ok(response)) = (is_conditional, boxed_response.as_ref()) {
+        if response.status == 304 {
+            return Ok(ApiResponse::NotModified);
+        }
+    }
+
+    let validators = boxed_response.as_ref().map(get_validators).unwrap_or_default();
+    let body = read_response(endpoint, url, boxed_response)?;
+    Ok(ApiResponse::Modified(body, validators))
+}
+
+fn get_validators(response: &HttpResponse) -> CacheValidators {
+    CacheValidators {
+        etag: response.header("ETag").map(|etag| etag.to_string()),
+        last_modified: response.header("Last-Modified").map(|last_modified| last_modified.to_string()),
+    }
+}
+
+static DEFAULT_CLIENT: Mutex<Option<Arc<SteamClient>>> = Mutex::new(None);
+
+/// Returns the client used by the free functions of the crate. Unless replaced via
+/// `set_default_client`, it is built from the `STEAM_WEBAPI_KEY` environment variable on first use.
+pub fn get_default_client() -> Arc<SteamClient> {
+    let mut default_client = DEFAULT_CLIENT.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    if default_client.is_none() {
+        *default_client = Some(Arc::new(SteamClient::from_env()));
+    }
+    default_client.as_ref().unwrap().clone()
+}
+
+/// Replaces the client used by the free functions of the crate.
+pub fn set_default_client(client: SteamClient) {
+    let mut default_client = DEFAULT_CLIENT.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    *default_client = Some(Arc::new(client));
+}
+
+impl Default for SteamClient {
+    fn default() -> SteamClient {
+        SteamClient::from_env()
+    }
+}
+
+impl SteamClient {
+    pub fn builder() -> SteamClientBuilder {
+        SteamClientBuilder::default()
+    }
+
+    /// Builds a client with default settings and the key from the `STEAM_WEBAPI_KEY` environment variable.
+    pub fn from_env() -> SteamClient {
+        SteamClient::builder()
+            .api_key(get_steam_web_api_key().as_str())
+            .build()
+    }
+
+    pub fn api_key(&self) -> &str {
+        &self.api_key
+    }
+
+    pub fn api_base_url(&self) -> &str {
+        &self.api_base_url
+    }
+
+    pub fn store_base_url(&self) -> &str {
+        &self.store_base_url
+    }
+
+    pub fn community_base_url(&self) -> &str {
+        &self.community_base_url
+    }
+
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    pub fn user_agent(&self) -> &str {
+        &self.user_agent
+    }
+
+    pub fn cache_enabled(&self) -> bool {
+        self.cache_enabled
+    }
+
+    pub fn cache_dir(&self) -> &str {
+        &self.cache_dir
+    }
+
+    pub fn transport(&self) -> &dyn Transport {
+        self.transport.as_ref()
+    }
+
+    #[cfg(feature = "async")]
+    pub fn async_transport(&self) -> &dyn AsyncTransport {
+        self.async_transport.as_ref()
+    }
+
+    pub fn rate_limiter(&self) -> Option<&RateLimiter> {
+        self.rate_limiter.as_deref()
+    }
+
+    pub fn retry_policy(&self) -> RetryPolicy {
+        self.retry_policy
+    }
+
+    pub fn cache_store(&self) -> &dyn CacheStore {
+        self.cache_store.as_ref()
+    }
+
+    pub fn cache_policy(&self) -> &CachePolicy {
+        &self.cache_policy
+    }
+
+    /// Points a url built by one of the `get_api_url` functions at this client: swaps the default
+    /// `api.steampowered.com`/`store.steampowered.com` scheme and host for the configured base url
+    /// and adds the client's key as the `key` parameter of Steam Web API urls. Endpoint urls don't
+    /// carry a key themselves, so the key only ever comes from the client.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use steam_webapi_rust_sdk::client::SteamClient;
+    ///
+    /// let client = SteamClient::builder()
+    ///     .api_key("KEY")
+    ///     .api_base_url("http://127.0.0.1:8080")
+    ///     .build();
+    ///
+    /// let url = client.resolve_url("https://api.steampowered.com/ISteamUser/GetPlayerBans/v1").unwrap();
+    /// assert_eq!("http://127.0.0.1:8080/ISteamUser/GetPlayerBans/v1?key=KEY", url);
+    /// ```
+    pub fn resolve_url(&self, url: &str) -> Result<String, String> {
+        let mut components = parse_url(url)?;
+
+        let host = match &components.authority {
+            Some(authority) => authority.host.to_string(),
+            None => return Ok(url.to_string()),
+        };
+
+        let base_url = if host == get_host() {
+            self.api_base_url.as_str()
+        } else if host == STORE_HOST {
+            self.store_base_url.as_str()
+        } else {
+            return Ok(url.to_string());
+        };
+
+        let base = parse_base_url(base_url)?;
+        components.scheme = base.scheme;
+        components.authority = base.authority;
+        components.path = [base.path.trim_end_matches('/').to_string(), components.path].join("");
+
+        if host == get_host() && !self.api_key.is_empty() {
+            components.query
+                .get_or_insert_with(HashMap::new)
+                .insert("key".to_string(), self.api_key.to_string());
+        }
+
+        build_url(components)
+    }
+
+    /// Sends the request through the transport, waiting for the rate limiter before every attempt
+    /// and retrying failures the retry policy allows.
+    pub(crate) fn make_api_call(&self, endpoint: &str, url: String) -> Result<String, SteamApiError> {
+        match self.send_api_call(endpoint, url.clone(), &CacheValidators::default())? {
+            ApiResponse::Modified(body, _) => Ok(body),
+            ApiResponse::NotModified => Err(SteamApiError::http_status(endpoint, url.as_str(), 304, "")),
+        }
+    }
+
+    #[cfg(feature = "async")]
+    pub(crate) async fn make_api_call_async(&self, endpoint: &str, url: String) -> Result<String, SteamApiError> {
+        match self.send_api_call_async(endpoint, url.clone(), &CacheValidators::default()).await? {
+            ApiResponse::Modified(body, _) => Ok(body),
+            ApiResponse::NotModified => Err(SteamApiError::http_status(endpoint, url.as_str(), 304, "")),
+        }
+    }
+
+    /// Like `make_api_call`, made conditional on `validators` unless they are empty.
+    fn send_api_call(&self, endpoint: &str, url: String, validators: &CacheValidators) -> Result<ApiResponse, SteamApiError> {
+        let boxed_request = self.build_request(endpoint, url.clone(), validators);
+        if boxed_request.is_err() {
+            return Err(boxed_request.err().unwrap());
+        }
+        let request = boxed_request.unwrap();
+
+        let mut attempt = 0;
+        loop {
+            if let Some(rate_limiter) = self.rate_limiter.as_ref() {
+                let host = get_url_host(url.as_str());
+                let boxed_acquire = rate_limiter.acquire(host.as_str());
+                if boxed_acquire.is_err() {
+                    return Err(SteamApiError::rate_limited(endpoint, url.as_str(), boxed_acquire.err().unwrap()));
+                }
+            }
+
+            let boxed_response = self.transport.send(&request);
+            let retry_after = get_retry_after(&boxed_response);
+            let boxed_api_response = read_api_response(endpoint, request.url.as_str(), boxed_response, !validators.is_empty());
+            if boxed_api_response.is_ok() {
+                return boxed_api_response;
+            }
+
+            let error = boxed_api_response.err().unwrap();
+            let boxed_delay = self.retry_policy.get_retry_delay(attempt, &error, retry_after);
+            if boxed_delay.is_none() {
+                return Err(error);
+            }
+            thread::sleep(boxed_delay.unwrap());
+            attempt += 1;
+        }
+    }
+
+    #[cfg(feature = "async")]
+    async fn send_api_call_async(&self, endpoint: &str, url: String, validators: &CacheValidators) -> Result<ApiResponse, SteamApiError> {
+        let boxed_request = self.build_request(endpoint, url.clone(), validators);
+        if boxed_request.is_err() {
+            return Err(boxed_request.err().unwrap());
+        }
+        let request = boxed_request.unwrap();
+
+        let mut attempt = 0;
+        loop {
+            if let Some(rate_limiter) = self.rate_limiter.as_ref() {
+                let host = get_url_host(url.as_str());
+                let boxed_acquire = rate_limiter.acquire_async(host.as_str()).await;
+                if boxed_acquire.is_err() {
+                    return Err(SteamApiError::rate_limited(endpoint, url.as_str(), boxed_acquire.err().unwrap()));
+                }
+            }
+
+            let boxed_response = self.async_transport.send(&request).await;
+            let retry_after = get_retry_after(&boxed_response);
+            let boxed_api_response = read_api_response(endpoint, request.url.as_str(), boxed_response, !validators.is_empty());
+            if boxed_api_response.is_ok() {
+                return boxed_api_response;
+            }
+
+            let error = boxed_api_response.err().unwrap();
+            let boxed_delay = self.retry_policy.get_retry_delay(attempt, &error, retry_after);
+            if boxed_delay.is_none() {
+                return Err(error);
+            }
+            tokio::time::sleep(boxed_delay.unwrap()).await;
+            attempt += 1;
+        }
+    }
+
+    fn build_request(&self, endpoint: &str, url: String, validators: &CacheValidators) -> Result<HttpRequest, SteamApiError> {
+        let boxed_url = self.resolve_url(url.as_str());
+        if boxed_url.is_err() {
+            return Err(SteamApiError::transport(endpoint, url.as_str(), boxed_url.err().unwrap().as_str()));
+        }
+        let url = boxed_url.unwrap();
+
+        let mut request = HttpRequest::get(url.as_str());
+        request.headers.push(("User-Agent".to_string(), self.user_agent.to_string()));
+        if let Some(etag) = validators.etag.as_ref() {
+            request.headers.push(("If-None-Match".to_string(), etag.to_string()));
+        }
+        if let Some(last_modified) = validators.last_modified.as_ref() {
+            request.headers.push(("If-Modified-Since".to_string(), last_modified.to_string()));
+        }
+        request.timeout = self.timeout;
+
+        Ok(request)
+    }
+
+    /// Returns where the cached resource with the given key lives: its filepath inside the cache
+    /// directory for the default `FilesystemCache`.
+    pub fn get_resource_filepath(&self, cache_key: &str) -> String {
+        self.cache_store.get_location(cache_key)
+    }
+
+    fn lookup_cached(&self, endpoint: &str, cache_key: &str) -> CachedResponse {
+        let boxed_entry = self.cache_store.get(cache_key);
+        if boxed_entry.is_err() {
+            return CachedResponse::Missing(SteamApiError::cache_miss(endpoint, self.get_resource_filepath(cache_key).as_str()));
+        }
+
+        let boxed_cached = boxed_entry.unwrap();
+        if boxed_cached.is_none() {
+            return CachedResponse::Missing(SteamApiError::cache_miss(endpoint, self.get_resource_filepath(cache_key).as_str()));
+        }
+
+        let entry = boxed_cached.unwrap();
+        if self.cache_policy.is_fresh(endpoint, entry.metadata.written_at) {
+            CachedResponse::Fresh(entry.body)
+        } else {
+            CachedResponse::Stale(entry.body)
+        }
+    }
+
+    /// Parses a cached body. A body the parser rejects as malformed, e.g. truncated by a crash or
+    /// written by an incompatible version, is evicted from the store and `None` returned.
+    fn parse_cached<T>(&self,
+                       cache_key: &str,
+                       body: String,
+                       parse: &impl Fn(String) -> Result<T, SteamApiError>)
+        -> Option<Result<T, SteamApiError>> {
+        let boxed_parse = parse(body);
+        if let Err(SteamApiError::MalformedResponse { .. }) = boxed_parse {
+            let _ = self.cache_store.delete(cache_key);
+            return None;
+        }
+        Some(boxed_parse.map_err(|error| error.with_url(self.get_resource_filepath(cache_key).as_str())))
+    }
+
+    /// Decides, as the client's `CachePolicy` mode says, whether the response stored under
+    /// `cache_key` is served or Steam is called.
+    fn decide_cached<T>(&self,
+                        endpoint: &str,
+                        cache_key: &str,
+                        parse: &impl Fn(String) -> Result<T, SteamApiError>)
+        -> CacheDecision<T> {
+        let mode = self.cache_policy.get_mode();
+        if mode == CacheMode::NetworkOnly {
+            return CacheDecision::Fetch;
+        }
+
+        let body = match (mode, self.lookup_cached(endpoint, cache_key)) {
+            (CacheMode::CacheOnly, CachedResponse::Missing(error)) => return CacheDecision::Serve(Err(error)),
+            (CacheMode::CacheOnly, CachedResponse::Stale(body)) => body,
+            (CacheMode::StaleWhileError, CachedResponse::Stale(body)) => return CacheDecision::FetchOrServeStale(body),
+            (_, CachedResponse::Fresh(body)) => body,
+            (_, _) => return CacheDecision::Fetch,
+        };
+
+        let boxed_parse = self.parse_cached(cache_key, body, parse);
+        if let Some(result) = boxed_parse {
+            return CacheDecision::Serve(result);
+        }
+
+        if mode == CacheMode::CacheOnly {
+            return CacheDecision::Serve(Err(SteamApiError::cache_miss(endpoint, self.get_resource_filepath(cache_key).as_str())));
+        }
+        CacheDecision::Fetch
+    }
+
+    /// Serves the response stored under `cache_key` or calls `fetch`, as the client's
+    /// `CachePolicy` mode says. `parse` turns a cached body into the result; `fetch` is expected to
+    /// call Steam and write the response to the cache.
+    pub(crate) fn get_cached_or_fetch<T>(&self,
+                                         endpoint: &str,
+                                         cache_key: &str,
+                                         parse: impl Fn(String) -> Result<T, SteamApiError>,
+                                         fetch: impl FnOnce() -> Result<T, SteamApiError>)
+        -> Result<T, SteamApiError> {
+        match self.decide_cached(endpoint, cache_key, &parse) {
+            CacheDecision::Serve(result) => result,
+            CacheDecision::Fetch => fetch(),
+            CacheDecision::FetchOrServeStale(body) => {
+                fetch().or_else(|error| self.parse_cached(cache_key, body, &parse).unwrap_or(Err(error)))
+            },
+        }
+    }
+
+    /// Async version of `get_cached_or_fetch`. `fetch` is only awaited when the network is needed.
+    #[cfg(feature = "async")]
+    pub(crate) async fn get_cached_or_fetch_async<T>(&self,
+                                                     endpoint: &str,
+                                                     cache_key: &str,
+                                                     parse: impl Fn(String) -> Result<T, SteamApiError>,
+                                                     fetch: impl std::future::Future<Output = Result<T, SteamApiError>>)
+        -> Result<T, SteamApiError> {
+        match self.decide_cached(endpoint, cache_key, &parse) {
+            CacheDecision::Serve(result) => result,
+            CacheDecision::Fetch => fetch.await,
+            CacheDecision::FetchOrServeStale(body) => {
+                fetch.await.or_else(|error| self.parse_cached(cache_key, body, &parse).unwrap_or(Err(error)))
+            },
+        }
+    }
+
+    /// Calls Steam and, once `parse` accepts the response, writes it to the cache along with its
+    /// validators. If the cached response has validators the request is made conditional: a
+    /// `304 Not Modified` serves the cached body and marks it fresh again instead of downloading
+    /// the same body. Should the cached body no longer parse, it is evicted and Steam called again.
+    pub(crate) fn fetch_and_cache<T>(&self,
+                                     endpoint: &str,
+                                     cache_key: &str,
+                                     url: String,
+                                     parse: impl Fn(String) -> Result<T, SteamApiError>)
+        -> Result<T, SteamApiError> {
+        if let Some(cached) = self.get_revalidatable(cache_key) {
+            let boxed_response = self.send_api_call(endpoint, url.clone(), &cached.metadata.validators);
+            if boxed_response.is_err() {
+                return Err(boxed_response.err().unwrap());
+            }
+
+            let boxed_result = self.read_fetched(cache_key, url.as_str(), boxed_response.unwrap(), Some(cached.body), &parse);
+            if let Some(result) = boxed_result {
+                return result;
+            }
+        }
+
+        let boxed_response = self.send_api_call(endpoint, url.clone(), &CacheValidators::default());
+        if boxed_response.is_err() {
+            return Err(boxed_response.err().unwrap());
+        }
+
+        self.read_fetched(cache_key, url.as_str(), boxed_response.unwrap(), None, &parse)
+            .unwrap_or_else(|| Err(SteamApiError::http_status(endpoint, url.as_str(), 304, "")))
+    }
+
+    /// Async version of `fetch_and_cache`.
+    #[cfg(feature = "async")]
+    pub(crate) async fn fetch_and_cache_async<T>(&self,
+                                                 endpoint: &str,
+                                                 cache_key: &str,
+                                                 url: String,
+                                                 parse: impl Fn(String) -> Result<T, SteamApiError>)
+        -> Result<T, SteamApiError> {
+        if let Some(cached) = self.get_revalidatable(cache_key) {
+            let boxed_response = self.send_api_call_async(endpoint, url.clone(), &cached.metadata.validators).await;
+            if boxed_response.is_err() {
+                return Err(boxed_response.err().unwrap());
+            }
+
+            let boxed_result = self.read_fetched(cache_key, url.as_str(), boxed_response.unwrap(), Some(cached.body), &parse);
+            if let Some(result) = boxed_result {
+                return result;
+            }
+        }
+
+        let boxed_response = self.send_api_call_async(endpoint, url.clone(), &CacheValidators::default()).await;
+        if boxed_response.is_err() {
+            return Err(boxed_response.err().unwrap());
+        }
+
+        self.read_fetched(cache_key, url.as_str(), boxed_response.unwrap(), None, &parse)
+            .unwrap_or_else(|| Err(SteamApiError::http_status(endpoint, url.as_str(), 304, "")))
+    }
+
+    /// Returns the cached entry if it can be revalidated with a conditional request.
+    fn get_revalidatable(&self, cache_key: &str) -> Option<CacheEntry> {
+        if !self.cache_enabled {
+            return None;
+        }
+
+        let entry = self.cache_store.get(cache_key).ok()??;
+        if entry.metadata.validators.is_empty() {
+            return None;
+        }
+        Some(entry)
+    }
+
+    /// Parses a fetched response and caches it, or serves and touches the cached body on
+    /// `304 Not Modified`. `None` if the cached body doesn't parse and Steam has to be asked again
+    /// unconditionally.
+    fn read_fetched<T>(&self,
+                       cache_key: &str,
+                       url: &str,
+                       response: ApiResponse,
+                       cached_body: Option<String>,
+                       parse: &impl Fn(String) -> Result<T, SteamApiError>)
+        -> Option<Result<T, SteamApiError>> {
+        match response {
+            ApiResponse::Modified(body, validators) => {
+                let boxed_result = parse(body.clone());
+                if boxed_result.is_err() {
+                    return Some(Err(boxed_result.err().unwrap().with_url(url)));
+                }
+
+                self.write_cached(cache_key, body.as_str(), &validators);
+                Some(boxed_result)
+            },
+            ApiResponse::NotModified => {
+                let boxed_result = parse(cached_body?);
+                if boxed_result.is_err() {
+                    let _ = self.cache_store.delete(cache_key);
+                    return None;
+                }
+
+                if self.cache_enabled {
+                    let _ = self.cache_store.touch(cache_key, SystemTime::now());
+                }
+                Some(boxed_result)
+            },
+        }
+    }
+
+    /// Writes the response to the cache store. Failing to write, e.g. on a read-only filesystem,
+    /// doesn't fail the call the response came from.
+    pub(crate) fn write_cached(&self, cache_key: &str, response_string: &str, validators: &CacheValidators) {
+        if !self.cache_enabled {
+            return;
+        }
+
+        let _ = self.cache_store.put_with_validators(cache_key, response_string, validators);
+    }
+
+    pub fn get_app_details(&self, app_id: i64) -> Result<SteamAppDetails, SteamApiError> {
+        store_steampowered_com::appdetails::get_with_client(self, app_id)
+    }
+
+    #[cfg(feature = "async")]
+    pub async fn get_app_details_async(&self, app_id: i64) -> Result<SteamAppDetails, SteamApiError> {
+        store_steampowered_com::appdetails::get_async_with_client(self, app_id).await
+    }
+
+    pub fn get_cached_app_details(&self, app_id: i64) -> Result<SteamAppDetails, SteamApiError> {
+        store_steampowered_com::appdetails::get_cached_with_client(self, app_id)
+    }
+
+    #[cfg(feature = "async")]
+    pub async fn get_cached_app_details_async(&self, app_id: i64) -> Result<SteamAppDetails, SteamApiError> {
+        store_steampowered_com::appdetails::get_cached_async_with_client(self, app_id).await
+    }
+
+    pub fn get_app_list(&self) -> Result<Vec<SteamApp>, SteamApiError> {
+        isteam_apps::get_app_list::get_with_client(self)
+    }
+
+    #[cfg(feature = "async")]
+    pub async fn get_app_list_async(&self) -> Result<Vec<SteamApp>, SteamApiError> {
+        isteam_apps::get_app_list::get_async_with_client(self).await
+    }
+
+    pub fn get_cached_app_list(&self) -> Result<Vec<SteamApp>, SteamApiError> {
+        isteam_apps::get_app_list::get_cached_with_client(self)
+    }
+
+    #[cfg(feature = "async")]
+    pub async fn get_cached_app_list_async(&self) -> Result<Vec<SteamApp>, SteamApiError> {
+        isteam_apps::get_app_list::get_cached_async_with_client(self).await
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn get_dota2_match_history(&self,
+                                   account_id: Option<SteamId>,
+                                   game_mode: Option<GameMode>,
+                                   skill: Option<Skill>,
+                                   min_players: Option<u32>,
+                                   start_at_match_id: Option<i64>,
+                                   matches_requested: Option<u32>,
+                                   tournament_games_only: Option<bool>)
+        -> Result<ResponseMatchHistory, SteamApiError> {
+        idota2match_570::get_match_history::get_with_client(
+            self,
+            account_id,
+            game_mode,
+            skill,
+            min_players,
+            start_at_match_id,
+            matches_requested,
+            tournament_games_only
+        )
+    }
+
+    #[cfg(feature = "async")]
+    #[allow(clippy::too_many_arguments)]
+    pub async fn get_dota2_match_history_async(&self,
+                                               account_id: Option<SteamId>,
+                                               game_mode: Option<GameMode>,
+                                               skill: Option<Skill>,
+                                               min_players: Option<u32>,
+                                               start_at_match_id: Option<i64>,
+                                               matches_requested: Option<u32>,
+                                               tournament_games_only: Option<bool>)
+        -> Result<ResponseMatchHistory, SteamApiError> {
+        idota2match_570::get_match_history::get_async_with_client(
+            self,
+            account_id,
+            game_mode,
+            skill,
+            min_players,
+            start_at_match_id,
+            matches_requested,
+            tournament_games_only
+        ).await
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn get_cached_dota2_match_history(&self,
+                                          account_id: Option<SteamId>,
+                                          game_mode: Option<GameMode>,
+                                          skill: Option<Skill>,
+                                          min_players: Option<u32>,
+                                          start_at_match_id: Option<i64>,
+                                          matches_requested: Option<u32>,
+                                          tournament_games_only: Option<bool>)
+        -> Result<ResponseMatchHistory, SteamApiError> {
+        idota2match_570::get_match_history::get_cached_with_client(
+            self,
+            account_id,
+            game_mode,
+            skill,
+            min_players,
+            start_at_match_id,
+            matches_requested,
+            tournament_games_only
+        )
+    }
+
+    #[cfg(feature = "async")]
+    #[allow(clippy::too_many_arguments)]
+    pub async fn get_cached_dota2_match_history_async(&self,
+                                                      account_id: Option<SteamId>,
+                                                      game_mode: Option<GameMode>,
+                                                      skill: Option<Skill>,
+                                                      min_players: Option<u32>,
+                                                      start_at_match_id: Option<i64>,
+                                                      matches_requested: Option<u32>,
+                                                      tournament_games_only: Option<bool>)
+        -> Result<ResponseMatchHistory, SteamApiError> {
+        idota2match_570::get_match_history::get_cached_async_with_client(
+            self,
+            account_id,
+            game_mode,
+            skill,
+            min_players,
+            start_at_match_id,
+            matches_requested,
+            tournament_games_only
+        ).await
+    }
+
+    pub fn get_dota2_match_history_pages(&self, filter: MatchHistoryFilter) -> MatchHistoryPages {
+        idota2match_570::get_match_history::get_pages_with_client(self, filter)
+    }
+
+    pub fn get_dota2_match_history_by_sequence_num(&self, start_at_match_seq_num: Option<u64>, matches_requested: Option<u32>) -> Result<Vec<MatchResult>, SteamApiError> {
+        idota2match_570::get_match_history_by_sequence_num::get_with_client(self, start_at_match_seq_num, matches_requested)
+    }
+
+    #[cfg(feature = "async")]
+    pub async fn get_dota2_match_history_by_sequence_num_async(&self, start_at_match_seq_num: Option<u64>, matches_requested: Option<u32>) -> Result<Vec<MatchResult>, SteamApiError> {
+        idota2match_570::get_match_history_by_sequence_num::get_async_with_client(self, start_at_match_seq_num, matches_requested).await
+    }
+
+    pub fn get_cached_dota2_match_history_by_sequence_num(&self, start_at_match_seq_num: Option<u64>, matches_requested: Option<u32>) -> Result<Vec<MatchResult>, SteamApiError> {
+        idota2match_570::get_match_history_by_sequence_num::get_cached_with_client(self, start_at_match_seq_num, matches_requested)
+    }
+
+    #[cfg(feature = "async")]
+    pub async fn get_cached_dota2_match_history_by_sequence_num_async(&self, start_at_match_seq_num: Option<u64>, matches_requested: Option<u32>) -> Result<Vec<MatchResult>, SteamApiError> {
+        idota2match_570::get_match_history_by_sequence_num::get_cached_async_with_client(self, start_at_match_seq_num, matches_requested).await
+    }
+
+    pub fn get_dota2_match_details(&self, match_id: u64) -> Result<MatchResult, SteamApiError> {
+        idota2match_570::get_match_details::get_with_client(self, match_id)
+    }
+
+    #[cfg(feature = "async")]
+    pub async fn get_dota2_match_details_async(&self, match_id: u64) -> Result<MatchResult, SteamApiError> {
+        idota2match_570::get_match_details::get_async_with_client(self, match_id).await
+    }
+
+    pub fn get_cached_dota2_match_details(&self, match_id: u64) -> Result<MatchResult, SteamApiError> {
+        idota2match_570::get_match_details::get_cached_with_client(self, match_id)
+    }
+
+    #[cfg(feature = "async")]
+    pub async fn get_cached_dota2_match_details_async(&self, match_id: u64) -> Result<MatchResult, SteamApiError> {
+        idota2match_570::get_match_details::get_cached_async_with_client(self, match_id).await
+    }
+
+    pub fn get_dota2_heroes(&self, language: Option<String>) -> Result<Vec<Hero>, SteamApiError> {
+        idota2match_570::get_heroes::get_with_client(self, language)
+    }
+
+    #[cfg(feature = "async")]
+    pub async fn get_dota2_heroes_async(&self, language: Option<String>) -> Result<Vec<Hero>, SteamApiError> {
+        idota2match_570::get_heroes::get_async_with_client(self, language).await
+    }
+
+    pub fn get_cached_dota2_heroes(&self, language: Option<String>) -> Result<Vec<Hero>, SteamApiError> {
+        idota2match_570::get_heroes::get_cached_with_client(self, language)
+    }
+
+    #[cfg(feature = "async")]
+    pub async fn get_cached_dota2_heroes_async(&self, language: Option<String>) -> Result<Vec<Hero>, SteamApiError> {
+        idota2match_570::get_heroes::get_cached_async_with_client(self, language).await
+    }
+
+    pub fn get_dota2_game_items(&self, language: Option<String>) -> Result<Vec<GameItem>, SteamApiError> {
+        iecon_dota2_570::get_game_items::get_with_client(self, language)
+    }
+
+    #[cfg(feature = "async")]
+    pub async fn get_dota2_game_items_async(&self, language: Option<String>) -> Result<Vec<GameItem>, SteamApiError> {
+        iecon_dota2_570::get_game_items::get_async_with_client(self, language).await
+    }
+
+    pub fn get_cached_dota2_game_items(&self, language: Option<String>) -> Result<Vec<GameItem>, SteamApiError> {
+        iecon_dota2_570::get_game_items::get_cached_with_client(self, language)
+    }
+
+    #[cfg(feature = "async")]
+    pub async fn get_cached_dota2_game_items_async(&self, language: Option<String>) -> Result<Vec<GameItem>, SteamApiError> {
+        iecon_dota2_570::get_game_items::get_cached_async_with_client(self, language).await
+    }
+
+    pub fn get_dota2_league_listing(&self, language: Option<String>) -> Result<Vec<League>, SteamApiError> {
+        idota2match_570::get_league_listing::get_with_client(self, language)
+    }
+
+    #[cfg(feature = "async")]
+    pub async fn get_dota2_league_listing_async(&self, language: Option<String>) -> Result<Vec<League>, SteamApiError> {
+        idota2match_570::get_league_listing::get_async_with_client(self, language).await
+    }
+
+    pub fn get_cached_dota2_league_listing(&self, language: Option<String>) -> Result<Vec<League>, SteamApiError> {
+        idota2match_570::get_league_listing::get_cached_with_client(self, language)
+    }
+
+    #[cfg(feature = "async")]
+    pub async fn get_cached_dota2_league_listing_async(&self, language: Option<String>) -> Result<Vec<League>, SteamApiError> {
+        idota2match_570::get_league_listing::get_cached_async_with_client(self, language).await
+    }
+
+    pub fn get_dota2_live_league_games(&self) -> Result<Vec<LiveLeagueGame>, SteamApiError> {
+        idota2match_570::get_live_league_games::get_with_client(self)
+    }
+
+    #[cfg(feature = "async")]
+    pub async fn get_dota2_live_league_games_async(&self) -> Result<Vec<LiveLeagueGame>, SteamApiError> {
+        idota2match_570::get_live_league_games::get_async_with_client(self).await
+    }
+
+    pub fn get_cached_dota2_live_league_games(&self) -> Result<Vec<LiveLeagueGame>, SteamApiError> {
+        idota2match_570::get_live_league_games::get_cached_with_client(self)
+    }
+
+    #[cfg(feature = "async")]
+    pub async fn get_cached_dota2_live_league_games_async(&self) -> Result<Vec<LiveLeagueGame>, SteamApiError> {
+        idota2match_570::get_live_league_games::get_cached_async_with_client(self).await
+    }
+
+    pub fn get_dota2_team_info_by_team_id(&self, start_at_team_id: Option<u64>, teams_requested: Option<u32>) -> Result<Vec<TeamInfo>, SteamApiError> {
+        idota2match_570::get_team_info_by_team_id::get_with_client(self, start_at_team_id, teams_requested)
+    }
+
+    #[cfg(feature = "async")]
+    pub async fn get_dota2_team_info_by_team_id_async(&self, start_at_team_id: Option<u64>, teams_requested: Option<u32>) -> Result<Vec<TeamInfo>, SteamApiError> {
+        idota2match_570::get_team_info_by_team_id::get_async_with_client(self, start_at_team_id, teams_requested).await
+    }
+
+    pub fn get_cached_dota2_team_info_by_team_id(&self, start_at_team_id: Option<u64>, teams_requested: Option<u32>) -> Result<Vec<TeamInfo>, SteamApiError> {
+        idota2match_570::get_team_info_by_team_id::get_cached_with_client(self, start_at_team_id, teams_requested)
+    }
+
+    #[cfg(feature = "async")]
+    pub async fn get_cached_dota2_team_info_by_team_id_async(&self, start_at_team_id: Option<u64>, teams_requested: Option<u32>) -> Result<Vec<TeamInfo>, SteamApiError> {
+        idota2match_570::get_team_info_by_team_id::get_cached_async_with_client(self, start_at_team_id, teams_requested).await
+    }
+
+    pub fn get_player_summaries(&self, steamids: Vec<impl TryInto<SteamId, Error = impl Display>>) -> Result<Vec<PlayerSummary>, SteamApiError> {
+        isteam_user::get_player_summaries::get_with_client(self, steamids)
+    }
+
+    #[cfg(feature = "async")]
+    pub async fn get_player_summaries_async(&self, steamids: Vec<impl TryInto<SteamId, Error = impl Display>>) -> Result<Vec<PlayerSummary>, SteamApiError> {
+        isteam_user::get_player_summaries::get_async_with_client(self, steamids).await
+    }
+
+    pub fn get_cached_player_summaries(&self, steamids: Vec<impl TryInto<SteamId, Error = impl Display>>) -> Result<Vec<PlayerSummary>, SteamApiError> {
+        isteam_user::get_player_summaries::get_cached_with_client(self, steamids)
+    }
+
+    #[cfg(feature = "async")]
+    pub async fn get_cached_player_summaries_async(&self, steamids: Vec<impl TryInto<SteamId, Error = impl Display>>) -> Result<Vec<PlayerSummary>, SteamApiError> {
+        isteam_user::get_player_summaries::get_cached_async_with_client(self, steamids).await
+    }
+
+    pub fn get_friend_list(&self, steamid: impl TryInto<SteamId, Error = impl Display>, relationship: Option<String>) -> Result<Vec<Friend>, SteamApiError> {
+        isteam_user::get_friend_list::get_with_client(self, steamid, relationship)
+    }
+
+    #[cfg(feature = "async")]
+    pub async fn get_friend_list_async(&self, steamid: impl TryInto<SteamId, Error = impl Display>, relationship: Option<String>) -> Result<Vec<Friend>, SteamApiError> {
+        isteam_user::get_friend_list::get_async_with_client(self, steamid, relationship).await
+    }
+
+    pub fn get_cached_friend_list(&self, steamid: impl TryInto<SteamId, Error = impl Display>, relationship: Option<String>) -> Result<Vec<Friend>, SteamApiError> {
+        isteam_user::get_friend_list::get_cached_with_client(self, steamid, relationship)
+    }
+
+    #[cfg(feature = "async")]
+    pub async fn get_cached_friend_list_async(&self, steamid: impl TryInto<SteamId, Error = impl Display>, relationship: Option<String>) -> Result<Vec<Friend>, SteamApiError> {
+        isteam_user::get_friend_list::get_cached_async_with_client(self, steamid, relationship).await
+    }
+
+    pub fn get_player_bans(&self, steamids: Vec<impl TryInto<SteamId, Error = impl Display>>) -> Result<Vec<PlayerBans>, SteamApiError> {
+        isteam_user::get_player_bans::get_with_client(self, steamids)
+    }
+
+    #[cfg(feature = "async")]
+    pub async fn get_player_bans_async(&self, steamids: Vec<impl TryInto<SteamId, Error = impl Display>>) -> Result<Vec<PlayerBans>, SteamApiError> {
+        isteam_user::get_player_bans::get_async_with_client(self, steamids).await
+    }
+
+    pub fn get_cached_player_bans(&self, steamids: Vec<impl TryInto<SteamId, Error = impl Display>>) -> Result<Vec<PlayerBans>, SteamApiError> {
+        isteam_user::get_player_bans::get_cached_with_client(self, steamids)
+    }
+
+    #[cfg(feature = "async")]
+    pub async fn get_cached_player_bans_async(&self, steamids: Vec<impl TryInto<SteamId, Error = impl Display>>) -> Result<Vec<PlayerBans>, SteamApiError> {
+        isteam_user::get_player_bans::get_cached_async_with_client(self, steamids).await
+    }
+
+    pub fn resolve_vanity_url(&self, vanity_url: String, url_type: Option<u8>) -> Result<VanityUrlResolution, SteamApiError> {
+        isteam_user::resolve_vanity_url::get_with_client(self, vanity_url, url_type)
+    }
+
+    #[cfg(feature = "async")]
+    pub async fn resolve_vanity_url_async(&self, vanity_url: String, url_type: Option<u8>) -> Result<VanityUrlResolution, SteamApiError> {
+        isteam_user::resolve_vanity_url::get_async_with_client(self, vanity_url, url_type).await
+    }
+
+    pub fn resolve_cached_vanity_url(&self, vanity_url: String, url_type: Option<u8>) -> Result<VanityUrlResolution, SteamApiError> {
+        isteam_user::resolve_vanity_url::get_cached_with_client(self, vanity_url, url_type)
+    }
+
+    #[cfg(feature = "async")]
+    pub async fn resolve_cached_vanity_url_async(&self, vanity_url: String, url_type: Option<u8>) -> Result<VanityUrlResolution, SteamApiError> {
+        isteam_user::resolve_vanity_url::get_cached_async_with_client(self, vanity_url, url_type).await
+    }
+
+    /// See `isteam_user::resolve_vanity_url::resolve`.
+    pub fn resolve_steam_id(&self, input: &str, url_type: Option<u8>) -> Result<SteamIdResolution, SteamApiError> {
+        isteam_user::resolve_vanity_url::resolve_with_client(self, input, url_type)
+    }
+
+    #[cfg(feature = "async")]
+    pub async fn resolve_steam_id_async(&self, input: &str, url_type: Option<u8>) -> Result<SteamIdResolution, SteamApiError> {
+        isteam_user::resolve_vanity_url::resolve_async_with_client(self, input, url_type).await
+    }
+
+    pub fn get_owned_games(&self, steamid: impl TryInto<SteamId, Error = impl Display>, include_appinfo: Option<bool>, include_played_free_games: Option<bool>) -> Result<OwnedGames, SteamApiError> {
+        iplayer_service::get_owned_games::get_with_client(self, steamid, include_appinfo, include_played_free_games)
+    }
+
+    #[cfg(feature = "async")]
+    pub async fn get_owned_games_async(&self, steamid: impl TryInto<SteamId, Error = impl Display>, include_appinfo: Option<bool>, include_played_free_games: Option<bool>) -> Result<OwnedGames, SteamApiError> {
+        iplayer_service::get_owned_games::get_async_with_client(self, steamid, include_appinfo, include_played_free_games).await
+    }
+
+    pub fn get_cached_owned_games(&self, steamid: impl TryInto<SteamId, Error = impl Display>, include_appinfo: Option<bool>, include_played_free_games: Option<bool>) -> Result<OwnedGames, SteamApiError> {
+        iplayer_service::get_owned_games::get_cached_with_client(self, steamid, include_appinfo, include_played_free_games)
+    }
+
+    #[cfg(feature = "async")]
+    pub async fn get_cached_owned_games_async(&self, steamid: impl TryInto<SteamId, Error = impl Display>, include_appinfo: Option<bool>, include_played_free_games: Option<bool>) -> Result<OwnedGames, SteamApiError> {
+        iplayer_service::get_owned_games::get_cached_async_with_client(self, steamid, include_appinfo, include_played_free_games).await
+    }
+
+    pub fn get_recently_played_games(&self, steamid: impl TryInto<SteamId, Error = impl Display>, count: Option<u32>) -> Result<RecentlyPlayedGames, SteamApiError> {
+        iplayer_service::get_recently_played_games::get_with_client(self, steamid, count)
+    }
+
+    #[cfg(feature = "async")]
+    pub async fn get_recently_played_games_async(&self, steamid: impl TryInto<SteamId, Error = impl Display>, count: Option<u32>) -> Result<RecentlyPlayedGames, SteamApiError> {
+        iplayer_service::get_recently_played_games::get_async_with_client(self, steamid, count).await
+    }
+
+    pub fn get_cached_recently_played_games(&self, steamid: impl TryInto<SteamId, Error = impl Display>, count: Option<u32>) -> Result<RecentlyPlayedGames, SteamApiError> {
+        iplayer_service::get_recently_played_games::get_cached_with_client(self, steamid, count)
+    }
+
+    #[cfg(feature = "async")]
+    pub async fn get_cached_recently_played_games_async(&self, steamid: impl TryInto<SteamId, Error = impl Display>, count: Option<u32>) -> Result<RecentlyPlayedGames, SteamApiError> {
+        iplayer_service::get_recently_played_games::get_cached_async_with_client(self, steamid, count).await
+    }
+
+    pub fn get_steam_level(&self, steamid: impl TryInto<SteamId, Error = impl Display>) -> Result<u64, SteamApiError> {
+        iplayer_service::get_steam_level::get_with_client(self, steamid)
+    }
+
+    #[cfg(feature = "async")]
+    pub async fn get_steam_level_async(&self, steamid: impl TryInto<SteamId, Error = impl Display>) -> Result<u64, SteamApiError> {
+        iplayer_service::get_steam_level::get_async_with_client(self, steamid).await
+    }
+
+    pub fn get_cached_steam_level(&self, steamid: impl TryInto<SteamId, Error = impl Display>) -> Result<u64, SteamApiError> {
+        iplayer_service::get_steam_level::get_cached_with_client(self, steamid)
+    }
+
+    #[cfg(feature = "async")]
+    pub async fn get_cached_steam_level_async(&self, steamid: impl TryInto<SteamId, Error = impl Display>) -> Result<u64, SteamApiError> {
+        iplayer_service::get_steam_level::get_cached_async_with_client(self, steamid).await
+    }
+
+    pub fn get_badges(&self, steamid: impl TryInto<SteamId, Error = impl Display>) -> Result<Badges, SteamApiError> {
+        iplayer_service::get_badges::get_with_client(self, steamid)
+    }
+
+    #[cfg(feature = "async")]
+    pub async fn get_badges_async(&self, steamid: impl TryInto<SteamId, Error = impl Display>) -> Result<Badges, SteamApiError> {
+        iplayer_service::get_badges::get_async_with_client(self, steamid).await
+    }
+
+    pub fn get_cached_badges(&self, steamid: impl TryInto<SteamId, Error = impl Display>) -> Result<Badges, SteamApiError> {
+        iplayer_service::get_badges::get_cached_with_client(self, steamid)
+    }
+
+    #[cfg(feature = "async")]
+    pub async fn get_cached_badges_async(&self, steamid: impl TryInto<SteamId, Error = impl Display>) -> Result<Badges, SteamApiError> {
+        iplayer_service::get_badges::get_cached_async_with_client(self, steamid).await
+    }
+
+    pub fn get_player_achievements(&self, steamid: impl TryInto<SteamId, Error = impl Display>, appid: i64, language: Option<String>) -> Result<PlayerAchievements, SteamApiError> {
+        isteam_user_stats::get_player_achievements::get_with_client(self, steamid, appid, language)
+    }
+
+    #[cfg(feature = "async")]
+    pub async fn get_player_achievements_async(&self, steamid: impl TryInto<SteamId, Error = impl Display>, appid: i64, language: Option<String>) -> Result<PlayerAchievements, SteamApiError> {
+        isteam_user_stats::get_player_achievements::get_async_with_client(self, steamid, appid, language).await
+    }
+
+    pub fn get_cached_player_achievements(&self, steamid: impl TryInto<SteamId, Error = impl Display>, appid: i64, language: Option<String>) -> Result<PlayerAchievements, SteamApiError> {
+        isteam_user_stats::get_player_achievements::get_cached_with_client(self, steamid, appid, language)
+    }
+
+    #[cfg(feature = "async")]
+    pub async fn get_cached_player_achievements_async(&self, steamid: impl TryInto<SteamId, Error = impl Display>, appid: i64, language: Option<String>) -> Result<PlayerAchievements, SteamApiError> {
+        isteam_user_stats::get_player_achievements::get_cached_async_with_client(self, steamid, appid, language).await
+    }
+
+    pub fn get_user_stats_for_game(&self, steamid: impl TryInto<SteamId, Error = impl Display>, appid: i64) -> Result<UserStatsForGame, SteamApiError> {
+        isteam_user_stats::get_user_stats_for_game::get_with_client(self, steamid, appid)
+    }
+
+    #[cfg(feature = "async")]
+    pub async fn get_user_stats_for_game_async(&self, steamid: impl TryInto<SteamId, Error = impl Display>, appid: i64) -> Result<UserStatsForGame, SteamApiError> {
+        isteam_user_stats::get_user_stats_for_game::get_async_with_client(self, steamid, appid).await
+    }
+
+    pub fn get_cached_user_stats_for_game(&self, steamid: impl TryInto<SteamId, Error = impl Display>, appid: i64) -> Result<UserStatsForGame, SteamApiError> {
+        isteam_user_stats::get_user_stats_for_game::get_cached_with_client(self, steamid, appid)
+    }
+
+    #[cfg(feature = "async")]
+    pub async fn get_cached_user_stats_for_game_async(&self, steamid: impl TryInto<SteamId, Error = impl Display>, appid: i64) -> Result<UserStatsForGame, SteamApiError> {
+        isteam_user_stats::get_user_stats_for_game::get_cached_async_with_client(self, steamid, appid).await
+    }
+
+    pub fn get_schema_for_game(&self, appid: i64) -> Result<GameSchema, SteamApiError> {
+        isteam_user_stats::get_schema_for_game::get_with_client(self, appid)
+    }
+
+    #[cfg(feature = "async")]
+    pub async fn get_schema_for_game_async(&self, appid: i64) -> Result<GameSchema, SteamApiError> {
+        isteam_user_stats::get_schema_for_game::get_async_with_client(self, appid).await
+    }
+
+    pub fn get_cached_schema_for_game(&self, appid: i64) -> Result<GameSchema, SteamApiError> {
+        isteam_user_stats::get_schema_for_game::get_cached_with_client(self, appid)
+    }
+
+    #[cfg(feature = "async")]
+    pub async fn get_cached_schema_for_game_async(&self, appid: i64) -> Result<GameSchema, SteamApiError> {
+        isteam_user_stats::get_schema_for_game::get_cached_async_with_client(self, appid).await
+    }
+
+    pub fn get_global_achievement_percentages_for_app(&self, appid: i64) -> Result<Vec<AchievementPercentage>, SteamApiError> {
+        isteam_user_stats::get_global_achievement_percentages_for_app::get_with_client(self, appid)
+    }
+
+    #[cfg(feature = "async")]
+    pub async fn get_global_achievement_percentages_for_app_async(&self, appid: i64) -> Result<Vec<AchievementPercentage>, SteamApiError> {
+        isteam_user_stats::get_global_achievement_percentages_for_app::get_async_with_client(self, appid).await
+    }
+
+    pub fn get_cached_global_achievement_percentages_for_app(&self, appid: i64) -> Result<Vec<AchievementPercentage>, SteamApiError> {
+        isteam_user_stats::get_global_achievement_percentages_for_app::get_cached_with_client(self, appid)
+    }
+
+    #[cfg(feature = "async")]
+    pub async fn get_cached_global_achievement_percentages_for_app_async(&self, appid: i64) -> Result<Vec<AchievementPercentage>, SteamApiError> {
+        isteam_user_stats::get_global_achievement_percentages_for_app::get_cached_async_with_client(self, appid).await
+    }
+
+    pub fn get_number_of_current_players(&self, appid: i64) -> Result<i64, SteamApiError> {
+        isteam_user_stats::get_number_of_current_players::get_with_client(self, appid)
+    }
+
+    #[cfg(feature = "async")]
+    pub async fn get_number_of_current_players_async(&self, appid: i64) -> Result<i64, SteamApiError> {
+        isteam_user_stats::get_number_of_current_players::get_async_with_client(self, appid).await
+    }
+
+    pub fn get_cached_number_of_current_players(&self, appid: i64) -> Result<i64, SteamApiError> {
+        isteam_user_stats::get_number_of_current_players::get_cached_with_client(self, appid)
+    }
+
+    #[cfg(feature = "async")]
+    pub async fn get_cached_number_of_current_players_async(&self, appid: i64) -> Result<i64, SteamApiError> {
+        isteam_user_stats::get_number_of_current_players::get_cached_async_with_client(self, appid).await
+    }
+
+    pub fn get_news_for_app(&self, appid: i64, count: Option<u32>, maxlength: Option<u32>) -> Result<Vec<NewsItem>, SteamApiError> {
+        isteam_news::get_news_for_app::get_with_client(self, appid, count, maxlength)
+    }
+
+    #[cfg(feature = "async")]
+    pub async fn get_news_for_app_async(&self, appid: i64, count: Option<u32>, maxlength: Option<u32>) -> Result<Vec<NewsItem>, SteamApiError> {
+        isteam_news::get_news_for_app::get_async_with_client(self, appid, count, maxlength).await
+    }
+
+    pub fn get_cached_news_for_app(&self, appid: i64, count: Option<u32>, maxlength: Option<u32>) -> Result<Vec<NewsItem>, SteamApiError> {
+        isteam_news::get_news_for_app::get_cached_with_client(self, appid, count, maxlength)
+    }
+
+    #[cfg(feature = "async")]
+    pub async fn get_cached_news_for_app_async(&self, appid: i64, count: Option<u32>, maxlength: Option<u32>) -> Result<Vec<NewsItem>, SteamApiError> {
+        isteam_news::get_news_for_app::get_cached_async_with_client(self, appid, count, maxlength).await
+    }
+}
+
+/// Returns the host of the url, or an empty string if it can't be parsed.
+fn get_url_host(url: &str) -> String {
+    let boxed_components = parse_url(url);
+    if boxed_components.is_err() {
+        return "".to_string();
+    }
+    let components = boxed_components.unwrap();
+    if components.authority.is_none() {
+        return "".to_string();
+    }
+    components.authority.unwrap().host
+}
+
+fn parse_base_url(base_url: &str) -> Result<UrlComponents, String> {
+    let boxed_base = parse_url(base_url);
+    if boxed_base.is_err() {
+        return Err(["invalid base url ", base_url, ": ", boxed_base.err().unwrap().as_str()].join(""));
+    }
+    let base = boxed_base.unwrap();
+    if base.authority.is_none() {
+        return Err(["invalid base url ", base_url, ": no host"].join(""));
+    }
+    Ok(base)
+}
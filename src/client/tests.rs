@@ -0,0 +1,197 @@
+use std::sync::Arc;
+use std::time::Duration;
+use url_build_parse::parse_url;
+use crate::client::{get_default_user_agent, SteamClient, DEFAULT_API_BASE_URL, DEFAULT_COMMUNITY_BASE_URL, DEFAULT_STORE_BASE_URL};
+use crate::rate_limit::{get_shared_rate_limiter, RateLimiter};
+use crate::util::get_cache_dir_path;
+
+#[test]
+fn builder_defaults() {
+    let client = SteamClient::builder().build();
+
+    assert_eq!("", client.api_key());
+    assert_eq!(DEFAULT_API_BASE_URL, client.api_base_url());
+    assert_eq!(DEFAULT_STORE_BASE_URL, client.store_base_url());
+    assert_eq!(DEFAULT_COMMUNITY_BASE_URL, client.community_base_url());
+    assert_eq!(None, client.timeout());
+    assert_eq!(get_default_user_agent(), client.user_agent());
+    assert!(client.cache_enabled());
+    assert_eq!(get_cache_dir_path(), client.cache_dir());
+    assert!(client.rate_limiter().is_some());
+    assert_eq!(&crate::cache::CachePolicy::default(), client.cache_policy());
+
+    let other_client = SteamClient::builder().build();
+    assert!(std::ptr::eq(client.rate_limiter().unwrap(), other_client.rate_limiter().unwrap()));
+    assert!(std::ptr::eq(client.rate_limiter().unwrap(), get_shared_rate_limiter().as_ref()));
+
+    let own_client = SteamClient::builder().rate_limiter(Arc::new(RateLimiter::default())).build();
+    assert!(!std::ptr::eq(client.rate_limiter().unwrap(), own_client.rate_limiter().unwrap()));
+}
+
+#[test]
+fn builder_overrides() {
+    let client = SteamClient::builder()
+        .api_key("KEY")
+        .api_base_url("http://127.0.0.1:8080")
+        .store_base_url("http://127.0.0.1:8081")
+        .community_base_url("http://127.0.0.1:8082")
+        .timeout(Duration::from_secs(5))
+        .user_agent("my-app/1.0")
+        .cache_enabled(false)
+        .cache_dir("/tmp/steam-cache")
+        .rate_limiter_enabled(false)
+        .build();
+
+    assert_eq!("KEY", client.api_key());
+    assert_eq!("http://127.0.0.1:8080", client.api_base_url());
+    assert_eq!("http://127.0.0.1:8081", client.store_base_url());
+    assert_eq!("http://127.0.0.1:8082", client.community_base_url());
+    assert_eq!(Some(Duration::from_secs(5)), client.timeout());
+    assert_eq!("my-app/1.0", client.user_agent());
+    assert!(!client.cache_enabled());
+    assert_eq!("/tmp/steam-cache", client.cache_dir());
+    assert!(client.rate_limiter().is_none());
+}
+
+#[test]
+fn resolve_api_url() {
+    let client = SteamClient::builder()
+        .api_key("KEY")
+        .api_base_url("http://localhost:8080/steam/")
+        .build();
+
+    let url = client.resolve_url("https://api.steampowered.com/ISteamUser/GetPlayerSummaries/v2?key=OTHER&steamids=76561197960434622").unwrap();
+    let url_components = parse_url(url.as_str()).unwrap();
+
+    assert_eq!("http", url_components.scheme);
+    let authority = url_components.authority.unwrap();
+    assert_eq!("localhost", authority.host);
+    assert_eq!(8080, authority.port.unwrap());
+    assert_eq!("/steam/ISteamUser/GetPlayerSummaries/v2", url_components.path);
+    let params = url_components.query.unwrap();
+    assert_eq!("KEY", params.get("key").unwrap());
+    assert_eq!("76561197960434622", params.get("steamids").unwrap());
+}
+
+#[test]
+fn resolve_api_url_adds_key() {
+    let client = SteamClient::builder()
+        .api_key("KEY")
+        .build();
+
+    let url = client.resolve_url("https://api.steampowered.com/ISteamApps/GetAppList/v2").unwrap();
+    assert_eq!("https://api.steampowered.com/ISteamApps/GetAppList/v2?key=KEY", url);
+
+    let client_without_key = SteamClient::builder().build();
+    let url = client_without_key.resolve_url("https://api.steampowered.com/ISteamApps/GetAppList/v2").unwrap();
+    assert_eq!("https://api.steampowered.com/ISteamApps/GetAppList/v2", url);
+}
+
+#[test]
+fn resolve_store_url() {
+    let client = SteamClient::builder()
+        .api_key("KEY")
+        .store_base_url("http://localhost:8081")
+        .build();
+
+    let url = client.resolve_url("https://store.steampowered.com/api/appdetails?appids=730&lang=en").unwrap();
+    let url_components = parse_url(url.as_str()).unwrap();
+
+    assert_eq!("http", url_components.scheme);
+    assert_eq!("localhost", url_components.authority.unwrap().host);
+    assert_eq!("/api/appdetails", url_components.path);
+    let params = url_components.query.unwrap();
+    assert_eq!("730", params.get("appids").unwrap());
+    assert!(!params.contains_key("key"));
+}
+
+#[test]
+fn resolve_unknown_host_url() {
+    let client = SteamClient::builder()
+        .api_base_url("http://localhost:8080")
+        .build();
+
+    let url = "https://example.com/ISteamUser/GetPlayerSummaries/v2";
+    assert_eq!(url, client.resolve_url(url).unwrap());
+}
+
+#[test]
+fn resource_filepath() {
+    let client = SteamClient::builder()
+        .cache_dir("/tmp/steam-cache")
+        .build();
+
+    assert_eq!("/tmp/steam-cache/ISteamApps-GetAppList-v2.json", client.get_resource_filepath("ISteamApps-GetAppList-v2.json"));
+}
+
+#[test]
+fn errors_do_not_expose_api_key() {
+    let transport = std::sync::Arc::new(crate::transport::FakeTransport::new());
+    transport.respond_with(
+        "https://api.steampowered.com/ISteamUser/GetPlayerSummaries/v2?key=SECRET&steamids=76561197960435530",
+        403,
+        "<html><body>Forbidden</body></html>",
+    );
+    let client = SteamClient::builder()
+        .api_key("SECRET")
+        .cache_enabled(false)
+        .rate_limiter_enabled(false)
+        .retry_policy(crate::retry::RetryPolicy::none())
+        .transport(transport.clone())
+        .build();
+
+    let error = client.get_player_summaries(vec![76561197960435530]).err().unwrap();
+
+    assert_eq!(Some(403), error.http_status_code());
+    assert!(transport.requests().first().unwrap().url.contains("key=SECRET"));
+    assert!(!error.url().contains("SECRET"));
+    assert!(!error.to_string().contains("SECRET"));
+}
+
+#[cfg(feature = "async")]
+fn get_fake_async_client() -> (SteamClient, std::sync::Arc<crate::transport::FakeTransport>) {
+    let transport = std::sync::Arc::new(crate::transport::FakeTransport::new());
+    transport.respond_with(
+        "https://api.steampowered.com/ISteamUser/GetPlayerSummaries/v2?key=KEY&steamids=76561197960435530",
+        200,
+        r#"{"response":{"players":[{"steamid":"76561197960435530","communityvisibilitystate":3,"profilestate":1,"personaname":"Robin","profileurl":"https://steamcommunity.com/id/robinwalker/","avatar":"","avatarmedium":"","avatarfull":"","personastate":0,"realname":"Robin Walker","primaryclanid":"103582791429521412","timecreated":1063407589,"personastateflags":0,"loccountrycode":"US"}]}}"#,
+    );
+    transport.respond_with(
+        "https://store.steampowered.com/api/appdetails?appids=440&lang=en",
+        200,
+        r#"{"440":{"success":false}}"#,
+    );
+
+    let client = SteamClient::builder()
+        .api_key("KEY")
+        .cache_enabled(false)
+        .transport(transport.clone())
+        .async_transport(transport.clone())
+        .build();
+
+    (client, transport)
+}
+
+#[cfg(feature = "async")]
+#[tokio::test]
+async fn async_matches_blocking() {
+    let (client, transport) = get_fake_async_client();
+
+    let player_summaries = client.get_player_summaries(vec![76561197960435530]).unwrap();
+    let async_player_summaries = client.get_player_summaries_async(vec![76561197960435530]).await.unwrap();
+
+    assert_eq!(player_summaries, async_player_summaries);
+    assert_eq!("Robin", async_player_summaries.first().unwrap().personaname);
+    assert_eq!(2, transport.requests().len());
+}
+
+#[cfg(feature = "async")]
+#[tokio::test]
+async fn async_reports_steam_failure() {
+    let (client, _) = get_fake_async_client();
+
+    let error = client.get_app_details_async(440).await.err().unwrap();
+
+    assert_eq!(client.get_app_details(440).err().unwrap(), error);
+    assert!(matches!(error, crate::error::SteamApiError::SteamFailure { .. }));
+}
@@ -0,0 +1,183 @@
+use std::error::Error;
+use std::fmt;
+use std::time::Duration;
+use crate::util::strip_api_key;
+
+#[cfg(test)]
+mod tests;
+
+/// Steam's `result.status` code for a Dota2 match history that the account owner hasn't exposed.
+pub const STATUS_NOT_ALLOWED_BY_USER_PREFERENCES: i64 = 15;
+
+/// Error returned by every endpoint of the SDK.
+///
+/// Each variant carries the `endpoint` it originated from (e.g. `"ISteamUser/GetPlayerSummaries"`
+/// or `"steampowered/appdetails"`) and the `url` that was requested. For `CacheMiss` the `url` is
+/// the filepath of the cached resource. The `key` query parameter is removed from the `url`, so
+/// errors can be logged without exposing the Steam Web API key.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum SteamApiError {
+    /// The request did not produce an HTTP response: DNS failure, refused connection, timeout, TLS error.
+    Transport { endpoint: String, url: String, message: String },
+    /// Steam responded with a non-success HTTP status, e.g. 401/403 for a bad key, 429 when
+    /// throttled or 5xx when Steam itself is having trouble. `body` is the raw response body.
+    HttpStatus { endpoint: String, url: String, status: u16, body: String },
+    /// The response body is not valid UTF-8/JSON or is missing the fields the endpoint expects.
+    MalformedResponse { endpoint: String, url: String, message: String },
+    /// Steam returned a well-formed response reporting a failure, such as `success: false`,
+    /// `result.error` or a non-success `result.status` (see `STATUS_NOT_ALLOWED_BY_USER_PREFERENCES`).
+    SteamFailure { endpoint: String, url: String, status: Option<i64>, message: String },
+    /// The requested data belongs to a private profile (friends list, stats, achievements).
+    PrivateProfile { endpoint: String, url: String },
+    /// The resource is absent from the local cache or can't be read from it.
+    CacheMiss { endpoint: String, url: String },
+    /// Sending the request would exceed the rate limit budget of its host; `retry_after` is the time
+    /// until the budget allows another request. Only returned by limiters in `RateLimitMode::Error`.
+    RateLimited { endpoint: String, url: String, retry_after: Duration },
+    /// Reading or writing local state other than the cache failed, such as the checkpoint file of
+    /// `idota2match_570::match_crawler::MatchCrawler`. The `url` is the filepath.
+    Storage { endpoint: String, url: String, message: String },
+    /// An argument can't be sent to Steam, such as a number that is no valid `SteamId`. Returned
+    /// before any request is made, so the `url` is empty.
+    InvalidInput { endpoint: String, url: String, message: String },
+}
+
+impl SteamApiError {
+    pub fn transport(endpoint: &str, url: &str, message: &str) -> SteamApiError {
+        SteamApiError::Transport { endpoint: endpoint.to_string(), url: strip_api_key(url), message: message.to_string() }
+    }
+
+    pub fn http_status(endpoint: &str, url: &str, status: u16, body: &str) -> SteamApiError {
+        SteamApiError::HttpStatus { endpoint: endpoint.to_string(), url: strip_api_key(url), status, body: body.to_string() }
+    }
+
+    pub fn malformed_response(endpoint: &str, message: &str) -> SteamApiError {
+        SteamApiError::MalformedResponse { endpoint: endpoint.to_string(), url: "".to_string(), message: message.to_string() }
+    }
+
+    pub fn steam_failure(endpoint: &str, status: Option<i64>, message: &str) -> SteamApiError {
+        SteamApiError::SteamFailure { endpoint: endpoint.to_string(), url: "".to_string(), status, message: message.to_string() }
+    }
+
+    pub fn private_profile(endpoint: &str) -> SteamApiError {
+        SteamApiError::PrivateProfile { endpoint: endpoint.to_string(), url: "".to_string() }
+    }
+
+    pub fn cache_miss(endpoint: &str, filepath: &str) -> SteamApiError {
+        SteamApiError::CacheMiss { endpoint: endpoint.to_string(), url: filepath.to_string() }
+    }
+
+    pub fn rate_limited(endpoint: &str, url: &str, retry_after: Duration) -> SteamApiError {
+        SteamApiError::RateLimited { endpoint: endpoint.to_string(), url: strip_api_key(url), retry_after }
+    }
+
+    pub fn storage(endpoint: &str, filepath: &str, message: &str) -> SteamApiError {
+        SteamApiError::Storage { endpoint: endpoint.to_string(), url: filepath.to_string(), message: message.to_string() }
+    }
+
+    pub fn invalid_input(endpoint: &str, message: &str) -> SteamApiError {
+        SteamApiError::InvalidInput { endpoint: endpoint.to_string(), url: "".to_string(), message: message.to_string() }
+    }
+
+    /// Returns the endpoint the error originated from.
+    pub fn endpoint(&self) -> &str {
+        match self {
+            SteamApiError::Transport { endpoint, .. } => endpoint,
+            SteamApiError::HttpStatus { endpoint, .. } => endpoint,
+            SteamApiError::MalformedResponse { endpoint, .. } => endpoint,
+            SteamApiError::SteamFailure { endpoint, .. } => endpoint,
+            SteamApiError::PrivateProfile { endpoint, .. } => endpoint,
+            SteamApiError::CacheMiss { endpoint, .. } => endpoint,
+            SteamApiError::RateLimited { endpoint, .. } => endpoint,
+            SteamApiError::Storage { endpoint, .. } => endpoint,
+            SteamApiError::InvalidInput { endpoint, .. } => endpoint,
+        }
+    }
+
+    /// Returns the requested url, or the filepath for `CacheMiss` and `Storage`.
+    pub fn url(&self) -> &str {
+        match self {
+            SteamApiError::Transport { url, .. } => url,
+            SteamApiError::HttpStatus { url, .. } => url,
+            SteamApiError::MalformedResponse { url, .. } => url,
+            SteamApiError::SteamFailure { url, .. } => url,
+            SteamApiError::PrivateProfile { url, .. } => url,
+            SteamApiError::CacheMiss { url, .. } => url,
+            SteamApiError::RateLimited { url, .. } => url,
+            SteamApiError::Storage { url, .. } => url,
+            SteamApiError::InvalidInput { url, .. } => url,
+        }
+    }
+
+    /// Fills in the requested url if the error doesn't carry one yet. Response parsers don't know
+    /// the url they were fed from, so `get` functions attach it afterwards.
+    pub fn with_url(mut self, requested_url: &str) -> SteamApiError {
+        let url = match &mut self {
+            SteamApiError::Transport { url, .. } => url,
+            SteamApiError::HttpStatus { url, .. } => url,
+            SteamApiError::MalformedResponse { url, .. } => url,
+            SteamApiError::SteamFailure { url, .. } => url,
+            SteamApiError::PrivateProfile { url, .. } => url,
+            SteamApiError::CacheMiss { url, .. } => url,
+            SteamApiError::RateLimited { url, .. } => url,
+            SteamApiError::Storage { url, .. } => url,
+            SteamApiError::InvalidInput { url, .. } => url,
+        };
+        if url.is_empty() {
+            *url = strip_api_key(requested_url);
+        }
+        self
+    }
+
+    /// Returns the HTTP status code for `HttpStatus` errors.
+    pub fn http_status_code(&self) -> Option<u16> {
+        match self {
+            SteamApiError::HttpStatus { status, .. } => Some(*status),
+            _ => None,
+        }
+    }
+
+    /// Whether repeating the same request later may succeed: transport failures, 429 and 5xx
+    /// responses and exhausted rate limit budgets. Bad keys (401/403), private profiles and
+    /// malformed responses are not retryable.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            SteamApiError::Transport { .. } => true,
+            SteamApiError::RateLimited { .. } => true,
+            SteamApiError::HttpStatus { status, .. } => *status == 429 || *status >= 500,
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Display for SteamApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SteamApiError::Transport { endpoint, url, message } =>
+                write!(f, "{}: transport error calling {}: {}", endpoint, url, message),
+            SteamApiError::HttpStatus { endpoint, url, status, .. } =>
+                write!(f, "{}: {} responded with HTTP status {}", endpoint, url, status),
+            SteamApiError::MalformedResponse { endpoint, message, .. } =>
+                write!(f, "{}: malformed response: {}", endpoint, message),
+            SteamApiError::SteamFailure { endpoint, status, message, .. } => {
+                if status.is_some() {
+                    write!(f, "{}: steam returned failed response (status {}): {}", endpoint, status.unwrap(), message)
+                } else {
+                    write!(f, "{}: steam returned failed response: {}", endpoint, message)
+                }
+            },
+            SteamApiError::PrivateProfile { endpoint, .. } =>
+                write!(f, "{}: requested profile data is not public", endpoint),
+            SteamApiError::CacheMiss { endpoint, url } =>
+                write!(f, "{}: cached resource {} not readable. Consider use get call to retrieve data from steam api", endpoint, url),
+            SteamApiError::RateLimited { endpoint, url, retry_after } =>
+                write!(f, "{}: request to {} would exceed the rate limit budget, retry in {} ms", endpoint, url, retry_after.as_millis()),
+            SteamApiError::Storage { endpoint, url, message } =>
+                write!(f, "{}: could not access {}: {}", endpoint, url, message),
+            SteamApiError::InvalidInput { endpoint, message, .. } =>
+                write!(f, "{}: invalid input: {}", endpoint, message),
+        }
+    }
+}
+
+impl Error for SteamApiError {}
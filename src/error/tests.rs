@@ -0,0 +1,86 @@
+use std::time::Duration;
+use crate::error::SteamApiError;
+
+#[test]
+fn with_url_fills_missing_url() {
+    let error = SteamApiError::malformed_response("ISteamUser/GetPlayerSummaries", "response does not contain any players")
+        .with_url("https://api.steampowered.com/ISteamUser/GetPlayerSummaries/v2");
+
+    assert_eq!("ISteamUser/GetPlayerSummaries", error.endpoint());
+    assert_eq!("https://api.steampowered.com/ISteamUser/GetPlayerSummaries/v2", error.url());
+}
+
+#[test]
+fn with_url_keeps_existing_url() {
+    let error = SteamApiError::transport("ISteamUser/GetPlayerSummaries", "https://api.steampowered.com/a", "timed out")
+        .with_url("https://api.steampowered.com/b");
+
+    assert_eq!("https://api.steampowered.com/a", error.url());
+}
+
+#[test]
+fn retryable() {
+    let url = "https://api.steampowered.com/ISteamUser/GetPlayerSummaries/v2";
+    let endpoint = "ISteamUser/GetPlayerSummaries";
+
+    assert!(SteamApiError::transport(endpoint, url, "timed out").is_retryable());
+    assert!(SteamApiError::http_status(endpoint, url, 429, "").is_retryable());
+    assert!(SteamApiError::http_status(endpoint, url, 503, "").is_retryable());
+
+    assert!(!SteamApiError::http_status(endpoint, url, 401, "").is_retryable());
+    assert!(!SteamApiError::http_status(endpoint, url, 403, "").is_retryable());
+    assert!(!SteamApiError::private_profile(endpoint).is_retryable());
+    assert!(!SteamApiError::malformed_response(endpoint, "expected value").is_retryable());
+    assert!(!SteamApiError::cache_miss(endpoint, "steam-webapi-cache/a.json").is_retryable());
+}
+
+#[test]
+fn http_status_code() {
+    let error = SteamApiError::http_status("steampowered/appdetails", "https://store.steampowered.com/api/appdetails", 429, "");
+    assert_eq!(Some(429), error.http_status_code());
+
+    let error = SteamApiError::steam_failure("steampowered/appdetails", None, "steampowered api returned failed response");
+    assert_eq!(None, error.http_status_code());
+}
+
+#[test]
+fn display() {
+    let error = SteamApiError::steam_failure("IDOTA2Match_570/GetMatchHistory", Some(15), "Cannot get match history for a user that hasn't allowed it");
+    assert_eq!("IDOTA2Match_570/GetMatchHistory: steam returned failed response (status 15): Cannot get match history for a user that hasn't allowed it", error.to_string());
+
+    let error = SteamApiError::http_status("ISteamUser/GetPlayerBans", "https://api.steampowered.com/ISteamUser/GetPlayerBans/v1", 403, "<html></html>");
+    assert_eq!("ISteamUser/GetPlayerBans: https://api.steampowered.com/ISteamUser/GetPlayerBans/v1 responded with HTTP status 403", error.to_string());
+}
+
+#[test]
+fn display_rate_limited() {
+    let error = SteamApiError::rate_limited("steampowered/appdetails", "https://store.steampowered.com/api/appdetails?appids=570", Duration::from_millis(1500));
+    assert_eq!("steampowered/appdetails: request to https://store.steampowered.com/api/appdetails?appids=570 would exceed the rate limit budget, retry in 1500 ms", error.to_string());
+    assert!(error.is_retryable());
+}
+
+#[test]
+fn display_storage() {
+    let error = SteamApiError::storage("IDOTA2Match_570/GetMatchHistoryBySequenceNum", "crawler.json", "permission denied");
+    assert_eq!("IDOTA2Match_570/GetMatchHistoryBySequenceNum: could not access crawler.json: permission denied", error.to_string());
+    assert_eq!("crawler.json", error.url());
+    assert!(!error.is_retryable());
+}
+
+#[test]
+fn url_without_api_key() {
+    let endpoint = "ISteamUser/GetPlayerSummaries";
+    let url = "https://api.steampowered.com/ISteamUser/GetPlayerSummaries/v2?key=SECRET&steamids=76561197960435530";
+
+    let errors = vec![
+        SteamApiError::transport(endpoint, url, "timed out"),
+        SteamApiError::http_status(endpoint, url, 403, ""),
+        SteamApiError::rate_limited(endpoint, url, Duration::from_secs(1)),
+        SteamApiError::private_profile(endpoint).with_url(url),
+    ];
+
+    for error in errors {
+        assert_eq!("https://api.steampowered.com/ISteamUser/GetPlayerSummaries/v2?steamids=76561197960435530", error.url());
+        assert!(!error.to_string().contains("SECRET"));
+    }
+}
@@ -1,15 +1,21 @@
 use std::collections::HashMap;
 use serde_json::Value;
-use crate::{idota2match_570, make_api_call};
-use crate::util::{build_steam_api_url, get_steam_web_api_key, json_str, json_u64};
+use serde::Deserialize;
+use serde::Serialize;
+use crate::idota2match_570;
+use crate::client::{get_default_client, SteamClient};
+use crate::error::SteamApiError;
+use crate::util::{build_cache_key, build_steam_api_url, json_opt_str, json_str, json_u64};
 
 #[cfg(test)]
 mod tests;
 
-#[derive(PartialEq, Clone, Debug)]
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
 pub struct Hero {
     pub id: u64,
     pub name: String,
+    /// Present when the heroes are requested with a `language`.
+    pub localized_name: Option<String>,
 }
 
 pub fn get_method_name() -> String {
@@ -20,14 +26,64 @@ pub fn get_version() -> String {
     "v1".to_string()
 }
 
-pub fn get(language: Option<String>) -> Result<Vec<Hero>, String> {
+pub fn get_endpoint() -> String {
+    [idota2match_570::get_interface(), "/".to_string(), get_method_name()].join("")
+}
+
+pub fn get(language: Option<String>) -> Result<Vec<Hero>, SteamApiError> {
+    get_with_client(get_default_client().as_ref(), language)
+}
+
+/// Async version of `get`, available with the `async` feature.
+#[cfg(feature = "async")]
+pub async fn get_async(language: Option<String>) -> Result<Vec<Hero>, SteamApiError> {
+    get_async_with_client(get_default_client().as_ref(), language).await
+}
+
+pub fn get_with_client(client: &SteamClient, language: Option<String>) -> Result<Vec<Hero>, SteamApiError> {
+    let cache_key = get_cache_key(language.clone());
     let api_url = get_api_url(language);
-    let boxed_response = make_api_call(api_url);
-    if boxed_response.is_err() {
-        return Err(boxed_response.err().unwrap());
-    }
+    client.fetch_and_cache(get_endpoint().as_str(), cache_key.as_str(), api_url, parse_response)
+}
+
+/// Async version of `get_with_client`, available with the `async` feature.
+#[cfg(feature = "async")]
+pub async fn get_async_with_client(client: &SteamClient, language: Option<String>) -> Result<Vec<Hero>, SteamApiError> {
+    let cache_key = get_cache_key(language.clone());
+    let api_url = get_api_url(language);
+    client.fetch_and_cache_async(get_endpoint().as_str(), cache_key.as_str(), api_url, parse_response).await
+}
 
-    parse_response(boxed_response.unwrap())
+/// Like `get`, but serves the cached response while it is fresh as per the client's
+/// `cache::CachePolicy`, otherwise calls Steam and caches the response.
+pub fn get_cached(language: Option<String>) -> Result<Vec<Hero>, SteamApiError> {
+    get_cached_with_client(get_default_client().as_ref(), language)
+}
+
+/// Async version of `get_cached`, available with the `async` feature.
+#[cfg(feature = "async")]
+pub async fn get_cached_async(language: Option<String>) -> Result<Vec<Hero>, SteamApiError> {
+    get_cached_async_with_client(get_default_client().as_ref(), language).await
+}
+
+pub fn get_cached_with_client(client: &SteamClient, language: Option<String>) -> Result<Vec<Hero>, SteamApiError> {
+    client.get_cached_or_fetch(
+        get_endpoint().as_str(),
+        get_cache_key(language.clone()).as_str(),
+        parse_response,
+        || get_with_client(client, language),
+    )
+}
+
+/// Async version of `get_cached_with_client`, available with the `async` feature.
+#[cfg(feature = "async")]
+pub async fn get_cached_async_with_client(client: &SteamClient, language: Option<String>) -> Result<Vec<Hero>, SteamApiError> {
+    client.get_cached_or_fetch_async(
+        get_endpoint().as_str(),
+        get_cache_key(language.clone()).as_str(),
+        parse_response,
+        get_async_with_client(client, language),
+    ).await
 }
 
 pub fn get_api_url(language: Option<String>) -> String {
@@ -39,21 +95,34 @@ pub fn get_api_url(language: Option<String>) -> String {
     if language.is_some() {
         params_map.insert("language".to_string(), language.unwrap());
     }
-    params_map.insert("key".to_string(), get_steam_web_api_key());
 
     build_steam_api_url(interface.as_str(), method.as_str(), version.as_str(), params_map)
 }
 
-pub fn parse_response(response: String) -> Result<Vec<Hero>, String> {
+/// Returns path of the cached resource relative to the cache directory.
+pub fn get_cache_key(language: Option<String>) -> String {
+    let mut params = vec![];
+    if let Some(language) = language {
+        params.push(("language", language));
+    }
+
+    build_cache_key(idota2match_570::get_interface().as_str(), get_method_name().as_str(), get_version().as_str(), params)
+}
+
+pub fn get_resource_filepath(language: Option<String>) -> String {
+    get_default_client().get_resource_filepath(get_cache_key(language).as_str())
+}
+
+pub fn parse_response(response: String) -> Result<Vec<Hero>, SteamApiError> {
     let boxed_initial_parse = serde_json::from_str(&response);
     if boxed_initial_parse.is_err() {
-        return Err(boxed_initial_parse.err().unwrap().to_string());
+        return Err(SteamApiError::malformed_response(get_endpoint().as_str(), boxed_initial_parse.err().unwrap().to_string().as_str()));
     }
     let json: Value = boxed_initial_parse.unwrap();
 
     let boxed_heroes = json.get("result").and_then(|r| r.get("heroes")).and_then(Value::as_array);
     if boxed_heroes.is_none() {
-        return Err("response does not contain any heroes".to_string());
+        return Err(SteamApiError::malformed_response(get_endpoint().as_str(), "response does not contain any heroes"));
     }
 
     let mut heroes = vec![];
@@ -61,6 +130,7 @@ pub fn parse_response(response: String) -> Result<Vec<Hero>, String> {
         heroes.push(Hero {
             id: json_u64(hero, "id"),
             name: json_str(hero, "name"),
+            localized_name: json_opt_str(hero, "localized_name"),
         });
     }
 
@@ -1,7 +1,8 @@
 use url_build_parse::parse_url;
 use crate::{get_host, get_scheme};
-use crate::idota2match_570::get_heroes::{get_api_url, parse_response};
-use crate::util::get_steam_web_api_key;
+use crate::idota2match_570::get_heroes::{get_api_url, get_resource_filepath, parse_response};
+use crate::client::get_default_client;
+use crate::tests::assert_serde_roundtrip;
 
 #[test]
 fn api_url() {
@@ -17,8 +18,14 @@ fn api_url() {
     let boxed_language = params.get("language");
     assert_eq!("en", boxed_language.unwrap().to_string());
 
-    let boxed_key = params.get("key");
-    assert_eq!(get_steam_web_api_key(), boxed_key.unwrap().to_string());
+    assert!(!params.contains_key("key"));
+}
+
+#[test]
+fn resource_filepath() {
+    let filepath = get_resource_filepath(Some("en".to_string()));
+
+    assert_eq!([get_default_client().cache_dir(), "/IDOTA2Match_570-GetHeroes-v1-language=en.json"].join(""), filepath);
 }
 
 #[test]
@@ -26,7 +33,7 @@ fn parse() {
     let response = r#"{
         "result": {
             "heroes": [
-                {"name": "npc_dota_hero_antimage", "id": 1},
+                {"name": "npc_dota_hero_antimage", "id": 1, "localized_name": "Anti-Mage"},
                 {"name": "npc_dota_hero_axe", "id": 2}
             ],
             "count": 2,
@@ -38,9 +45,12 @@ fn parse() {
     assert!(boxed_parse.is_ok());
 
     let heroes = boxed_parse.unwrap();
+    assert_serde_roundtrip(&heroes);
     assert_eq!(2, heroes.len());
     assert_eq!(1, heroes.get(0).unwrap().id);
     assert_eq!("npc_dota_hero_antimage", heroes.get(0).unwrap().name);
+    assert_eq!(Some("Anti-Mage".to_string()), heroes.first().unwrap().localized_name);
+    assert_eq!(None, heroes.get(1).unwrap().localized_name);
 }
 
 #[test]
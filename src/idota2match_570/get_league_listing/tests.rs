@@ -1,7 +1,8 @@
 use url_build_parse::parse_url;
 use crate::{get_host, get_scheme};
-use crate::idota2match_570::get_league_listing::{get_api_url, parse_response};
-use crate::util::get_steam_web_api_key;
+use crate::idota2match_570::get_league_listing::{get_api_url, get_resource_filepath, parse_response};
+use crate::client::get_default_client;
+use crate::tests::assert_serde_roundtrip;
 
 #[test]
 fn api_url() {
@@ -13,9 +14,14 @@ fn api_url() {
     assert_eq!(get_host(), components.authority.unwrap().host);
     assert_eq!("/IDOTA2Match_570/GetLeagueListing/v1", components.path);
 
-    let params = components.query.unwrap();
-    let boxed_key = params.get("key");
-    assert_eq!(get_steam_web_api_key(), boxed_key.unwrap().to_string());
+    assert!(components.query.is_none());
+}
+
+#[test]
+fn resource_filepath() {
+    let filepath = get_resource_filepath(None);
+
+    assert_eq!([get_default_client().cache_dir(), "/IDOTA2Match_570-GetLeagueListing-v1.json"].join(""), filepath);
 }
 
 #[test]
@@ -37,6 +43,7 @@ fn parse() {
     assert!(boxed_parse.is_ok());
 
     let leagues = boxed_parse.unwrap();
+    assert_serde_roundtrip(&leagues);
     assert_eq!(1, leagues.len());
 
     let league = leagues.get(0).unwrap();
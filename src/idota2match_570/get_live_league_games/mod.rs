@@ -1,19 +1,23 @@
 use std::collections::HashMap;
 use serde_json::Value;
-use crate::{idota2match_570, make_api_call};
-use crate::util::{build_steam_api_url, get_steam_web_api_key, json_str, json_u64};
+use serde::Deserialize;
+use serde::Serialize;
+use crate::idota2match_570;
+use crate::client::{get_default_client, SteamClient};
+use crate::error::SteamApiError;
+use crate::util::{build_cache_key, build_steam_api_url, json_str, json_u64};
 
 #[cfg(test)]
 mod tests;
 
-#[derive(PartialEq, Clone, Debug)]
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
 pub struct LivePlayer {
     pub account_id: u64,
     pub hero_id: u64,
     pub team: u64,
 }
 
-#[derive(PartialEq, Clone, Debug)]
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
 pub struct LiveTeam {
     pub team_name: String,
     pub team_id: u64,
@@ -21,11 +25,11 @@ pub struct LiveTeam {
     pub complete: bool,
 }
 
-#[derive(PartialEq, Clone, Debug)]
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
 pub struct LiveLeagueGame {
     pub players: Vec<LivePlayer>,
-    pub radiant_team: LiveTeam,
-    pub dire_team: LiveTeam,
+    pub radiant_team: Option<LiveTeam>,
+    pub dire_team: Option<LiveTeam>,
     pub lobby_id: u64,
     pub match_id: u64,
     pub spectators: u64,
@@ -43,14 +47,64 @@ pub fn get_version() -> String {
     "v1".to_string()
 }
 
-pub fn get() -> Result<Vec<LiveLeagueGame>, String> {
+pub fn get_endpoint() -> String {
+    [idota2match_570::get_interface(), "/".to_string(), get_method_name()].join("")
+}
+
+pub fn get() -> Result<Vec<LiveLeagueGame>, SteamApiError> {
+    get_with_client(get_default_client().as_ref())
+}
+
+/// Async version of `get`, available with the `async` feature.
+#[cfg(feature = "async")]
+pub async fn get_async() -> Result<Vec<LiveLeagueGame>, SteamApiError> {
+    get_async_with_client(get_default_client().as_ref()).await
+}
+
+pub fn get_with_client(client: &SteamClient) -> Result<Vec<LiveLeagueGame>, SteamApiError> {
+    let cache_key = get_cache_key();
     let api_url = get_api_url();
-    let boxed_response = make_api_call(api_url);
-    if boxed_response.is_err() {
-        return Err(boxed_response.err().unwrap());
-    }
+    client.fetch_and_cache(get_endpoint().as_str(), cache_key.as_str(), api_url, parse_response)
+}
+
+/// Async version of `get_with_client`, available with the `async` feature.
+#[cfg(feature = "async")]
+pub async fn get_async_with_client(client: &SteamClient) -> Result<Vec<LiveLeagueGame>, SteamApiError> {
+    let cache_key = get_cache_key();
+    let api_url = get_api_url();
+    client.fetch_and_cache_async(get_endpoint().as_str(), cache_key.as_str(), api_url, parse_response).await
+}
+
+/// Like `get`, but serves the cached response while it is fresh as per the client's
+/// `cache::CachePolicy`, otherwise calls Steam and caches the response.
+pub fn get_cached() -> Result<Vec<LiveLeagueGame>, SteamApiError> {
+    get_cached_with_client(get_default_client().as_ref())
+}
+
+/// Async version of `get_cached`, available with the `async` feature.
+#[cfg(feature = "async")]
+pub async fn get_cached_async() -> Result<Vec<LiveLeagueGame>, SteamApiError> {
+    get_cached_async_with_client(get_default_client().as_ref()).await
+}
 
-    parse_response(boxed_response.unwrap())
+pub fn get_cached_with_client(client: &SteamClient) -> Result<Vec<LiveLeagueGame>, SteamApiError> {
+    client.get_cached_or_fetch(
+        get_endpoint().as_str(),
+        get_cache_key().as_str(),
+        parse_response,
+        || get_with_client(client),
+    )
+}
+
+/// Async version of `get_cached_with_client`, available with the `async` feature.
+#[cfg(feature = "async")]
+pub async fn get_cached_async_with_client(client: &SteamClient) -> Result<Vec<LiveLeagueGame>, SteamApiError> {
+    client.get_cached_or_fetch_async(
+        get_endpoint().as_str(),
+        get_cache_key().as_str(),
+        parse_response,
+        get_async_with_client(client),
+    ).await
 }
 
 pub fn get_api_url() -> String {
@@ -58,36 +112,39 @@ pub fn get_api_url() -> String {
     let method = get_method_name();
     let version = get_version();
 
-    let mut params_map = HashMap::new();
-    params_map.insert("key".to_string(), get_steam_web_api_key());
+    let params_map = HashMap::new();
 
     build_steam_api_url(interface.as_str(), method.as_str(), version.as_str(), params_map)
 }
 
-fn parse_team(team: Option<&Value>) -> LiveTeam {
-    let mut result = LiveTeam { team_name: "".to_string(), team_id: 0, team_logo: 0, complete: false };
+fn parse_team(team: Option<&Value>) -> Option<LiveTeam> {
+    team.filter(|team_value| team_value.is_object()).map(|team_value| LiveTeam {
+        team_name: json_str(team_value, "team_name"),
+        team_id: json_u64(team_value, "team_id"),
+        team_logo: json_u64(team_value, "team_logo"),
+        complete: json_u64(team_value, "complete") == 1,
+    })
+}
 
-    if team.is_some() {
-        let team_value = team.unwrap();
-        result.team_name = json_str(team_value, "team_name");
-        result.team_id = json_u64(team_value, "team_id");
-        result.team_logo = json_u64(team_value, "team_logo");
-        result.complete = json_u64(team_value, "complete") == 1;
-    }
+/// Returns path of the cached resource relative to the cache directory.
+pub fn get_cache_key() -> String {
+    build_cache_key(idota2match_570::get_interface().as_str(), get_method_name().as_str(), get_version().as_str(), vec![])
+}
 
-    result
+pub fn get_resource_filepath() -> String {
+    get_default_client().get_resource_filepath(get_cache_key().as_str())
 }
 
-pub fn parse_response(response: String) -> Result<Vec<LiveLeagueGame>, String> {
+pub fn parse_response(response: String) -> Result<Vec<LiveLeagueGame>, SteamApiError> {
     let boxed_initial_parse = serde_json::from_str(&response);
     if boxed_initial_parse.is_err() {
-        return Err(boxed_initial_parse.err().unwrap().to_string());
+        return Err(SteamApiError::malformed_response(get_endpoint().as_str(), boxed_initial_parse.err().unwrap().to_string().as_str()));
     }
     let json: Value = boxed_initial_parse.unwrap();
 
     let boxed_games = json.get("result").and_then(|r| r.get("games")).and_then(Value::as_array);
     if boxed_games.is_none() {
-        return Err("response does not contain any games".to_string());
+        return Err(SteamApiError::malformed_response(get_endpoint().as_str(), "response does not contain any games"));
     }
 
     let mut games = vec![];
@@ -1,7 +1,8 @@
 use url_build_parse::parse_url;
 use crate::{get_host, get_scheme};
-use crate::idota2match_570::get_live_league_games::{get_api_url, parse_response};
-use crate::util::get_steam_web_api_key;
+use crate::idota2match_570::get_live_league_games::{get_api_url, get_resource_filepath, parse_response};
+use crate::client::get_default_client;
+use crate::tests::assert_serde_roundtrip;
 
 #[test]
 fn api_url() {
@@ -13,9 +14,14 @@ fn api_url() {
     assert_eq!(get_host(), components.authority.unwrap().host);
     assert_eq!("/IDOTA2Match_570/GetLiveLeagueGames/v1", components.path);
 
-    let params = components.query.unwrap();
-    let boxed_key = params.get("key");
-    assert_eq!(get_steam_web_api_key(), boxed_key.unwrap().to_string());
+    assert!(components.query.is_none());
+}
+
+#[test]
+fn resource_filepath() {
+    let filepath = get_resource_filepath();
+
+    assert_eq!([get_default_client().cache_dir(), "/IDOTA2Match_570-GetLiveLeagueGames-v1.json"].join(""), filepath);
 }
 
 #[test]
@@ -45,15 +51,17 @@ fn parse() {
     assert!(boxed_parse.is_ok());
 
     let games = boxed_parse.unwrap();
+    assert_serde_roundtrip(&games);
     assert_eq!(1, games.len());
 
     let game = games.get(0).unwrap();
     assert_eq!(222, game.match_id);
     assert_eq!(1, game.players.len());
     assert_eq!(12345, game.players.get(0).unwrap().account_id);
-    assert_eq!("Radiant Squad", game.radiant_team.team_name);
-    assert_eq!(true, game.radiant_team.complete);
-    assert_eq!("Dire Squad", game.dire_team.team_name);
+    let radiant_team = game.radiant_team.as_ref().unwrap();
+    assert_eq!("Radiant Squad", radiant_team.team_name);
+    assert!(radiant_team.complete);
+    assert_eq!("Dire Squad", game.dire_team.as_ref().unwrap().team_name);
     assert_eq!(1, game.dire_series_wins);
 }
 
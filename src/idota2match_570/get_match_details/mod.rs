@@ -1,23 +1,25 @@
 // curl https://api.steampowered.com/IDOTA2Match_570/GetMatchDetails/v1\?match_id\=1461414523\&key\=1F2709FC907F0DEE1D1EB4787E06B695
 
 use std::collections::HashMap;
-use std::fs;
-use std::fs::{File, read_to_string};
-use std::io::Write;
-use std::path::Path;
 use serde_json::Value;
+use serde::Deserialize;
+use serde::Serialize;
 use url_build_parse::{build_url, UrlAuthority, UrlComponents};
-use crate::{get_host, get_scheme, idota2match_570, make_api_call};
-use crate::util::{get_cache_dir_path, get_json_filetype, get_steam_web_api_key};
+use crate::{get_host, get_scheme, idota2match_570};
+use crate::client::{get_default_client, SteamClient};
+use crate::error::SteamApiError;
+use crate::idota2match_570::types::{BarracksStatus, GameMode, LobbyType, PlayerSlot, Team, TowerStatus};
+use crate::util::{get_json_filetype, json_i64, json_opt_array, json_opt_str, json_opt_u64, json_str, json_u64};
 
 #[cfg(test)]
 mod tests;
 
-#[derive(PartialEq, Clone, Debug)]
+/// Fields missing from older matches are `None` rather than `0`.
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
 pub struct MatchResult {
     pub radiant_win: bool,
     pub duration: u64,
-    pub pre_game_duration: u64,
+    pub pre_game_duration: Option<u64>,
     pub start_time: u64,
     pub match_id: u64,
     pub match_seq_num: u64,
@@ -26,26 +28,71 @@ pub struct MatchResult {
     pub barracks_status_radiant: u64,
     pub barracks_status_dire: u64,
     pub cluster: u64,
-    pub first_blood_time: u64,
-    pub lobby_type: u64,
+    pub first_blood_time: Option<u64>,
+    pub lobby_type: LobbyType,
     pub human_players: u64,
     pub leagueid: u64,
-    pub positive_votes: u64,
-    pub negative_votes: u64,
-    pub game_mode: u64,
-    pub flags: u64,
-    pub engine: u64,
-    pub radiant_score: u64,
-    pub dire_score: u64,
+    pub positive_votes: Option<u64>,
+    pub negative_votes: Option<u64>,
+    pub game_mode: GameMode,
+    pub flags: Option<u64>,
+    pub engine: Option<u64>,
+    pub radiant_score: Option<u64>,
+    pub dire_score: Option<u64>,
+    /// Team fields are only set for matches between registered teams, e.g. league matches.
+    pub radiant_team_id: Option<u64>,
+    pub radiant_name: Option<String>,
+    pub radiant_logo: Option<u64>,
+    pub radiant_captain: Option<u64>,
+    pub dire_team_id: Option<u64>,
+    pub dire_name: Option<String>,
+    pub dire_logo: Option<u64>,
+    pub dire_captain: Option<u64>,
+    /// The draft, `None` for game modes without picks and bans.
+    pub picks_bans: Option<Vec<PickBan>>,
     pub players: Vec<PlayerStats>,
 }
 
-#[derive(PartialEq, Clone, Debug)]
+/// A pick or ban of the draft, `order` counting from 0.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Debug)]
+pub struct PickBan {
+    pub is_pick: bool,
+    pub hero_id: u64,
+    pub team: Team,
+    pub order: u64,
+}
+
+/// An ability level taken by a player, `time` being seconds since the start of the match.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Debug)]
+pub struct AbilityUpgrade {
+    pub ability: u64,
+    pub time: u64,
+    pub level: u64,
+}
+
+/// A unit controlled by a player carrying its own items, such as Lone Druid's `spirit_bear`.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Debug)]
+pub struct AdditionalUnit {
+    pub unitname: String,
+    pub item_0: u64,
+    pub item_1: u64,
+    pub item_2: u64,
+    pub item_3: u64,
+    pub item_4: u64,
+    pub item_5: u64,
+    pub backpack_0: Option<u64>,
+    pub backpack_1: Option<u64>,
+    pub backpack_2: Option<u64>,
+    pub item_neutral: Option<u64>,
+}
+
+/// `account_id` is `None` for bots; newer item and team fields are `None` on older matches.
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
 pub struct PlayerStats {
-    pub account_id: u64,
+    pub account_id: Option<u64>,
     pub player_slot: u64,
-    pub team_number: u64,
-    pub team_slot: u64,
+    pub team_number: Option<u64>,
+    pub team_slot: Option<u64>,
     pub hero_id: u64,
     pub item_0: u64,
     pub item_1: u64,
@@ -53,23 +100,125 @@ pub struct PlayerStats {
     pub item_3: u64,
     pub item_4: u64,
     pub item_5: u64,
-    pub backpack_0: u64,
-    pub backpack_1: u64,
-    pub backpack_2: u64,
-    pub item_neutral: u64,
+    pub backpack_0: Option<u64>,
+    pub backpack_1: Option<u64>,
+    pub backpack_2: Option<u64>,
+    pub item_neutral: Option<u64>,
     pub kills: u64,
     pub deaths: u64,
     pub assists: u64,
-    pub leaver_status: u64,
+    pub leaver_status: Option<u64>,
     pub last_hits: u64,
     pub denies: u64,
     pub gold_per_min: u64,
     pub xp_per_min: u64,
     pub level: u64,
-    pub net_worth: u64,
-    pub aghanims_scepter: u64,
-    pub aghanims_shard: u64,
-    pub moonshard: u64,
+    pub net_worth: Option<u64>,
+    pub aghanims_scepter: Option<u64>,
+    pub aghanims_shard: Option<u64>,
+    pub moonshard: Option<u64>,
+    pub hero_damage: Option<u64>,
+    pub tower_damage: Option<u64>,
+    pub hero_healing: Option<u64>,
+    pub gold: Option<u64>,
+    pub gold_spent: Option<u64>,
+    pub scaled_hero_damage: Option<u64>,
+    pub scaled_tower_damage: Option<u64>,
+    pub scaled_hero_healing: Option<u64>,
+    pub ability_upgrades: Option<Vec<AbilityUpgrade>>,
+    pub additional_units: Option<Vec<AdditionalUnit>>,
+}
+
+impl MatchResult {
+    pub fn get_winner(&self) -> Team {
+        if self.radiant_win { Team::Radiant } else { Team::Dire }
+    }
+
+    pub fn radiant_players(&self) -> Vec<&PlayerStats> {
+        self.get_team_players(Team::Radiant)
+    }
+
+    pub fn dire_players(&self) -> Vec<&PlayerStats> {
+        self.get_team_players(Team::Dire)
+    }
+
+    pub fn get_team_players(&self, team: Team) -> Vec<&PlayerStats> {
+        self.players.iter().filter(|player| player.get_team() == team).collect()
+    }
+
+    pub fn get_tower_status(&self, team: Team) -> TowerStatus {
+        match team {
+            Team::Radiant => TowerStatus::from_bits(self.tower_status_radiant),
+            Team::Dire => TowerStatus::from_bits(self.tower_status_dire),
+        }
+    }
+
+    pub fn get_team_name(&self, team: Team) -> Option<&str> {
+        match team {
+            Team::Radiant => self.radiant_name.as_deref(),
+            Team::Dire => self.dire_name.as_deref(),
+        }
+    }
+
+    /// Heroes picked by `team` in draft order, empty without a draft.
+    pub fn get_picks(&self, team: Team) -> Vec<&PickBan> {
+        self.get_draft(team, true)
+    }
+
+    /// Heroes banned by `team` in draft order, empty without a draft.
+    pub fn get_bans(&self, team: Team) -> Vec<&PickBan> {
+        self.get_draft(team, false)
+    }
+
+    fn get_draft(&self, team: Team, is_pick: bool) -> Vec<&PickBan> {
+        self.picks_bans.iter()
+            .flatten()
+            .filter(|pick_ban| pick_ban.team == team && pick_ban.is_pick == is_pick)
+            .collect()
+    }
+
+    pub fn get_barracks_status(&self, team: Team) -> BarracksStatus {
+        match team {
+            Team::Radiant => BarracksStatus::from_bits(self.barracks_status_radiant),
+            Team::Dire => BarracksStatus::from_bits(self.barracks_status_dire),
+        }
+    }
+}
+
+impl PlayerStats {
+    pub fn get_player_slot(&self) -> PlayerSlot {
+        PlayerSlot::from_id(self.player_slot)
+    }
+
+    pub fn get_team(&self) -> Team {
+        self.get_player_slot().team
+    }
+
+    /// Whether this player's team won the given match.
+    pub fn won(&self, match_result: &MatchResult) -> bool {
+        self.get_team() == match_result.get_winner()
+    }
+
+    /// Item ids of the inventory, backpack and neutral slots, skipping empty slots.
+    pub fn get_item_ids(&self) -> Vec<u64> {
+        let inventory = [self.item_0, self.item_1, self.item_2, self.item_3, self.item_4, self.item_5];
+        get_item_ids(inventory, [self.backpack_0, self.backpack_1, self.backpack_2, self.item_neutral])
+    }
+}
+
+impl AdditionalUnit {
+    /// Item ids of the inventory, backpack and neutral slots, skipping empty slots.
+    pub fn get_item_ids(&self) -> Vec<u64> {
+        let inventory = [self.item_0, self.item_1, self.item_2, self.item_3, self.item_4, self.item_5];
+        get_item_ids(inventory, [self.backpack_0, self.backpack_1, self.backpack_2, self.item_neutral])
+    }
+}
+
+fn get_item_ids(inventory: [u64; 6], others: [Option<u64>; 4]) -> Vec<u64> {
+    inventory.into_iter()
+        .chain(others.into_iter().flatten())
+        .filter(|item_id| *item_id != 0)
+        .collect()
 }
 
 pub fn get_method_name() -> String {
@@ -80,31 +229,75 @@ pub fn get_version() -> String {
     "v1".to_string()
 }
 
+pub fn get_endpoint() -> String {
+    [idota2match_570::get_interface(), "/".to_string(), get_method_name()].join("")
+}
+
 /// Retrieves match details for the given match id. Makes an API call to Steam and caches the
 /// response, mirroring `store_steampowered_com::appdetails::get`.
-pub fn get(match_id: u64) -> Result<MatchResult, String> {
+pub fn get(match_id: u64) -> Result<MatchResult, SteamApiError> {
+    get_with_client(get_default_client().as_ref(), match_id)
+}
+
+/// Async version of `get`, available with the `async` feature.
+#[cfg(feature = "async")]
+pub async fn get_async(match_id: u64) -> Result<MatchResult, SteamApiError> {
+    get_async_with_client(get_default_client().as_ref(), match_id).await
+}
+
+pub fn get_with_client(client: &SteamClient, match_id: u64) -> Result<MatchResult, SteamApiError> {
     let api_url = get_api_url(match_id);
-    let boxed_response = make_api_call(api_url);
-    if boxed_response.is_err() {
-        return Err(boxed_response.err().unwrap());
-    }
+    client.fetch_and_cache(
+        get_endpoint().as_str(),
+        get_cache_key(match_id).as_str(),
+        api_url,
+        |response| parse_response(response, match_id),
+    )
+}
+
+/// Async version of `get_with_client`, available with the `async` feature.
+#[cfg(feature = "async")]
+pub async fn get_async_with_client(client: &SteamClient, match_id: u64) -> Result<MatchResult, SteamApiError> {
+    let api_url = get_api_url(match_id);
+    client.fetch_and_cache_async(
+        get_endpoint().as_str(),
+        get_cache_key(match_id).as_str(),
+        api_url,
+        |response| parse_response(response, match_id),
+    ).await
+}
 
-    let response = boxed_response.unwrap();
-    parse_response(response, match_id)
+/// Retrieves match details for the given match id from the local cache, calling Steam when the
+/// match hasn't been cached yet. Finished matches never change, so cached ones never go stale
+/// unless `cache::CachePolicy` says otherwise.
+pub fn get_cached(match_id: u64) -> Result<MatchResult, SteamApiError> {
+    get_cached_with_client(get_default_client().as_ref(), match_id)
 }
 
-/// Retrieves match details for the given match id from the local cache. Returns an error if the
-/// resource hasn't been cached yet, mirroring `store_steampowered_com::appdetails::get_cached`.
-pub fn get_cached(match_id: u64) -> Result<MatchResult, String> {
-    let filepath = get_resource_filepath(match_id);
-
-    let boxed_read = read_to_string(filepath);
-    if boxed_read.is_ok() {
-        let cached_api_response = boxed_read.unwrap();
-        parse_response(cached_api_response, match_id)
-    } else {
-        Err("Cached resource not readable. Consider use get call to retrieve data from steam api".to_string())
-    }
+/// Async version of `get_cached`, available with the `async` feature.
+#[cfg(feature = "async")]
+pub async fn get_cached_async(match_id: u64) -> Result<MatchResult, SteamApiError> {
+    get_cached_async_with_client(get_default_client().as_ref(), match_id).await
+}
+
+pub fn get_cached_with_client(client: &SteamClient, match_id: u64) -> Result<MatchResult, SteamApiError> {
+    client.get_cached_or_fetch(
+        get_endpoint().as_str(),
+        get_cache_key(match_id).as_str(),
+        |response| parse_response(response, match_id),
+        || get_with_client(client, match_id),
+    )
+}
+
+/// Async version of `get_cached_with_client`, available with the `async` feature.
+#[cfg(feature = "async")]
+pub async fn get_cached_async_with_client(client: &SteamClient, match_id: u64) -> Result<MatchResult, SteamApiError> {
+    client.get_cached_or_fetch_async(
+        get_endpoint().as_str(),
+        get_cache_key(match_id).as_str(),
+        |response| parse_response(response, match_id),
+        get_async_with_client(client, match_id),
+    ).await
 }
 
 pub fn get_api_url(match_id: u64) -> String {
@@ -123,7 +316,6 @@ pub fn get_api_url(match_id: u64) -> String {
 
     params_map.insert("match_id".to_string(), match_id.to_string());
 
-    params_map.insert("key".to_string(), get_steam_web_api_key());
 
     let url_builder = UrlComponents{
         scheme: get_scheme(),
@@ -141,122 +333,165 @@ pub fn get_api_url(match_id: u64) -> String {
     url
 }
 
-pub fn get_resource_filepath(match_id: u64) -> String {
+/// Returns path of the cached resource relative to the cache directory.
+pub fn get_cache_key(match_id: u64) -> String {
     let interface = idota2match_570::get_interface();
     let method = get_method_name();
     let version = get_version();
 
-    let resource = [
+    [
         interface, "-".to_string(),
         method, "-".to_string(),
         version, "-".to_string(),
         match_id.to_string(), ".".to_string(),
         get_json_filetype()
-    ].join("");
-
-    [get_cache_dir_path(), "/".to_string(), resource].join("")
+    ].join("")
 }
 
-fn as_u64(value: &Value, key: &str) -> u64 {
-    value.get(key).and_then(Value::as_u64).unwrap_or(0)
+pub fn get_resource_filepath(match_id: u64) -> String {
+    get_default_client().get_resource_filepath(get_cache_key(match_id).as_str())
 }
 
-pub fn parse_response(response: String, match_id: u64) -> Result<MatchResult, String> {
+pub fn parse_response(response: String, match_id: u64) -> Result<MatchResult, SteamApiError> {
     let boxed_initial_parse = serde_json::from_str(&response);
     if boxed_initial_parse.is_err() {
-        return Err(boxed_initial_parse.err().unwrap().to_string());
+        return Err(SteamApiError::malformed_response(get_endpoint().as_str(), boxed_initial_parse.err().unwrap().to_string().as_str()));
     }
     let json: Value = boxed_initial_parse.unwrap();
 
     let boxed_result = json.get("result");
     if boxed_result.is_none() {
-        return Err("response does not contain a result".to_string());
+        return Err(SteamApiError::malformed_response(get_endpoint().as_str(), "response does not contain a result"));
     }
     let result = boxed_result.unwrap();
 
-    let boxed_error = result.get("error").and_then(Value::as_str);
-    if boxed_error.is_some() {
-        return Err(boxed_error.unwrap().to_string());
+    if let Some(error) = result.get("error").and_then(Value::as_str) {
+        return Err(SteamApiError::steam_failure(get_endpoint().as_str(), None, error));
     }
 
+    parse_match(result, match_id)
+}
+
+/// Parses a single match object, as found in `result` of a `GetMatchDetails` response and in
+/// `result.matches` of `GetMatchHistoryBySequenceNum`. `match_id` is used if the object has none.
+pub fn parse_match(result: &Value, match_id: u64) -> Result<MatchResult, SteamApiError> {
     let boxed_players = result.get("players").and_then(Value::as_array);
     if boxed_players.is_none() {
-        return Err("response does not contain players!".to_string());
+        return Err(SteamApiError::malformed_response(get_endpoint().as_str(), "response does not contain players!"));
     }
 
     let mut players = vec![];
     for player_result in boxed_players.unwrap() {
         let player_stats = PlayerStats {
-            account_id: as_u64(player_result, "account_id"),
-            player_slot: as_u64(player_result, "player_slot"),
-            team_number: as_u64(player_result, "team_number"),
-            team_slot: as_u64(player_result, "team_slot"),
-            hero_id: as_u64(player_result, "hero_id"),
-            item_0: as_u64(player_result, "item_0"),
-            item_1: as_u64(player_result, "item_1"),
-            item_2: as_u64(player_result, "item_2"),
-            item_3: as_u64(player_result, "item_3"),
-            item_4: as_u64(player_result, "item_4"),
-            item_5: as_u64(player_result, "item_5"),
-            backpack_0: as_u64(player_result, "backpack_0"),
-            backpack_1: as_u64(player_result, "backpack_1"),
-            backpack_2: as_u64(player_result, "backpack_2"),
-            item_neutral: as_u64(player_result, "item_neutral"),
-            kills: as_u64(player_result, "kills"),
-            deaths: as_u64(player_result, "deaths"),
-            assists: as_u64(player_result, "assists"),
-            leaver_status: as_u64(player_result, "leaver_status"),
-            last_hits: as_u64(player_result, "last_hits"),
-            denies: as_u64(player_result, "denies"),
-            gold_per_min: as_u64(player_result, "gold_per_min"),
-            xp_per_min: as_u64(player_result, "xp_per_min"),
-            level: as_u64(player_result, "level"),
-            net_worth: as_u64(player_result, "net_worth"),
-            aghanims_scepter: as_u64(player_result, "aghanims_scepter"),
-            aghanims_shard: as_u64(player_result, "aghanims_shard"),
-            moonshard: as_u64(player_result, "moonshard"),
+            account_id: json_opt_u64(player_result, "account_id"),
+            player_slot: json_u64(player_result, "player_slot"),
+            team_number: json_opt_u64(player_result, "team_number"),
+            team_slot: json_opt_u64(player_result, "team_slot"),
+            hero_id: json_u64(player_result, "hero_id"),
+            item_0: json_u64(player_result, "item_0"),
+            item_1: json_u64(player_result, "item_1"),
+            item_2: json_u64(player_result, "item_2"),
+            item_3: json_u64(player_result, "item_3"),
+            item_4: json_u64(player_result, "item_4"),
+            item_5: json_u64(player_result, "item_5"),
+            backpack_0: json_opt_u64(player_result, "backpack_0"),
+            backpack_1: json_opt_u64(player_result, "backpack_1"),
+            backpack_2: json_opt_u64(player_result, "backpack_2"),
+            item_neutral: json_opt_u64(player_result, "item_neutral"),
+            kills: json_u64(player_result, "kills"),
+            deaths: json_u64(player_result, "deaths"),
+            assists: json_u64(player_result, "assists"),
+            leaver_status: json_opt_u64(player_result, "leaver_status"),
+            last_hits: json_u64(player_result, "last_hits"),
+            denies: json_u64(player_result, "denies"),
+            gold_per_min: json_u64(player_result, "gold_per_min"),
+            xp_per_min: json_u64(player_result, "xp_per_min"),
+            level: json_u64(player_result, "level"),
+            net_worth: json_opt_u64(player_result, "net_worth"),
+            aghanims_scepter: json_opt_u64(player_result, "aghanims_scepter"),
+            aghanims_shard: json_opt_u64(player_result, "aghanims_shard"),
+            moonshard: json_opt_u64(player_result, "moonshard"),
+            hero_damage: json_opt_u64(player_result, "hero_damage"),
+            tower_damage: json_opt_u64(player_result, "tower_damage"),
+            hero_healing: json_opt_u64(player_result, "hero_healing"),
+            gold: json_opt_u64(player_result, "gold"),
+            gold_spent: json_opt_u64(player_result, "gold_spent"),
+            scaled_hero_damage: json_opt_u64(player_result, "scaled_hero_damage"),
+            scaled_tower_damage: json_opt_u64(player_result, "scaled_tower_damage"),
+            scaled_hero_healing: json_opt_u64(player_result, "scaled_hero_healing"),
+            ability_upgrades: json_opt_array(player_result, "ability_upgrades").map(|ability_upgrades| {
+                ability_upgrades.iter()
+                    .map(|ability_upgrade| AbilityUpgrade {
+                        ability: json_u64(ability_upgrade, "ability"),
+                        time: json_u64(ability_upgrade, "time"),
+                        level: json_u64(ability_upgrade, "level"),
+                    })
+                    .collect()
+            }),
+            additional_units: json_opt_array(player_result, "additional_units").map(|additional_units| {
+                additional_units.iter()
+                    .map(|additional_unit| AdditionalUnit {
+                        unitname: json_str(additional_unit, "unitname"),
+                        item_0: json_u64(additional_unit, "item_0"),
+                        item_1: json_u64(additional_unit, "item_1"),
+                        item_2: json_u64(additional_unit, "item_2"),
+                        item_3: json_u64(additional_unit, "item_3"),
+                        item_4: json_u64(additional_unit, "item_4"),
+                        item_5: json_u64(additional_unit, "item_5"),
+                        backpack_0: json_opt_u64(additional_unit, "backpack_0"),
+                        backpack_1: json_opt_u64(additional_unit, "backpack_1"),
+                        backpack_2: json_opt_u64(additional_unit, "backpack_2"),
+                        item_neutral: json_opt_u64(additional_unit, "item_neutral"),
+                    })
+                    .collect()
+            }),
         };
         players.push(player_stats);
     }
 
     let match_result = MatchResult {
         radiant_win: result.get("radiant_win").and_then(Value::as_bool).unwrap_or(false),
-        duration: as_u64(result, "duration"),
-        pre_game_duration: as_u64(result, "pre_game_duration"),
-        start_time: as_u64(result, "start_time"),
-        match_id: as_u64(result, "match_id"),
-        match_seq_num: as_u64(result, "match_seq_num"),
-        tower_status_radiant: as_u64(result, "tower_status_radiant"),
-        tower_status_dire: as_u64(result, "tower_status_dire"),
-        barracks_status_radiant: as_u64(result, "barracks_status_radiant"),
-        barracks_status_dire: as_u64(result, "barracks_status_dire"),
-        cluster: as_u64(result, "cluster"),
-        first_blood_time: as_u64(result, "first_blood_time"),
-        lobby_type: as_u64(result, "lobby_type"),
-        human_players: as_u64(result, "human_players"),
-        leagueid: as_u64(result, "leagueid"),
-        positive_votes: as_u64(result, "positive_votes"),
-        negative_votes: as_u64(result, "negative_votes"),
-        game_mode: as_u64(result, "game_mode"),
-        flags: as_u64(result, "flags"),
-        engine: as_u64(result, "engine"),
-        radiant_score: as_u64(result, "radiant_score"),
-        dire_score: as_u64(result, "dire_score"),
+        duration: json_u64(result, "duration"),
+        pre_game_duration: json_opt_u64(result, "pre_game_duration"),
+        start_time: json_u64(result, "start_time"),
+        match_id: result.get("match_id").and_then(Value::as_u64).unwrap_or(match_id),
+        match_seq_num: json_u64(result, "match_seq_num"),
+        tower_status_radiant: json_u64(result, "tower_status_radiant"),
+        tower_status_dire: json_u64(result, "tower_status_dire"),
+        barracks_status_radiant: json_u64(result, "barracks_status_radiant"),
+        barracks_status_dire: json_u64(result, "barracks_status_dire"),
+        cluster: json_u64(result, "cluster"),
+        first_blood_time: json_opt_u64(result, "first_blood_time"),
+        lobby_type: LobbyType::from_id(json_i64(result, "lobby_type")),
+        human_players: json_u64(result, "human_players"),
+        leagueid: json_u64(result, "leagueid"),
+        positive_votes: json_opt_u64(result, "positive_votes"),
+        negative_votes: json_opt_u64(result, "negative_votes"),
+        game_mode: GameMode::from_id(json_u64(result, "game_mode")),
+        flags: json_opt_u64(result, "flags"),
+        engine: json_opt_u64(result, "engine"),
+        radiant_score: json_opt_u64(result, "radiant_score"),
+        dire_score: json_opt_u64(result, "dire_score"),
+        radiant_team_id: json_opt_u64(result, "radiant_team_id"),
+        radiant_name: json_opt_str(result, "radiant_name"),
+        radiant_logo: json_opt_u64(result, "radiant_logo"),
+        radiant_captain: json_opt_u64(result, "radiant_captain"),
+        dire_team_id: json_opt_u64(result, "dire_team_id"),
+        dire_name: json_opt_str(result, "dire_name"),
+        dire_logo: json_opt_u64(result, "dire_logo"),
+        dire_captain: json_opt_u64(result, "dire_captain"),
+        picks_bans: json_opt_array(result, "picks_bans").map(|picks_bans| {
+            picks_bans.iter()
+                .map(|pick_ban| PickBan {
+                    is_pick: pick_ban.get("is_pick").and_then(Value::as_bool).unwrap_or(false),
+                    hero_id: json_u64(pick_ban, "hero_id"),
+                    team: Team::from_id(json_u64(pick_ban, "team")),
+                    order: json_u64(pick_ban, "order"),
+                })
+                .collect()
+        }),
         players,
     };
 
-    cache_response(match_id, &response);
-
     Ok(match_result)
 }
-
-fn cache_response(match_id: u64, response_string: &str) {
-    let cache_dir = get_cache_dir_path();
-    if !Path::new(cache_dir.as_str()).is_dir() {
-        fs::create_dir_all(cache_dir).unwrap();
-    }
-
-    let filepath = get_resource_filepath(match_id);
-    let mut file: File = File::create(filepath).unwrap();
-    file.write_all(response_string.as_ref()).unwrap();
-}
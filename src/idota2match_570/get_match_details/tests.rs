@@ -1,8 +1,11 @@
 use std::fs::read_to_string;
 use url_build_parse::parse_url;
 use crate::{get_host, get_scheme};
-use crate::idota2match_570::get_match_details::{get_api_url, get_resource_filepath, parse_response};
-use crate::util::get_steam_web_api_key;
+use crate::idota2match_570::get_match_details::{get_api_url, get_resource_filepath, parse_response, AbilityUpgrade, PickBan};
+use crate::error::SteamApiError;
+use crate::idota2match_570::types::{GameMode, LobbyType, Team};
+use crate::client::get_default_client;
+use crate::tests::assert_serde_roundtrip;
 
 #[test]
 fn api_url() {
@@ -18,15 +21,14 @@ fn api_url() {
     let boxed_match_id = params.get("match_id");
     assert_eq!(1461414523, boxed_match_id.unwrap().parse::<u64>().unwrap());
 
-    let boxed_key = params.get("key");
-    assert_eq!(get_steam_web_api_key(), boxed_key.unwrap().to_string());
+    assert!(!params.contains_key("key"));
 }
 
 #[test]
 fn resource_filepath() {
     let filepath = get_resource_filepath(1461414523);
 
-    assert_eq!("steam-webapi-cache/IDOTA2Match_570-GetMatchDetails-v1-1461414523.json", filepath);
+    assert_eq!([get_default_client().cache_dir(), "/IDOTA2Match_570-GetMatchDetails-v1-1461414523.json"].join(""), filepath);
 }
 
 #[test]
@@ -40,10 +42,11 @@ fn parse() {
     assert!(boxed_parse.is_ok());
 
     let match_result = boxed_parse.unwrap();
+    assert_serde_roundtrip(&match_result);
 
     assert_eq!(false, match_result.radiant_win);
     assert_eq!(2675, match_result.duration);
-    assert_eq!(0, match_result.pre_game_duration);
+    assert_eq!(Some(0), match_result.pre_game_duration);
     assert_eq!(1431220172, match_result.start_time);
     assert_eq!(1461414523, match_result.match_id);
     assert_eq!(1310985420, match_result.match_seq_num);
@@ -52,25 +55,25 @@ fn parse() {
     assert_eq!(12, match_result.barracks_status_radiant);
     assert_eq!(63, match_result.barracks_status_dire);
     assert_eq!(123, match_result.cluster);
-    assert_eq!(109, match_result.first_blood_time);
-    assert_eq!(7, match_result.lobby_type);
+    assert_eq!(Some(109), match_result.first_blood_time);
+    assert_eq!(LobbyType::RankedMatchmaking, match_result.lobby_type);
     assert_eq!(10, match_result.human_players);
     assert_eq!(0, match_result.leagueid);
-    assert_eq!(0, match_result.positive_votes);
-    assert_eq!(0, match_result.negative_votes);
-    assert_eq!(22, match_result.game_mode);
-    assert_eq!(0, match_result.flags);
-    assert_eq!(0, match_result.engine);
-    assert_eq!(0, match_result.radiant_score);
-    assert_eq!(0, match_result.dire_score);
+    assert_eq!(Some(0), match_result.positive_votes);
+    assert_eq!(Some(0), match_result.negative_votes);
+    assert_eq!(GameMode::AllDraft, match_result.game_mode);
+    assert_eq!(Some(0), match_result.flags);
+    assert_eq!(Some(0), match_result.engine);
+    assert_eq!(Some(0), match_result.radiant_score);
+    assert_eq!(Some(0), match_result.dire_score);
 
     assert_eq!(10, match_result.players.len());
 
     let player_1 = match_result.players.get(0).unwrap();
-    assert_eq!(172120682, player_1.account_id);
+    assert_eq!(Some(172120682), player_1.account_id);
     assert_eq!(0, player_1.player_slot);
-    assert_eq!(0, player_1.team_number);
-    assert_eq!(0, player_1.team_slot);
+    assert_eq!(Some(0), player_1.team_number);
+    assert_eq!(Some(0), player_1.team_slot);
     assert_eq!(12, player_1.hero_id);
     assert_eq!(61, player_1.item_0);
     assert_eq!(185, player_1.item_1);
@@ -78,50 +81,146 @@ fn parse() {
     assert_eq!(174, player_1.item_3);
     assert_eq!(212, player_1.item_4);
     assert_eq!(63, player_1.item_5);
-    assert_eq!(0, player_1.backpack_0);
-    assert_eq!(0, player_1.backpack_1);
-    assert_eq!(0, player_1.backpack_2);
-    assert_eq!(0, player_1.item_neutral);
+    assert_eq!(Some(0), player_1.backpack_0);
+    assert_eq!(Some(0), player_1.backpack_1);
+    assert_eq!(Some(0), player_1.backpack_2);
+    assert_eq!(Some(0), player_1.item_neutral);
     assert_eq!(7, player_1.kills);
     assert_eq!(10, player_1.deaths);
     assert_eq!(3, player_1.assists);
-    assert_eq!(0, player_1.leaver_status);
+    assert_eq!(Some(0), player_1.leaver_status);
     assert_eq!(186, player_1.last_hits);
     assert_eq!(4, player_1.denies);
     assert_eq!(371, player_1.gold_per_min);
     assert_eq!(457, player_1.xp_per_min);
     assert_eq!(19, player_1.level);
-    assert_eq!(10772, player_1.net_worth);
-    assert_eq!(0, player_1.aghanims_scepter);
-    assert_eq!(0, player_1.aghanims_shard);
-    assert_eq!(0, player_1.moonshard);
+    assert_eq!(Some(10772), player_1.net_worth);
+    assert_eq!(Some(0), player_1.aghanims_scepter);
+    assert_eq!(Some(0), player_1.aghanims_shard);
+    assert_eq!(Some(0), player_1.moonshard);
 
     let player_6 = match_result.players.get(5).unwrap();
-    assert_eq!(95816, player_6.account_id);
+    assert_eq!(Some(95816), player_6.account_id);
     assert_eq!(128, player_6.player_slot);
-    assert_eq!(1, player_6.team_number);
-    assert_eq!(0, player_6.team_slot);
+    assert_eq!(Some(1), player_6.team_number);
+    assert_eq!(Some(0), player_6.team_slot);
     assert_eq!(112, player_6.hero_id);
     assert_eq!(4, player_6.kills);
     assert_eq!(4, player_6.deaths);
     assert_eq!(19, player_6.assists);
 
     let player_10 = match_result.players.get(9).unwrap();
-    assert_eq!(115823162, player_10.account_id);
+    assert_eq!(Some(115823162), player_10.account_id);
     assert_eq!(132, player_10.player_slot);
-    assert_eq!(1, player_10.team_number);
-    assert_eq!(4, player_10.team_slot);
+    assert_eq!(Some(1), player_10.team_number);
+    assert_eq!(Some(4), player_10.team_slot);
     assert_eq!(17, player_10.hero_id);
     assert_eq!(18, player_10.kills);
     assert_eq!(8, player_10.deaths);
     assert_eq!(14, player_10.assists);
 }
 
+#[test]
+fn decode_teams_and_buildings() {
+    let response = read_to_string("test/idota2match_570/get_match_details/1461414523.json").unwrap();
+    let match_result = parse_response(response, 1461414523).unwrap();
+
+    assert_eq!(Team::Dire, match_result.get_winner());
+
+    let radiant_players = match_result.radiant_players();
+    let dire_players = match_result.dire_players();
+    assert_eq!(5, radiant_players.len());
+    assert_eq!(5, dire_players.len());
+    assert!(radiant_players.iter().all(|player| !player.won(&match_result)));
+    assert!(dire_players.iter().all(|player| player.won(&match_result)));
+
+    let player_10 = dire_players.get(4).unwrap();
+    assert_eq!(Team::Dire, player_10.get_player_slot().team);
+    assert_eq!(4, player_10.get_player_slot().position);
+
+    let radiant_towers = match_result.get_tower_status(Team::Radiant);
+    assert_eq!(1, radiant_towers.count_standing());
+    assert!(radiant_towers.middle_tier_3);
+    assert_eq!(7, match_result.get_tower_status(Team::Dire).count_standing());
+
+    let radiant_barracks = match_result.get_barracks_status(Team::Radiant);
+    assert!(radiant_barracks.middle_melee && radiant_barracks.middle_ranged);
+    assert_eq!(2, radiant_barracks.count_standing());
+    assert_eq!(6, match_result.get_barracks_status(Team::Dire).count_standing());
+}
+
+#[test]
+fn parse_old_match_without_newer_fields() {
+    let response = r#"{"result":{"radiant_win":true,"duration":1800,"start_time":1300000000,"match_id":42,"players":[{"player_slot":0,"hero_id":1,"kills":3}]}}"#.to_string();
+
+    let match_result = parse_response(response, 42).unwrap();
+    assert!(match_result.radiant_win);
+    assert_eq!(None, match_result.first_blood_time);
+    assert_eq!(None, match_result.radiant_score);
+
+    let bot = match_result.players.first().unwrap();
+    assert_eq!(None, bot.account_id);
+    assert_eq!(None, bot.item_neutral);
+    assert_eq!(None, bot.net_worth);
+    assert_eq!(3, bot.kills);
+    assert_eq!(None, bot.hero_damage);
+    assert_eq!(None, bot.ability_upgrades);
+    assert_eq!(None, bot.additional_units);
+}
+
+#[test]
+fn parse_captains_mode_league_match() {
+    let response = read_to_string("test/idota2match_570/get_match_details/7200000001.json").unwrap();
+    let match_result = parse_response(response, 7200000001).unwrap();
+    assert_serde_roundtrip(&match_result);
+
+    assert_eq!(GameMode::CaptainsMode, match_result.game_mode);
+    assert_eq!(LobbyType::Practice, match_result.lobby_type);
+    assert_eq!(15438, match_result.leagueid);
+    assert_eq!(Some(8255888), match_result.radiant_team_id);
+    assert_eq!(Some("Northern Lights"), match_result.get_team_name(Team::Radiant));
+    assert_eq!(Some("Crimson Harbor"), match_result.get_team_name(Team::Dire));
+    assert_eq!(Some(1799427418163224012), match_result.dire_logo);
+    assert_eq!(Some(94054712), match_result.radiant_captain);
+
+    let picks_bans = match_result.picks_bans.as_ref().unwrap();
+    assert_eq!(24, picks_bans.len());
+    assert_eq!(PickBan { is_pick: false, hero_id: 19, team: Team::Radiant, order: 0 }, picks_bans[0]);
+    assert_eq!(PickBan { is_pick: true, hero_id: 80, team: Team::Radiant, order: 7 }, picks_bans[7]);
+
+    let radiant_picks : Vec<u64> = match_result.get_picks(Team::Radiant).iter().map(|pick| pick.hero_id).collect();
+    assert_eq!(vec![80, 26, 1, 86, 50], radiant_picks);
+    assert_eq!(7, match_result.get_bans(Team::Dire).len());
+    let radiant_heroes : Vec<u64> = match_result.radiant_players().iter().map(|player| player.hero_id).collect();
+    assert_eq!(radiant_picks, radiant_heroes);
+
+    let lone_druid = match_result.players.first().unwrap();
+    assert_eq!(Some(39550), lone_druid.hero_damage);
+    assert_eq!(Some(6850), lone_druid.tower_damage);
+    assert_eq!(Some(2702), lone_druid.hero_healing);
+    assert_eq!(Some(3201), lone_druid.gold);
+    assert_eq!(Some(19208), lone_druid.gold_spent);
+    assert_eq!(Some(32826), lone_druid.scaled_hero_damage);
+    assert_eq!(Some(6233), lone_druid.scaled_tower_damage);
+    assert_eq!(Some(2080), lone_druid.scaled_hero_healing);
+
+    let ability_upgrades = lone_druid.ability_upgrades.as_ref().unwrap();
+    assert_eq!(30, ability_upgrades.len());
+    assert_eq!(AbilityUpgrade { ability: 5801, time: 120, level: 1 }, ability_upgrades[0]);
+    assert_eq!(30, ability_upgrades.last().unwrap().level);
+
+    let spirit_bear = &lone_druid.additional_units.as_ref().unwrap()[0];
+    assert_eq!("spirit_bear", spirit_bear.unitname);
+    assert_eq!(vec![1, 50, 135, 208], spirit_bear.get_item_ids());
+
+    assert!(match_result.players.iter().skip(1).all(|player| player.additional_units.is_none()));
+}
+
 #[test]
 fn parse_missing_result() {
     let boxed_parse = parse_response("{}".to_string(), 1461414523);
     assert!(boxed_parse.is_err());
-    assert_eq!("response does not contain a result", boxed_parse.err().unwrap());
+    assert_eq!(SteamApiError::malformed_response("IDOTA2Match_570/GetMatchDetails", "response does not contain a result"), boxed_parse.err().unwrap());
 }
 
 #[test]
@@ -129,5 +228,5 @@ fn parse_error_response() {
     let response = "{\"result\":{\"error\":\"Match ID not found\"}}".to_string();
     let boxed_parse = parse_response(response, 1461414523);
     assert!(boxed_parse.is_err());
-    assert_eq!("Match ID not found", boxed_parse.err().unwrap());
+    assert_eq!(SteamApiError::steam_failure("IDOTA2Match_570/GetMatchDetails", None, "Match ID not found"), boxed_parse.err().unwrap());
 }
@@ -3,8 +3,12 @@ use serde::Serialize;
 use serde::Deserialize;
 use serde_json::Value;
 use url_build_parse::{build_url, UrlAuthority, UrlComponents};
-use crate::{get_host, get_scheme, idota2match_570, make_api_call};
-use crate::util::{get_steam_web_api_key};
+use crate::{get_host, get_scheme, idota2match_570};
+use crate::client::{get_default_client, SteamClient};
+use crate::error::{SteamApiError, STATUS_NOT_ALLOWED_BY_USER_PREFERENCES};
+use crate::idota2match_570::types::{GameMode, LobbyType, PlayerSlot, Skill, Team};
+use crate::steam_id::SteamId;
+use crate::util::{build_cache_key};
 
 #[cfg(test)]
 mod tests;
@@ -34,12 +38,206 @@ pub struct MatchHistory {
     pub match_id: i64,
     pub match_seq_num: i64,
     pub start_time: i64,
-    pub lobby_type: i64,
+    pub lobby_type: LobbyType,
     pub radiant_team_id: i64,
     pub dire_team_id: i64,
     pub players: Vec<Participant>,
 }
 
+impl Participant {
+    pub fn get_player_slot(&self) -> PlayerSlot {
+        PlayerSlot::from_id(self.player_slot as u64)
+    }
+
+    pub fn get_team(&self) -> Team {
+        self.get_player_slot().team
+    }
+}
+
+/// Filters of `GetMatchHistory` shared by every page, see `MatchHistoryPages`.
+#[derive(PartialEq, Eq, Clone, Debug, Default)]
+pub struct MatchHistoryFilter {
+    account_id: Option<SteamId>,
+    hero_id: Option<u64>,
+    game_mode: Option<GameMode>,
+    skill: Option<Skill>,
+    min_players: Option<u32>,
+    tournament_games_only: Option<bool>,
+}
+
+impl MatchHistoryFilter {
+    pub fn new() -> MatchHistoryFilter {
+        MatchHistoryFilter::default()
+    }
+
+    pub fn with_account_id(mut self, account_id: SteamId) -> MatchHistoryFilter {
+        self.account_id = Some(account_id);
+        self
+    }
+
+    pub fn with_hero_id(mut self, hero_id: u64) -> MatchHistoryFilter {
+        self.hero_id = Some(hero_id);
+        self
+    }
+
+    pub fn with_game_mode(mut self, game_mode: GameMode) -> MatchHistoryFilter {
+        self.game_mode = Some(game_mode);
+        self
+    }
+
+    pub fn with_skill(mut self, skill: Skill) -> MatchHistoryFilter {
+        self.skill = Some(skill);
+        self
+    }
+
+    pub fn with_min_players(mut self, min_players: u32) -> MatchHistoryFilter {
+        self.min_players = Some(min_players);
+        self
+    }
+
+    pub fn with_tournament_games_only(mut self, tournament_games_only: bool) -> MatchHistoryFilter {
+        self.tournament_games_only = Some(tournament_games_only);
+        self
+    }
+
+    pub fn get_account_id(&self) -> Option<SteamId> {
+        self.account_id
+    }
+
+    pub fn get_hero_id(&self) -> Option<u64> {
+        self.hero_id
+    }
+
+    pub fn get_game_mode(&self) -> Option<GameMode> {
+        self.game_mode
+    }
+
+    pub fn get_skill(&self) -> Option<Skill> {
+        self.skill
+    }
+
+    pub fn get_min_players(&self) -> Option<u32> {
+        self.min_players
+    }
+
+    pub fn get_tournament_games_only(&self) -> Option<bool> {
+        self.tournament_games_only
+    }
+}
+
+/// Position of `MatchHistoryPages`, to be saved and passed to `MatchHistoryPages::resume`.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Copy, Debug, Default)]
+pub struct MatchHistoryCursor {
+    /// `start_at_match_id` of the next page, `None` for the newest matches.
+    pub start_at_match_id: Option<i64>,
+    /// Set once the last page was returned.
+    pub finished: bool,
+}
+
+/// Iterator over every page of the match history for a `MatchHistoryFilter`, newest matches first.
+///
+/// Each page starts right below the oldest match of the previous one. Iteration ends when
+/// Steam reports no `results_remaining`, which happens at the latest after 500 matches as
+/// Steam does not return older ones. Requests go through the client, so its rate limiter and
+/// retry policy apply. After an error the iterator ends, with the cursor still pointing at the
+/// page that failed.
+#[derive(Clone, Debug)]
+pub struct MatchHistoryPages {
+    client: SteamClient,
+    filter: MatchHistoryFilter,
+    matches_requested: Option<u32>,
+    cursor: MatchHistoryCursor,
+    failed: bool,
+}
+
+impl MatchHistoryPages {
+    pub fn new(client: SteamClient, filter: MatchHistoryFilter) -> MatchHistoryPages {
+        MatchHistoryPages::resume(client, filter, MatchHistoryCursor::default())
+    }
+
+    /// Continues from a cursor saved with `get_cursor`, using the same filter.
+    pub fn resume(client: SteamClient, filter: MatchHistoryFilter, cursor: MatchHistoryCursor) -> MatchHistoryPages {
+        MatchHistoryPages {
+            client,
+            filter,
+            matches_requested: None,
+            cursor,
+            failed: false,
+        }
+    }
+
+    /// Number of matches per page, Steam defaults to (and caps at) 100.
+    pub fn with_matches_requested(mut self, matches_requested: u32) -> MatchHistoryPages {
+        self.matches_requested = Some(matches_requested);
+        self
+    }
+
+    pub fn get_filter(&self) -> &MatchHistoryFilter {
+        &self.filter
+    }
+
+    pub fn get_cursor(&self) -> MatchHistoryCursor {
+        self.cursor
+    }
+
+    /// Flattens the pages into their matches.
+    pub fn matches(self) -> impl Iterator<Item = Result<MatchHistory, SteamApiError>> {
+        self.flat_map(|boxed_page| match boxed_page {
+            Ok(page) => page.matches.into_iter().map(Ok).collect::<Vec<_>>(),
+            Err(error) => vec![Err(error)],
+        })
+    }
+
+    /// Async version of `next`, available with the `async` feature.
+    #[cfg(feature = "async")]
+    pub async fn next_async(&mut self) -> Option<Result<ResponseMatchHistory, SteamApiError>> {
+        if self.cursor.finished || self.failed {
+            return None;
+        }
+
+        let boxed_page = get_page_async_with_client(&self.client, &self.filter, self.cursor.start_at_match_id, self.matches_requested).await;
+        Some(self.advance(boxed_page))
+    }
+
+    fn advance(&mut self, boxed_page: Result<ResponseMatchHistory, SteamApiError>) -> Result<ResponseMatchHistory, SteamApiError> {
+        if boxed_page.is_err() {
+            self.failed = true;
+            return boxed_page;
+        }
+        let page = boxed_page.unwrap();
+
+        let boxed_last_match = page.matches.last();
+        if boxed_last_match.is_none() {
+            self.cursor.finished = true;
+            return Ok(page);
+        }
+
+        let start_at_match_id = boxed_last_match.unwrap().match_id - 1;
+        let went_backwards = self.cursor.start_at_match_id.map_or(true, |current| start_at_match_id < current);
+        if !went_backwards || page.results_remaining <= 0 || start_at_match_id < 0 {
+            self.cursor.finished = true;
+        }
+        if went_backwards {
+            self.cursor.start_at_match_id = Some(start_at_match_id);
+        }
+
+        Ok(page)
+    }
+}
+
+impl Iterator for MatchHistoryPages {
+    type Item = Result<ResponseMatchHistory, SteamApiError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.cursor.finished || self.failed {
+            return None;
+        }
+
+        let boxed_page = get_page_with_client(&self.client, &self.filter, self.cursor.start_at_match_id, self.matches_requested);
+        Some(self.advance(boxed_page))
+    }
+}
+
 pub fn get_method_name() -> String {
     "GetMatchHistory".to_string()
 }
@@ -48,45 +246,244 @@ pub fn get_version() -> String {
     "v1".to_string()
 }
 
-pub fn get(account_id: Option<i64>,
-           game_mode: Option<u8>,
-           skill: Option<u8>,
+pub fn get_endpoint() -> String {
+    [idota2match_570::get_interface(), "/".to_string(), get_method_name()].join("")
+}
+
+pub fn get(account_id: Option<SteamId>,
+           game_mode: Option<GameMode>,
+           skill: Option<Skill>,
            min_players: Option<u32>,
            start_at_match_id: Option<i64>,
            matches_requested: Option<u32>,
            tournament_games_only: Option<bool>)
-    -> Result<ResponseMatchHistory, String> {
-    let api_url = get_api_url(
-                            account_id,
-                            game_mode,
-                            skill,
-                            min_players,
-                            start_at_match_id,
-                            matches_requested,
-                            tournament_games_only
-    );
-    let boxed_response = make_api_call(api_url);
-    if boxed_response.is_err() {
-        return Err(boxed_response.err().unwrap());
-    }
+    -> Result<ResponseMatchHistory, SteamApiError> {
+    get_with_client(
+        get_default_client().as_ref(),
+        account_id,
+        game_mode,
+        skill,
+        min_players,
+        start_at_match_id,
+        matches_requested,
+        tournament_games_only
+    )
+}
 
-    let response = boxed_response.unwrap();
-    let boxed_result = parse_response(response);
-    if boxed_result.is_err() {
-        return Err(boxed_result.err().unwrap());
-    }
+/// Async version of `get`, available with the `async` feature.
+#[cfg(feature = "async")]
+pub async fn get_async(account_id: Option<SteamId>,
+                       game_mode: Option<GameMode>,
+                       skill: Option<Skill>,
+                       min_players: Option<u32>,
+                       start_at_match_id: Option<i64>,
+                       matches_requested: Option<u32>,
+                       tournament_games_only: Option<bool>)
+    -> Result<ResponseMatchHistory, SteamApiError> {
+    get_async_with_client(
+        get_default_client().as_ref(),
+        account_id,
+        game_mode,
+        skill,
+        min_players,
+        start_at_match_id,
+        matches_requested,
+        tournament_games_only
+    ).await
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn get_with_client(client: &SteamClient,
+                       account_id: Option<SteamId>,
+                       game_mode: Option<GameMode>,
+                       skill: Option<Skill>,
+                       min_players: Option<u32>,
+                       start_at_match_id: Option<i64>,
+                       matches_requested: Option<u32>,
+                       tournament_games_only: Option<bool>)
+    -> Result<ResponseMatchHistory, SteamApiError> {
+    let filter = get_filter(account_id, game_mode, skill, min_players, tournament_games_only);
+    get_page_with_client(client, &filter, start_at_match_id, matches_requested)
+}
+
+/// Async version of `get_with_client`, available with the `async` feature.
+#[cfg(feature = "async")]
+#[allow(clippy::too_many_arguments)]
+pub async fn get_async_with_client(client: &SteamClient,
+                                   account_id: Option<SteamId>,
+                                   game_mode: Option<GameMode>,
+                                   skill: Option<Skill>,
+                                   min_players: Option<u32>,
+                                   start_at_match_id: Option<i64>,
+                                   matches_requested: Option<u32>,
+                                   tournament_games_only: Option<bool>)
+    -> Result<ResponseMatchHistory, SteamApiError> {
+    let filter = get_filter(account_id, game_mode, skill, min_players, tournament_games_only);
+    get_page_async_with_client(client, &filter, start_at_match_id, matches_requested).await
+}
+
+/// Fetches one page of the match history for `filter`, `MatchHistoryPages` walks all of them.
+pub fn get_page_with_client(client: &SteamClient,
+                            filter: &MatchHistoryFilter,
+                            start_at_match_id: Option<i64>,
+                            matches_requested: Option<u32>)
+    -> Result<ResponseMatchHistory, SteamApiError> {
+    let cache_key = get_page_cache_key(filter, start_at_match_id, matches_requested);
+    let api_url = get_page_api_url(filter, start_at_match_id, matches_requested);
+    client.fetch_and_cache(get_endpoint().as_str(), cache_key.as_str(), api_url, parse_response)
+}
+
+/// Async version of `get_page_with_client`, available with the `async` feature.
+#[cfg(feature = "async")]
+pub async fn get_page_async_with_client(client: &SteamClient,
+                                        filter: &MatchHistoryFilter,
+                                        start_at_match_id: Option<i64>,
+                                        matches_requested: Option<u32>)
+    -> Result<ResponseMatchHistory, SteamApiError> {
+    let cache_key = get_page_cache_key(filter, start_at_match_id, matches_requested);
+    let api_url = get_page_api_url(filter, start_at_match_id, matches_requested);
+    client.fetch_and_cache_async(get_endpoint().as_str(), cache_key.as_str(), api_url, parse_response).await
+}
+
+/// Iterates over every page of the match history for `filter`, see `MatchHistoryPages`.
+pub fn get_pages(filter: MatchHistoryFilter) -> MatchHistoryPages {
+    get_pages_with_client(get_default_client().as_ref(), filter)
+}
+
+pub fn get_pages_with_client(client: &SteamClient, filter: MatchHistoryFilter) -> MatchHistoryPages {
+    MatchHistoryPages::new(client.clone(), filter)
+}
+
+/// Like `get`, but serves the cached response while it is fresh as per the client's
+/// `cache::CachePolicy`, otherwise calls Steam and caches the response.
+pub fn get_cached(account_id: Option<SteamId>,
+                  game_mode: Option<GameMode>,
+                  skill: Option<Skill>,
+                  min_players: Option<u32>,
+                  start_at_match_id: Option<i64>,
+                  matches_requested: Option<u32>,
+                  tournament_games_only: Option<bool>)
+    -> Result<ResponseMatchHistory, SteamApiError> {
+    get_cached_with_client(
+        get_default_client().as_ref(),
+        account_id,
+        game_mode,
+        skill,
+        min_players,
+        start_at_match_id,
+        matches_requested,
+        tournament_games_only
+    )
+}
+
+/// Async version of `get_cached`, available with the `async` feature.
+#[cfg(feature = "async")]
+pub async fn get_cached_async(account_id: Option<SteamId>,
+                              game_mode: Option<GameMode>,
+                              skill: Option<Skill>,
+                              min_players: Option<u32>,
+                              start_at_match_id: Option<i64>,
+                              matches_requested: Option<u32>,
+                              tournament_games_only: Option<bool>)
+    -> Result<ResponseMatchHistory, SteamApiError> {
+    get_cached_async_with_client(
+        get_default_client().as_ref(),
+        account_id,
+        game_mode,
+        skill,
+        min_players,
+        start_at_match_id,
+        matches_requested,
+        tournament_games_only
+    ).await
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn get_cached_with_client(client: &SteamClient,
+                              account_id: Option<SteamId>,
+                              game_mode: Option<GameMode>,
+                              skill: Option<Skill>,
+                              min_players: Option<u32>,
+                              start_at_match_id: Option<i64>,
+                              matches_requested: Option<u32>,
+                              tournament_games_only: Option<bool>)
+    -> Result<ResponseMatchHistory, SteamApiError> {
+    client.get_cached_or_fetch(
+        get_endpoint().as_str(),
+        get_cache_key(
+            account_id,
+            game_mode,
+            skill,
+            min_players,
+            start_at_match_id,
+            matches_requested,
+            tournament_games_only
+        ).as_str(),
+        parse_response,
+        || get_with_client(
+            client,
+            account_id,
+            game_mode,
+            skill,
+            min_players,
+            start_at_match_id,
+            matches_requested,
+            tournament_games_only
+        ),
+    )
+}
 
-    let result = boxed_result.unwrap();
-    Ok(result)
+/// Async version of `get_cached_with_client`, available with the `async` feature.
+#[cfg(feature = "async")]
+#[allow(clippy::too_many_arguments)]
+pub async fn get_cached_async_with_client(client: &SteamClient,
+                                          account_id: Option<SteamId>,
+                                          game_mode: Option<GameMode>,
+                                          skill: Option<Skill>,
+                                          min_players: Option<u32>,
+                                          start_at_match_id: Option<i64>,
+                                          matches_requested: Option<u32>,
+                                          tournament_games_only: Option<bool>)
+    -> Result<ResponseMatchHistory, SteamApiError> {
+    client.get_cached_or_fetch_async(
+        get_endpoint().as_str(),
+        get_cache_key(
+            account_id,
+            game_mode,
+            skill,
+            min_players,
+            start_at_match_id,
+            matches_requested,
+            tournament_games_only
+        ).as_str(),
+        parse_response,
+        get_async_with_client(
+            client,
+            account_id,
+            game_mode,
+            skill,
+            min_players,
+            start_at_match_id,
+            matches_requested,
+            tournament_games_only
+        ),
+    ).await
 }
 
-pub fn get_api_url(account_id: Option<i64>,
-                             game_mode: Option<u8>,
-                             skill: Option<u8>,
+pub fn get_api_url(account_id: Option<SteamId>,
+                             game_mode: Option<GameMode>,
+                             skill: Option<Skill>,
                              min_players: Option<u32>,
                              start_at_match_id: Option<i64>,
                              matches_requested: Option<u32>,
                              tournament_games_only: Option<bool>) -> String {
+    let filter = get_filter(account_id, game_mode, skill, min_players, tournament_games_only);
+    get_page_api_url(&filter, start_at_match_id, matches_requested)
+}
+
+pub fn get_page_api_url(filter: &MatchHistoryFilter,
+                        start_at_match_id: Option<i64>,
+                        matches_requested: Option<u32>) -> String {
 
 
     let  interface = idota2match_570::get_interface();
@@ -102,35 +499,38 @@ pub fn get_api_url(account_id: Option<i64>,
 
     let mut params_map = HashMap::new();
 
-    if account_id.is_some() {
-        params_map.insert("account_id".to_string(), account_id.unwrap().to_string());
+    if let Some(account_id) = filter.account_id {
+        params_map.insert("account_id".to_string(), account_id.to_string());
+    }
+
+    if let Some(hero_id) = filter.hero_id {
+        params_map.insert("hero_id".to_string(), hero_id.to_string());
     }
 
-    if game_mode.is_some() {
-        params_map.insert("game_mode".to_string(), game_mode.unwrap().to_string());
+    if let Some(game_mode) = filter.game_mode {
+        params_map.insert("game_mode".to_string(), game_mode.get_id().to_string());
     }
 
-    if skill.is_some() {
-        params_map.insert("skill".to_string(), skill.unwrap().to_string());
+    if let Some(skill) = filter.skill {
+        params_map.insert("skill".to_string(), skill.get_id().to_string());
     }
 
-    if min_players.is_some() {
-        params_map.insert("min_players".to_string(), min_players.unwrap().to_string());
+    if let Some(min_players) = filter.min_players {
+        params_map.insert("min_players".to_string(), min_players.to_string());
     }
 
-    if start_at_match_id.is_some() {
-        params_map.insert("start_at_match_id".to_string(), start_at_match_id.unwrap().to_string());
+    if let Some(start_at_match_id) = start_at_match_id {
+        params_map.insert("start_at_match_id".to_string(), start_at_match_id.to_string());
     }
 
-    if matches_requested.is_some() {
-        params_map.insert("matches_requested".to_string(), matches_requested.unwrap().to_string());
+    if let Some(matches_requested) = matches_requested {
+        params_map.insert("matches_requested".to_string(), matches_requested.to_string());
     }
 
-    if tournament_games_only.is_some() {
-        params_map.insert("tournament_games_only".to_string(), tournament_games_only.unwrap().to_string());
+    if let Some(tournament_games_only) = filter.tournament_games_only {
+        params_map.insert("tournament_games_only".to_string(), tournament_games_only.to_string());
     }
 
-    params_map.insert("key".to_string(), get_steam_web_api_key());
 
     let url_builder = UrlComponents{
         scheme: get_scheme(),
@@ -148,20 +548,100 @@ pub fn get_api_url(account_id: Option<i64>,
     url
 }
 
-const MATCH_HISTORY_IS_NOT_ALLOWED_BY_USER_PREFERENCES: u8 = 15;
+/// Returns path of the cached resource relative to the cache directory.
+pub fn get_cache_key(account_id: Option<SteamId>,
+                      game_mode: Option<GameMode>,
+                      skill: Option<Skill>,
+                      min_players: Option<u32>,
+                      start_at_match_id: Option<i64>,
+                      matches_requested: Option<u32>,
+                      tournament_games_only: Option<bool>) -> String {
+    let filter = get_filter(account_id, game_mode, skill, min_players, tournament_games_only);
+    get_page_cache_key(&filter, start_at_match_id, matches_requested)
+}
 
-pub fn parse_response(response: String) -> Result<ResponseMatchHistory, String> {
+pub fn get_page_cache_key(filter: &MatchHistoryFilter,
+                          start_at_match_id: Option<i64>,
+                          matches_requested: Option<u32>) -> String {
+    let mut params = vec![];
+    if let Some(account_id) = filter.account_id {
+        params.push(("account_id", account_id.to_string()));
+    }
+    if let Some(hero_id) = filter.hero_id {
+        params.push(("hero_id", hero_id.to_string()));
+    }
+    if let Some(game_mode) = filter.game_mode {
+        params.push(("game_mode", game_mode.get_id().to_string()));
+    }
+    if let Some(skill) = filter.skill {
+        params.push(("skill", skill.get_id().to_string()));
+    }
+    if let Some(min_players) = filter.min_players {
+        params.push(("min_players", min_players.to_string()));
+    }
+    if let Some(start_at_match_id) = start_at_match_id {
+        params.push(("start_at_match_id", start_at_match_id.to_string()));
+    }
+    if let Some(matches_requested) = matches_requested {
+        params.push(("matches_requested", matches_requested.to_string()));
+    }
+    if let Some(tournament_games_only) = filter.tournament_games_only {
+        params.push(("tournament_games_only", tournament_games_only.to_string()));
+    }
+
+    build_cache_key(idota2match_570::get_interface().as_str(), get_method_name().as_str(), get_version().as_str(), params)
+}
+
+pub fn get_resource_filepath(account_id: Option<SteamId>,
+                             game_mode: Option<GameMode>,
+                             skill: Option<Skill>,
+                             min_players: Option<u32>,
+                             start_at_match_id: Option<i64>,
+                             matches_requested: Option<u32>,
+                             tournament_games_only: Option<bool>) -> String {
+    let cache_key = get_cache_key(
+        account_id,
+        game_mode,
+        skill,
+        min_players,
+        start_at_match_id,
+        matches_requested,
+        tournament_games_only
+    );
+    get_default_client().get_resource_filepath(cache_key.as_str())
+}
+
+fn get_filter(account_id: Option<SteamId>,
+              game_mode: Option<GameMode>,
+              skill: Option<Skill>,
+              min_players: Option<u32>,
+              tournament_games_only: Option<bool>) -> MatchHistoryFilter {
+    MatchHistoryFilter {
+        account_id,
+        hero_id: None,
+        game_mode,
+        skill,
+        min_players,
+        tournament_games_only,
+    }
+}
+
+pub fn parse_response(response: String) -> Result<ResponseMatchHistory, SteamApiError> {
 
     let boxed_initial_parse = serde_json::from_str(&response);
     if boxed_initial_parse.is_err() {
-        return Err(boxed_initial_parse.err().unwrap().to_string());
+        return Err(SteamApiError::malformed_response(get_endpoint().as_str(), boxed_initial_parse.err().unwrap().to_string().as_str()));
     }
     let mut json: Value = boxed_initial_parse.unwrap();
 
     let mut result = json["result".to_string()].take();
-    let status = result["status".to_string()].take().as_i64().unwrap();
-    if status as u8 == MATCH_HISTORY_IS_NOT_ALLOWED_BY_USER_PREFERENCES {
-        return Err("Cannot get match history for a user that hasn't allowed it".to_string())
+    let boxed_status = result["status".to_string()].take().as_i64();
+    if boxed_status.is_none() {
+        return Err(SteamApiError::malformed_response(get_endpoint().as_str(), "response does not contain a status"));
+    }
+    let status = boxed_status.unwrap();
+    if status == STATUS_NOT_ALLOWED_BY_USER_PREFERENCES {
+        return Err(SteamApiError::steam_failure(get_endpoint().as_str(), Some(status), "Cannot get match history for a user that hasn't allowed it"));
     }
 
     let num_results_clone = result["num_results".to_string()].clone();
@@ -184,7 +664,7 @@ pub fn parse_response(response: String) -> Result<ResponseMatchHistory, String>
 
     let matches_clone = result["matches".to_string()].clone();
     if matches_clone.as_array().is_none() {
-        return Err("response does not contain any matches!".to_string())
+        return Err(SteamApiError::malformed_response(get_endpoint().as_str(), "response does not contain any matches!"))
     }
     let matches = result["matches".to_string()].as_array().unwrap();
 
@@ -235,7 +715,7 @@ pub fn parse_response(response: String) -> Result<ResponseMatchHistory, String>
 
         let players_clone = match_result["players"].clone();
         if players_clone.as_array().is_none() {
-            return Err("response does not contain players!".to_string())
+            return Err(SteamApiError::malformed_response(get_endpoint().as_str(), "response does not contain players!"))
         }
         let players  = players_clone.as_array().unwrap();
 
@@ -244,7 +724,7 @@ pub fn parse_response(response: String) -> Result<ResponseMatchHistory, String>
             match_id,
             match_seq_num,
             start_time,
-            lobby_type,
+            lobby_type: LobbyType::from_id(lobby_type),
             radiant_team_id,
             dire_team_id,
             players: vec![]
@@ -304,81 +784,3 @@ pub fn parse_response(response: String) -> Result<ResponseMatchHistory, String>
 
     Ok(match_history_result)
 }
-
-pub const GAME_MODE: GameMode = GameMode{
-    none: 0,
-    all_pick: 1,
-    captains_mode: 2,
-    random_draft: 3,
-    single_draft: 4,
-    all_random: 5,
-    intro: 6,
-    diretide: 7,
-    reverse_captains_mode: 8,
-    the_greeviling: 9,
-    tutorial: 10,
-    mid_only: 11,
-    least_played: 12,
-    new_player_pool: 13,
-    compendium_matchmaking: 14,
-    captains_draft: 16
-};
-
-pub struct GameMode {
-    pub none: u8,
-    pub all_pick: u8,
-    pub captains_mode: u8,
-    pub random_draft: u8,
-    pub single_draft: u8,
-    pub all_random: u8,
-    pub intro: u8,
-    pub diretide: u8,
-    pub reverse_captains_mode: u8,
-    pub the_greeviling: u8,
-    pub tutorial: u8,
-    pub mid_only: u8,
-    pub least_played: u8,
-    pub new_player_pool: u8,
-    pub compendium_matchmaking: u8,
-    pub captains_draft: u8,
-}
-
-pub struct Skill {
-    pub any: u8,
-    pub normal: u8,
-    pub high: u8,
-    pub very_high: u8,
-}
-
-pub const PLAYER_SKILL : Skill = Skill {
-    any: 0,
-    normal: 1,
-    high: 2,
-    very_high: 3
-};
-
-pub struct LobbyType {
-    pub invalid: i8,
-    pub public_matchmaking: u8,
-    pub practise: u8,
-    pub tournament: u8,
-    pub tutorial: u8,
-    pub co_op_with_bots: u8,
-    pub team_match: u8,
-    pub solo_queue: u8,
-    pub ranked_matchmaking: u8,
-    pub one_vs_one_solo_mid: u8,
-}
-
-pub const LOBBY_TYPE: LobbyType = LobbyType {
-    invalid: -1,
-    public_matchmaking: 0,
-    practise: 1,
-    tournament: 2,
-    tutorial: 3,
-    co_op_with_bots: 4,
-    team_match: 5,
-    solo_queue: 6,
-    ranked_matchmaking: 7,
-    one_vs_one_solo_mid: 8
-};
@@ -1,35 +1,22 @@
 use std::fs::read_to_string;
+use std::sync::Arc;
 use url_build_parse::parse_url;
 use crate::{get_host, get_scheme};
-use crate::idota2match_570::get_match_history::{GAME_MODE, get_api_url, parse_response, PLAYER_SKILL};
-use crate::util::get_steam_web_api_key;
+use crate::client::SteamClient;
+use crate::idota2match_570::get_match_history::{get_api_url, get_page_api_url, get_resource_filepath, parse_response, MatchHistoryCursor, MatchHistoryFilter, MatchHistoryPages};
+use crate::retry::RetryPolicy;
+use crate::transport::FakeTransport;
+use crate::idota2match_570::types::{GameMode, LobbyType, Skill, Team};
+use crate::error::SteamApiError;
+use crate::steam_id::SteamId;
+use crate::client::get_default_client;
+use crate::tests::assert_serde_roundtrip;
 
 #[test]
-fn modes() {
-    assert_eq!(0, GAME_MODE.none);
-    assert_eq!(1, GAME_MODE.all_pick);
-    assert_eq!(2, GAME_MODE.captains_mode);
-    assert_eq!(3, GAME_MODE.random_draft);
-    assert_eq!(4, GAME_MODE.single_draft);
-    assert_eq!(5, GAME_MODE.all_random);
-    assert_eq!(6, GAME_MODE.intro);
-    assert_eq!(7, GAME_MODE.diretide);
-    assert_eq!(8, GAME_MODE.reverse_captains_mode);
-    assert_eq!(9, GAME_MODE.the_greeviling);
-    assert_eq!(10, GAME_MODE.tutorial);
-    assert_eq!(11, GAME_MODE.mid_only);
-    assert_eq!(12, GAME_MODE.least_played);
-    assert_eq!(13, GAME_MODE.new_player_pool);
-    assert_eq!(14, GAME_MODE.compendium_matchmaking);
-    assert_eq!(16, GAME_MODE.captains_draft);
-}
+fn resource_filepath() {
+    let filepath = get_resource_filepath(Some(SteamId::try_from(76561197960361544).unwrap()), None, None, None, Some(1461414523), Some(25), None);
 
-#[test]
-fn skill() {
-    assert_eq!(0, PLAYER_SKILL.any);
-    assert_eq!(1, PLAYER_SKILL.normal);
-    assert_eq!(2, PLAYER_SKILL.high);
-    assert_eq!(3, PLAYER_SKILL.very_high);
+    assert_eq!([get_default_client().cache_dir(), "/IDOTA2Match_570-GetMatchHistory-v1-account_id=76561197960361544-start_at_match_id=1461414523-matches_requested=25.json"].join(""), filepath);
 }
 
 #[test]
@@ -69,8 +56,7 @@ fn api_url_no_options() {
     let boxed_matches_requested = params.get("matches_requested");
     assert_eq!(None, boxed_matches_requested);
 
-    let boxed_key = params.get("key");
-    assert_eq!(get_steam_web_api_key(), boxed_key.unwrap().to_string());
+    assert!(!params.contains_key("key"));
 
 
 }
@@ -79,8 +65,8 @@ fn api_url_no_options() {
 #[test]
 fn api_url_options() {
     let api_url = get_api_url(
-        Some(76561197960361544),
-        Some(GAME_MODE.all_pick),
+        Some(SteamId::try_from(76561197960361544).unwrap()),
+        Some(GameMode::AllPick),
         None,
         None,
         None,
@@ -99,7 +85,7 @@ fn api_url_options() {
     assert_eq!(76561197960361544, boxed_account_id.unwrap().parse::<u64>().unwrap());
 
     let boxed_game_mode = params.get("game_mode");
-    assert_eq!(GAME_MODE.all_pick, boxed_game_mode.unwrap().parse::<u8>().unwrap());
+    assert_eq!("1", boxed_game_mode.unwrap());
 
     let boxed_skill = params.get("skill");
     assert_eq!(None, boxed_skill);
@@ -113,8 +99,7 @@ fn api_url_options() {
     let boxed_matches_requested = params.get("matches_requested");
     assert_eq!(None, boxed_matches_requested);
 
-    let boxed_key = params.get("key");
-    assert_eq!(get_steam_web_api_key(), boxed_key.unwrap().to_string());
+    assert!(!params.contains_key("key"));
 
 
 }
@@ -130,6 +115,7 @@ fn parse(){
     assert!(boxed_parse.is_ok());
 
     let response_match_history = boxed_parse.unwrap();
+    assert_serde_roundtrip(&response_match_history);
     assert_eq!(1, response_match_history.status);
     assert_eq!(1, response_match_history.num_results);
     assert_eq!(500, response_match_history.total_results);
@@ -142,7 +128,10 @@ fn parse(){
     assert_eq!(5066503471, match_1.match_id);
     assert_eq!(4250346899, match_1.match_seq_num);
     assert_eq!(1570905295, match_1.start_time);
-    assert_eq!(0, match_1.lobby_type);
+    assert_eq!(LobbyType::PublicMatchmaking, match_1.lobby_type);
+    assert_eq!(Team::Radiant, match_1.players.get(4).unwrap().get_team());
+    assert_eq!(Team::Dire, match_1.players.get(5).unwrap().get_team());
+    assert_eq!(1, match_1.players.get(6).unwrap().get_player_slot().position);
     assert_eq!(0, match_1.radiant_team_id);
     assert_eq!(0, match_1.dire_team_id);
 
@@ -218,3 +207,120 @@ fn parse(){
     assert_eq!(4, player_10.team_slot);
     assert_eq!(59, player_10.hero_id);
 }
+
+#[test]
+fn parse_not_allowed_by_user_preferences() {
+    let response = r#"{"result":{"status":15,"statusDetail":"Cannot get match history for a user that hasn't allowed it"}}"#.to_string();
+
+    let boxed_parse = parse_response(response);
+    assert!(boxed_parse.is_err());
+
+    let error = boxed_parse.err().unwrap();
+    match error {
+        SteamApiError::SteamFailure { status, .. } => assert_eq!(Some(15), status),
+        _ => panic!("expected steam failure, got {:?}", error),
+    }
+}
+
+const PAGES_URL: &str = "https://api.steampowered.com/IDOTA2Match_570/GetMatchHistory/v1?key=KEY&account_id=76561197960361544&matches_requested=2";
+
+fn get_page_body(match_ids: Vec<i64>, results_remaining: i64) -> String {
+    let matches = match_ids.iter()
+        .map(|match_id| [r#"{"match_id":"#, match_id.to_string().as_str(), r#","match_seq_num":1,"start_time":0,"lobby_type":0,"radiant_team_id":0,"dire_team_id":0,"players":[]}"#].join(""))
+        .collect::<Vec<String>>()
+        .join(",");
+    [
+        r#"{"result":{"status":1,"num_results":"#, match_ids.len().to_string().as_str(),
+        r#","total_results":500,"results_remaining":"#, results_remaining.to_string().as_str(),
+        r#","matches":["#, matches.as_str(), "]}}",
+    ].join("")
+}
+
+fn get_pages_client(transport: Arc<FakeTransport>) -> SteamClient {
+    SteamClient::builder()
+        .api_key("KEY")
+        .cache_enabled(false)
+        .rate_limiter_enabled(false)
+        .retry_policy(RetryPolicy::none())
+        .transport(transport)
+        .build()
+}
+
+fn get_pages_filter() -> MatchHistoryFilter {
+    MatchHistoryFilter::new().with_account_id(SteamId::try_from(76561197960361544).unwrap())
+}
+
+#[test]
+fn page_api_url_filter() {
+    let filter = get_pages_filter()
+        .with_hero_id(1)
+        .with_skill(Skill::High)
+        .with_tournament_games_only(true);
+    let api_url = get_page_api_url(&filter, Some(6000000000), Some(100));
+
+    let params = parse_url(api_url.as_str()).unwrap().query.unwrap();
+    assert_eq!("76561197960361544", params.get("account_id").unwrap());
+    assert_eq!("1", params.get("hero_id").unwrap());
+    assert_eq!("2", params.get("skill").unwrap());
+    assert_eq!("true", params.get("tournament_games_only").unwrap());
+    assert_eq!("6000000000", params.get("start_at_match_id").unwrap());
+    assert_eq!("100", params.get("matches_requested").unwrap());
+    assert_eq!(None, params.get("game_mode"));
+}
+
+#[test]
+fn pages_walk_until_no_results_remaining() {
+    let transport = Arc::new(FakeTransport::new());
+    transport.respond_with(PAGES_URL, 200, get_page_body(vec![100, 90], 3).as_str());
+    transport.respond_with([PAGES_URL, "&start_at_match_id=89"].join("").as_str(), 200, get_page_body(vec![80, 70], 1).as_str());
+    transport.respond_with([PAGES_URL, "&start_at_match_id=69"].join("").as_str(), 200, get_page_body(vec![60], 0).as_str());
+    let client = get_pages_client(transport.clone());
+
+    let match_ids : Vec<i64> = client.get_dota2_match_history_pages(get_pages_filter())
+        .with_matches_requested(2)
+        .matches()
+        .map(|boxed_match| boxed_match.unwrap().match_id)
+        .collect();
+
+    assert_eq!(vec![100, 90, 80, 70, 60], match_ids);
+    assert_eq!(3, transport.requests().len());
+}
+
+#[test]
+fn pages_stop_on_empty_page() {
+    let transport = Arc::new(FakeTransport::new());
+    transport.respond_with(PAGES_URL, 200, get_page_body(vec![100, 90], 3).as_str());
+    transport.respond_with([PAGES_URL, "&start_at_match_id=89"].join("").as_str(), 200, get_page_body(vec![], 3).as_str());
+    let client = get_pages_client(transport.clone());
+
+    let mut pages = client.get_dota2_match_history_pages(get_pages_filter()).with_matches_requested(2);
+    assert_eq!(2, pages.next().unwrap().unwrap().matches.len());
+    assert_eq!(0, pages.next().unwrap().unwrap().matches.len());
+    assert!(pages.next().is_none());
+    assert!(pages.get_cursor().finished);
+    assert_eq!(2, transport.requests().len());
+}
+
+#[test]
+fn pages_resume_from_cursor() {
+    let transport = Arc::new(FakeTransport::new());
+    transport.respond_with([PAGES_URL, "&start_at_match_id=89"].join("").as_str(), 200, get_page_body(vec![80, 70], 1).as_str());
+    transport.respond_with([PAGES_URL, "&start_at_match_id=69"].join("").as_str(), 500, "");
+    let client = get_pages_client(transport.clone());
+
+    let cursor = MatchHistoryCursor { start_at_match_id: Some(89), finished: false };
+    let mut pages = MatchHistoryPages::resume(client.clone(), get_pages_filter(), cursor).with_matches_requested(2);
+    assert_eq!(80, pages.next().unwrap().unwrap().matches[0].match_id);
+    assert!(pages.next().unwrap().is_err());
+    assert!(pages.next().is_none());
+
+    let cursor = pages.get_cursor();
+    assert_eq!(MatchHistoryCursor { start_at_match_id: Some(69), finished: false }, cursor);
+    assert_serde_roundtrip(&cursor);
+
+    transport.respond_with([PAGES_URL, "&start_at_match_id=69"].join("").as_str(), 200, get_page_body(vec![60], 0).as_str());
+    let mut pages = MatchHistoryPages::resume(client, get_pages_filter(), cursor).with_matches_requested(2);
+    assert_eq!(60, pages.next().unwrap().unwrap().matches[0].match_id);
+    assert!(pages.next().is_none());
+    assert!(pages.get_cursor().finished);
+}
@@ -0,0 +1,178 @@
+// curl https://api.steampowered.com/IDOTA2Match_570/GetMatchHistoryBySequenceNum/v1\?start_at_match_seq_num\=1300000000\&matches_requested\=10\&key\=1F2709FC907F0DEE1D1EB4787E06B695
+
+use std::collections::HashMap;
+use serde_json::Value;
+use crate::idota2match_570;
+use crate::client::{get_default_client, SteamClient};
+use crate::error::SteamApiError;
+use crate::idota2match_570::get_match_details::{self, MatchResult};
+use crate::util::{build_cache_key, build_steam_api_url};
+
+#[cfg(test)]
+mod tests;
+
+/// Steam's `result.status` for a successful response.
+const STATUS_OK: i64 = 1;
+
+pub fn get_method_name() -> String {
+    "GetMatchHistoryBySequenceNum".to_string()
+}
+
+pub fn get_version() -> String {
+    "v1".to_string()
+}
+
+pub fn get_endpoint() -> String {
+    [idota2match_570::get_interface(), "/".to_string(), get_method_name()].join("")
+}
+
+/// Retrieves up to `matches_requested` (Steam's default and maximum is 100) public matches in the
+/// order they were recorded, starting with `start_at_match_seq_num`. Unlike `get_match_history`
+/// it is neither filtered nor capped, see `match_crawler::MatchCrawler` to follow it.
+pub fn get(start_at_match_seq_num: Option<u64>, matches_requested: Option<u32>) -> Result<Vec<MatchResult>, SteamApiError> {
+    get_with_client(get_default_client().as_ref(), start_at_match_seq_num, matches_requested)
+}
+
+/// Async version of `get`, available with the `async` feature.
+#[cfg(feature = "async")]
+pub async fn get_async(start_at_match_seq_num: Option<u64>, matches_requested: Option<u32>) -> Result<Vec<MatchResult>, SteamApiError> {
+    get_async_with_client(get_default_client().as_ref(), start_at_match_seq_num, matches_requested).await
+}
+
+pub fn get_with_client(client: &SteamClient, start_at_match_seq_num: Option<u64>, matches_requested: Option<u32>) -> Result<Vec<MatchResult>, SteamApiError> {
+    let cache_key = get_cache_key(start_at_match_seq_num, matches_requested);
+    let api_url = get_api_url(start_at_match_seq_num, matches_requested);
+    client.fetch_and_cache(get_endpoint().as_str(), cache_key.as_str(), api_url, parse_response)
+}
+
+/// Async version of `get_with_client`, available with the `async` feature.
+#[cfg(feature = "async")]
+pub async fn get_async_with_client(client: &SteamClient, start_at_match_seq_num: Option<u64>, matches_requested: Option<u32>) -> Result<Vec<MatchResult>, SteamApiError> {
+    let cache_key = get_cache_key(start_at_match_seq_num, matches_requested);
+    let api_url = get_api_url(start_at_match_seq_num, matches_requested);
+    client.fetch_and_cache_async(get_endpoint().as_str(), cache_key.as_str(), api_url, parse_response).await
+}
+
+/// Like `get`, but serves the cached response while it is fresh as per the client's
+/// `cache::CachePolicy`, otherwise calls Steam and caches the response.
+pub fn get_cached(start_at_match_seq_num: Option<u64>, matches_requested: Option<u32>) -> Result<Vec<MatchResult>, SteamApiError> {
+    get_cached_with_client(get_default_client().as_ref(), start_at_match_seq_num, matches_requested)
+}
+
+/// Async version of `get_cached`, available with the `async` feature.
+#[cfg(feature = "async")]
+pub async fn get_cached_async(start_at_match_seq_num: Option<u64>, matches_requested: Option<u32>) -> Result<Vec<MatchResult>, SteamApiError> {
+    get_cached_async_with_client(get_default_client().as_ref(), start_at_match_seq_num, matches_requested).await
+}
+
+pub fn get_cached_with_client(client: &SteamClient, start_at_match_seq_num: Option<u64>, matches_requested: Option<u32>) -> Result<Vec<MatchResult>, SteamApiError> {
+    client.get_cached_or_fetch(
+        get_endpoint().as_str(),
+        get_cache_key(start_at_match_seq_num, matches_requested).as_str(),
+        parse_response,
+        || get_with_client(client, start_at_match_seq_num, matches_requested),
+    )
+}
+
+/// Async version of `get_cached_with_client`, available with the `async` feature.
+#[cfg(feature = "async")]
+pub async fn get_cached_async_with_client(client: &SteamClient, start_at_match_seq_num: Option<u64>, matches_requested: Option<u32>) -> Result<Vec<MatchResult>, SteamApiError> {
+    client.get_cached_or_fetch_async(
+        get_endpoint().as_str(),
+        get_cache_key(start_at_match_seq_num, matches_requested).as_str(),
+        parse_response,
+        get_async_with_client(client, start_at_match_seq_num, matches_requested),
+    ).await
+}
+
+/// Calls Steam without caching the response, returning the raw body.
+pub fn make_api_call_with_client(client: &SteamClient, start_at_match_seq_num: Option<u64>, matches_requested: Option<u32>) -> Result<String, SteamApiError> {
+    let url = get_api_url(start_at_match_seq_num, matches_requested);
+    client.make_api_call(get_endpoint().as_str(), url)
+}
+
+/// Async version of `make_api_call_with_client`, available with the `async` feature.
+#[cfg(feature = "async")]
+pub async fn make_api_call_async_with_client(client: &SteamClient, start_at_match_seq_num: Option<u64>, matches_requested: Option<u32>) -> Result<String, SteamApiError> {
+    let url = get_api_url(start_at_match_seq_num, matches_requested);
+    client.make_api_call_async(get_endpoint().as_str(), url).await
+}
+
+pub fn get_api_url(start_at_match_seq_num: Option<u64>, matches_requested: Option<u32>) -> String {
+    let interface = idota2match_570::get_interface();
+    let method = get_method_name();
+    let version = get_version();
+
+    let mut params_map = HashMap::new();
+    if let Some(start_at_match_seq_num) = start_at_match_seq_num {
+        params_map.insert("start_at_match_seq_num".to_string(), start_at_match_seq_num.to_string());
+    }
+    if let Some(matches_requested) = matches_requested {
+        params_map.insert("matches_requested".to_string(), matches_requested.to_string());
+    }
+
+    build_steam_api_url(interface.as_str(), method.as_str(), version.as_str(), params_map)
+}
+
+/// Returns path of the cached resource relative to the cache directory.
+pub fn get_cache_key(start_at_match_seq_num: Option<u64>, matches_requested: Option<u32>) -> String {
+    let mut params = vec![];
+    if let Some(start_at_match_seq_num) = start_at_match_seq_num {
+        params.push(("start_at_match_seq_num", start_at_match_seq_num.to_string()));
+    }
+    if let Some(matches_requested) = matches_requested {
+        params.push(("matches_requested", matches_requested.to_string()));
+    }
+
+    build_cache_key(idota2match_570::get_interface().as_str(), get_method_name().as_str(), get_version().as_str(), params)
+}
+
+pub fn get_resource_filepath(start_at_match_seq_num: Option<u64>, matches_requested: Option<u32>) -> String {
+    get_default_client().get_resource_filepath(get_cache_key(start_at_match_seq_num, matches_requested).as_str())
+}
+
+pub fn parse_response(response: String) -> Result<Vec<MatchResult>, SteamApiError> {
+    let boxed_matches = parse_match_values(response);
+    if boxed_matches.is_err() {
+        return Err(boxed_matches.err().unwrap());
+    }
+
+    let mut matches = vec![];
+    for match_value in boxed_matches.unwrap() {
+        let boxed_match = get_match_details::parse_match(&match_value, 0);
+        if boxed_match.is_err() {
+            return Err(boxed_match.err().unwrap());
+        }
+        matches.push(boxed_match.unwrap());
+    }
+
+    Ok(matches)
+}
+
+/// Returns the raw match objects of the response, each one shaped like the `result` of
+/// `GetMatchDetails`.
+pub fn parse_match_values(response: String) -> Result<Vec<Value>, SteamApiError> {
+    let boxed_initial_parse = serde_json::from_str(&response);
+    if boxed_initial_parse.is_err() {
+        return Err(SteamApiError::malformed_response(get_endpoint().as_str(), boxed_initial_parse.err().unwrap().to_string().as_str()));
+    }
+    let mut json: Value = boxed_initial_parse.unwrap();
+
+    let mut result = json["result"].take();
+    let boxed_status = result.get("status").and_then(Value::as_i64);
+    if boxed_status.is_none() {
+        return Err(SteamApiError::malformed_response(get_endpoint().as_str(), "response does not contain a status"));
+    }
+    let status = boxed_status.unwrap();
+    if status != STATUS_OK {
+        let status_detail = result.get("statusDetail").and_then(Value::as_str).unwrap_or("steam returned failed response");
+        return Err(SteamApiError::steam_failure(get_endpoint().as_str(), Some(status), status_detail));
+    }
+
+    let boxed_matches = result["matches"].take();
+    if let Value::Array(matches) = boxed_matches {
+        return Ok(matches);
+    }
+
+    Err(SteamApiError::malformed_response(get_endpoint().as_str(), "response does not contain any matches"))
+}
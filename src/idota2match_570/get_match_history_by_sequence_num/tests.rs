@@ -0,0 +1,66 @@
+use url_build_parse::parse_url;
+use crate::{get_host, get_scheme};
+use crate::error::SteamApiError;
+use crate::idota2match_570::get_match_history_by_sequence_num::{get_api_url, get_resource_filepath, parse_response};
+use crate::client::get_default_client;
+use crate::tests::{assert_serde_roundtrip, get_match_sequence_response};
+
+#[test]
+fn api_url() {
+    let api_url = get_api_url(Some(1300000000), Some(10));
+
+    let components = parse_url(api_url.as_str()).unwrap();
+
+    assert_eq!(get_scheme(), components.scheme);
+    assert_eq!(get_host(), components.authority.unwrap().host);
+    assert_eq!("/IDOTA2Match_570/GetMatchHistoryBySequenceNum/v1", components.path);
+
+    let params = components.query.unwrap();
+    assert_eq!("1300000000", params.get("start_at_match_seq_num").unwrap());
+    assert_eq!("10", params.get("matches_requested").unwrap());
+    assert!(!params.contains_key("key"));
+}
+
+#[test]
+fn resource_filepath() {
+    let filepath = get_resource_filepath(Some(1300000000), Some(10));
+
+    assert_eq!([get_default_client().cache_dir(), "/IDOTA2Match_570-GetMatchHistoryBySequenceNum-v1-start_at_match_seq_num=1300000000-matches_requested=10.json"].join(""), filepath);
+}
+
+#[test]
+fn parse() {
+    let response = get_match_sequence_response(vec![1300000000, 1300000001]);
+
+    let matches = parse_response(response).unwrap();
+    assert_serde_roundtrip(&matches);
+    assert_eq!(2, matches.len());
+    assert_eq!(1300000010, matches[0].match_id);
+    assert_eq!(1300000001, matches[1].match_seq_num);
+    assert_eq!(10, matches[1].players.len());
+}
+
+#[test]
+fn parse_empty_page() {
+    let matches = parse_response(r#"{"result":{"status":1,"matches":[]}}"#.to_string()).unwrap();
+    assert!(matches.is_empty());
+}
+
+#[test]
+fn parse_failed_status() {
+    let error = parse_response(r#"{"result":{"status":2,"statusDetail":"Error retrieving match data."}}"#.to_string()).err().unwrap();
+
+    match error {
+        SteamApiError::SteamFailure { status, message, .. } => {
+            assert_eq!(Some(2), status);
+            assert_eq!("Error retrieving match data.", message);
+        },
+        _ => panic!("unexpected error {}", error),
+    }
+}
+
+#[test]
+fn parse_missing_matches() {
+    assert!(parse_response("{}".to_string()).is_err());
+    assert!(parse_response(r#"{"result":{"status":1}}"#.to_string()).is_err());
+}
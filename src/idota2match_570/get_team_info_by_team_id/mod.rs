@@ -1,12 +1,16 @@
 use std::collections::HashMap;
 use serde_json::Value;
-use crate::{idota2match_570, make_api_call};
-use crate::util::{build_steam_api_url, get_steam_web_api_key, json_str, json_u64};
+use serde::Deserialize;
+use serde::Serialize;
+use crate::idota2match_570;
+use crate::client::{get_default_client, SteamClient};
+use crate::error::SteamApiError;
+use crate::util::{build_cache_key, build_steam_api_url, json_str, json_u64};
 
 #[cfg(test)]
 mod tests;
 
-#[derive(PartialEq, Clone, Debug)]
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
 pub struct TeamInfo {
     pub team_id: u64,
     pub name: String,
@@ -26,14 +30,64 @@ pub fn get_version() -> String {
     "v1".to_string()
 }
 
-pub fn get(start_at_team_id: Option<u64>, teams_requested: Option<u32>) -> Result<Vec<TeamInfo>, String> {
+pub fn get_endpoint() -> String {
+    [idota2match_570::get_interface(), "/".to_string(), get_method_name()].join("")
+}
+
+pub fn get(start_at_team_id: Option<u64>, teams_requested: Option<u32>) -> Result<Vec<TeamInfo>, SteamApiError> {
+    get_with_client(get_default_client().as_ref(), start_at_team_id, teams_requested)
+}
+
+/// Async version of `get`, available with the `async` feature.
+#[cfg(feature = "async")]
+pub async fn get_async(start_at_team_id: Option<u64>, teams_requested: Option<u32>) -> Result<Vec<TeamInfo>, SteamApiError> {
+    get_async_with_client(get_default_client().as_ref(), start_at_team_id, teams_requested).await
+}
+
+pub fn get_with_client(client: &SteamClient, start_at_team_id: Option<u64>, teams_requested: Option<u32>) -> Result<Vec<TeamInfo>, SteamApiError> {
+    let cache_key = get_cache_key(start_at_team_id, teams_requested);
     let api_url = get_api_url(start_at_team_id, teams_requested);
-    let boxed_response = make_api_call(api_url);
-    if boxed_response.is_err() {
-        return Err(boxed_response.err().unwrap());
-    }
+    client.fetch_and_cache(get_endpoint().as_str(), cache_key.as_str(), api_url, parse_response)
+}
+
+/// Async version of `get_with_client`, available with the `async` feature.
+#[cfg(feature = "async")]
+pub async fn get_async_with_client(client: &SteamClient, start_at_team_id: Option<u64>, teams_requested: Option<u32>) -> Result<Vec<TeamInfo>, SteamApiError> {
+    let cache_key = get_cache_key(start_at_team_id, teams_requested);
+    let api_url = get_api_url(start_at_team_id, teams_requested);
+    client.fetch_and_cache_async(get_endpoint().as_str(), cache_key.as_str(), api_url, parse_response).await
+}
+
+/// Like `get`, but serves the cached response while it is fresh as per the client's
+/// `cache::CachePolicy`, otherwise calls Steam and caches the response.
+pub fn get_cached(start_at_team_id: Option<u64>, teams_requested: Option<u32>) -> Result<Vec<TeamInfo>, SteamApiError> {
+    get_cached_with_client(get_default_client().as_ref(), start_at_team_id, teams_requested)
+}
+
+/// Async version of `get_cached`, available with the `async` feature.
+#[cfg(feature = "async")]
+pub async fn get_cached_async(start_at_team_id: Option<u64>, teams_requested: Option<u32>) -> Result<Vec<TeamInfo>, SteamApiError> {
+    get_cached_async_with_client(get_default_client().as_ref(), start_at_team_id, teams_requested).await
+}
 
-    parse_response(boxed_response.unwrap())
+pub fn get_cached_with_client(client: &SteamClient, start_at_team_id: Option<u64>, teams_requested: Option<u32>) -> Result<Vec<TeamInfo>, SteamApiError> {
+    client.get_cached_or_fetch(
+        get_endpoint().as_str(),
+        get_cache_key(start_at_team_id, teams_requested).as_str(),
+        parse_response,
+        || get_with_client(client, start_at_team_id, teams_requested),
+    )
+}
+
+/// Async version of `get_cached_with_client`, available with the `async` feature.
+#[cfg(feature = "async")]
+pub async fn get_cached_async_with_client(client: &SteamClient, start_at_team_id: Option<u64>, teams_requested: Option<u32>) -> Result<Vec<TeamInfo>, SteamApiError> {
+    client.get_cached_or_fetch_async(
+        get_endpoint().as_str(),
+        get_cache_key(start_at_team_id, teams_requested).as_str(),
+        parse_response,
+        get_async_with_client(client, start_at_team_id, teams_requested),
+    ).await
 }
 
 pub fn get_api_url(start_at_team_id: Option<u64>, teams_requested: Option<u32>) -> String {
@@ -48,21 +102,37 @@ pub fn get_api_url(start_at_team_id: Option<u64>, teams_requested: Option<u32>)
     if teams_requested.is_some() {
         params_map.insert("teams_requested".to_string(), teams_requested.unwrap().to_string());
     }
-    params_map.insert("key".to_string(), get_steam_web_api_key());
 
     build_steam_api_url(interface.as_str(), method.as_str(), version.as_str(), params_map)
 }
 
-pub fn parse_response(response: String) -> Result<Vec<TeamInfo>, String> {
+/// Returns path of the cached resource relative to the cache directory.
+pub fn get_cache_key(start_at_team_id: Option<u64>, teams_requested: Option<u32>) -> String {
+    let mut params = vec![];
+    if let Some(start_at_team_id) = start_at_team_id {
+        params.push(("start_at_team_id", start_at_team_id.to_string()));
+    }
+    if let Some(teams_requested) = teams_requested {
+        params.push(("teams_requested", teams_requested.to_string()));
+    }
+
+    build_cache_key(idota2match_570::get_interface().as_str(), get_method_name().as_str(), get_version().as_str(), params)
+}
+
+pub fn get_resource_filepath(start_at_team_id: Option<u64>, teams_requested: Option<u32>) -> String {
+    get_default_client().get_resource_filepath(get_cache_key(start_at_team_id, teams_requested).as_str())
+}
+
+pub fn parse_response(response: String) -> Result<Vec<TeamInfo>, SteamApiError> {
     let boxed_initial_parse = serde_json::from_str(&response);
     if boxed_initial_parse.is_err() {
-        return Err(boxed_initial_parse.err().unwrap().to_string());
+        return Err(SteamApiError::malformed_response(get_endpoint().as_str(), boxed_initial_parse.err().unwrap().to_string().as_str()));
     }
     let json: Value = boxed_initial_parse.unwrap();
 
     let boxed_teams = json.get("result").and_then(|r| r.get("teams")).and_then(Value::as_array);
     if boxed_teams.is_none() {
-        return Err("response does not contain any teams".to_string());
+        return Err(SteamApiError::malformed_response(get_endpoint().as_str(), "response does not contain any teams"));
     }
 
     let mut teams = vec![];
@@ -1,7 +1,8 @@
 use url_build_parse::parse_url;
 use crate::{get_host, get_scheme};
-use crate::idota2match_570::get_team_info_by_team_id::{get_api_url, parse_response};
-use crate::util::get_steam_web_api_key;
+use crate::idota2match_570::get_team_info_by_team_id::{get_api_url, get_resource_filepath, parse_response};
+use crate::client::get_default_client;
+use crate::tests::assert_serde_roundtrip;
 
 #[test]
 fn api_url() {
@@ -20,8 +21,14 @@ fn api_url() {
     let boxed_teams_requested = params.get("teams_requested");
     assert_eq!("1", boxed_teams_requested.unwrap().to_string());
 
-    let boxed_key = params.get("key");
-    assert_eq!(get_steam_web_api_key(), boxed_key.unwrap().to_string());
+    assert!(!params.contains_key("key"));
+}
+
+#[test]
+fn resource_filepath() {
+    let filepath = get_resource_filepath(Some(36), Some(10));
+
+    assert_eq!([get_default_client().cache_dir(), "/IDOTA2Match_570-GetTeamInfoByTeamID-v1-start_at_team_id=36-teams_requested=10.json"].join(""), filepath);
 }
 
 #[test]
@@ -48,6 +55,7 @@ fn parse() {
     assert!(boxed_parse.is_ok());
 
     let teams = boxed_parse.unwrap();
+    assert_serde_roundtrip(&teams);
     assert_eq!(1, teams.len());
 
     let team = teams.get(0).unwrap();
@@ -0,0 +1,314 @@
+use std::collections::VecDeque;
+use std::fs;
+use std::io::ErrorKind;
+use std::thread;
+use std::time::Duration;
+use serde::Deserialize;
+use serde::Serialize;
+use crate::cache::CacheValidators;
+use crate::client::SteamClient;
+use crate::error::SteamApiError;
+use crate::idota2match_570::get_match_details::{self, MatchResult};
+use crate::idota2match_570::get_match_history_by_sequence_num;
+use crate::util::{json_opt_u64, json_u64};
+
+#[cfg(test)]
+mod tests;
+
+/// Where `MatchCrawler` continues from.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Copy, Debug)]
+pub struct CrawlerCheckpoint {
+    /// Sequence number of the next match to request.
+    pub start_at_match_seq_num: u64,
+}
+
+impl CrawlerCheckpoint {
+    pub fn new(start_at_match_seq_num: u64) -> CrawlerCheckpoint {
+        CrawlerCheckpoint { start_at_match_seq_num }
+    }
+
+    /// Reads a checkpoint saved with `save`, `None` if there is no file at `filepath` yet.
+    pub fn load(filepath: &str) -> Result<Option<CrawlerCheckpoint>, SteamApiError> {
+        let boxed_content = fs::read_to_string(filepath);
+        if let Err(error) = boxed_content.as_ref() {
+            if error.kind() == ErrorKind::NotFound {
+                return Ok(None);
+            }
+            return Err(SteamApiError::storage(get_endpoint().as_str(), filepath, error.to_string().as_str()));
+        }
+
+        let boxed_checkpoint = serde_json::from_str(boxed_content.unwrap().as_str());
+        if boxed_checkpoint.is_err() {
+            return Err(SteamApiError::storage(get_endpoint().as_str(), filepath, boxed_checkpoint.err().unwrap().to_string().as_str()));
+        }
+
+        Ok(Some(boxed_checkpoint.unwrap()))
+    }
+
+    /// Writes the checkpoint to `filepath`, replacing the previous one in a single step so an
+    /// interrupted crawler never leaves a partial file behind.
+    pub fn save(&self, filepath: &str) -> Result<(), SteamApiError> {
+        let content = serde_json::to_string(self).unwrap();
+        let temp_filepath = [filepath, ".tmp"].join("");
+
+        let boxed_write = fs::write(temp_filepath.as_str(), content);
+        if boxed_write.is_err() {
+            return Err(SteamApiError::storage(get_endpoint().as_str(), temp_filepath.as_str(), boxed_write.err().unwrap().to_string().as_str()));
+        }
+
+        let boxed_rename = fs::rename(temp_filepath.as_str(), filepath);
+        if boxed_rename.is_err() {
+            return Err(SteamApiError::storage(get_endpoint().as_str(), filepath, boxed_rename.err().unwrap().to_string().as_str()));
+        }
+
+        Ok(())
+    }
+}
+
+/// Follows `GetMatchHistoryBySequenceNum`, returning every public match in the order Steam
+/// recorded it.
+///
+/// Each crawled match is written to the `GetMatchDetails` cache, so `get_cached_dota2_match_details`
+/// serves it without another request. The checkpoint advances past a page, and is saved if set with
+/// `with_checkpoint_file`, once the page is handed over: `crawl_page` returns the page whole, the
+/// iterator commits it when it returns the page's last match. A crawler resumed from the checkpoint
+/// file thus never skips matches the previous one hadn't returned. Once the crawler catches up with
+/// Steam, pages come back empty and it waits before the next request, doubling the wait up to the
+/// maximum backoff until matches show up again.
+///
+/// As an iterator it never ends on its own, except after an error, which is returned once with
+/// the checkpoint still pointing at the page that failed.
+#[derive(Clone, Debug)]
+pub struct MatchCrawler {
+    client: SteamClient,
+    checkpoint: CrawlerCheckpoint,
+    checkpoint_filepath: Option<String>,
+    matches_requested: Option<u32>,
+    min_backoff: Duration,
+    max_backoff: Duration,
+    backoff: Duration,
+    crawled: VecDeque<MatchResult>,
+    pending_checkpoint: Option<CrawlerCheckpoint>,
+    failed: bool,
+}
+
+impl MatchCrawler {
+    pub fn new(client: SteamClient, checkpoint: CrawlerCheckpoint) -> MatchCrawler {
+        MatchCrawler {
+            client,
+            checkpoint,
+            checkpoint_filepath: None,
+            matches_requested: None,
+            min_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(60),
+            backoff: Duration::ZERO,
+            crawled: VecDeque::new(),
+            pending_checkpoint: None,
+            failed: false,
+        }
+    }
+
+    /// Saves the checkpoint to `filepath` whenever it advances, see `CrawlerCheckpoint::load` to resume.
+    pub fn with_checkpoint_file(mut self, filepath: &str) -> MatchCrawler {
+        self.checkpoint_filepath = Some(filepath.to_string());
+        self
+    }
+
+    /// Number of matches per page, Steam defaults to (and caps at) 100.
+    pub fn with_matches_requested(mut self, matches_requested: u32) -> MatchCrawler {
+        self.matches_requested = Some(matches_requested);
+        self
+    }
+
+    /// Waits `min_backoff` after the first empty page, doubling up to `max_backoff` while pages
+    /// stay empty. Defaults to 1 and 60 seconds.
+    pub fn with_backoff(mut self, min_backoff: Duration, max_backoff: Duration) -> MatchCrawler {
+        self.min_backoff = min_backoff;
+        self.max_backoff = max_backoff;
+        self
+    }
+
+    pub fn get_checkpoint(&self) -> CrawlerCheckpoint {
+        self.checkpoint
+    }
+
+    /// Time waited before the next request, zero unless the last page was empty.
+    pub fn get_backoff(&self) -> Duration {
+        self.backoff
+    }
+
+    /// Waits out the backoff, then requests the page at the checkpoint and advances the checkpoint
+    /// past it.
+    pub fn crawl_page(&mut self) -> Result<Vec<MatchResult>, SteamApiError> {
+        let boxed_page = self.fetch_page();
+        if boxed_page.is_err() {
+            return Err(boxed_page.err().unwrap());
+        }
+
+        let boxed_commit = self.commit_checkpoint();
+        if boxed_commit.is_err() {
+            return Err(boxed_commit.err().unwrap());
+        }
+
+        boxed_page
+    }
+
+    /// Async version of `crawl_page`, available with the `async` feature.
+    #[cfg(feature = "async")]
+    pub async fn crawl_page_async(&mut self) -> Result<Vec<MatchResult>, SteamApiError> {
+        let boxed_page = self.fetch_page_async().await;
+        if boxed_page.is_err() {
+            return Err(boxed_page.err().unwrap());
+        }
+
+        let boxed_commit = self.commit_checkpoint();
+        if boxed_commit.is_err() {
+            return Err(boxed_commit.err().unwrap());
+        }
+
+        boxed_page
+    }
+
+    /// Waits out the backoff and requests the page at the checkpoint, leaving the checkpoint to
+    /// `commit_checkpoint`.
+    fn fetch_page(&mut self) -> Result<Vec<MatchResult>, SteamApiError> {
+        if !self.backoff.is_zero() {
+            thread::sleep(self.backoff);
+        }
+
+        let boxed_response = get_match_history_by_sequence_num::make_api_call_with_client(
+            &self.client,
+            Some(self.checkpoint.start_at_match_seq_num),
+            self.matches_requested,
+        );
+        if boxed_response.is_err() {
+            return Err(boxed_response.err().unwrap());
+        }
+
+        self.read_page(boxed_response.unwrap())
+    }
+
+    #[cfg(feature = "async")]
+    async fn fetch_page_async(&mut self) -> Result<Vec<MatchResult>, SteamApiError> {
+        if !self.backoff.is_zero() {
+            tokio::time::sleep(self.backoff).await;
+        }
+
+        let boxed_response = get_match_history_by_sequence_num::make_api_call_async_with_client(
+            &self.client,
+            Some(self.checkpoint.start_at_match_seq_num),
+            self.matches_requested,
+        ).await;
+        if boxed_response.is_err() {
+            return Err(boxed_response.err().unwrap());
+        }
+
+        self.read_page(boxed_response.unwrap())
+    }
+
+    /// Caches the matches of the page and remembers the checkpoint past them until it's committed.
+    /// Records without a `match_id` have no key to be cached under and are skipped, though the
+    /// checkpoint still moves past them.
+    fn read_page(&mut self, response: String) -> Result<Vec<MatchResult>, SteamApiError> {
+        let boxed_values = get_match_history_by_sequence_num::parse_match_values(response);
+        if boxed_values.is_err() {
+            return Err(boxed_values.err().unwrap());
+        }
+        let match_values = boxed_values.unwrap();
+
+        if match_values.is_empty() {
+            self.backoff = (self.backoff * 2).max(self.min_backoff).min(self.max_backoff);
+            return Ok(vec![]);
+        }
+        self.backoff = Duration::ZERO;
+
+        let mut matches = vec![];
+        let mut last_match_seq_num = 0;
+        for match_value in match_values {
+            last_match_seq_num = last_match_seq_num.max(json_u64(&match_value, "match_seq_num"));
+
+            let boxed_match_id = json_opt_u64(&match_value, "match_id");
+            if boxed_match_id.is_none() {
+                continue;
+            }
+
+            let boxed_match = get_match_details::parse_match(&match_value, boxed_match_id.unwrap());
+            if boxed_match.is_err() {
+                return Err(boxed_match.err().unwrap());
+            }
+            let match_result = boxed_match.unwrap();
+
+            let body = ["{\"result\":", match_value.to_string().as_str(), "}"].join("");
+            self.client.write_cached(get_match_details::get_cache_key(match_result.match_id).as_str(), body.as_str(), &CacheValidators::default());
+            matches.push(match_result);
+        }
+
+        let start_at_match_seq_num = self.checkpoint.start_at_match_seq_num.max(last_match_seq_num + 1);
+        self.pending_checkpoint = Some(CrawlerCheckpoint::new(start_at_match_seq_num));
+
+        Ok(matches)
+    }
+
+    /// Advances the checkpoint past the last fetched page and saves it. On error the checkpoint
+    /// keeps pointing at that page.
+    fn commit_checkpoint(&mut self) -> Result<(), SteamApiError> {
+        let pending_checkpoint = self.pending_checkpoint.take();
+        if pending_checkpoint.is_none() {
+            return Ok(());
+        }
+        let checkpoint = pending_checkpoint.unwrap();
+
+        if let Some(checkpoint_filepath) = self.checkpoint_filepath.as_ref() {
+            let boxed_save = checkpoint.save(checkpoint_filepath.as_str());
+            if boxed_save.is_err() {
+                return Err(boxed_save.err().unwrap());
+            }
+        }
+
+        self.checkpoint = checkpoint;
+        Ok(())
+    }
+}
+
+impl Iterator for MatchCrawler {
+    type Item = Result<MatchResult, SteamApiError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.crawled.is_empty() {
+            if self.failed {
+                return None;
+            }
+
+            let boxed_page = self.fetch_page();
+            if boxed_page.is_err() {
+                self.failed = true;
+                return Some(Err(boxed_page.err().unwrap()));
+            }
+            self.crawled.extend(boxed_page.unwrap());
+
+            // a page of skipped records still moves the checkpoint
+            if self.crawled.is_empty() {
+                let boxed_commit = self.commit_checkpoint();
+                if boxed_commit.is_err() {
+                    self.failed = true;
+                    return Some(Err(boxed_commit.err().unwrap()));
+                }
+            }
+        }
+
+        let match_result = self.crawled.pop_front().unwrap();
+        if self.crawled.is_empty() {
+            let boxed_commit = self.commit_checkpoint();
+            if boxed_commit.is_err() {
+                self.failed = true;
+                return Some(Err(boxed_commit.err().unwrap()));
+            }
+        }
+
+        Some(Ok(match_result))
+    }
+}
+
+fn get_endpoint() -> String {
+    get_match_history_by_sequence_num::get_endpoint()
+}
@@ -0,0 +1,162 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+use crate::cache::{CachePolicy, CacheStore, MemoryCache};
+use crate::client::SteamClient;
+use crate::idota2match_570::match_crawler::{CrawlerCheckpoint, MatchCrawler};
+use crate::retry::RetryPolicy;
+use crate::tests::{assert_serde_roundtrip, get_match_sequence_response};
+use crate::transport::FakeTransport;
+
+const SEQUENCE_URL: &str = "https://api.steampowered.com/IDOTA2Match_570/GetMatchHistoryBySequenceNum/v1?key=KEY&matches_requested=2";
+
+fn get_sequence_url(start_at_match_seq_num: u64) -> String {
+    [SEQUENCE_URL, "&start_at_match_seq_num=", start_at_match_seq_num.to_string().as_str()].join("")
+}
+
+fn get_crawler_client(transport: Arc<FakeTransport>, cache: Arc<MemoryCache>) -> SteamClient {
+    SteamClient::builder()
+        .api_key("KEY")
+        .transport(transport)
+        .cache_store(cache)
+        .cache_policy(CachePolicy::default())
+        .rate_limiter_enabled(false)
+        .retry_policy(RetryPolicy::none())
+        .build()
+}
+
+fn get_checkpoint_filepath(test_name: &str) -> PathBuf {
+    let filepath = std::env::temp_dir()
+        .join(["steam-webapi-rust-sdk-", test_name, "-", std::process::id().to_string().as_str(), ".json"].join(""));
+    let _ = std::fs::remove_file(filepath.as_path());
+    filepath
+}
+
+#[test]
+fn crawl_caches_matches_and_advances_checkpoint() {
+    let transport = Arc::new(FakeTransport::new());
+    transport.respond_with(get_sequence_url(100).as_str(), 200, get_match_sequence_response(vec![100, 101]).as_str());
+    transport.respond_with(get_sequence_url(102).as_str(), 200, get_match_sequence_response(vec![102, 105]).as_str());
+    let cache = Arc::new(MemoryCache::new(10));
+    let client = get_crawler_client(transport.clone(), cache.clone());
+
+    let mut crawler = MatchCrawler::new(client.clone(), CrawlerCheckpoint::new(100)).with_matches_requested(2);
+    let match_ids : Vec<u64> = crawler.by_ref()
+        .take(4)
+        .map(|boxed_match| boxed_match.unwrap().match_id)
+        .collect();
+
+    assert_eq!(vec![110, 111, 112, 115], match_ids);
+    assert_eq!(CrawlerCheckpoint::new(106), crawler.get_checkpoint());
+    assert_eq!(2, transport.requests().len());
+
+    assert!(cache.get("IDOTA2Match_570-GetMatchDetails-v1-115.json").unwrap().is_some());
+    let match_result = client.get_cached_dota2_match_details(115).unwrap();
+    assert_eq!(105, match_result.match_seq_num);
+    assert_eq!(10, match_result.players.len());
+    assert_eq!(2, transport.requests().len());
+}
+
+/// `get_match_sequence_response` without the `match_id` of the matches at `match_seq_nums_without_id`.
+fn get_match_sequence_response_without_ids(match_seq_nums: Vec<u64>, match_seq_nums_without_id: Vec<u64>) -> String {
+    let mut response : serde_json::Value = serde_json::from_str(get_match_sequence_response(match_seq_nums).as_str()).unwrap();
+    for match_value in response["result"]["matches"].as_array_mut().unwrap() {
+        if match_seq_nums_without_id.contains(&match_value["match_seq_num"].as_u64().unwrap()) {
+            match_value.as_object_mut().unwrap().remove("match_id");
+        }
+    }
+    response.to_string()
+}
+
+#[test]
+fn crawl_skips_matches_without_id() {
+    let transport = Arc::new(FakeTransport::new());
+    transport.respond_with(get_sequence_url(100).as_str(), 200, get_match_sequence_response_without_ids(vec![100, 101], vec![101]).as_str());
+    transport.respond_with(get_sequence_url(102).as_str(), 200, get_match_sequence_response_without_ids(vec![102], vec![102]).as_str());
+    transport.respond_with(get_sequence_url(103).as_str(), 200, get_match_sequence_response(vec![103]).as_str());
+    let cache = Arc::new(MemoryCache::new(10));
+    let client = get_crawler_client(transport.clone(), cache.clone());
+
+    let mut crawler = MatchCrawler::new(client, CrawlerCheckpoint::new(100)).with_matches_requested(2);
+    let match_ids : Vec<u64> = crawler.by_ref()
+        .take(2)
+        .map(|boxed_match| boxed_match.unwrap().match_id)
+        .collect();
+
+    assert_eq!(vec![110, 113], match_ids);
+    assert_eq!(CrawlerCheckpoint::new(104), crawler.get_checkpoint());
+    assert_eq!(3, transport.requests().len());
+    assert_eq!(2, cache.len());
+    assert!(cache.get("IDOTA2Match_570-GetMatchDetails-v1-0.json").unwrap().is_none());
+}
+
+#[test]
+fn crawl_backs_off_on_empty_pages() {
+    let transport = Arc::new(FakeTransport::new());
+    transport.respond_with(get_sequence_url(100).as_str(), 200, r#"{"result":{"status":1,"matches":[]}}"#);
+    let client = get_crawler_client(transport.clone(), Arc::new(MemoryCache::new(10)));
+
+    let mut crawler = MatchCrawler::new(client, CrawlerCheckpoint::new(100))
+        .with_matches_requested(2)
+        .with_backoff(Duration::from_millis(1), Duration::from_millis(3));
+
+    assert!(crawler.crawl_page().unwrap().is_empty());
+    assert_eq!(Duration::from_millis(1), crawler.get_backoff());
+    assert!(crawler.crawl_page().unwrap().is_empty());
+    assert_eq!(Duration::from_millis(2), crawler.get_backoff());
+    assert!(crawler.crawl_page().unwrap().is_empty());
+    assert_eq!(Duration::from_millis(3), crawler.get_backoff());
+    assert_eq!(CrawlerCheckpoint::new(100), crawler.get_checkpoint());
+
+    transport.respond_with(get_sequence_url(100).as_str(), 200, get_match_sequence_response(vec![100]).as_str());
+    assert_eq!(1, crawler.crawl_page().unwrap().len());
+    assert_eq!(Duration::ZERO, crawler.get_backoff());
+    assert_eq!(4, transport.requests().len());
+}
+
+#[test]
+fn crawl_resumes_from_checkpoint_file() {
+    let checkpoint_filepath = get_checkpoint_filepath("crawl_resumes_from_checkpoint_file");
+    let checkpoint_filepath = checkpoint_filepath.to_str().unwrap();
+    assert_eq!(None, CrawlerCheckpoint::load(checkpoint_filepath).unwrap());
+
+    let transport = Arc::new(FakeTransport::new());
+    transport.respond_with(get_sequence_url(100).as_str(), 200, get_match_sequence_response(vec![100, 101]).as_str());
+    transport.respond_with(get_sequence_url(102).as_str(), 500, "");
+    let client = get_crawler_client(transport.clone(), Arc::new(MemoryCache::new(10)));
+
+    let mut crawler = MatchCrawler::new(client.clone(), CrawlerCheckpoint::new(100))
+        .with_checkpoint_file(checkpoint_filepath)
+        .with_matches_requested(2);
+    assert_eq!(110, crawler.next().unwrap().unwrap().match_id);
+    assert_eq!(None, CrawlerCheckpoint::load(checkpoint_filepath).unwrap());
+    assert_eq!(CrawlerCheckpoint::new(100), crawler.get_checkpoint());
+    assert_eq!(111, crawler.next().unwrap().unwrap().match_id);
+    assert_eq!(Some(CrawlerCheckpoint::new(102)), CrawlerCheckpoint::load(checkpoint_filepath).unwrap());
+    assert!(crawler.next().unwrap().is_err());
+    assert!(crawler.next().is_none());
+
+    let checkpoint = CrawlerCheckpoint::load(checkpoint_filepath).unwrap().unwrap();
+    assert_eq!(CrawlerCheckpoint::new(102), checkpoint);
+    assert_serde_roundtrip(&checkpoint);
+
+    transport.respond_with(get_sequence_url(102).as_str(), 200, get_match_sequence_response(vec![102]).as_str());
+    let mut crawler = MatchCrawler::new(client, checkpoint)
+        .with_checkpoint_file(checkpoint_filepath)
+        .with_matches_requested(2);
+    assert_eq!(112, crawler.next().unwrap().unwrap().match_id);
+    assert_eq!(Some(CrawlerCheckpoint::new(103)), CrawlerCheckpoint::load(checkpoint_filepath).unwrap());
+
+    std::fs::remove_file(checkpoint_filepath).unwrap();
+}
+
+#[test]
+fn load_malformed_checkpoint() {
+    let checkpoint_filepath = get_checkpoint_filepath("load_malformed_checkpoint");
+    std::fs::write(checkpoint_filepath.as_path(), "{").unwrap();
+
+    let error = CrawlerCheckpoint::load(checkpoint_filepath.to_str().unwrap()).err().unwrap();
+    assert!(matches!(error, crate::SteamApiError::Storage { .. }));
+
+    std::fs::remove_file(checkpoint_filepath).unwrap();
+}
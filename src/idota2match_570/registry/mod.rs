@@ -0,0 +1,102 @@
+use std::collections::HashMap;
+use crate::client::{get_default_client, SteamClient};
+use crate::error::SteamApiError;
+use crate::idota2match_570::get_heroes::{self, Hero};
+use crate::idota2match_570::get_match_details::PlayerStats;
+use crate::iecon_dota2_570::get_game_items::{self, GameItem};
+
+#[cfg(test)]
+mod tests;
+
+/// Resolves Dota2 hero and item ids to their names, preferring the localized name if the
+/// registry was loaded with a `language`.
+#[derive(Clone, Debug, Default)]
+pub struct IdNameRegistry {
+    heroes: HashMap<u64, Hero>,
+    items: HashMap<u64, GameItem>,
+}
+
+impl IdNameRegistry {
+    pub fn new(heroes: Vec<Hero>, items: Vec<GameItem>) -> IdNameRegistry {
+        IdNameRegistry {
+            heroes: heroes.into_iter().map(|hero| (hero.id, hero)).collect(),
+            items: items.into_iter().map(|item| (item.id, item)).collect(),
+        }
+    }
+
+    /// Builds the registry from the cached heroes and game items, calling Steam if the cached
+    /// resources are absent or stale.
+    pub fn load(language: Option<String>) -> Result<IdNameRegistry, SteamApiError> {
+        IdNameRegistry::load_with_client(get_default_client().as_ref(), language)
+    }
+
+    /// Async version of `load`, available with the `async` feature.
+    #[cfg(feature = "async")]
+    pub async fn load_async(language: Option<String>) -> Result<IdNameRegistry, SteamApiError> {
+        IdNameRegistry::load_async_with_client(get_default_client().as_ref(), language).await
+    }
+
+    pub fn load_with_client(client: &SteamClient, language: Option<String>) -> Result<IdNameRegistry, SteamApiError> {
+        let boxed_heroes = get_heroes::get_cached_with_client(client, language.clone());
+        if boxed_heroes.is_err() {
+            return Err(boxed_heroes.err().unwrap());
+        }
+
+        let boxed_items = get_game_items::get_cached_with_client(client, language);
+        if boxed_items.is_err() {
+            return Err(boxed_items.err().unwrap());
+        }
+
+        Ok(IdNameRegistry::new(boxed_heroes.unwrap(), boxed_items.unwrap()))
+    }
+
+    /// Async version of `load_with_client`, available with the `async` feature.
+    #[cfg(feature = "async")]
+    pub async fn load_async_with_client(client: &SteamClient, language: Option<String>) -> Result<IdNameRegistry, SteamApiError> {
+        let boxed_heroes = get_heroes::get_cached_async_with_client(client, language.clone()).await;
+        if boxed_heroes.is_err() {
+            return Err(boxed_heroes.err().unwrap());
+        }
+
+        let boxed_items = get_game_items::get_cached_async_with_client(client, language).await;
+        if boxed_items.is_err() {
+            return Err(boxed_items.err().unwrap());
+        }
+
+        Ok(IdNameRegistry::new(boxed_heroes.unwrap(), boxed_items.unwrap()))
+    }
+
+    pub fn get_hero(&self, hero_id: u64) -> Option<&Hero> {
+        self.heroes.get(&hero_id)
+    }
+
+    pub fn get_item(&self, item_id: u64) -> Option<&GameItem> {
+        self.items.get(&item_id)
+    }
+
+    /// Localized hero name, falling back to the internal name such as `npc_dota_hero_axe`.
+    pub fn get_hero_name(&self, hero_id: u64) -> Option<&str> {
+        self.get_hero(hero_id)
+            .map(|hero| hero.localized_name.as_deref().unwrap_or(hero.name.as_str()))
+    }
+
+    /// Localized item name, falling back to the internal name such as `item_blink`.
+    pub fn get_item_name(&self, item_id: u64) -> Option<&str> {
+        self.get_item(item_id)
+            .map(|item| item.localized_name.as_deref().unwrap_or(item.name.as_str()))
+    }
+
+    /// Name of the hero the player picked.
+    pub fn get_player_hero_name(&self, player: &PlayerStats) -> Option<&str> {
+        self.get_hero_name(player.hero_id)
+    }
+
+    /// Names of the items the player finished the match with, see `PlayerStats::get_item_ids`.
+    /// Items missing from the registry are skipped.
+    pub fn get_player_item_names(&self, player: &PlayerStats) -> Vec<&str> {
+        player.get_item_ids()
+            .into_iter()
+            .filter_map(|item_id| self.get_item_name(item_id))
+            .collect()
+    }
+}
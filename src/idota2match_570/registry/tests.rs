@@ -0,0 +1,93 @@
+use std::fs::read_to_string;
+use std::sync::Arc;
+use crate::cache::{CachePolicy, MemoryCache};
+use crate::client::SteamClient;
+use crate::idota2match_570::get_heroes::Hero;
+use crate::idota2match_570::get_match_details::parse_response;
+use crate::idota2match_570::registry::IdNameRegistry;
+use crate::iecon_dota2_570::get_game_items::GameItem;
+use crate::retry::RetryPolicy;
+use crate::transport::FakeTransport;
+
+fn get_hero(id: u64, name: &str, localized_name: Option<&str>) -> Hero {
+    Hero { id, name: name.to_string(), localized_name: localized_name.map(str::to_string) }
+}
+
+fn get_item(id: u64, name: &str, localized_name: Option<&str>) -> GameItem {
+    GameItem {
+        id,
+        name: name.to_string(),
+        cost: None,
+        secret_shop: None,
+        side_shop: None,
+        recipe: None,
+        localized_name: localized_name.map(str::to_string),
+    }
+}
+
+#[test]
+fn resolve_names() {
+    let registry = IdNameRegistry::new(
+        vec![get_hero(1, "npc_dota_hero_antimage", Some("Anti-Mage")), get_hero(2, "npc_dota_hero_axe", None)],
+        vec![get_item(1, "item_blink", Some("Blink Dagger")), get_item(2, "item_blades_of_attack", None)],
+    );
+
+    assert_eq!(Some("Anti-Mage"), registry.get_hero_name(1));
+    assert_eq!(Some("npc_dota_hero_axe"), registry.get_hero_name(2));
+    assert_eq!(None, registry.get_hero_name(3));
+
+    assert_eq!(Some("Blink Dagger"), registry.get_item_name(1));
+    assert_eq!(Some("item_blades_of_attack"), registry.get_item_name(2));
+    assert_eq!(None, registry.get_item_name(0));
+}
+
+#[test]
+fn resolve_match_players() {
+    let response = read_to_string("test/idota2match_570/get_match_details/1461414523.json").unwrap();
+    let match_result = parse_response(response, 1461414523).unwrap();
+    let registry = IdNameRegistry::new(
+        vec![get_hero(12, "npc_dota_hero_phantom_lancer", Some("Phantom Lancer"))],
+        vec![get_item(61, "item_ultimate_orb", Some("Ultimate Orb")), get_item(63, "item_power_treads", Some("Power Treads"))],
+    );
+
+    let player = match_result.players.first().unwrap();
+    assert_eq!(vec![61, 185, 113, 174, 212, 63], player.get_item_ids());
+    assert_eq!(Some("Phantom Lancer"), registry.get_player_hero_name(player));
+    assert_eq!(vec!["Ultimate Orb", "Power Treads"], registry.get_player_item_names(player));
+
+    let player = match_result.players.get(1).unwrap();
+    assert_eq!(vec![102, 100, 214, 21, 46], player.get_item_ids());
+    assert_eq!(None, registry.get_player_hero_name(player));
+}
+
+#[test]
+fn load_with_client() {
+    let transport = Arc::new(FakeTransport::new());
+    transport.respond_with(
+        "https://api.steampowered.com/IDOTA2Match_570/GetHeroes/v1?key=KEY&language=en",
+        200,
+        r#"{"result":{"heroes":[{"name":"npc_dota_hero_antimage","id":1,"localized_name":"Anti-Mage"}],"count":1}}"#,
+    );
+    transport.respond_with(
+        "https://api.steampowered.com/IEconDOTA2_570/GetGameItems/v1?key=KEY&language=en",
+        200,
+        r#"{"result":{"items":[{"id":1,"name":"item_blink","cost":2250,"secret_shop":0,"side_shop":0,"recipe":0,"localized_name":"Blink Dagger"}],"status":200}}"#,
+    );
+    let client = SteamClient::builder()
+        .api_key("KEY")
+        .transport(transport.clone())
+        .cache_store(Arc::new(MemoryCache::new(10)))
+        .cache_policy(CachePolicy::default())
+        .retry_policy(RetryPolicy::none())
+        .build();
+
+    let registry = IdNameRegistry::load_with_client(&client, Some("en".to_string())).unwrap();
+    assert_eq!(Some("Anti-Mage"), registry.get_hero_name(1));
+    assert_eq!(Some("Blink Dagger"), registry.get_item_name(1));
+    assert_eq!(2, transport.requests().len());
+
+    IdNameRegistry::load_with_client(&client, Some("en".to_string())).unwrap();
+    assert_eq!(2, transport.requests().len());
+
+    assert!(IdNameRegistry::load_with_client(&client, None).is_err());
+}
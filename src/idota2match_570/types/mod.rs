@@ -0,0 +1,519 @@
+use std::fmt;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+#[cfg(test)]
+mod tests;
+
+/// Dota 2 game mode, the `game_mode` of a match and a `GetMatchHistory` filter.
+#[non_exhaustive]
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub enum GameMode {
+    None,
+    AllPick,
+    CaptainsMode,
+    RandomDraft,
+    SingleDraft,
+    AllRandom,
+    Intro,
+    Diretide,
+    ReverseCaptainsMode,
+    TheGreeviling,
+    Tutorial,
+    MidOnly,
+    LeastPlayed,
+    NewPlayerPool,
+    CompendiumMatchmaking,
+    Custom,
+    CaptainsDraft,
+    BalancedDraft,
+    AbilityDraft,
+    Event,
+    AllRandomDeathmatch,
+    OneVsOneMid,
+    AllDraft,
+    Turbo,
+    Mutation,
+    CoachesChallenge,
+    /// A mode added after this version of the crate.
+    Unknown(u64),
+}
+
+impl GameMode {
+    /// Returns the game mode with the given id, `Unknown` if it isn't a known one.
+    pub fn from_id(id: u64) -> GameMode {
+        match id {
+            0 => GameMode::None,
+            1 => GameMode::AllPick,
+            2 => GameMode::CaptainsMode,
+            3 => GameMode::RandomDraft,
+            4 => GameMode::SingleDraft,
+            5 => GameMode::AllRandom,
+            6 => GameMode::Intro,
+            7 => GameMode::Diretide,
+            8 => GameMode::ReverseCaptainsMode,
+            9 => GameMode::TheGreeviling,
+            10 => GameMode::Tutorial,
+            11 => GameMode::MidOnly,
+            12 => GameMode::LeastPlayed,
+            13 => GameMode::NewPlayerPool,
+            14 => GameMode::CompendiumMatchmaking,
+            15 => GameMode::Custom,
+            16 => GameMode::CaptainsDraft,
+            17 => GameMode::BalancedDraft,
+            18 => GameMode::AbilityDraft,
+            19 => GameMode::Event,
+            20 => GameMode::AllRandomDeathmatch,
+            21 => GameMode::OneVsOneMid,
+            22 => GameMode::AllDraft,
+            23 => GameMode::Turbo,
+            24 => GameMode::Mutation,
+            25 => GameMode::CoachesChallenge,
+            _ => GameMode::Unknown(id),
+        }
+    }
+
+    pub fn get_id(&self) -> u64 {
+        match self {
+            GameMode::None => 0,
+            GameMode::AllPick => 1,
+            GameMode::CaptainsMode => 2,
+            GameMode::RandomDraft => 3,
+            GameMode::SingleDraft => 4,
+            GameMode::AllRandom => 5,
+            GameMode::Intro => 6,
+            GameMode::Diretide => 7,
+            GameMode::ReverseCaptainsMode => 8,
+            GameMode::TheGreeviling => 9,
+            GameMode::Tutorial => 10,
+            GameMode::MidOnly => 11,
+            GameMode::LeastPlayed => 12,
+            GameMode::NewPlayerPool => 13,
+            GameMode::CompendiumMatchmaking => 14,
+            GameMode::Custom => 15,
+            GameMode::CaptainsDraft => 16,
+            GameMode::BalancedDraft => 17,
+            GameMode::AbilityDraft => 18,
+            GameMode::Event => 19,
+            GameMode::AllRandomDeathmatch => 20,
+            GameMode::OneVsOneMid => 21,
+            GameMode::AllDraft => 22,
+            GameMode::Turbo => 23,
+            GameMode::Mutation => 24,
+            GameMode::CoachesChallenge => 25,
+            GameMode::Unknown(id) => *id,
+        }
+    }
+
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            GameMode::None => "None",
+            GameMode::AllPick => "All Pick",
+            GameMode::CaptainsMode => "Captains Mode",
+            GameMode::RandomDraft => "Random Draft",
+            GameMode::SingleDraft => "Single Draft",
+            GameMode::AllRandom => "All Random",
+            GameMode::Intro => "Intro",
+            GameMode::Diretide => "Diretide",
+            GameMode::ReverseCaptainsMode => "Reverse Captains Mode",
+            GameMode::TheGreeviling => "The Greeviling",
+            GameMode::Tutorial => "Tutorial",
+            GameMode::MidOnly => "Mid Only",
+            GameMode::LeastPlayed => "Least Played",
+            GameMode::NewPlayerPool => "New Player Pool",
+            GameMode::CompendiumMatchmaking => "Compendium Matchmaking",
+            GameMode::Custom => "Custom",
+            GameMode::CaptainsDraft => "Captains Draft",
+            GameMode::BalancedDraft => "Balanced Draft",
+            GameMode::AbilityDraft => "Ability Draft",
+            GameMode::Event => "Event",
+            GameMode::AllRandomDeathmatch => "All Random Deathmatch",
+            GameMode::OneVsOneMid => "1v1 Mid",
+            GameMode::AllDraft => "All Draft",
+            GameMode::Turbo => "Turbo",
+            GameMode::Mutation => "Mutation",
+            GameMode::CoachesChallenge => "Coaches Challenge",
+            GameMode::Unknown(_) => "Unknown",
+        }
+    }
+}
+
+impl TryFrom<u64> for GameMode {
+    type Error = String;
+
+    fn try_from(id: u64) -> Result<Self, Self::Error> {
+        match GameMode::from_id(id) {
+            GameMode::Unknown(_) => Err(format!("unknown game mode {}", id)),
+            game_mode => Ok(game_mode),
+        }
+    }
+}
+
+impl fmt::Display for GameMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GameMode::Unknown(id) => write!(f, "Unknown ({})", id),
+            _ => f.write_str(self.get_name()),
+        }
+    }
+}
+
+impl Serialize for GameMode {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u64(self.get_id())
+    }
+}
+
+impl<'de> Deserialize<'de> for GameMode {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        u64::deserialize(deserializer).map(GameMode::from_id)
+    }
+}
+
+/// Dota 2 lobby type, the `lobby_type` of a match. Steam uses `-1` for invalid lobbies, so ids are
+/// signed.
+#[non_exhaustive]
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub enum LobbyType {
+    Invalid,
+    PublicMatchmaking,
+    Practice,
+    Tournament,
+    Tutorial,
+    CoopWithBots,
+    TeamMatch,
+    SoloQueue,
+    RankedMatchmaking,
+    OneVsOneSoloMid,
+    BattleCup,
+    LocalBotMatch,
+    Spectator,
+    Event,
+    /// A lobby type added after this version of the crate.
+    Unknown(u64),
+}
+
+impl LobbyType {
+    /// Returns the lobby type with the given id, `Unknown` if it isn't a known one.
+    pub fn from_id(id: i64) -> LobbyType {
+        match id {
+            -1 => LobbyType::Invalid,
+            0 => LobbyType::PublicMatchmaking,
+            1 => LobbyType::Practice,
+            2 => LobbyType::Tournament,
+            3 => LobbyType::Tutorial,
+            4 => LobbyType::CoopWithBots,
+            5 => LobbyType::TeamMatch,
+            6 => LobbyType::SoloQueue,
+            7 => LobbyType::RankedMatchmaking,
+            8 => LobbyType::OneVsOneSoloMid,
+            9 => LobbyType::BattleCup,
+            10 => LobbyType::LocalBotMatch,
+            11 => LobbyType::Spectator,
+            12 => LobbyType::Event,
+            id if id < 0 => LobbyType::Invalid,
+            _ => LobbyType::Unknown(id as u64),
+        }
+    }
+
+    pub fn get_id(&self) -> i64 {
+        match self {
+            LobbyType::Invalid => -1,
+            LobbyType::PublicMatchmaking => 0,
+            LobbyType::Practice => 1,
+            LobbyType::Tournament => 2,
+            LobbyType::Tutorial => 3,
+            LobbyType::CoopWithBots => 4,
+            LobbyType::TeamMatch => 5,
+            LobbyType::SoloQueue => 6,
+            LobbyType::RankedMatchmaking => 7,
+            LobbyType::OneVsOneSoloMid => 8,
+            LobbyType::BattleCup => 9,
+            LobbyType::LocalBotMatch => 10,
+            LobbyType::Spectator => 11,
+            LobbyType::Event => 12,
+            LobbyType::Unknown(id) => *id as i64,
+        }
+    }
+
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            LobbyType::Invalid => "Invalid",
+            LobbyType::PublicMatchmaking => "Public Matchmaking",
+            LobbyType::Practice => "Practice",
+            LobbyType::Tournament => "Tournament",
+            LobbyType::Tutorial => "Tutorial",
+            LobbyType::CoopWithBots => "Co-op with Bots",
+            LobbyType::TeamMatch => "Team Match",
+            LobbyType::SoloQueue => "Solo Queue",
+            LobbyType::RankedMatchmaking => "Ranked Matchmaking",
+            LobbyType::OneVsOneSoloMid => "1v1 Solo Mid",
+            LobbyType::BattleCup => "Battle Cup",
+            LobbyType::LocalBotMatch => "Local Bot Match",
+            LobbyType::Spectator => "Spectator",
+            LobbyType::Event => "Event",
+            LobbyType::Unknown(_) => "Unknown",
+        }
+    }
+}
+
+impl TryFrom<u64> for LobbyType {
+    type Error = String;
+
+    fn try_from(id: u64) -> Result<Self, Self::Error> {
+        match i64::try_from(id).map(LobbyType::from_id) {
+            Ok(LobbyType::Unknown(_)) | Err(_) => Err(format!("unknown lobby type {}", id)),
+            Ok(lobby_type) => Ok(lobby_type),
+        }
+    }
+}
+
+impl fmt::Display for LobbyType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LobbyType::Unknown(id) => write!(f, "Unknown ({})", id),
+            _ => f.write_str(self.get_name()),
+        }
+    }
+}
+
+impl Serialize for LobbyType {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_i64(self.get_id())
+    }
+}
+
+impl<'de> Deserialize<'de> for LobbyType {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        i64::deserialize(deserializer).map(LobbyType::from_id)
+    }
+}
+
+/// Skill bracket, a `GetMatchHistory` filter.
+#[non_exhaustive]
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub enum Skill {
+    Any,
+    Normal,
+    High,
+    VeryHigh,
+    /// A bracket added after this version of the crate.
+    Unknown(u64),
+}
+
+impl Skill {
+    /// Returns the skill bracket with the given id, `Unknown` if it isn't a known one.
+    pub fn from_id(id: u64) -> Skill {
+        match id {
+            0 => Skill::Any,
+            1 => Skill::Normal,
+            2 => Skill::High,
+            3 => Skill::VeryHigh,
+            _ => Skill::Unknown(id),
+        }
+    }
+
+    pub fn get_id(&self) -> u64 {
+        match self {
+            Skill::Any => 0,
+            Skill::Normal => 1,
+            Skill::High => 2,
+            Skill::VeryHigh => 3,
+            Skill::Unknown(id) => *id,
+        }
+    }
+
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            Skill::Any => "Any",
+            Skill::Normal => "Normal",
+            Skill::High => "High",
+            Skill::VeryHigh => "Very High",
+            Skill::Unknown(_) => "Unknown",
+        }
+    }
+}
+
+impl TryFrom<u64> for Skill {
+    type Error = String;
+
+    fn try_from(id: u64) -> Result<Self, Self::Error> {
+        match Skill::from_id(id) {
+            Skill::Unknown(_) => Err(format!("unknown skill bracket {}", id)),
+            skill => Ok(skill),
+        }
+    }
+}
+
+impl fmt::Display for Skill {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Skill::Unknown(id) => write!(f, "Unknown ({})", id),
+            _ => f.write_str(self.get_name()),
+        }
+    }
+}
+
+impl Serialize for Skill {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u64(self.get_id())
+    }
+}
+
+impl<'de> Deserialize<'de> for Skill {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        u64::deserialize(deserializer).map(Skill::from_id)
+    }
+}
+
+const DIRE_SLOT_BIT: u64 = 0x80;
+const POSITION_MASK: u64 = 0x7;
+
+/// Side of the map a player is on.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub enum Team {
+    Radiant,
+    Dire,
+}
+
+impl Team {
+    /// Reads the `0`/`1` team of draft entries and `team_number`, anything but `0` being Dire.
+    pub fn from_id(team: u64) -> Team {
+        if team == 0 { Team::Radiant } else { Team::Dire }
+    }
+
+    pub fn get_id(&self) -> u64 {
+        match self {
+            Team::Radiant => 0,
+            Team::Dire => 1,
+        }
+    }
+}
+
+impl fmt::Display for Team {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Team::Radiant => f.write_str("Radiant"),
+            Team::Dire => f.write_str("Dire"),
+        }
+    }
+}
+
+/// A decoded `player_slot`: the high bit is set for Dire, the low three bits are the position 0-4.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub struct PlayerSlot {
+    pub team: Team,
+    pub position: u8,
+}
+
+impl PlayerSlot {
+    pub fn from_id(player_slot: u64) -> PlayerSlot {
+        let team = if player_slot & DIRE_SLOT_BIT != 0 { Team::Dire } else { Team::Radiant };
+        PlayerSlot { team, position: (player_slot & POSITION_MASK) as u8 }
+    }
+
+    pub fn get_id(&self) -> u64 {
+        let team_bit = if self.team == Team::Dire { DIRE_SLOT_BIT } else { 0 };
+        team_bit | u64::from(self.position)
+    }
+}
+
+/// A decoded `tower_status_radiant`/`tower_status_dire` bitmask, `true` for every tower still
+/// standing.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub struct TowerStatus {
+    pub top_tier_1: bool,
+    pub top_tier_2: bool,
+    pub top_tier_3: bool,
+    pub middle_tier_1: bool,
+    pub middle_tier_2: bool,
+    pub middle_tier_3: bool,
+    pub bottom_tier_1: bool,
+    pub bottom_tier_2: bool,
+    pub bottom_tier_3: bool,
+    pub ancient_top: bool,
+    pub ancient_bottom: bool,
+}
+
+impl TowerStatus {
+    pub fn from_bits(bits: u64) -> TowerStatus {
+        let is_set = |bit: u64| bits & (1 << bit) != 0;
+        TowerStatus {
+            top_tier_1: is_set(0),
+            top_tier_2: is_set(1),
+            top_tier_3: is_set(2),
+            middle_tier_1: is_set(3),
+            middle_tier_2: is_set(4),
+            middle_tier_3: is_set(5),
+            bottom_tier_1: is_set(6),
+            bottom_tier_2: is_set(7),
+            bottom_tier_3: is_set(8),
+            ancient_top: is_set(9),
+            ancient_bottom: is_set(10),
+        }
+    }
+
+    pub fn get_bits(&self) -> u64 {
+        self.to_array().iter().enumerate()
+            .filter(|(_, standing)| **standing)
+            .fold(0, |bits, (bit, _)| bits | (1 << bit))
+    }
+
+    /// Returns how many of the 11 towers are still standing.
+    pub fn count_standing(&self) -> usize {
+        self.to_array().iter().filter(|standing| **standing).count()
+    }
+
+    fn to_array(self) -> [bool; 11] {
+        [
+            self.top_tier_1, self.top_tier_2, self.top_tier_3,
+            self.middle_tier_1, self.middle_tier_2, self.middle_tier_3,
+            self.bottom_tier_1, self.bottom_tier_2, self.bottom_tier_3,
+            self.ancient_top, self.ancient_bottom,
+        ]
+    }
+}
+
+/// A decoded `barracks_status_radiant`/`barracks_status_dire` bitmask, `true` for every barracks
+/// still standing.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub struct BarracksStatus {
+    pub top_melee: bool,
+    pub top_ranged: bool,
+    pub middle_melee: bool,
+    pub middle_ranged: bool,
+    pub bottom_melee: bool,
+    pub bottom_ranged: bool,
+}
+
+impl BarracksStatus {
+    pub fn from_bits(bits: u64) -> BarracksStatus {
+        let is_set = |bit: u64| bits & (1 << bit) != 0;
+        BarracksStatus {
+            top_melee: is_set(0),
+            top_ranged: is_set(1),
+            middle_melee: is_set(2),
+            middle_ranged: is_set(3),
+            bottom_melee: is_set(4),
+            bottom_ranged: is_set(5),
+        }
+    }
+
+    pub fn get_bits(&self) -> u64 {
+        self.to_array().iter().enumerate()
+            .filter(|(_, standing)| **standing)
+            .fold(0, |bits, (bit, _)| bits | (1 << bit))
+    }
+
+    /// Returns how many of the 6 barracks are still standing.
+    pub fn count_standing(&self) -> usize {
+        self.to_array().iter().filter(|standing| **standing).count()
+    }
+
+    fn to_array(self) -> [bool; 6] {
+        [
+            self.top_melee, self.top_ranged,
+            self.middle_melee, self.middle_ranged,
+            self.bottom_melee, self.bottom_ranged,
+        ]
+    }
+}
@@ -0,0 +1,131 @@
+use crate::idota2match_570::types::{BarracksStatus, GameMode, LobbyType, PlayerSlot, Skill, Team, TowerStatus};
+use crate::tests::assert_serde_roundtrip;
+
+#[test]
+fn game_modes() {
+    assert_eq!(0, GameMode::None.get_id());
+    assert_eq!(1, GameMode::AllPick.get_id());
+    assert_eq!(2, GameMode::CaptainsMode.get_id());
+    assert_eq!(16, GameMode::CaptainsDraft.get_id());
+    assert_eq!(18, GameMode::AbilityDraft.get_id());
+    assert_eq!(21, GameMode::OneVsOneMid.get_id());
+    assert_eq!(22, GameMode::AllDraft.get_id());
+    assert_eq!(23, GameMode::Turbo.get_id());
+
+    for id in 0..=25 {
+        let game_mode = GameMode::try_from(id).unwrap();
+        assert_eq!(id, game_mode.get_id());
+        assert_eq!(game_mode, GameMode::from_id(id));
+    }
+
+    assert_eq!(GameMode::Unknown(99), GameMode::from_id(99));
+    assert_eq!(99, GameMode::Unknown(99).get_id());
+    assert!(GameMode::try_from(99).is_err());
+
+    assert_eq!("Turbo", GameMode::Turbo.to_string());
+    assert_eq!("1v1 Mid", GameMode::OneVsOneMid.to_string());
+    assert_eq!("Unknown (99)", GameMode::Unknown(99).to_string());
+}
+
+#[test]
+fn lobby_types() {
+    assert_eq!(-1, LobbyType::Invalid.get_id());
+    assert_eq!(0, LobbyType::PublicMatchmaking.get_id());
+    assert_eq!(7, LobbyType::RankedMatchmaking.get_id());
+
+    assert_eq!(LobbyType::Invalid, LobbyType::from_id(-1));
+    assert_eq!(LobbyType::BattleCup, LobbyType::from_id(9));
+    assert_eq!(LobbyType::Unknown(42), LobbyType::from_id(42));
+
+    assert_eq!(LobbyType::Practice, LobbyType::try_from(1).unwrap());
+    assert!(LobbyType::try_from(42).is_err());
+    assert!(LobbyType::try_from(u64::MAX).is_err());
+
+    assert_eq!("Ranked Matchmaking", LobbyType::RankedMatchmaking.to_string());
+}
+
+#[test]
+fn skills() {
+    assert_eq!(0, Skill::Any.get_id());
+    assert_eq!(1, Skill::Normal.get_id());
+    assert_eq!(2, Skill::High.get_id());
+    assert_eq!(3, Skill::VeryHigh.get_id());
+
+    assert_eq!(Skill::VeryHigh, Skill::try_from(3).unwrap());
+    assert!(Skill::try_from(4).is_err());
+    assert_eq!(Skill::Unknown(4), Skill::from_id(4));
+    assert_eq!("Very High", Skill::VeryHigh.to_string());
+}
+
+#[test]
+fn serde_as_ids() {
+    assert_eq!("23", serde_json::to_string(&GameMode::Turbo).unwrap());
+    assert_eq!("-1", serde_json::to_string(&LobbyType::Invalid).unwrap());
+    assert_eq!(GameMode::Unknown(99), serde_json::from_str::<GameMode>("99").unwrap());
+
+    assert_serde_roundtrip(&GameMode::AbilityDraft);
+    assert_serde_roundtrip(&GameMode::Unknown(99));
+    assert_serde_roundtrip(&LobbyType::Invalid);
+    assert_serde_roundtrip(&Skill::High);
+}
+
+#[test]
+fn player_slots() {
+    assert_eq!(PlayerSlot { team: Team::Radiant, position: 0 }, PlayerSlot::from_id(0));
+    assert_eq!(PlayerSlot { team: Team::Radiant, position: 4 }, PlayerSlot::from_id(4));
+    assert_eq!(PlayerSlot { team: Team::Dire, position: 0 }, PlayerSlot::from_id(128));
+    assert_eq!(PlayerSlot { team: Team::Dire, position: 4 }, PlayerSlot::from_id(132));
+
+    for player_slot in [0, 1, 2, 3, 4, 128, 129, 130, 131, 132] {
+        assert_eq!(player_slot, PlayerSlot::from_id(player_slot).get_id());
+    }
+}
+
+#[test]
+fn teams() {
+    assert_eq!(Team::Radiant, Team::from_id(0));
+    assert_eq!(Team::Dire, Team::from_id(1));
+    assert_eq!(1, Team::Dire.get_id());
+    assert_eq!("Dire", Team::Dire.to_string());
+}
+
+#[test]
+fn tower_status() {
+    let all_standing = TowerStatus::from_bits(0b111_1111_1111);
+    assert_eq!(11, all_standing.count_standing());
+    assert!(all_standing.ancient_bottom);
+
+    let none_standing = TowerStatus::from_bits(0);
+    assert_eq!(0, none_standing.count_standing());
+
+    // top T2/T3, mid T3, bottom T2/T3 and both ancient towers
+    let towers = TowerStatus::from_bits(1958);
+    assert!(!towers.top_tier_1);
+    assert!(towers.top_tier_2);
+    assert!(towers.top_tier_3);
+    assert!(!towers.middle_tier_1);
+    assert!(!towers.middle_tier_2);
+    assert!(towers.middle_tier_3);
+    assert!(!towers.bottom_tier_1);
+    assert!(towers.bottom_tier_2);
+    assert!(towers.bottom_tier_3);
+    assert!(towers.ancient_top);
+    assert!(towers.ancient_bottom);
+    assert_eq!(7, towers.count_standing());
+    assert_eq!(1958, towers.get_bits());
+}
+
+#[test]
+fn barracks_status() {
+    let barracks = BarracksStatus::from_bits(12);
+    assert!(!barracks.top_melee);
+    assert!(!barracks.top_ranged);
+    assert!(barracks.middle_melee);
+    assert!(barracks.middle_ranged);
+    assert!(!barracks.bottom_melee);
+    assert!(!barracks.bottom_ranged);
+    assert_eq!(2, barracks.count_standing());
+    assert_eq!(12, barracks.get_bits());
+
+    assert_eq!(6, BarracksStatus::from_bits(63).count_standing());
+}
@@ -0,0 +1,5 @@
+pub mod get_game_items;
+
+pub fn get_interface() -> String {
+    "IEconDOTA2_570".to_string()
+}
@@ -0,0 +1,146 @@
+use std::collections::HashMap;
+use serde_json::Value;
+use serde::Deserialize;
+use serde::Serialize;
+use crate::iecon_dota2_570;
+use crate::client::{get_default_client, SteamClient};
+use crate::error::SteamApiError;
+use crate::util::{build_cache_key, build_steam_api_url, json_opt_bool, json_opt_str, json_opt_u64, json_str, json_u64};
+
+#[cfg(test)]
+mod tests;
+
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
+pub struct GameItem {
+    pub id: u64,
+    pub name: String,
+    pub cost: Option<u64>,
+    pub secret_shop: Option<bool>,
+    pub side_shop: Option<bool>,
+    pub recipe: Option<bool>,
+    /// Present when the items are requested with a `language`.
+    pub localized_name: Option<String>,
+}
+
+pub fn get_method_name() -> String {
+    "GetGameItems".to_string()
+}
+
+pub fn get_version() -> String {
+    "v1".to_string()
+}
+
+pub fn get_endpoint() -> String {
+    [iecon_dota2_570::get_interface(), "/".to_string(), get_method_name()].join("")
+}
+
+pub fn get(language: Option<String>) -> Result<Vec<GameItem>, SteamApiError> {
+    get_with_client(get_default_client().as_ref(), language)
+}
+
+/// Async version of `get`, available with the `async` feature.
+#[cfg(feature = "async")]
+pub async fn get_async(language: Option<String>) -> Result<Vec<GameItem>, SteamApiError> {
+    get_async_with_client(get_default_client().as_ref(), language).await
+}
+
+pub fn get_with_client(client: &SteamClient, language: Option<String>) -> Result<Vec<GameItem>, SteamApiError> {
+    let cache_key = get_cache_key(language.clone());
+    let api_url = get_api_url(language);
+    client.fetch_and_cache(get_endpoint().as_str(), cache_key.as_str(), api_url, parse_response)
+}
+
+/// Async version of `get_with_client`, available with the `async` feature.
+#[cfg(feature = "async")]
+pub async fn get_async_with_client(client: &SteamClient, language: Option<String>) -> Result<Vec<GameItem>, SteamApiError> {
+    let cache_key = get_cache_key(language.clone());
+    let api_url = get_api_url(language);
+    client.fetch_and_cache_async(get_endpoint().as_str(), cache_key.as_str(), api_url, parse_response).await
+}
+
+/// Like `get`, but serves the cached response while it is fresh as per the client's
+/// `cache::CachePolicy`, otherwise calls Steam and caches the response.
+pub fn get_cached(language: Option<String>) -> Result<Vec<GameItem>, SteamApiError> {
+    get_cached_with_client(get_default_client().as_ref(), language)
+}
+
+/// Async version of `get_cached`, available with the `async` feature.
+#[cfg(feature = "async")]
+pub async fn get_cached_async(language: Option<String>) -> Result<Vec<GameItem>, SteamApiError> {
+    get_cached_async_with_client(get_default_client().as_ref(), language).await
+}
+
+pub fn get_cached_with_client(client: &SteamClient, language: Option<String>) -> Result<Vec<GameItem>, SteamApiError> {
+    client.get_cached_or_fetch(
+        get_endpoint().as_str(),
+        get_cache_key(language.clone()).as_str(),
+        parse_response,
+        || get_with_client(client, language),
+    )
+}
+
+/// Async version of `get_cached_with_client`, available with the `async` feature.
+#[cfg(feature = "async")]
+pub async fn get_cached_async_with_client(client: &SteamClient, language: Option<String>) -> Result<Vec<GameItem>, SteamApiError> {
+    client.get_cached_or_fetch_async(
+        get_endpoint().as_str(),
+        get_cache_key(language.clone()).as_str(),
+        parse_response,
+        get_async_with_client(client, language),
+    ).await
+}
+
+pub fn get_api_url(language: Option<String>) -> String {
+    let interface = iecon_dota2_570::get_interface();
+    let method = get_method_name();
+    let version = get_version();
+
+    let mut params_map = HashMap::new();
+    if let Some(language) = language {
+        params_map.insert("language".to_string(), language);
+    }
+
+    build_steam_api_url(interface.as_str(), method.as_str(), version.as_str(), params_map)
+}
+
+/// Returns path of the cached resource relative to the cache directory.
+pub fn get_cache_key(language: Option<String>) -> String {
+    let mut params = vec![];
+    if let Some(language) = language {
+        params.push(("language", language));
+    }
+
+    build_cache_key(iecon_dota2_570::get_interface().as_str(), get_method_name().as_str(), get_version().as_str(), params)
+}
+
+pub fn get_resource_filepath(language: Option<String>) -> String {
+    get_default_client().get_resource_filepath(get_cache_key(language).as_str())
+}
+
+pub fn parse_response(response: String) -> Result<Vec<GameItem>, SteamApiError> {
+    let boxed_initial_parse = serde_json::from_str(&response);
+    if boxed_initial_parse.is_err() {
+        return Err(SteamApiError::malformed_response(get_endpoint().as_str(), boxed_initial_parse.err().unwrap().to_string().as_str()));
+    }
+    let json: Value = boxed_initial_parse.unwrap();
+
+    let boxed_items = json.get("result").and_then(|r| r.get("items")).and_then(Value::as_array);
+    if boxed_items.is_none() {
+        return Err(SteamApiError::malformed_response(get_endpoint().as_str(), "response does not contain any items"));
+    }
+
+    let mut items = vec![];
+    for item in boxed_items.unwrap() {
+        items.push(GameItem {
+            id: json_u64(item, "id"),
+            name: json_str(item, "name"),
+            cost: json_opt_u64(item, "cost"),
+            secret_shop: json_opt_bool(item, "secret_shop"),
+            side_shop: json_opt_bool(item, "side_shop"),
+            recipe: json_opt_bool(item, "recipe"),
+            localized_name: json_opt_str(item, "localized_name"),
+        });
+    }
+
+    Ok(items)
+}
@@ -0,0 +1,66 @@
+use url_build_parse::parse_url;
+use crate::{get_host, get_scheme};
+use crate::iecon_dota2_570::get_game_items::{get_api_url, get_resource_filepath, parse_response};
+use crate::client::get_default_client;
+use crate::tests::assert_serde_roundtrip;
+
+#[test]
+fn api_url() {
+    let api_url = get_api_url(Some("en".to_string()));
+
+    let components = parse_url(api_url.as_str()).unwrap();
+
+    assert_eq!(get_scheme(), components.scheme);
+    assert_eq!(get_host(), components.authority.unwrap().host);
+    assert_eq!("/IEconDOTA2_570/GetGameItems/v1", components.path);
+
+    let params = components.query.unwrap();
+    let boxed_language = params.get("language");
+    assert_eq!("en", boxed_language.unwrap().to_string());
+
+    assert!(!params.contains_key("key"));
+}
+
+#[test]
+fn resource_filepath() {
+    let filepath = get_resource_filepath(Some("en".to_string()));
+
+    assert_eq!([get_default_client().cache_dir(), "/IEconDOTA2_570-GetGameItems-v1-language=en.json"].join(""), filepath);
+}
+
+#[test]
+fn parse() {
+    let response = r#"{
+        "result": {
+            "items": [
+                {"id": 1, "name": "item_blink", "cost": 2250, "secret_shop": 0, "side_shop": 0, "recipe": 0, "localized_name": "Blink Dagger"},
+                {"id": 116, "name": "item_recipe_black_king_bar", "cost": 1375, "secret_shop": 0, "side_shop": 0, "recipe": 1}
+            ],
+            "status": 200
+        }
+    }"#.to_string();
+
+    let boxed_parse = parse_response(response);
+    assert!(boxed_parse.is_ok());
+
+    let items = boxed_parse.unwrap();
+    assert_serde_roundtrip(&items);
+    assert_eq!(2, items.len());
+
+    let blink = items.first().unwrap();
+    assert_eq!(1, blink.id);
+    assert_eq!("item_blink", blink.name);
+    assert_eq!(Some(2250), blink.cost);
+    assert_eq!(Some(false), blink.recipe);
+    assert_eq!(Some("Blink Dagger".to_string()), blink.localized_name);
+
+    let recipe = items.get(1).unwrap();
+    assert_eq!(Some(true), recipe.recipe);
+    assert_eq!(None, recipe.localized_name);
+}
+
+#[test]
+fn parse_missing_items() {
+    let boxed_parse = parse_response("{}".to_string());
+    assert!(boxed_parse.is_err());
+}
@@ -1,23 +1,30 @@
 use std::collections::HashMap;
+use std::fmt::Display;
 use serde_json::Value;
-use crate::{iplayer_service, make_api_call};
-use crate::util::{build_steam_api_url, get_steam_web_api_key, json_u64};
+use serde::Deserialize;
+use serde::Serialize;
+use crate::iplayer_service;
+use crate::client::{get_default_client, SteamClient};
+use crate::error::SteamApiError;
+use crate::steam_id::{to_steam_id, SteamId};
+use crate::util::{build_cache_key, build_steam_api_url, json_opt_u64, json_u64};
 
 #[cfg(test)]
 mod tests;
 
-#[derive(PartialEq, Clone, Debug)]
+/// `appid` and `border_color` are only set on game badges.
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
 pub struct Badge {
     pub badgeid: u64,
     pub level: u64,
     pub completion_time: u64,
     pub xp: u64,
     pub scarcity: u64,
-    pub appid: u64,
-    pub border_color: u64,
+    pub appid: Option<u64>,
+    pub border_color: Option<u64>,
 }
 
-#[derive(PartialEq, Clone, Debug)]
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
 pub struct Badges {
     pub badges: Vec<Badge>,
     pub player_xp: u64,
@@ -34,38 +41,100 @@ pub fn get_version() -> String {
     "v1".to_string()
 }
 
-pub fn get(steamid: u64) -> Result<Badges, String> {
+pub fn get_endpoint() -> String {
+    [iplayer_service::get_interface(), "/".to_string(), get_method_name()].join("")
+}
+
+pub fn get(steamid: impl TryInto<SteamId, Error = impl Display>) -> Result<Badges, SteamApiError> {
+    get_with_client(get_default_client().as_ref(), steamid)
+}
+
+/// Async version of `get`, available with the `async` feature.
+#[cfg(feature = "async")]
+pub async fn get_async(steamid: impl TryInto<SteamId, Error = impl Display>) -> Result<Badges, SteamApiError> {
+    get_async_with_client(get_default_client().as_ref(), steamid).await
+}
+
+pub fn get_with_client(client: &SteamClient, steamid: impl TryInto<SteamId, Error = impl Display>) -> Result<Badges, SteamApiError> {
+    let steamid = to_steam_id(get_endpoint().as_str(), steamid)?;
+    let cache_key = get_cache_key(steamid);
     let api_url = get_api_url(steamid);
-    let boxed_response = make_api_call(api_url);
-    if boxed_response.is_err() {
-        return Err(boxed_response.err().unwrap());
-    }
+    client.fetch_and_cache(get_endpoint().as_str(), cache_key.as_str(), api_url, parse_response)
+}
+
+/// Async version of `get_with_client`, available with the `async` feature.
+#[cfg(feature = "async")]
+pub async fn get_async_with_client(client: &SteamClient, steamid: impl TryInto<SteamId, Error = impl Display>) -> Result<Badges, SteamApiError> {
+    let steamid = to_steam_id(get_endpoint().as_str(), steamid)?;
+    let cache_key = get_cache_key(steamid);
+    let api_url = get_api_url(steamid);
+    client.fetch_and_cache_async(get_endpoint().as_str(), cache_key.as_str(), api_url, parse_response).await
+}
 
-    parse_response(boxed_response.unwrap())
+/// Like `get`, but serves the cached response while it is fresh as per the client's
+/// `cache::CachePolicy`, otherwise calls Steam and caches the response.
+pub fn get_cached(steamid: impl TryInto<SteamId, Error = impl Display>) -> Result<Badges, SteamApiError> {
+    get_cached_with_client(get_default_client().as_ref(), steamid)
 }
 
-pub fn get_api_url(steamid: u64) -> String {
+/// Async version of `get_cached`, available with the `async` feature.
+#[cfg(feature = "async")]
+pub async fn get_cached_async(steamid: impl TryInto<SteamId, Error = impl Display>) -> Result<Badges, SteamApiError> {
+    get_cached_async_with_client(get_default_client().as_ref(), steamid).await
+}
+
+pub fn get_cached_with_client(client: &SteamClient, steamid: impl TryInto<SteamId, Error = impl Display>) -> Result<Badges, SteamApiError> {
+    let steamid = to_steam_id(get_endpoint().as_str(), steamid)?;
+    client.get_cached_or_fetch(
+        get_endpoint().as_str(),
+        get_cache_key(steamid).as_str(),
+        parse_response,
+        || get_with_client(client, steamid),
+    )
+}
+
+/// Async version of `get_cached_with_client`, available with the `async` feature.
+#[cfg(feature = "async")]
+pub async fn get_cached_async_with_client(client: &SteamClient, steamid: impl TryInto<SteamId, Error = impl Display>) -> Result<Badges, SteamApiError> {
+    let steamid = to_steam_id(get_endpoint().as_str(), steamid)?;
+    client.get_cached_or_fetch_async(
+        get_endpoint().as_str(),
+        get_cache_key(steamid).as_str(),
+        parse_response,
+        get_async_with_client(client, steamid),
+    ).await
+}
+
+pub fn get_api_url(steamid: SteamId) -> String {
     let interface = iplayer_service::get_interface();
     let method = get_method_name();
     let version = get_version();
 
     let mut params_map = HashMap::new();
     params_map.insert("steamid".to_string(), steamid.to_string());
-    params_map.insert("key".to_string(), get_steam_web_api_key());
 
     build_steam_api_url(interface.as_str(), method.as_str(), version.as_str(), params_map)
 }
 
-pub fn parse_response(response: String) -> Result<Badges, String> {
+/// Returns path of the cached resource relative to the cache directory.
+pub fn get_cache_key(steamid: SteamId) -> String {
+    build_cache_key(iplayer_service::get_interface().as_str(), get_method_name().as_str(), get_version().as_str(), vec![("steamid", steamid.to_string())])
+}
+
+pub fn get_resource_filepath(steamid: SteamId) -> String {
+    get_default_client().get_resource_filepath(get_cache_key(steamid).as_str())
+}
+
+pub fn parse_response(response: String) -> Result<Badges, SteamApiError> {
     let boxed_initial_parse = serde_json::from_str(&response);
     if boxed_initial_parse.is_err() {
-        return Err(boxed_initial_parse.err().unwrap().to_string());
+        return Err(SteamApiError::malformed_response(get_endpoint().as_str(), boxed_initial_parse.err().unwrap().to_string().as_str()));
     }
     let json: Value = boxed_initial_parse.unwrap();
 
     let boxed_result = json.get("response");
     if boxed_result.is_none() {
-        return Err("response does not contain a result".to_string());
+        return Err(SteamApiError::malformed_response(get_endpoint().as_str(), "response does not contain a result"));
     }
     let result = boxed_result.unwrap();
 
@@ -79,8 +148,8 @@ pub fn parse_response(response: String) -> Result<Badges, String> {
                 completion_time: json_u64(badge, "completion_time"),
                 xp: json_u64(badge, "xp"),
                 scarcity: json_u64(badge, "scarcity"),
-                appid: json_u64(badge, "appid"),
-                border_color: json_u64(badge, "border_color"),
+                appid: json_opt_u64(badge, "appid"),
+                border_color: json_opt_u64(badge, "border_color"),
             });
         }
     }
@@ -1,11 +1,13 @@
 use url_build_parse::parse_url;
 use crate::{get_host, get_scheme};
-use crate::iplayer_service::get_badges::{get_api_url, parse_response};
-use crate::util::get_steam_web_api_key;
+use crate::iplayer_service::get_badges::{get_api_url, get_resource_filepath, parse_response};
+use crate::steam_id::SteamId;
+use crate::client::get_default_client;
+use crate::tests::assert_serde_roundtrip;
 
 #[test]
 fn api_url() {
-    let api_url = get_api_url(76561197960435530);
+    let api_url = get_api_url(SteamId::try_from(76561197960435530).unwrap());
 
     let components = parse_url(api_url.as_str()).unwrap();
 
@@ -17,8 +19,14 @@ fn api_url() {
     let boxed_steamid = params.get("steamid");
     assert_eq!(76561197960435530, boxed_steamid.unwrap().parse::<u64>().unwrap());
 
-    let boxed_key = params.get("key");
-    assert_eq!(get_steam_web_api_key(), boxed_key.unwrap().to_string());
+    assert!(!params.contains_key("key"));
+}
+
+#[test]
+fn resource_filepath() {
+    let filepath = get_resource_filepath(SteamId::try_from(76561197960435530).unwrap());
+
+    assert_eq!([get_default_client().cache_dir(), "/IPlayerService-GetBadges-v1-steamid=76561197960435530.json"].join(""), filepath);
 }
 
 #[test]
@@ -47,6 +55,7 @@ fn parse() {
     assert!(boxed_parse.is_ok());
 
     let badges = boxed_parse.unwrap();
+    assert_serde_roundtrip(&badges);
     assert_eq!(1, badges.badges.len());
     assert_eq!(7401, badges.player_xp);
     assert_eq!(22, badges.player_level);
@@ -54,7 +63,7 @@ fn parse() {
     let badge = badges.badges.get(0).unwrap();
     assert_eq!(1, badge.badgeid);
     assert_eq!(6, badge.level);
-    assert_eq!(440, badge.appid);
+    assert_eq!(Some(440), badge.appid);
 }
 
 #[test]
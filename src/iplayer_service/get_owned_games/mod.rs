@@ -1,25 +1,32 @@
 use std::collections::HashMap;
+use std::fmt::Display;
 use serde_json::Value;
-use crate::{iplayer_service, make_api_call};
-use crate::util::{build_steam_api_url, get_steam_web_api_key, json_bool, json_str, json_u64};
+use serde::Deserialize;
+use serde::Serialize;
+use crate::iplayer_service;
+use crate::client::{get_default_client, SteamClient};
+use crate::error::SteamApiError;
+use crate::steam_id::{to_steam_id, SteamId};
+use crate::util::{build_cache_key, build_steam_api_url, json_opt_bool, json_opt_str, json_opt_u64, json_u64};
 
 #[cfg(test)]
 mod tests;
 
-#[derive(PartialEq, Clone, Debug)]
+/// `name` and `img_icon_url` are only returned with `include_appinfo`.
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
 pub struct OwnedGame {
     pub appid: u64,
-    pub name: String,
+    pub name: Option<String>,
     pub playtime_forever: u64,
-    pub img_icon_url: String,
-    pub has_community_visible_stats: bool,
-    pub playtime_windows_forever: u64,
-    pub playtime_mac_forever: u64,
-    pub playtime_linux_forever: u64,
-    pub rtime_last_played: u64,
+    pub img_icon_url: Option<String>,
+    pub has_community_visible_stats: Option<bool>,
+    pub playtime_windows_forever: Option<u64>,
+    pub playtime_mac_forever: Option<u64>,
+    pub playtime_linux_forever: Option<u64>,
+    pub rtime_last_played: Option<u64>,
 }
 
-#[derive(PartialEq, Clone, Debug)]
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
 pub struct OwnedGames {
     pub game_count: u64,
     pub games: Vec<OwnedGame>,
@@ -33,17 +40,71 @@ pub fn get_version() -> String {
     "v1".to_string()
 }
 
-pub fn get(steamid: u64, include_appinfo: Option<bool>, include_played_free_games: Option<bool>) -> Result<OwnedGames, String> {
+pub fn get_endpoint() -> String {
+    [iplayer_service::get_interface(), "/".to_string(), get_method_name()].join("")
+}
+
+pub fn get(steamid: impl TryInto<SteamId, Error = impl Display>, include_appinfo: Option<bool>, include_played_free_games: Option<bool>) -> Result<OwnedGames, SteamApiError> {
+    get_with_client(get_default_client().as_ref(), steamid, include_appinfo, include_played_free_games)
+}
+
+/// Async version of `get`, available with the `async` feature.
+#[cfg(feature = "async")]
+pub async fn get_async(steamid: impl TryInto<SteamId, Error = impl Display>, include_appinfo: Option<bool>, include_played_free_games: Option<bool>) -> Result<OwnedGames, SteamApiError> {
+    get_async_with_client(get_default_client().as_ref(), steamid, include_appinfo, include_played_free_games).await
+}
+
+pub fn get_with_client(client: &SteamClient, steamid: impl TryInto<SteamId, Error = impl Display>, include_appinfo: Option<bool>, include_played_free_games: Option<bool>) -> Result<OwnedGames, SteamApiError> {
+    let steamid = to_steam_id(get_endpoint().as_str(), steamid)?;
+    let cache_key = get_cache_key(steamid, include_appinfo, include_played_free_games);
     let api_url = get_api_url(steamid, include_appinfo, include_played_free_games);
-    let boxed_response = make_api_call(api_url);
-    if boxed_response.is_err() {
-        return Err(boxed_response.err().unwrap());
-    }
+    client.fetch_and_cache(get_endpoint().as_str(), cache_key.as_str(), api_url, parse_response)
+}
+
+/// Async version of `get_with_client`, available with the `async` feature.
+#[cfg(feature = "async")]
+pub async fn get_async_with_client(client: &SteamClient, steamid: impl TryInto<SteamId, Error = impl Display>, include_appinfo: Option<bool>, include_played_free_games: Option<bool>) -> Result<OwnedGames, SteamApiError> {
+    let steamid = to_steam_id(get_endpoint().as_str(), steamid)?;
+    let cache_key = get_cache_key(steamid, include_appinfo, include_played_free_games);
+    let api_url = get_api_url(steamid, include_appinfo, include_played_free_games);
+    client.fetch_and_cache_async(get_endpoint().as_str(), cache_key.as_str(), api_url, parse_response).await
+}
+
+/// Like `get`, but serves the cached response while it is fresh as per the client's
+/// `cache::CachePolicy`, otherwise calls Steam and caches the response.
+pub fn get_cached(steamid: impl TryInto<SteamId, Error = impl Display>, include_appinfo: Option<bool>, include_played_free_games: Option<bool>) -> Result<OwnedGames, SteamApiError> {
+    get_cached_with_client(get_default_client().as_ref(), steamid, include_appinfo, include_played_free_games)
+}
+
+/// Async version of `get_cached`, available with the `async` feature.
+#[cfg(feature = "async")]
+pub async fn get_cached_async(steamid: impl TryInto<SteamId, Error = impl Display>, include_appinfo: Option<bool>, include_played_free_games: Option<bool>) -> Result<OwnedGames, SteamApiError> {
+    get_cached_async_with_client(get_default_client().as_ref(), steamid, include_appinfo, include_played_free_games).await
+}
 
-    parse_response(boxed_response.unwrap())
+pub fn get_cached_with_client(client: &SteamClient, steamid: impl TryInto<SteamId, Error = impl Display>, include_appinfo: Option<bool>, include_played_free_games: Option<bool>) -> Result<OwnedGames, SteamApiError> {
+    let steamid = to_steam_id(get_endpoint().as_str(), steamid)?;
+    client.get_cached_or_fetch(
+        get_endpoint().as_str(),
+        get_cache_key(steamid, include_appinfo, include_played_free_games).as_str(),
+        parse_response,
+        || get_with_client(client, steamid, include_appinfo, include_played_free_games),
+    )
 }
 
-pub fn get_api_url(steamid: u64, include_appinfo: Option<bool>, include_played_free_games: Option<bool>) -> String {
+/// Async version of `get_cached_with_client`, available with the `async` feature.
+#[cfg(feature = "async")]
+pub async fn get_cached_async_with_client(client: &SteamClient, steamid: impl TryInto<SteamId, Error = impl Display>, include_appinfo: Option<bool>, include_played_free_games: Option<bool>) -> Result<OwnedGames, SteamApiError> {
+    let steamid = to_steam_id(get_endpoint().as_str(), steamid)?;
+    client.get_cached_or_fetch_async(
+        get_endpoint().as_str(),
+        get_cache_key(steamid, include_appinfo, include_played_free_games).as_str(),
+        parse_response,
+        get_async_with_client(client, steamid, include_appinfo, include_played_free_games),
+    ).await
+}
+
+pub fn get_api_url(steamid: SteamId, include_appinfo: Option<bool>, include_played_free_games: Option<bool>) -> String {
     let interface = iplayer_service::get_interface();
     let method = get_method_name();
     let version = get_version();
@@ -52,21 +113,37 @@ pub fn get_api_url(steamid: u64, include_appinfo: Option<bool>, include_played_f
     params_map.insert("steamid".to_string(), steamid.to_string());
     params_map.insert("include_appinfo".to_string(), include_appinfo.unwrap_or(false).to_string());
     params_map.insert("include_played_free_games".to_string(), include_played_free_games.unwrap_or(false).to_string());
-    params_map.insert("key".to_string(), get_steam_web_api_key());
 
     build_steam_api_url(interface.as_str(), method.as_str(), version.as_str(), params_map)
 }
 
-pub fn parse_response(response: String) -> Result<OwnedGames, String> {
+/// Returns path of the cached resource relative to the cache directory.
+pub fn get_cache_key(steamid: SteamId, include_appinfo: Option<bool>, include_played_free_games: Option<bool>) -> String {
+    let mut params = vec![("steamid", steamid.to_string())];
+    if let Some(include_appinfo) = include_appinfo {
+        params.push(("include_appinfo", include_appinfo.to_string()));
+    }
+    if let Some(include_played_free_games) = include_played_free_games {
+        params.push(("include_played_free_games", include_played_free_games.to_string()));
+    }
+
+    build_cache_key(iplayer_service::get_interface().as_str(), get_method_name().as_str(), get_version().as_str(), params)
+}
+
+pub fn get_resource_filepath(steamid: SteamId, include_appinfo: Option<bool>, include_played_free_games: Option<bool>) -> String {
+    get_default_client().get_resource_filepath(get_cache_key(steamid, include_appinfo, include_played_free_games).as_str())
+}
+
+pub fn parse_response(response: String) -> Result<OwnedGames, SteamApiError> {
     let boxed_initial_parse = serde_json::from_str(&response);
     if boxed_initial_parse.is_err() {
-        return Err(boxed_initial_parse.err().unwrap().to_string());
+        return Err(SteamApiError::malformed_response(get_endpoint().as_str(), boxed_initial_parse.err().unwrap().to_string().as_str()));
     }
     let json: Value = boxed_initial_parse.unwrap();
 
     let boxed_result = json.get("response");
     if boxed_result.is_none() {
-        return Err("response does not contain a result".to_string());
+        return Err(SteamApiError::malformed_response(get_endpoint().as_str(), "response does not contain a result"));
     }
     let result = boxed_result.unwrap();
 
@@ -76,14 +153,14 @@ pub fn parse_response(response: String) -> Result<OwnedGames, String> {
         for game in boxed_games.unwrap() {
             games.push(OwnedGame {
                 appid: json_u64(game, "appid"),
-                name: json_str(game, "name"),
+                name: json_opt_str(game, "name"),
                 playtime_forever: json_u64(game, "playtime_forever"),
-                img_icon_url: json_str(game, "img_icon_url"),
-                has_community_visible_stats: json_bool(game, "has_community_visible_stats"),
-                playtime_windows_forever: json_u64(game, "playtime_windows_forever"),
-                playtime_mac_forever: json_u64(game, "playtime_mac_forever"),
-                playtime_linux_forever: json_u64(game, "playtime_linux_forever"),
-                rtime_last_played: json_u64(game, "rtime_last_played"),
+                img_icon_url: json_opt_str(game, "img_icon_url"),
+                has_community_visible_stats: json_opt_bool(game, "has_community_visible_stats"),
+                playtime_windows_forever: json_opt_u64(game, "playtime_windows_forever"),
+                playtime_mac_forever: json_opt_u64(game, "playtime_mac_forever"),
+                playtime_linux_forever: json_opt_u64(game, "playtime_linux_forever"),
+                rtime_last_played: json_opt_u64(game, "rtime_last_played"),
             });
         }
     }
@@ -1,11 +1,13 @@
 use url_build_parse::parse_url;
 use crate::{get_host, get_scheme};
-use crate::iplayer_service::get_owned_games::{get_api_url, parse_response};
-use crate::util::get_steam_web_api_key;
+use crate::iplayer_service::get_owned_games::{get_api_url, get_resource_filepath, parse_response};
+use crate::steam_id::SteamId;
+use crate::client::get_default_client;
+use crate::tests::assert_serde_roundtrip;
 
 #[test]
 fn api_url() {
-    let api_url = get_api_url(76561197960435530, Some(true), Some(true));
+    let api_url = get_api_url(SteamId::try_from(76561197960435530).unwrap(), Some(true), Some(true));
 
     let components = parse_url(api_url.as_str()).unwrap();
 
@@ -20,8 +22,14 @@ fn api_url() {
     let boxed_include_appinfo = params.get("include_appinfo");
     assert_eq!("true", boxed_include_appinfo.unwrap().to_string());
 
-    let boxed_key = params.get("key");
-    assert_eq!(get_steam_web_api_key(), boxed_key.unwrap().to_string());
+    assert!(!params.contains_key("key"));
+}
+
+#[test]
+fn resource_filepath() {
+    let filepath = get_resource_filepath(SteamId::try_from(76561197960435530).unwrap(), Some(true), None);
+
+    assert_eq!([get_default_client().cache_dir(), "/IPlayerService-GetOwnedGames-v1-steamid=76561197960435530-include_appinfo=true.json"].join(""), filepath);
 }
 
 #[test]
@@ -49,14 +57,30 @@ fn parse() {
     assert!(boxed_parse.is_ok());
 
     let owned_games = boxed_parse.unwrap();
+    assert_serde_roundtrip(&owned_games);
     assert_eq!(1, owned_games.game_count);
     assert_eq!(1, owned_games.games.len());
 
     let game = owned_games.games.get(0).unwrap();
     assert_eq!(570, game.appid);
-    assert_eq!("Dota 2", game.name);
+    assert_eq!(Some("Dota 2".to_string()), game.name);
     assert_eq!(12345, game.playtime_forever);
-    assert_eq!(true, game.has_community_visible_stats);
+    assert_eq!(Some(true), game.has_community_visible_stats);
+    assert_eq!(Some(0), game.playtime_mac_forever);
+    assert_eq!(Some(1234567890), game.rtime_last_played);
+}
+
+#[test]
+fn parse_without_appinfo() {
+    let response = r#"{"response": {"game_count": 1, "games": [{"appid": 570, "playtime_forever": 0}]}}"#.to_string();
+
+    let owned_games = parse_response(response).unwrap();
+    let game = owned_games.games.first().unwrap();
+    assert_eq!(570, game.appid);
+    assert_eq!(None, game.name);
+    assert_eq!(None, game.img_icon_url);
+    assert_eq!(None, game.has_community_visible_stats);
+    assert_eq!(None, game.rtime_last_played);
 }
 
 #[test]
@@ -1,12 +1,18 @@
 use std::collections::HashMap;
+use std::fmt::Display;
 use serde_json::Value;
-use crate::{iplayer_service, make_api_call};
-use crate::util::{build_steam_api_url, get_steam_web_api_key, json_str, json_u64};
+use serde::Deserialize;
+use serde::Serialize;
+use crate::iplayer_service;
+use crate::client::{get_default_client, SteamClient};
+use crate::error::SteamApiError;
+use crate::steam_id::{to_steam_id, SteamId};
+use crate::util::{build_cache_key, build_steam_api_url, json_str, json_u64};
 
 #[cfg(test)]
 mod tests;
 
-#[derive(PartialEq, Clone, Debug)]
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
 pub struct RecentlyPlayedGame {
     pub appid: u64,
     pub name: String,
@@ -15,7 +21,7 @@ pub struct RecentlyPlayedGame {
     pub img_icon_url: String,
 }
 
-#[derive(PartialEq, Clone, Debug)]
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
 pub struct RecentlyPlayedGames {
     pub total_count: u64,
     pub games: Vec<RecentlyPlayedGame>,
@@ -29,17 +35,71 @@ pub fn get_version() -> String {
     "v1".to_string()
 }
 
-pub fn get(steamid: u64, count: Option<u32>) -> Result<RecentlyPlayedGames, String> {
+pub fn get_endpoint() -> String {
+    [iplayer_service::get_interface(), "/".to_string(), get_method_name()].join("")
+}
+
+pub fn get(steamid: impl TryInto<SteamId, Error = impl Display>, count: Option<u32>) -> Result<RecentlyPlayedGames, SteamApiError> {
+    get_with_client(get_default_client().as_ref(), steamid, count)
+}
+
+/// Async version of `get`, available with the `async` feature.
+#[cfg(feature = "async")]
+pub async fn get_async(steamid: impl TryInto<SteamId, Error = impl Display>, count: Option<u32>) -> Result<RecentlyPlayedGames, SteamApiError> {
+    get_async_with_client(get_default_client().as_ref(), steamid, count).await
+}
+
+pub fn get_with_client(client: &SteamClient, steamid: impl TryInto<SteamId, Error = impl Display>, count: Option<u32>) -> Result<RecentlyPlayedGames, SteamApiError> {
+    let steamid = to_steam_id(get_endpoint().as_str(), steamid)?;
+    let cache_key = get_cache_key(steamid, count);
     let api_url = get_api_url(steamid, count);
-    let boxed_response = make_api_call(api_url);
-    if boxed_response.is_err() {
-        return Err(boxed_response.err().unwrap());
-    }
+    client.fetch_and_cache(get_endpoint().as_str(), cache_key.as_str(), api_url, parse_response)
+}
+
+/// Async version of `get_with_client`, available with the `async` feature.
+#[cfg(feature = "async")]
+pub async fn get_async_with_client(client: &SteamClient, steamid: impl TryInto<SteamId, Error = impl Display>, count: Option<u32>) -> Result<RecentlyPlayedGames, SteamApiError> {
+    let steamid = to_steam_id(get_endpoint().as_str(), steamid)?;
+    let cache_key = get_cache_key(steamid, count);
+    let api_url = get_api_url(steamid, count);
+    client.fetch_and_cache_async(get_endpoint().as_str(), cache_key.as_str(), api_url, parse_response).await
+}
 
-    parse_response(boxed_response.unwrap())
+/// Like `get`, but serves the cached response while it is fresh as per the client's
+/// `cache::CachePolicy`, otherwise calls Steam and caches the response.
+pub fn get_cached(steamid: impl TryInto<SteamId, Error = impl Display>, count: Option<u32>) -> Result<RecentlyPlayedGames, SteamApiError> {
+    get_cached_with_client(get_default_client().as_ref(), steamid, count)
 }
 
-pub fn get_api_url(steamid: u64, count: Option<u32>) -> String {
+/// Async version of `get_cached`, available with the `async` feature.
+#[cfg(feature = "async")]
+pub async fn get_cached_async(steamid: impl TryInto<SteamId, Error = impl Display>, count: Option<u32>) -> Result<RecentlyPlayedGames, SteamApiError> {
+    get_cached_async_with_client(get_default_client().as_ref(), steamid, count).await
+}
+
+pub fn get_cached_with_client(client: &SteamClient, steamid: impl TryInto<SteamId, Error = impl Display>, count: Option<u32>) -> Result<RecentlyPlayedGames, SteamApiError> {
+    let steamid = to_steam_id(get_endpoint().as_str(), steamid)?;
+    client.get_cached_or_fetch(
+        get_endpoint().as_str(),
+        get_cache_key(steamid, count).as_str(),
+        parse_response,
+        || get_with_client(client, steamid, count),
+    )
+}
+
+/// Async version of `get_cached_with_client`, available with the `async` feature.
+#[cfg(feature = "async")]
+pub async fn get_cached_async_with_client(client: &SteamClient, steamid: impl TryInto<SteamId, Error = impl Display>, count: Option<u32>) -> Result<RecentlyPlayedGames, SteamApiError> {
+    let steamid = to_steam_id(get_endpoint().as_str(), steamid)?;
+    client.get_cached_or_fetch_async(
+        get_endpoint().as_str(),
+        get_cache_key(steamid, count).as_str(),
+        parse_response,
+        get_async_with_client(client, steamid, count),
+    ).await
+}
+
+pub fn get_api_url(steamid: SteamId, count: Option<u32>) -> String {
     let interface = iplayer_service::get_interface();
     let method = get_method_name();
     let version = get_version();
@@ -47,21 +107,34 @@ pub fn get_api_url(steamid: u64, count: Option<u32>) -> String {
     let mut params_map = HashMap::new();
     params_map.insert("steamid".to_string(), steamid.to_string());
     params_map.insert("count".to_string(), count.unwrap_or(0).to_string());
-    params_map.insert("key".to_string(), get_steam_web_api_key());
 
     build_steam_api_url(interface.as_str(), method.as_str(), version.as_str(), params_map)
 }
 
-pub fn parse_response(response: String) -> Result<RecentlyPlayedGames, String> {
+/// Returns path of the cached resource relative to the cache directory.
+pub fn get_cache_key(steamid: SteamId, count: Option<u32>) -> String {
+    let mut params = vec![("steamid", steamid.to_string())];
+    if let Some(count) = count {
+        params.push(("count", count.to_string()));
+    }
+
+    build_cache_key(iplayer_service::get_interface().as_str(), get_method_name().as_str(), get_version().as_str(), params)
+}
+
+pub fn get_resource_filepath(steamid: SteamId, count: Option<u32>) -> String {
+    get_default_client().get_resource_filepath(get_cache_key(steamid, count).as_str())
+}
+
+pub fn parse_response(response: String) -> Result<RecentlyPlayedGames, SteamApiError> {
     let boxed_initial_parse = serde_json::from_str(&response);
     if boxed_initial_parse.is_err() {
-        return Err(boxed_initial_parse.err().unwrap().to_string());
+        return Err(SteamApiError::malformed_response(get_endpoint().as_str(), boxed_initial_parse.err().unwrap().to_string().as_str()));
     }
     let json: Value = boxed_initial_parse.unwrap();
 
     let boxed_result = json.get("response");
     if boxed_result.is_none() {
-        return Err("response does not contain a result".to_string());
+        return Err(SteamApiError::malformed_response(get_endpoint().as_str(), "response does not contain a result"));
     }
     let result = boxed_result.unwrap();
 
@@ -1,11 +1,13 @@
 use url_build_parse::parse_url;
 use crate::{get_host, get_scheme};
-use crate::iplayer_service::get_recently_played_games::{get_api_url, parse_response};
-use crate::util::get_steam_web_api_key;
+use crate::iplayer_service::get_recently_played_games::{get_api_url, get_resource_filepath, parse_response};
+use crate::steam_id::SteamId;
+use crate::client::get_default_client;
+use crate::tests::assert_serde_roundtrip;
 
 #[test]
 fn api_url() {
-    let api_url = get_api_url(76561197960435530, Some(3));
+    let api_url = get_api_url(SteamId::try_from(76561197960435530).unwrap(), Some(3));
 
     let components = parse_url(api_url.as_str()).unwrap();
 
@@ -20,8 +22,14 @@ fn api_url() {
     let boxed_count = params.get("count");
     assert_eq!("3", boxed_count.unwrap().to_string());
 
-    let boxed_key = params.get("key");
-    assert_eq!(get_steam_web_api_key(), boxed_key.unwrap().to_string());
+    assert!(!params.contains_key("key"));
+}
+
+#[test]
+fn resource_filepath() {
+    let filepath = get_resource_filepath(SteamId::try_from(76561197960435530).unwrap(), Some(2));
+
+    assert_eq!([get_default_client().cache_dir(), "/IPlayerService-GetRecentlyPlayedGames-v1-steamid=76561197960435530-count=2.json"].join(""), filepath);
 }
 
 #[test]
@@ -45,6 +53,7 @@ fn parse() {
     assert!(boxed_parse.is_ok());
 
     let recently_played = boxed_parse.unwrap();
+    assert_serde_roundtrip(&recently_played);
     assert_eq!(1, recently_played.total_count);
     assert_eq!(1, recently_played.games.len());
 
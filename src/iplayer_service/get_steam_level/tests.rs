@@ -1,11 +1,12 @@
 use url_build_parse::parse_url;
 use crate::{get_host, get_scheme};
-use crate::iplayer_service::get_steam_level::{get_api_url, parse_response};
-use crate::util::get_steam_web_api_key;
+use crate::iplayer_service::get_steam_level::{get_api_url, get_resource_filepath, parse_response};
+use crate::steam_id::SteamId;
+use crate::client::get_default_client;
 
 #[test]
 fn api_url() {
-    let api_url = get_api_url(76561197960435530);
+    let api_url = get_api_url(SteamId::try_from(76561197960435530).unwrap());
 
     let components = parse_url(api_url.as_str()).unwrap();
 
@@ -17,8 +18,23 @@ fn api_url() {
     let boxed_steamid = params.get("steamid");
     assert_eq!(76561197960435530, boxed_steamid.unwrap().parse::<u64>().unwrap());
 
-    let boxed_key = params.get("key");
-    assert_eq!(get_steam_web_api_key(), boxed_key.unwrap().to_string());
+    assert!(!params.contains_key("key"));
+}
+
+#[test]
+fn api_url_accepts_every_steam_id_format() {
+    let params = parse_url(get_api_url(SteamId::try_from(76561197960435530).unwrap()).as_str()).unwrap().query;
+
+    assert_eq!(params, parse_url(get_api_url(SteamId::from_account_id(169802)).as_str()).unwrap().query);
+    assert_eq!(params, parse_url(get_api_url(SteamId::parse("STEAM_0:0:84901").unwrap()).as_str()).unwrap().query);
+    assert_eq!(params, parse_url(get_api_url(SteamId::parse("[U:1:169802]").unwrap()).as_str()).unwrap().query);
+}
+
+#[test]
+fn resource_filepath() {
+    let filepath = get_resource_filepath(SteamId::try_from(76561197960435530).unwrap());
+
+    assert_eq!([get_default_client().cache_dir(), "/IPlayerService-GetSteamLevel-v1-steamid=76561197960435530.json"].join(""), filepath);
 }
 
 #[test]
@@ -2,7 +2,7 @@ use std::collections::HashMap;
 use serde::Deserialize;
 use serde::Serialize;
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
 pub struct SteamApp {
     pub appid: i64,
     pub name: String,
@@ -10,11 +10,9 @@ pub struct SteamApp {
 
 use serde_json::Value;
 use crate::{isteam_apps, util};
-use std::fs;
-use std::fs::{File, read_to_string};
-use std::io::Write;
-use std::path::Path;
-use crate::util::{get_cache_dir_path, get_json_filetype};
+use crate::client::{get_default_client, SteamClient};
+use crate::error::SteamApiError;
+use crate::util::{get_json_filetype};
 
 #[cfg(test)]
 mod tests;
@@ -25,22 +23,82 @@ mod tests;
 /// # Examples
 ///
 /// ```
+/// # use std::sync::Arc;
+/// # use steam_webapi_rust_sdk::client::{set_default_client, SteamClient};
+/// # use steam_webapi_rust_sdk::transport::FakeTransport;
+/// # let transport = FakeTransport::new();
+/// # transport.respond_with(
+/// #     "https://api.steampowered.com/ISteamApps/GetAppList/v2?key=KEY",
+/// #     200,
+/// #     r#"{"applist":{"apps":[{"appid":570,"name":"Dota 2"}]}}"#,
+/// # );
+/// # let cache_dir = std::env::temp_dir().join("steam-webapi-rust-sdk-doctest");
+/// # set_default_client(SteamClient::builder()
+/// #     .api_key("KEY")
+/// #     .cache_enabled(false)
+/// #     .cache_dir(cache_dir.to_str().unwrap())
+/// #     .transport(Arc::new(transport))
+/// #     .build());
+/// #
 /// let app_list = steam_webapi_rust_sdk::isteam_apps::get_app_list::get().unwrap();
 ///
 /// assert!(app_list.len() > 0);
 /// ```
-pub fn get() -> Result<Vec<SteamApp>, String> {
-    let api_response = make_api_call();
-    parse_api_call_result(api_response)
+pub fn get() -> Result<Vec<SteamApp>, SteamApiError> {
+    get_with_client(get_default_client().as_ref())
 }
 
-/// Will get cached response if present, otherwise retrieves apps
+/// Async version of `get`, available with the `async` feature.
+#[cfg(feature = "async")]
+pub async fn get_async() -> Result<Vec<SteamApp>, SteamApiError> {
+    get_async_with_client(get_default_client().as_ref()).await
+}
+
+pub fn get_with_client(client: &SteamClient) -> Result<Vec<SteamApp>, SteamApiError> {
+    client.fetch_and_cache(
+        get_endpoint().as_str(),
+        get_cache_key().as_str(),
+        get_api_url(),
+        parse_api_call_result,
+    )
+}
+
+/// Async version of `get_with_client`, available with the `async` feature.
+#[cfg(feature = "async")]
+pub async fn get_async_with_client(client: &SteamClient) -> Result<Vec<SteamApp>, SteamApiError> {
+    client.fetch_and_cache_async(
+        get_endpoint().as_str(),
+        get_cache_key().as_str(),
+        get_api_url(),
+        parse_api_call_result,
+    ).await
+}
+
+/// Will get cached response if present and not older than a day, otherwise retrieves apps
 /// available on the Steam store
-/// and puts it to local cache folder.
+/// and puts it to local cache folder. See `cache::CachePolicy` to change the time to live or
+/// the read mode.
 ///
 /// # Examples
 ///
 /// ```
+/// # use std::sync::Arc;
+/// # use steam_webapi_rust_sdk::client::{set_default_client, SteamClient};
+/// # use steam_webapi_rust_sdk::transport::FakeTransport;
+/// # let transport = FakeTransport::new();
+/// # transport.respond_with(
+/// #     "https://api.steampowered.com/ISteamApps/GetAppList/v2?key=KEY",
+/// #     200,
+/// #     r#"{"applist":{"apps":[{"appid":570,"name":"Dota 2"}]}}"#,
+/// # );
+/// # let cache_dir = std::env::temp_dir().join("steam-webapi-rust-sdk-doctest");
+/// # set_default_client(SteamClient::builder()
+/// #     .api_key("KEY")
+/// #     .cache_enabled(false)
+/// #     .cache_dir(cache_dir.to_str().unwrap())
+/// #     .transport(Arc::new(transport))
+/// #     .build());
+/// #
 /// let app_list = steam_webapi_rust_sdk::isteam_apps::get_app_list::get_cached().unwrap();
 ///
 /// assert!(app_list.len() > 0);
@@ -49,18 +107,34 @@ pub fn get() -> Result<Vec<SteamApp>, String> {
 ///
 /// assert!(steam_app.name.len() > 0);
 /// ```
-pub fn get_cached() -> Result<Vec<SteamApp>, String> {
-    let filepath = get_resource_filepath();
+pub fn get_cached() -> Result<Vec<SteamApp>, SteamApiError> {
+    get_cached_with_client(get_default_client().as_ref())
+}
 
-    let boxed_read = read_to_string(filepath);
-    let is_readable = boxed_read.is_ok();
-    if is_readable {
-        let cached_api_response = boxed_read.unwrap();
-        parse_api_call_result(cached_api_response)
-    } else {
-        get()
-    }
+/// Async version of `get_cached`, available with the `async` feature.
+#[cfg(feature = "async")]
+pub async fn get_cached_async() -> Result<Vec<SteamApp>, SteamApiError> {
+    get_cached_async_with_client(get_default_client().as_ref()).await
+}
+
+pub fn get_cached_with_client(client: &SteamClient) -> Result<Vec<SteamApp>, SteamApiError> {
+    client.get_cached_or_fetch(
+        get_endpoint().as_str(),
+        get_cache_key().as_str(),
+        parse_api_call_result,
+        || get_with_client(client),
+    )
+}
 
+/// Async version of `get_cached_with_client`, available with the `async` feature.
+#[cfg(feature = "async")]
+pub async fn get_cached_async_with_client(client: &SteamClient) -> Result<Vec<SteamApp>, SteamApiError> {
+    client.get_cached_or_fetch_async(
+        get_endpoint().as_str(),
+        get_cache_key().as_str(),
+        parse_api_call_result,
+        get_async_with_client(client),
+    ).await
 }
 
 /// Returns method name invoked on Steam API.
@@ -90,8 +164,13 @@ pub fn get_version() -> String {
     "v2".to_string()
 }
 
+pub fn get_endpoint() -> String {
+    [isteam_apps::get_interface(), "/".to_string(), get_method_name()].join("")
+}
+
 
-/// Returns path to the cached resource invoked on Steam API.
+/// Returns path to the cached resource invoked on Steam API, inside the cache directory of the
+/// default client (see `util::get_cache_dir_path`).
 /// The resource on the given filepath can be absent
 /// if the get method was not invoked previously.
 ///
@@ -99,20 +178,25 @@ pub fn get_version() -> String {
 /// # Examples
 ///
 /// ```
-/// let version = steam_webapi_rust_sdk::isteam_apps::get_app_list::get_resource_filepath();
+/// # use steam_webapi_rust_sdk::client::{set_default_client, SteamClient};
+/// # set_default_client(SteamClient::builder().cache_dir("/var/cache/steam").build());
+/// let filepath = steam_webapi_rust_sdk::isteam_apps::get_app_list::get_resource_filepath();
 ///
-/// assert!(version == "steam-webapi-cache/ISteamApps-GetAppList-v2.json".to_string());
+/// assert_eq!("/var/cache/steam/ISteamApps-GetAppList-v2.json", filepath);
 /// ```
 pub fn get_resource_filepath() -> String {
+    let filepath = get_default_client().get_resource_filepath(get_cache_key().as_str());
+
+    filepath
+}
+
+/// Returns path of the cached resource relative to the cache directory.
+pub fn get_cache_key() -> String {
     let  interface = isteam_apps::get_interface();
     let  method = get_method_name();
     let  version = get_version();
 
-    let resource = [interface, "-".to_string(), method, "-".to_string(), version, ".".to_string(), get_json_filetype()].join("");
-
-    let filepath = [get_cache_dir_path(), "/".to_string(), resource].join("");
-
-    filepath
+    [interface, "-".to_string(), method, "-".to_string(), version, ".".to_string(), get_json_filetype()].join("")
 }
 
 /// Returns API url invoked on Steam API.
@@ -123,10 +207,7 @@ pub fn get_resource_filepath() -> String {
 /// ```
 /// let api_url = steam_webapi_rust_sdk::isteam_apps::get_app_list::get_api_url();
 ///
-/// let steam_web_api_key = steam_webapi_rust_sdk::util::get_steam_web_api_key();
-/// let expected_api_url = ["https://api.steampowered.com/ISteamApps/GetAppList/v2?key=".to_string(), steam_web_api_key].join("");
-///
-/// assert_eq!(api_url, expected_api_url.to_string());
+/// assert_eq!("https://api.steampowered.com/ISteamApps/GetAppList/v2", api_url);
 /// ```
 pub fn get_api_url() -> String {
     let  interface = isteam_apps::get_interface();
@@ -145,30 +226,46 @@ pub fn get_api_url() -> String {
 /// # Examples
 ///
 /// ```
-/// let response = steam_webapi_rust_sdk::isteam_apps::get_app_list::make_api_call();
+/// # use std::sync::Arc;
+/// # use steam_webapi_rust_sdk::client::{set_default_client, SteamClient};
+/// # use steam_webapi_rust_sdk::transport::FakeTransport;
+/// # let transport = FakeTransport::new();
+/// # transport.respond_with(
+/// #     "https://api.steampowered.com/ISteamApps/GetAppList/v2?key=KEY",
+/// #     200,
+/// #     r#"{"applist":{"apps":[{"appid":570,"name":"Dota 2"}]}}"#,
+/// # );
+/// # let cache_dir = std::env::temp_dir().join("steam-webapi-rust-sdk-doctest");
+/// # set_default_client(SteamClient::builder()
+/// #     .api_key("KEY")
+/// #     .cache_enabled(false)
+/// #     .cache_dir(cache_dir.to_str().unwrap())
+/// #     .transport(Arc::new(transport))
+/// #     .build());
+/// #
+/// let response = steam_webapi_rust_sdk::isteam_apps::get_app_list::make_api_call().unwrap();
 /// assert!(response.len()>0);
 /// ```
-pub fn make_api_call() -> String {
-    let url = get_api_url();
-
-    let response = minreq::get(url).send();
-    let raw_response : Vec<u8> = response.unwrap().into_bytes();
-    let response_string = String::from_utf8(raw_response).unwrap();
-
-    let filepath = get_resource_filepath();
+pub fn make_api_call() -> Result<String, SteamApiError> {
+    make_api_call_with_client(get_default_client().as_ref())
+}
 
-    let mut file: File;
-    let directory_exists = Path::new(get_cache_dir_path().as_str()).is_dir();
-    if !directory_exists {
-        fs::create_dir_all(get_cache_dir_path()).unwrap();
-        file = File::create(filepath).unwrap();
-    } else {
-        file = File::create(filepath).unwrap();
-    }
+/// Async version of `make_api_call`, available with the `async` feature.
+#[cfg(feature = "async")]
+pub async fn make_api_call_async() -> Result<String, SteamApiError> {
+    make_api_call_async_with_client(get_default_client().as_ref()).await
+}
 
-    file.write_all(response_string.as_ref()).unwrap();
+pub fn make_api_call_with_client(client: &SteamClient) -> Result<String, SteamApiError> {
+    let url = get_api_url();
+    client.make_api_call(get_endpoint().as_str(), url)
+}
 
-    response_string
+/// Async version of `make_api_call_with_client`, available with the `async` feature.
+#[cfg(feature = "async")]
+pub async fn make_api_call_async_with_client(client: &SteamClient) -> Result<String, SteamApiError> {
+    let url = get_api_url();
+    client.make_api_call_async(get_endpoint().as_str(), url).await
 }
 
 /// Parses API call response body.
@@ -177,7 +274,24 @@ pub fn make_api_call() -> String {
 /// # Examples
 ///
 /// ```
-/// let response = steam_webapi_rust_sdk::isteam_apps::get_app_list::make_api_call();
+/// # use std::sync::Arc;
+/// # use steam_webapi_rust_sdk::client::{set_default_client, SteamClient};
+/// # use steam_webapi_rust_sdk::transport::FakeTransport;
+/// # let transport = FakeTransport::new();
+/// # transport.respond_with(
+/// #     "https://api.steampowered.com/ISteamApps/GetAppList/v2?key=KEY",
+/// #     200,
+/// #     r#"{"applist":{"apps":[{"appid":570,"name":"Dota 2"}]}}"#,
+/// # );
+/// # let cache_dir = std::env::temp_dir().join("steam-webapi-rust-sdk-doctest");
+/// # set_default_client(SteamClient::builder()
+/// #     .api_key("KEY")
+/// #     .cache_enabled(false)
+/// #     .cache_dir(cache_dir.to_str().unwrap())
+/// #     .transport(Arc::new(transport))
+/// #     .build());
+/// #
+/// let response = steam_webapi_rust_sdk::isteam_apps::get_app_list::make_api_call().unwrap();
 /// let boxed_steam_app_list = steam_webapi_rust_sdk::isteam_apps::get_app_list::parse_api_call_result(response);
 /// assert!(boxed_steam_app_list.is_ok());
 ///
@@ -188,14 +302,22 @@ pub fn make_api_call() -> String {
 /// assert!(steam_app.appid > 0);
 /// assert!(steam_app.name.len() > 0);
 /// ```
-pub fn parse_api_call_result(response_string: String) -> Result<Vec<SteamApp>, String> {
-    let mut json: Value = serde_json::from_str(&response_string).unwrap();
+pub fn parse_api_call_result(response_string: String) -> Result<Vec<SteamApp>, SteamApiError> {
+    let boxed_initial_parse = serde_json::from_str(&response_string);
+    if boxed_initial_parse.is_err() {
+        return Err(SteamApiError::malformed_response(get_endpoint().as_str(), boxed_initial_parse.err().unwrap().to_string().as_str()));
+    }
+    let mut json: Value = boxed_initial_parse.unwrap();
 
     let mut applist = json["applist"].take();
 
     let apps : Value = applist["apps"].take();
 
-    let list : Vec<SteamApp> = serde_json::from_value(apps).unwrap();
+    let boxed_list = serde_json::from_value(apps);
+    if boxed_list.is_err() {
+        return Err(SteamApiError::malformed_response(get_endpoint().as_str(), boxed_list.err().unwrap().to_string().as_str()));
+    }
+    let list : Vec<SteamApp> = boxed_list.unwrap();
 
     let filtered_list = list
         .into_iter()
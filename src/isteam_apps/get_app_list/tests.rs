@@ -1,20 +1,44 @@
-use crate::{get_app_list, isteam_apps, util};
+use std::fs::read_to_string;
+use std::sync::Arc;
+use crate::{isteam_apps, util};
+use crate::client::SteamClient;
+use crate::transport::FakeTransport;
+use crate::client::get_default_client;
+use crate::tests::assert_serde_roundtrip;
+
+fn get_fake_client(test_name: &str) -> SteamClient {
+    let transport = FakeTransport::new();
+    let app_list = read_to_string("test/isteam_apps/get_app_list/applist.json").unwrap();
+    transport.respond_with("https://api.steampowered.com/ISteamApps/GetAppList/v2?key=KEY", 200, app_list.as_str());
+
+    let cache_dir = std::env::temp_dir()
+        .join(["steam-webapi-rust-sdk-", test_name, "-", std::process::id().to_string().as_str()].join(""));
+
+    SteamClient::builder()
+        .api_key("KEY")
+        .cache_dir(cache_dir.to_str().unwrap())
+        .transport(Arc::new(transport))
+        .build()
+}
 
 #[cfg(test)]
 
 #[test]
 fn test_make_api_call() {
-    let response = isteam_apps::get_app_list::make_api_call();
+    let client = get_fake_client("get_app_list_test_make_api_call");
+    let response = isteam_apps::get_app_list::make_api_call_with_client(&client).unwrap();
     assert!(response.len()>0);
 }
 
 #[test]
 fn test_parse_api_call_result() {
-    let response = isteam_apps::get_app_list::make_api_call();
+    let client = get_fake_client("get_app_list_test_parse_api_call_result");
+    let response = isteam_apps::get_app_list::make_api_call_with_client(&client).unwrap();
     let boxed_steam_app_list = isteam_apps::get_app_list::parse_api_call_result(response);
     assert!(boxed_steam_app_list.is_ok());
 
     let steam_app_list = boxed_steam_app_list.unwrap();
+    assert_serde_roundtrip(&steam_app_list);
 
     assert!(steam_app_list.len()>0);
     let steam_app = steam_app_list.get(0).unwrap();
@@ -26,14 +50,17 @@ fn test_parse_api_call_result() {
 
 #[test]
 fn test_get() {
+    let client = get_fake_client("get_app_list_test_get");
+    let resource_filepath = client.get_resource_filepath(isteam_apps::get_app_list::get_cache_key().as_str());
+
     let mut cache_timestamp = 0;
-    let boxed_metadata = std::fs::metadata(isteam_apps::get_app_list::get_resource_filepath());
+    let boxed_metadata = std::fs::metadata(resource_filepath.as_str());
     if boxed_metadata.is_ok() {
         let system_time = boxed_metadata.unwrap().modified().unwrap();
         cache_timestamp = util::as_unix_timestamp(system_time);
     }
 
-    let boxed_steam_app_list = get_app_list();
+    let boxed_steam_app_list = isteam_apps::get_app_list::get_with_client(&client);
     assert!(boxed_steam_app_list.is_ok());
 
     let steam_app_list = boxed_steam_app_list.unwrap();
@@ -44,7 +71,7 @@ fn test_get() {
     assert!(steam_app.name.len() > 0);
 
     let mut latest_cache_timestamp = 0;
-    let boxed_metadata = std::fs::metadata(isteam_apps::get_app_list::get_resource_filepath());
+    let boxed_metadata = std::fs::metadata(resource_filepath.as_str());
     if boxed_metadata.is_ok() {
         let system_time = boxed_metadata.unwrap().modified().unwrap();
         latest_cache_timestamp = util::as_unix_timestamp(system_time);
@@ -56,7 +83,8 @@ fn test_get() {
 
 #[test]
 fn test_get_cached() {
-    let steam_app_list = isteam_apps::get_app_list::get_cached().unwrap();
+    let client = get_fake_client("get_app_list_test_get_cached");
+    let steam_app_list = isteam_apps::get_app_list::get_cached_with_client(&client).unwrap();
 
     assert!(steam_app_list.len()>0);
     let steam_app = steam_app_list.get(0).unwrap();
@@ -76,15 +104,12 @@ fn test_method_name() {
 fn test_get_resource_filepath() {
     let resource_filepath = isteam_apps::get_app_list::get_resource_filepath();
 
-    assert_eq!(resource_filepath, "steam-webapi-cache/ISteamApps-GetAppList-v2.json".to_string());
+    assert_eq!(resource_filepath, [get_default_client().cache_dir(), "/ISteamApps-GetAppList-v2.json"].join(""));
 }
 
 #[test]
 fn test_get_api_url() {
     let api_url = isteam_apps::get_app_list::get_api_url();
 
-    let steam_web_api_key = util::get_steam_web_api_key();
-    let expected_api_url = ["https://api.steampowered.com/ISteamApps/GetAppList/v2?key=".to_string(), steam_web_api_key].join("");
-
-    assert_eq!(api_url, expected_api_url.to_string());
+    assert_eq!(api_url, "https://api.steampowered.com/ISteamApps/GetAppList/v2");
 }
\ No newline at end of file
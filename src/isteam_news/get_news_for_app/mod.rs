@@ -1,12 +1,16 @@
 use std::collections::HashMap;
 use serde_json::Value;
-use crate::{isteam_news, make_api_call};
-use crate::util::{build_steam_api_url, get_steam_web_api_key, json_bool, json_str, json_u64};
+use serde::Deserialize;
+use serde::Serialize;
+use crate::isteam_news;
+use crate::client::{get_default_client, SteamClient};
+use crate::error::SteamApiError;
+use crate::util::{build_cache_key, build_steam_api_url, json_bool, json_str, json_u64};
 
 #[cfg(test)]
 mod tests;
 
-#[derive(PartialEq, Clone, Debug)]
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
 pub struct NewsItem {
     pub gid: String,
     pub title: String,
@@ -27,15 +31,65 @@ pub fn get_version() -> String {
     "v2".to_string()
 }
 
+pub fn get_endpoint() -> String {
+    [isteam_news::get_interface(), "/".to_string(), get_method_name()].join("")
+}
+
 /// Does not require a Steam Web API key.
-pub fn get(appid: i64, count: Option<u32>, maxlength: Option<u32>) -> Result<Vec<NewsItem>, String> {
+pub fn get(appid: i64, count: Option<u32>, maxlength: Option<u32>) -> Result<Vec<NewsItem>, SteamApiError> {
+    get_with_client(get_default_client().as_ref(), appid, count, maxlength)
+}
+
+/// Async version of `get`, available with the `async` feature.
+#[cfg(feature = "async")]
+pub async fn get_async(appid: i64, count: Option<u32>, maxlength: Option<u32>) -> Result<Vec<NewsItem>, SteamApiError> {
+    get_async_with_client(get_default_client().as_ref(), appid, count, maxlength).await
+}
+
+pub fn get_with_client(client: &SteamClient, appid: i64, count: Option<u32>, maxlength: Option<u32>) -> Result<Vec<NewsItem>, SteamApiError> {
+    let cache_key = get_cache_key(appid, count, maxlength);
     let api_url = get_api_url(appid, count, maxlength);
-    let boxed_response = make_api_call(api_url);
-    if boxed_response.is_err() {
-        return Err(boxed_response.err().unwrap());
-    }
+    client.fetch_and_cache(get_endpoint().as_str(), cache_key.as_str(), api_url, parse_response)
+}
+
+/// Async version of `get_with_client`, available with the `async` feature.
+#[cfg(feature = "async")]
+pub async fn get_async_with_client(client: &SteamClient, appid: i64, count: Option<u32>, maxlength: Option<u32>) -> Result<Vec<NewsItem>, SteamApiError> {
+    let cache_key = get_cache_key(appid, count, maxlength);
+    let api_url = get_api_url(appid, count, maxlength);
+    client.fetch_and_cache_async(get_endpoint().as_str(), cache_key.as_str(), api_url, parse_response).await
+}
+
+/// Like `get`, but serves the cached response while it is fresh as per the client's
+/// `cache::CachePolicy`, otherwise calls Steam and caches the response.
+pub fn get_cached(appid: i64, count: Option<u32>, maxlength: Option<u32>) -> Result<Vec<NewsItem>, SteamApiError> {
+    get_cached_with_client(get_default_client().as_ref(), appid, count, maxlength)
+}
+
+/// Async version of `get_cached`, available with the `async` feature.
+#[cfg(feature = "async")]
+pub async fn get_cached_async(appid: i64, count: Option<u32>, maxlength: Option<u32>) -> Result<Vec<NewsItem>, SteamApiError> {
+    get_cached_async_with_client(get_default_client().as_ref(), appid, count, maxlength).await
+}
 
-    parse_response(boxed_response.unwrap())
+pub fn get_cached_with_client(client: &SteamClient, appid: i64, count: Option<u32>, maxlength: Option<u32>) -> Result<Vec<NewsItem>, SteamApiError> {
+    client.get_cached_or_fetch(
+        get_endpoint().as_str(),
+        get_cache_key(appid, count, maxlength).as_str(),
+        parse_response,
+        || get_with_client(client, appid, count, maxlength),
+    )
+}
+
+/// Async version of `get_cached_with_client`, available with the `async` feature.
+#[cfg(feature = "async")]
+pub async fn get_cached_async_with_client(client: &SteamClient, appid: i64, count: Option<u32>, maxlength: Option<u32>) -> Result<Vec<NewsItem>, SteamApiError> {
+    client.get_cached_or_fetch_async(
+        get_endpoint().as_str(),
+        get_cache_key(appid, count, maxlength).as_str(),
+        parse_response,
+        get_async_with_client(client, appid, count, maxlength),
+    ).await
 }
 
 pub fn get_api_url(appid: i64, count: Option<u32>, maxlength: Option<u32>) -> String {
@@ -51,21 +105,37 @@ pub fn get_api_url(appid: i64, count: Option<u32>, maxlength: Option<u32>) -> St
     if maxlength.is_some() {
         params_map.insert("maxlength".to_string(), maxlength.unwrap().to_string());
     }
-    params_map.insert("key".to_string(), get_steam_web_api_key());
 
     build_steam_api_url(interface.as_str(), method.as_str(), version.as_str(), params_map)
 }
 
-pub fn parse_response(response: String) -> Result<Vec<NewsItem>, String> {
+/// Returns path of the cached resource relative to the cache directory.
+pub fn get_cache_key(appid: i64, count: Option<u32>, maxlength: Option<u32>) -> String {
+    let mut params = vec![("appid", appid.to_string())];
+    if let Some(count) = count {
+        params.push(("count", count.to_string()));
+    }
+    if let Some(maxlength) = maxlength {
+        params.push(("maxlength", maxlength.to_string()));
+    }
+
+    build_cache_key(isteam_news::get_interface().as_str(), get_method_name().as_str(), get_version().as_str(), params)
+}
+
+pub fn get_resource_filepath(appid: i64, count: Option<u32>, maxlength: Option<u32>) -> String {
+    get_default_client().get_resource_filepath(get_cache_key(appid, count, maxlength).as_str())
+}
+
+pub fn parse_response(response: String) -> Result<Vec<NewsItem>, SteamApiError> {
     let boxed_initial_parse = serde_json::from_str(&response);
     if boxed_initial_parse.is_err() {
-        return Err(boxed_initial_parse.err().unwrap().to_string());
+        return Err(SteamApiError::malformed_response(get_endpoint().as_str(), boxed_initial_parse.err().unwrap().to_string().as_str()));
     }
     let json: Value = boxed_initial_parse.unwrap();
 
     let boxed_newsitems = json.get("appnews").and_then(|r| r.get("newsitems")).and_then(Value::as_array);
     if boxed_newsitems.is_none() {
-        return Err("response does not contain any news items".to_string());
+        return Err(SteamApiError::malformed_response(get_endpoint().as_str(), "response does not contain any news items"));
     }
 
     let mut newsitems = vec![];
@@ -1,6 +1,8 @@
 use url_build_parse::parse_url;
 use crate::{get_host, get_scheme};
-use crate::isteam_news::get_news_for_app::{get_api_url, parse_response};
+use crate::isteam_news::get_news_for_app::{get_api_url, get_resource_filepath, parse_response};
+use crate::client::get_default_client;
+use crate::tests::assert_serde_roundtrip;
 
 #[test]
 fn api_url() {
@@ -23,6 +25,13 @@ fn api_url() {
     assert_eq!("300", boxed_maxlength.unwrap().to_string());
 }
 
+#[test]
+fn resource_filepath() {
+    let filepath = get_resource_filepath(440, Some(3), None);
+
+    assert_eq!([get_default_client().cache_dir(), "/ISteamNews-GetNewsForApp-v2-appid=440-count=3.json"].join(""), filepath);
+}
+
 #[test]
 fn parse() {
     let response = r#"{
@@ -49,6 +58,7 @@ fn parse() {
     assert!(boxed_parse.is_ok());
 
     let newsitems = boxed_parse.unwrap();
+    assert_serde_roundtrip(&newsitems);
     assert_eq!(1, newsitems.len());
 
     let item = newsitems.get(0).unwrap();
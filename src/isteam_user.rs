@@ -2,6 +2,7 @@ pub mod get_player_summaries;
 pub mod get_friend_list;
 pub mod get_player_bans;
 pub mod resolve_vanity_url;
+pub mod types;
 
 pub fn get_interface() -> String {
     "ISteamUser".to_string()
@@ -1,14 +1,20 @@
 use std::collections::HashMap;
+use std::fmt::Display;
 use serde_json::Value;
-use crate::{isteam_user, make_api_call};
-use crate::util::{build_steam_api_url, get_steam_web_api_key, json_str, json_u64};
+use serde::Deserialize;
+use serde::Serialize;
+use crate::isteam_user;
+use crate::client::{get_default_client, SteamClient};
+use crate::error::SteamApiError;
+use crate::steam_id::{json_steam_id, to_steam_id, SteamId};
+use crate::util::{build_cache_key, build_steam_api_url, json_str, json_u64};
 
 #[cfg(test)]
 mod tests;
 
-#[derive(PartialEq, Clone, Debug)]
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
 pub struct Friend {
-    pub steamid: String,
+    pub steamid: SteamId,
     pub relationship: String,
     pub friend_since: u64,
 }
@@ -21,17 +27,88 @@ pub fn get_version() -> String {
     "v1".to_string()
 }
 
-pub fn get(steamid: u64, relationship: Option<String>) -> Result<Vec<Friend>, String> {
+pub fn get_endpoint() -> String {
+    [isteam_user::get_interface(), "/".to_string(), get_method_name()].join("")
+}
+
+// steam responds to friend list requests for private profiles with 401 Unauthorized
+const HTTP_UNAUTHORIZED: u16 = 401;
+
+pub fn get(steamid: impl TryInto<SteamId, Error = impl Display>, relationship: Option<String>) -> Result<Vec<Friend>, SteamApiError> {
+    get_with_client(get_default_client().as_ref(), steamid, relationship)
+}
+
+/// Async version of `get`, available with the `async` feature.
+#[cfg(feature = "async")]
+pub async fn get_async(steamid: impl TryInto<SteamId, Error = impl Display>, relationship: Option<String>) -> Result<Vec<Friend>, SteamApiError> {
+    get_async_with_client(get_default_client().as_ref(), steamid, relationship).await
+}
+
+pub fn get_with_client(client: &SteamClient, steamid: impl TryInto<SteamId, Error = impl Display>, relationship: Option<String>) -> Result<Vec<Friend>, SteamApiError> {
+    let steamid = to_steam_id(get_endpoint().as_str(), steamid)?;
+    let cache_key = get_cache_key(steamid, relationship.clone());
     let api_url = get_api_url(steamid, relationship);
-    let boxed_response = make_api_call(api_url);
-    if boxed_response.is_err() {
-        return Err(boxed_response.err().unwrap());
+    let boxed_result = client.fetch_and_cache(get_endpoint().as_str(), cache_key.as_str(), api_url.clone(), parse_response);
+    if let Err(error) = boxed_result.as_ref() {
+        if error.http_status_code() == Some(HTTP_UNAUTHORIZED) {
+            return Err(SteamApiError::private_profile(get_endpoint().as_str()).with_url(api_url.as_str()));
+        }
     }
 
-    parse_response(boxed_response.unwrap())
+    boxed_result
+}
+
+/// Async version of `get_with_client`, available with the `async` feature.
+#[cfg(feature = "async")]
+pub async fn get_async_with_client(client: &SteamClient, steamid: impl TryInto<SteamId, Error = impl Display>, relationship: Option<String>) -> Result<Vec<Friend>, SteamApiError> {
+    let steamid = to_steam_id(get_endpoint().as_str(), steamid)?;
+    let cache_key = get_cache_key(steamid, relationship.clone());
+    let api_url = get_api_url(steamid, relationship);
+    let boxed_result = client.fetch_and_cache_async(get_endpoint().as_str(), cache_key.as_str(), api_url.clone(), parse_response).await;
+    if let Err(error) = boxed_result.as_ref() {
+        if error.http_status_code() == Some(HTTP_UNAUTHORIZED) {
+            return Err(SteamApiError::private_profile(get_endpoint().as_str()).with_url(api_url.as_str()));
+        }
+    }
+
+    boxed_result
+}
+
+/// Like `get`, but serves the cached response while it is fresh as per the client's
+/// `cache::CachePolicy`, otherwise calls Steam and caches the response.
+pub fn get_cached(steamid: impl TryInto<SteamId, Error = impl Display>, relationship: Option<String>) -> Result<Vec<Friend>, SteamApiError> {
+    get_cached_with_client(get_default_client().as_ref(), steamid, relationship)
+}
+
+/// Async version of `get_cached`, available with the `async` feature.
+#[cfg(feature = "async")]
+pub async fn get_cached_async(steamid: impl TryInto<SteamId, Error = impl Display>, relationship: Option<String>) -> Result<Vec<Friend>, SteamApiError> {
+    get_cached_async_with_client(get_default_client().as_ref(), steamid, relationship).await
+}
+
+pub fn get_cached_with_client(client: &SteamClient, steamid: impl TryInto<SteamId, Error = impl Display>, relationship: Option<String>) -> Result<Vec<Friend>, SteamApiError> {
+    let steamid = to_steam_id(get_endpoint().as_str(), steamid)?;
+    client.get_cached_or_fetch(
+        get_endpoint().as_str(),
+        get_cache_key(steamid, relationship.clone()).as_str(),
+        parse_response,
+        || get_with_client(client, steamid, relationship),
+    )
+}
+
+/// Async version of `get_cached_with_client`, available with the `async` feature.
+#[cfg(feature = "async")]
+pub async fn get_cached_async_with_client(client: &SteamClient, steamid: impl TryInto<SteamId, Error = impl Display>, relationship: Option<String>) -> Result<Vec<Friend>, SteamApiError> {
+    let steamid = to_steam_id(get_endpoint().as_str(), steamid)?;
+    client.get_cached_or_fetch_async(
+        get_endpoint().as_str(),
+        get_cache_key(steamid, relationship.clone()).as_str(),
+        parse_response,
+        get_async_with_client(client, steamid, relationship),
+    ).await
 }
 
-pub fn get_api_url(steamid: u64, relationship: Option<String>) -> String {
+pub fn get_api_url(steamid: SteamId, relationship: Option<String>) -> String {
     let interface = isteam_user::get_interface();
     let method = get_method_name();
     let version = get_version();
@@ -39,27 +116,40 @@ pub fn get_api_url(steamid: u64, relationship: Option<String>) -> String {
     let mut params_map = HashMap::new();
     params_map.insert("steamid".to_string(), steamid.to_string());
     params_map.insert("relationship".to_string(), relationship.unwrap_or("friend".to_string()));
-    params_map.insert("key".to_string(), get_steam_web_api_key());
 
     build_steam_api_url(interface.as_str(), method.as_str(), version.as_str(), params_map)
 }
 
-pub fn parse_response(response: String) -> Result<Vec<Friend>, String> {
+/// Returns path of the cached resource relative to the cache directory.
+pub fn get_cache_key(steamid: SteamId, relationship: Option<String>) -> String {
+    let mut params = vec![("steamid", steamid.to_string())];
+    if let Some(relationship) = relationship {
+        params.push(("relationship", relationship));
+    }
+
+    build_cache_key(isteam_user::get_interface().as_str(), get_method_name().as_str(), get_version().as_str(), params)
+}
+
+pub fn get_resource_filepath(steamid: SteamId, relationship: Option<String>) -> String {
+    get_default_client().get_resource_filepath(get_cache_key(steamid, relationship).as_str())
+}
+
+pub fn parse_response(response: String) -> Result<Vec<Friend>, SteamApiError> {
     let boxed_initial_parse = serde_json::from_str(&response);
     if boxed_initial_parse.is_err() {
-        return Err(boxed_initial_parse.err().unwrap().to_string());
+        return Err(SteamApiError::malformed_response(get_endpoint().as_str(), boxed_initial_parse.err().unwrap().to_string().as_str()));
     }
     let json: Value = boxed_initial_parse.unwrap();
 
     let boxed_friends = json.get("friendslist").and_then(|r| r.get("friends")).and_then(Value::as_array);
     if boxed_friends.is_none() {
-        return Err("response does not contain a friends list (the profile's friends list may be private)".to_string());
+        return Err(SteamApiError::malformed_response(get_endpoint().as_str(), "response does not contain a friends list"));
     }
 
     let mut friends = vec![];
     for friend in boxed_friends.unwrap() {
         friends.push(Friend {
-            steamid: json_str(friend, "steamid"),
+            steamid: json_steam_id(get_endpoint().as_str(), friend, "steamid")?,
             relationship: json_str(friend, "relationship"),
             friend_since: json_u64(friend, "friend_since"),
         });
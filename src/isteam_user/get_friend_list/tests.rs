@@ -1,11 +1,13 @@
 use url_build_parse::parse_url;
 use crate::{get_host, get_scheme};
-use crate::isteam_user::get_friend_list::{get_api_url, parse_response};
-use crate::util::get_steam_web_api_key;
+use crate::isteam_user::get_friend_list::{get_api_url, get_resource_filepath, parse_response};
+use crate::steam_id::SteamId;
+use crate::client::get_default_client;
+use crate::tests::assert_serde_roundtrip;
 
 #[test]
 fn api_url_default_relationship() {
-    let api_url = get_api_url(76561197960435530, None);
+    let api_url = get_api_url(SteamId::try_from(76561197960435530).unwrap(), None);
 
     let components = parse_url(api_url.as_str()).unwrap();
 
@@ -20,8 +22,14 @@ fn api_url_default_relationship() {
     let boxed_relationship = params.get("relationship");
     assert_eq!("friend", boxed_relationship.unwrap().to_string());
 
-    let boxed_key = params.get("key");
-    assert_eq!(get_steam_web_api_key(), boxed_key.unwrap().to_string());
+    assert!(!params.contains_key("key"));
+}
+
+#[test]
+fn resource_filepath() {
+    let filepath = get_resource_filepath(SteamId::try_from(76561197960435530).unwrap(), Some("friend".to_string()));
+
+    assert_eq!([get_default_client().cache_dir(), "/ISteamUser-GetFriendList-v1-steamid=76561197960435530-relationship=friend.json"].join(""), filepath);
 }
 
 #[test]
@@ -42,10 +50,11 @@ fn parse() {
     assert!(boxed_parse.is_ok());
 
     let friends = boxed_parse.unwrap();
+    assert_serde_roundtrip(&friends);
     assert_eq!(1, friends.len());
 
     let friend = friends.get(0).unwrap();
-    assert_eq!("76561197960265731", friend.steamid);
+    assert_eq!("76561197960265731", friend.steamid.to_string());
     assert_eq!("friend", friend.relationship);
     assert_eq!(1300856234, friend.friend_since);
 }
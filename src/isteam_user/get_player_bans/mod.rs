@@ -1,20 +1,34 @@
 use std::collections::HashMap;
+use std::fmt::Display;
 use serde_json::Value;
-use crate::{isteam_user, make_api_call};
-use crate::util::{build_steam_api_url, get_steam_web_api_key, json_bool, json_str, json_u64};
+use serde::Deserialize;
+use serde::Serialize;
+use crate::isteam_user;
+use crate::client::{get_default_client, SteamClient};
+use crate::error::SteamApiError;
+use crate::isteam_user::types::EconomyBan;
+use crate::steam_id::{json_steam_id, to_steam_ids, SteamId};
+use crate::util::{build_cache_key, build_steam_api_url, json_bool, json_str, json_u64};
 
 #[cfg(test)]
 mod tests;
 
-#[derive(PartialEq, Clone, Debug)]
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
 pub struct PlayerBans {
-    pub steamid: String,
+    pub steamid: SteamId,
     pub community_banned: bool,
     pub vac_banned: bool,
     pub number_of_vac_bans: u64,
     pub days_since_last_ban: u64,
     pub number_of_game_bans: u64,
-    pub economy_ban: String,
+    pub economy_ban: EconomyBan,
+}
+
+impl PlayerBans {
+    /// Whether the account has any community, VAC, game or economy ban. Economy probation doesn't count.
+    pub fn is_banned(&self) -> bool {
+        self.community_banned || self.vac_banned || self.number_of_game_bans > 0 || self.economy_ban == EconomyBan::Banned
+    }
 }
 
 pub fn get_method_name() -> String {
@@ -25,17 +39,71 @@ pub fn get_version() -> String {
     "v1".to_string()
 }
 
-pub fn get(steamids: Vec<u64>) -> Result<Vec<PlayerBans>, String> {
+pub fn get_endpoint() -> String {
+    [isteam_user::get_interface(), "/".to_string(), get_method_name()].join("")
+}
+
+pub fn get(steamids: Vec<impl TryInto<SteamId, Error = impl Display>>) -> Result<Vec<PlayerBans>, SteamApiError> {
+    get_with_client(get_default_client().as_ref(), steamids)
+}
+
+/// Async version of `get`, available with the `async` feature.
+#[cfg(feature = "async")]
+pub async fn get_async(steamids: Vec<impl TryInto<SteamId, Error = impl Display>>) -> Result<Vec<PlayerBans>, SteamApiError> {
+    get_async_with_client(get_default_client().as_ref(), steamids).await
+}
+
+pub fn get_with_client(client: &SteamClient, steamids: Vec<impl TryInto<SteamId, Error = impl Display>>) -> Result<Vec<PlayerBans>, SteamApiError> {
+    let steamids = to_steam_ids(get_endpoint().as_str(), steamids)?;
+    let cache_key = get_cache_key(steamids.clone());
     let api_url = get_api_url(steamids);
-    let boxed_response = make_api_call(api_url);
-    if boxed_response.is_err() {
-        return Err(boxed_response.err().unwrap());
-    }
+    client.fetch_and_cache(get_endpoint().as_str(), cache_key.as_str(), api_url, parse_response)
+}
+
+/// Async version of `get_with_client`, available with the `async` feature.
+#[cfg(feature = "async")]
+pub async fn get_async_with_client(client: &SteamClient, steamids: Vec<impl TryInto<SteamId, Error = impl Display>>) -> Result<Vec<PlayerBans>, SteamApiError> {
+    let steamids = to_steam_ids(get_endpoint().as_str(), steamids)?;
+    let cache_key = get_cache_key(steamids.clone());
+    let api_url = get_api_url(steamids);
+    client.fetch_and_cache_async(get_endpoint().as_str(), cache_key.as_str(), api_url, parse_response).await
+}
+
+/// Like `get`, but serves the cached response while it is fresh as per the client's
+/// `cache::CachePolicy`, otherwise calls Steam and caches the response.
+pub fn get_cached(steamids: Vec<impl TryInto<SteamId, Error = impl Display>>) -> Result<Vec<PlayerBans>, SteamApiError> {
+    get_cached_with_client(get_default_client().as_ref(), steamids)
+}
+
+/// Async version of `get_cached`, available with the `async` feature.
+#[cfg(feature = "async")]
+pub async fn get_cached_async(steamids: Vec<impl TryInto<SteamId, Error = impl Display>>) -> Result<Vec<PlayerBans>, SteamApiError> {
+    get_cached_async_with_client(get_default_client().as_ref(), steamids).await
+}
 
-    parse_response(boxed_response.unwrap())
+pub fn get_cached_with_client(client: &SteamClient, steamids: Vec<impl TryInto<SteamId, Error = impl Display>>) -> Result<Vec<PlayerBans>, SteamApiError> {
+    let steamids = to_steam_ids(get_endpoint().as_str(), steamids)?;
+    client.get_cached_or_fetch(
+        get_endpoint().as_str(),
+        get_cache_key(steamids.clone()).as_str(),
+        parse_response,
+        || get_with_client(client, steamids),
+    )
 }
 
-pub fn get_api_url(steamids: Vec<u64>) -> String {
+/// Async version of `get_cached_with_client`, available with the `async` feature.
+#[cfg(feature = "async")]
+pub async fn get_cached_async_with_client(client: &SteamClient, steamids: Vec<impl TryInto<SteamId, Error = impl Display>>) -> Result<Vec<PlayerBans>, SteamApiError> {
+    let steamids = to_steam_ids(get_endpoint().as_str(), steamids)?;
+    client.get_cached_or_fetch_async(
+        get_endpoint().as_str(),
+        get_cache_key(steamids.clone()).as_str(),
+        parse_response,
+        get_async_with_client(client, steamids),
+    ).await
+}
+
+pub fn get_api_url(steamids: Vec<SteamId>) -> String {
     let interface = isteam_user::get_interface();
     let method = get_method_name();
     let version = get_version();
@@ -44,34 +112,42 @@ pub fn get_api_url(steamids: Vec<u64>) -> String {
 
     let mut params_map = HashMap::new();
     params_map.insert("steamids".to_string(), steamids_csv);
-    params_map.insert("key".to_string(), get_steam_web_api_key());
 
     build_steam_api_url(interface.as_str(), method.as_str(), version.as_str(), params_map)
 }
 
 // note: unlike most ISteamUser/IPlayerService responses, this endpoint has no "response" wrapper.
-pub fn parse_response(response: String) -> Result<Vec<PlayerBans>, String> {
+/// Returns path of the cached resource relative to the cache directory.
+pub fn get_cache_key(steamids: Vec<SteamId>) -> String {
+    build_cache_key(isteam_user::get_interface().as_str(), get_method_name().as_str(), get_version().as_str(), vec![("steamids", steamids.iter().map(|id| id.to_string()).collect::<Vec<String>>().join(","))])
+}
+
+pub fn get_resource_filepath(steamids: Vec<SteamId>) -> String {
+    get_default_client().get_resource_filepath(get_cache_key(steamids).as_str())
+}
+
+pub fn parse_response(response: String) -> Result<Vec<PlayerBans>, SteamApiError> {
     let boxed_initial_parse = serde_json::from_str(&response);
     if boxed_initial_parse.is_err() {
-        return Err(boxed_initial_parse.err().unwrap().to_string());
+        return Err(SteamApiError::malformed_response(get_endpoint().as_str(), boxed_initial_parse.err().unwrap().to_string().as_str()));
     }
     let json: Value = boxed_initial_parse.unwrap();
 
     let boxed_players = json.get("players").and_then(Value::as_array);
     if boxed_players.is_none() {
-        return Err("response does not contain any players".to_string());
+        return Err(SteamApiError::malformed_response(get_endpoint().as_str(), "response does not contain any players"));
     }
 
     let mut bans = vec![];
     for player in boxed_players.unwrap() {
         bans.push(PlayerBans {
-            steamid: json_str(player, "SteamId"),
+            steamid: json_steam_id(get_endpoint().as_str(), player, "SteamId")?,
             community_banned: json_bool(player, "CommunityBanned"),
             vac_banned: json_bool(player, "VACBanned"),
             number_of_vac_bans: json_u64(player, "NumberOfVACBans"),
             days_since_last_ban: json_u64(player, "DaysSinceLastBan"),
             number_of_game_bans: json_u64(player, "NumberOfGameBans"),
-            economy_ban: json_str(player, "EconomyBan"),
+            economy_ban: EconomyBan::from_name(json_str(player, "EconomyBan").as_str()),
         });
     }
 
@@ -1,11 +1,14 @@
 use url_build_parse::parse_url;
 use crate::{get_host, get_scheme};
-use crate::isteam_user::get_player_bans::{get_api_url, parse_response};
-use crate::util::get_steam_web_api_key;
+use crate::isteam_user::get_player_bans::{get_api_url, get_resource_filepath, parse_response};
+use crate::steam_id::SteamId;
+use crate::isteam_user::types::EconomyBan;
+use crate::client::get_default_client;
+use crate::tests::assert_serde_roundtrip;
 
 #[test]
 fn api_url() {
-    let api_url = get_api_url(vec![76561197960435530]);
+    let api_url = get_api_url(vec![SteamId::try_from(76561197960435530).unwrap()]);
 
     let components = parse_url(api_url.as_str()).unwrap();
 
@@ -17,8 +20,14 @@ fn api_url() {
     let boxed_steamids = params.get("steamids");
     assert_eq!("76561197960435530", boxed_steamids.unwrap().to_string());
 
-    let boxed_key = params.get("key");
-    assert_eq!(get_steam_web_api_key(), boxed_key.unwrap().to_string());
+    assert!(!params.contains_key("key"));
+}
+
+#[test]
+fn resource_filepath() {
+    let filepath = get_resource_filepath(vec![SteamId::try_from(76561197960435530).unwrap()]);
+
+    assert_eq!([get_default_client().cache_dir(), "/ISteamUser-GetPlayerBans-v1-steamids=76561197960435530.json"].join(""), filepath);
 }
 
 #[test]
@@ -41,16 +50,18 @@ fn parse() {
     assert!(boxed_parse.is_ok());
 
     let bans = boxed_parse.unwrap();
+    assert_serde_roundtrip(&bans);
     assert_eq!(1, bans.len());
 
     let ban = bans.get(0).unwrap();
-    assert_eq!("76561197960435530", ban.steamid);
+    assert_eq!("76561197960435530", ban.steamid.to_string());
     assert_eq!(false, ban.community_banned);
     assert_eq!(true, ban.vac_banned);
     assert_eq!(1, ban.number_of_vac_bans);
     assert_eq!(42, ban.days_since_last_ban);
     assert_eq!(0, ban.number_of_game_bans);
-    assert_eq!("none", ban.economy_ban);
+    assert_eq!(EconomyBan::None, ban.economy_ban);
+    assert!(ban.is_banned());
 }
 
 #[test]
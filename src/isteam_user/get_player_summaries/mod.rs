@@ -1,31 +1,60 @@
 use std::collections::HashMap;
+use std::fmt::Display;
 use serde_json::Value;
-use crate::{isteam_user, make_api_call};
-use crate::util::{build_steam_api_url, get_steam_web_api_key, json_bool, json_str, json_u64};
+use serde::Deserialize;
+use serde::Serialize;
+use crate::isteam_user;
+use crate::client::{get_default_client, SteamClient};
+use crate::error::SteamApiError;
+use crate::isteam_user::types::{CommunityVisibility, PersonaState};
+use crate::steam_id::{json_steam_id, to_steam_ids, SteamId};
+use crate::util::{build_cache_key, build_steam_api_url, json_opt_bool, json_opt_str, json_opt_u64, json_str, json_u64};
 
 #[cfg(test)]
 mod tests;
 
-#[derive(PartialEq, Clone, Debug)]
+/// Fields Steam only returns for public or configured profiles are `None` when missing.
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
 pub struct PlayerSummary {
-    pub steamid: String,
-    pub communityvisibilitystate: u64,
-    pub profilestate: u64,
+    pub steamid: SteamId,
+    pub communityvisibilitystate: CommunityVisibility,
+    pub profilestate: Option<bool>,
     pub personaname: String,
     pub profileurl: String,
     pub avatar: String,
     pub avatarmedium: String,
     pub avatarfull: String,
-    pub lastlogoff: u64,
-    pub personastate: u64,
-    pub realname: String,
-    pub primaryclanid: String,
-    pub timecreated: u64,
-    pub loccountrycode: String,
-    pub locstatecode: String,
-    pub gameid: String,
-    pub gameextrainfo: String,
-    pub comment_permission: bool,
+    pub lastlogoff: Option<u64>,
+    pub personastate: PersonaState,
+    pub realname: Option<String>,
+    pub primaryclanid: Option<String>,
+    pub timecreated: Option<u64>,
+    pub loccountrycode: Option<String>,
+    pub locstatecode: Option<String>,
+    pub gameid: Option<String>,
+    pub gameextrainfo: Option<String>,
+    pub comment_permission: Option<bool>,
+}
+
+impl PlayerSummary {
+    /// Whether the profile is public, so private fields such as `realname` can be set.
+    pub fn is_public(&self) -> bool {
+        self.communityvisibilitystate == CommunityVisibility::Public
+    }
+
+    pub fn is_online(&self) -> bool {
+        self.personastate.is_online()
+    }
+
+    /// Whether the player is currently in a game, as reported by `gameid`.
+    pub fn is_in_game(&self) -> bool {
+        self.gameid.is_some()
+    }
+
+    /// Whether the player has set up their community profile.
+    pub fn is_profile_configured(&self) -> bool {
+        self.profilestate == Some(true)
+    }
 }
 
 pub fn get_method_name() -> String {
@@ -36,17 +65,71 @@ pub fn get_version() -> String {
     "v2".to_string()
 }
 
-pub fn get(steamids: Vec<u64>) -> Result<Vec<PlayerSummary>, String> {
+pub fn get_endpoint() -> String {
+    [isteam_user::get_interface(), "/".to_string(), get_method_name()].join("")
+}
+
+pub fn get(steamids: Vec<impl TryInto<SteamId, Error = impl Display>>) -> Result<Vec<PlayerSummary>, SteamApiError> {
+    get_with_client(get_default_client().as_ref(), steamids)
+}
+
+/// Async version of `get`, available with the `async` feature.
+#[cfg(feature = "async")]
+pub async fn get_async(steamids: Vec<impl TryInto<SteamId, Error = impl Display>>) -> Result<Vec<PlayerSummary>, SteamApiError> {
+    get_async_with_client(get_default_client().as_ref(), steamids).await
+}
+
+pub fn get_with_client(client: &SteamClient, steamids: Vec<impl TryInto<SteamId, Error = impl Display>>) -> Result<Vec<PlayerSummary>, SteamApiError> {
+    let steamids = to_steam_ids(get_endpoint().as_str(), steamids)?;
+    let cache_key = get_cache_key(steamids.clone());
     let api_url = get_api_url(steamids);
-    let boxed_response = make_api_call(api_url);
-    if boxed_response.is_err() {
-        return Err(boxed_response.err().unwrap());
-    }
+    client.fetch_and_cache(get_endpoint().as_str(), cache_key.as_str(), api_url, parse_response)
+}
+
+/// Async version of `get_with_client`, available with the `async` feature.
+#[cfg(feature = "async")]
+pub async fn get_async_with_client(client: &SteamClient, steamids: Vec<impl TryInto<SteamId, Error = impl Display>>) -> Result<Vec<PlayerSummary>, SteamApiError> {
+    let steamids = to_steam_ids(get_endpoint().as_str(), steamids)?;
+    let cache_key = get_cache_key(steamids.clone());
+    let api_url = get_api_url(steamids);
+    client.fetch_and_cache_async(get_endpoint().as_str(), cache_key.as_str(), api_url, parse_response).await
+}
+
+/// Like `get`, but serves the cached response while it is fresh as per the client's
+/// `cache::CachePolicy`, otherwise calls Steam and caches the response.
+pub fn get_cached(steamids: Vec<impl TryInto<SteamId, Error = impl Display>>) -> Result<Vec<PlayerSummary>, SteamApiError> {
+    get_cached_with_client(get_default_client().as_ref(), steamids)
+}
 
-    parse_response(boxed_response.unwrap())
+/// Async version of `get_cached`, available with the `async` feature.
+#[cfg(feature = "async")]
+pub async fn get_cached_async(steamids: Vec<impl TryInto<SteamId, Error = impl Display>>) -> Result<Vec<PlayerSummary>, SteamApiError> {
+    get_cached_async_with_client(get_default_client().as_ref(), steamids).await
 }
 
-pub fn get_api_url(steamids: Vec<u64>) -> String {
+pub fn get_cached_with_client(client: &SteamClient, steamids: Vec<impl TryInto<SteamId, Error = impl Display>>) -> Result<Vec<PlayerSummary>, SteamApiError> {
+    let steamids = to_steam_ids(get_endpoint().as_str(), steamids)?;
+    client.get_cached_or_fetch(
+        get_endpoint().as_str(),
+        get_cache_key(steamids.clone()).as_str(),
+        parse_response,
+        || get_with_client(client, steamids),
+    )
+}
+
+/// Async version of `get_cached_with_client`, available with the `async` feature.
+#[cfg(feature = "async")]
+pub async fn get_cached_async_with_client(client: &SteamClient, steamids: Vec<impl TryInto<SteamId, Error = impl Display>>) -> Result<Vec<PlayerSummary>, SteamApiError> {
+    let steamids = to_steam_ids(get_endpoint().as_str(), steamids)?;
+    client.get_cached_or_fetch_async(
+        get_endpoint().as_str(),
+        get_cache_key(steamids.clone()).as_str(),
+        parse_response,
+        get_async_with_client(client, steamids),
+    ).await
+}
+
+pub fn get_api_url(steamids: Vec<SteamId>) -> String {
     let interface = isteam_user::get_interface();
     let method = get_method_name();
     let version = get_version();
@@ -55,44 +138,52 @@ pub fn get_api_url(steamids: Vec<u64>) -> String {
 
     let mut params_map = HashMap::new();
     params_map.insert("steamids".to_string(), steamids_csv);
-    params_map.insert("key".to_string(), get_steam_web_api_key());
 
     build_steam_api_url(interface.as_str(), method.as_str(), version.as_str(), params_map)
 }
 
-pub fn parse_response(response: String) -> Result<Vec<PlayerSummary>, String> {
+/// Returns path of the cached resource relative to the cache directory.
+pub fn get_cache_key(steamids: Vec<SteamId>) -> String {
+    build_cache_key(isteam_user::get_interface().as_str(), get_method_name().as_str(), get_version().as_str(), vec![("steamids", steamids.iter().map(|id| id.to_string()).collect::<Vec<String>>().join(","))])
+}
+
+pub fn get_resource_filepath(steamids: Vec<SteamId>) -> String {
+    get_default_client().get_resource_filepath(get_cache_key(steamids).as_str())
+}
+
+pub fn parse_response(response: String) -> Result<Vec<PlayerSummary>, SteamApiError> {
     let boxed_initial_parse = serde_json::from_str(&response);
     if boxed_initial_parse.is_err() {
-        return Err(boxed_initial_parse.err().unwrap().to_string());
+        return Err(SteamApiError::malformed_response(get_endpoint().as_str(), boxed_initial_parse.err().unwrap().to_string().as_str()));
     }
     let json: Value = boxed_initial_parse.unwrap();
 
     let boxed_players = json.get("response").and_then(|r| r.get("players")).and_then(Value::as_array);
     if boxed_players.is_none() {
-        return Err("response does not contain any players".to_string());
+        return Err(SteamApiError::malformed_response(get_endpoint().as_str(), "response does not contain any players"));
     }
 
     let mut players = vec![];
     for player in boxed_players.unwrap() {
         players.push(PlayerSummary {
-            steamid: json_str(player, "steamid"),
-            communityvisibilitystate: json_u64(player, "communityvisibilitystate"),
-            profilestate: json_u64(player, "profilestate"),
+            steamid: json_steam_id(get_endpoint().as_str(), player, "steamid")?,
+            communityvisibilitystate: CommunityVisibility::from_id(json_u64(player, "communityvisibilitystate")),
+            profilestate: json_opt_bool(player, "profilestate"),
             personaname: json_str(player, "personaname"),
             profileurl: json_str(player, "profileurl"),
             avatar: json_str(player, "avatar"),
             avatarmedium: json_str(player, "avatarmedium"),
             avatarfull: json_str(player, "avatarfull"),
-            lastlogoff: json_u64(player, "lastlogoff"),
-            personastate: json_u64(player, "personastate"),
-            realname: json_str(player, "realname"),
-            primaryclanid: json_str(player, "primaryclanid"),
-            timecreated: json_u64(player, "timecreated"),
-            loccountrycode: json_str(player, "loccountrycode"),
-            locstatecode: json_str(player, "locstatecode"),
-            gameid: json_str(player, "gameid"),
-            gameextrainfo: json_str(player, "gameextrainfo"),
-            comment_permission: json_u64(player, "commentpermission") == 1 || json_bool(player, "commentpermission"),
+            lastlogoff: json_opt_u64(player, "lastlogoff"),
+            personastate: PersonaState::from_id(json_u64(player, "personastate")),
+            realname: json_opt_str(player, "realname"),
+            primaryclanid: json_opt_str(player, "primaryclanid"),
+            timecreated: json_opt_u64(player, "timecreated"),
+            loccountrycode: json_opt_str(player, "loccountrycode"),
+            locstatecode: json_opt_str(player, "locstatecode"),
+            gameid: json_opt_str(player, "gameid"),
+            gameextrainfo: json_opt_str(player, "gameextrainfo"),
+            comment_permission: json_opt_bool(player, "commentpermission"),
         });
     }
 
@@ -1,11 +1,18 @@
+use std::sync::Arc;
 use url_build_parse::parse_url;
 use crate::{get_host, get_scheme};
-use crate::isteam_user::get_player_summaries::{get_api_url, parse_response};
-use crate::util::get_steam_web_api_key;
+use crate::cache::MemoryCache;
+use crate::error::SteamApiError;
+use crate::isteam_user::get_player_summaries::{get_api_url, get_resource_filepath, get_with_client, parse_response};
+use crate::steam_id::SteamId;
+use crate::isteam_user::types::{CommunityVisibility, PersonaState};
+use crate::client::{get_default_client, SteamClient};
+use crate::transport::FakeTransport;
+use crate::tests::assert_serde_roundtrip;
 
 #[test]
 fn api_url() {
-    let api_url = get_api_url(vec![76561197960435530, 76561197960435531]);
+    let api_url = get_api_url(vec![SteamId::try_from(76561197960435530).unwrap(), SteamId::try_from(76561197960435531).unwrap()]);
 
     let components = parse_url(api_url.as_str()).unwrap();
 
@@ -17,8 +24,14 @@ fn api_url() {
     let boxed_steamids = params.get("steamids");
     assert_eq!("76561197960435530,76561197960435531", boxed_steamids.unwrap().to_string());
 
-    let boxed_key = params.get("key");
-    assert_eq!(get_steam_web_api_key(), boxed_key.unwrap().to_string());
+    assert!(!params.contains_key("key"));
+}
+
+#[test]
+fn resource_filepath() {
+    let filepath = get_resource_filepath(vec![SteamId::try_from(76561197960435530).unwrap(), SteamId::try_from(76561197960287930).unwrap()]);
+
+    assert_eq!([get_default_client().cache_dir(), "/ISteamUser-GetPlayerSummaries-v2-steamids=76561197960435530,76561197960287930.json"].join(""), filepath);
 }
 
 #[test]
@@ -52,15 +65,53 @@ fn parse() {
     assert!(boxed_parse.is_ok());
 
     let players = boxed_parse.unwrap();
+    assert_serde_roundtrip(&players);
     assert_eq!(1, players.len());
 
     let player = players.get(0).unwrap();
-    assert_eq!("76561197960435530", player.steamid);
-    assert_eq!(3, player.communityvisibilitystate);
+    assert_eq!("76561197960435530", player.steamid.to_string());
+    assert_eq!(CommunityVisibility::Public, player.communityvisibilitystate);
+    assert_eq!(PersonaState::Offline, player.personastate);
+    assert!(player.is_public());
+    assert!(player.is_profile_configured());
+    assert!(!player.is_online());
+    assert!(!player.is_in_game());
     assert_eq!("Robin", player.personaname);
-    assert_eq!(true, player.comment_permission);
-    assert_eq!("US", player.loccountrycode);
-    assert_eq!("", player.gameid);
+    assert_eq!(Some(true), player.comment_permission);
+    assert_eq!(Some("Robin Walker".to_string()), player.realname);
+    assert_eq!(Some("US".to_string()), player.loccountrycode);
+    assert_eq!(None, player.gameid);
+}
+
+#[test]
+fn parse_private_profile() {
+    let response = r#"{
+        "response": {
+            "players": [
+                {
+                    "steamid": "76561197960287930",
+                    "communityvisibilitystate": 1,
+                    "personaname": "Rabscuttle",
+                    "profileurl": "https://steamcommunity.com/profiles/76561197960287930/",
+                    "avatar": "",
+                    "avatarmedium": "",
+                    "avatarfull": "",
+                    "personastate": 0
+                }
+            ]
+        }
+    }"#.to_string();
+
+    let players = parse_response(response).unwrap();
+    let player = players.first().unwrap();
+    assert_eq!(CommunityVisibility::Private, player.communityvisibilitystate);
+    assert!(!player.is_public());
+    assert!(!player.is_profile_configured());
+    assert_eq!(None, player.profilestate);
+    assert_eq!(None, player.lastlogoff);
+    assert_eq!(None, player.realname);
+    assert_eq!(None, player.timecreated);
+    assert_eq!(None, player.comment_permission);
 }
 
 #[test]
@@ -68,3 +119,25 @@ fn parse_missing_players() {
     let boxed_parse = parse_response("{}".to_string());
     assert!(boxed_parse.is_err());
 }
+
+#[test]
+fn numeric_steamids_follow_parse_rules() {
+    let transport = Arc::new(FakeTransport::new());
+    transport.respond_with(
+        "https://api.steampowered.com/ISteamUser/GetPlayerSummaries/v2?steamids=76561197960361544&key=KEY",
+        200,
+        r#"{"response":{"players":[]}}"#,
+    );
+    let client = SteamClient::builder()
+        .api_key("KEY")
+        .transport(transport.clone())
+        .cache_store(Arc::new(MemoryCache::new(10)))
+        .build();
+
+    assert!(get_with_client(&client, vec![95816u64]).is_ok());
+    assert_eq!(1, transport.requests().len());
+
+    let boxed_summaries = get_with_client(&client, vec![95816u64, 0]);
+    assert!(matches!(boxed_summaries, Err(SteamApiError::InvalidInput { .. })));
+    assert_eq!(1, transport.requests().len());
+}
@@ -1,15 +1,35 @@
 use std::collections::HashMap;
 use serde_json::Value;
-use crate::{isteam_user, make_api_call};
-use crate::util::{build_steam_api_url, get_steam_web_api_key, json_str, json_u64};
+use serde::Deserialize;
+use serde::Serialize;
+use crate::isteam_user;
+use crate::client::{get_default_client, SteamClient};
+use crate::error::SteamApiError;
+use crate::steam_id::{json_steam_id, SteamId};
+use crate::util::{build_cache_key, build_steam_api_url, json_str, json_u64};
 
 #[cfg(test)]
 mod tests;
 
-#[derive(PartialEq, Clone, Debug)]
+/// `url_type` of individual profiles, `steamcommunity.com/id/<vanity>`, the default.
+pub const URL_TYPE_INDIVIDUAL: u8 = 1;
+/// `url_type` of groups, `steamcommunity.com/groups/<vanity>`.
+pub const URL_TYPE_GROUP: u8 = 2;
+/// `url_type` of official game groups.
+pub const URL_TYPE_OFFICIAL_GAME_GROUP: u8 = 3;
+
+/// `success` of a resolved vanity URL.
+pub const SUCCESS: u64 = 1;
+/// `success` of a vanity URL no profile or group uses.
+pub const SUCCESS_NO_MATCH: u64 = 42;
+
+const COMMUNITY_HOST: &str = "steamcommunity.com/";
+
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
 pub struct VanityUrlResolution {
     pub success: u64,
-    pub steamid: String,
+    /// Absent unless `success` is `SUCCESS`.
+    pub steamid: Option<SteamId>,
     pub message: String,
 }
 
@@ -21,14 +41,64 @@ pub fn get_version() -> String {
     "v1".to_string()
 }
 
-pub fn get(vanity_url: String, url_type: Option<u8>) -> Result<VanityUrlResolution, String> {
+pub fn get_endpoint() -> String {
+    [isteam_user::get_interface(), "/".to_string(), get_method_name()].join("")
+}
+
+pub fn get(vanity_url: String, url_type: Option<u8>) -> Result<VanityUrlResolution, SteamApiError> {
+    get_with_client(get_default_client().as_ref(), vanity_url, url_type)
+}
+
+/// Async version of `get`, available with the `async` feature.
+#[cfg(feature = "async")]
+pub async fn get_async(vanity_url: String, url_type: Option<u8>) -> Result<VanityUrlResolution, SteamApiError> {
+    get_async_with_client(get_default_client().as_ref(), vanity_url, url_type).await
+}
+
+pub fn get_with_client(client: &SteamClient, vanity_url: String, url_type: Option<u8>) -> Result<VanityUrlResolution, SteamApiError> {
+    let cache_key = get_cache_key(vanity_url.clone(), url_type);
     let api_url = get_api_url(vanity_url, url_type);
-    let boxed_response = make_api_call(api_url);
-    if boxed_response.is_err() {
-        return Err(boxed_response.err().unwrap());
-    }
+    client.fetch_and_cache(get_endpoint().as_str(), cache_key.as_str(), api_url, parse_response)
+}
+
+/// Async version of `get_with_client`, available with the `async` feature.
+#[cfg(feature = "async")]
+pub async fn get_async_with_client(client: &SteamClient, vanity_url: String, url_type: Option<u8>) -> Result<VanityUrlResolution, SteamApiError> {
+    let cache_key = get_cache_key(vanity_url.clone(), url_type);
+    let api_url = get_api_url(vanity_url, url_type);
+    client.fetch_and_cache_async(get_endpoint().as_str(), cache_key.as_str(), api_url, parse_response).await
+}
+
+/// Like `get`, but serves the cached response while it is fresh as per the client's
+/// `cache::CachePolicy`, otherwise calls Steam and caches the response.
+pub fn get_cached(vanity_url: String, url_type: Option<u8>) -> Result<VanityUrlResolution, SteamApiError> {
+    get_cached_with_client(get_default_client().as_ref(), vanity_url, url_type)
+}
+
+/// Async version of `get_cached`, available with the `async` feature.
+#[cfg(feature = "async")]
+pub async fn get_cached_async(vanity_url: String, url_type: Option<u8>) -> Result<VanityUrlResolution, SteamApiError> {
+    get_cached_async_with_client(get_default_client().as_ref(), vanity_url, url_type).await
+}
+
+pub fn get_cached_with_client(client: &SteamClient, vanity_url: String, url_type: Option<u8>) -> Result<VanityUrlResolution, SteamApiError> {
+    client.get_cached_or_fetch(
+        get_endpoint().as_str(),
+        get_cache_key(vanity_url.clone(), url_type).as_str(),
+        parse_response,
+        || get_with_client(client, vanity_url, url_type),
+    )
+}
 
-    parse_response(boxed_response.unwrap())
+/// Async version of `get_cached_with_client`, available with the `async` feature.
+#[cfg(feature = "async")]
+pub async fn get_cached_async_with_client(client: &SteamClient, vanity_url: String, url_type: Option<u8>) -> Result<VanityUrlResolution, SteamApiError> {
+    client.get_cached_or_fetch_async(
+        get_endpoint().as_str(),
+        get_cache_key(vanity_url.clone(), url_type).as_str(),
+        parse_response,
+        get_async_with_client(client, vanity_url, url_type),
+    ).await
 }
 
 pub fn get_api_url(vanity_url: String, url_type: Option<u8>) -> String {
@@ -41,27 +111,198 @@ pub fn get_api_url(vanity_url: String, url_type: Option<u8>) -> String {
     if url_type.is_some() {
         params_map.insert("url_type".to_string(), url_type.unwrap().to_string());
     }
-    params_map.insert("key".to_string(), get_steam_web_api_key());
 
     build_steam_api_url(interface.as_str(), method.as_str(), version.as_str(), params_map)
 }
 
-pub fn parse_response(response: String) -> Result<VanityUrlResolution, String> {
+/// Returns path of the cached resource relative to the cache directory.
+pub fn get_cache_key(vanity_url: String, url_type: Option<u8>) -> String {
+    let mut params = vec![("vanityurl", vanity_url)];
+    if let Some(url_type) = url_type {
+        params.push(("url_type", url_type.to_string()));
+    }
+
+    build_cache_key(isteam_user::get_interface().as_str(), get_method_name().as_str(), get_version().as_str(), params)
+}
+
+pub fn get_resource_filepath(vanity_url: String, url_type: Option<u8>) -> String {
+    get_default_client().get_resource_filepath(get_cache_key(vanity_url, url_type).as_str())
+}
+
+pub fn parse_response(response: String) -> Result<VanityUrlResolution, SteamApiError> {
     let boxed_initial_parse = serde_json::from_str(&response);
     if boxed_initial_parse.is_err() {
-        return Err(boxed_initial_parse.err().unwrap().to_string());
+        return Err(SteamApiError::malformed_response(get_endpoint().as_str(), boxed_initial_parse.err().unwrap().to_string().as_str()));
     }
     let json: Value = boxed_initial_parse.unwrap();
 
     let boxed_result = json.get("response");
     if boxed_result.is_none() {
-        return Err("response does not contain a result".to_string());
+        return Err(SteamApiError::malformed_response(get_endpoint().as_str(), "response does not contain a result"));
     }
     let result = boxed_result.unwrap();
 
+    let mut steamid = None;
+    if result.get("steamid").is_some() {
+        steamid = Some(json_steam_id(get_endpoint().as_str(), result, "steamid")?);
+    }
+
     Ok(VanityUrlResolution {
         success: json_u64(result, "success"),
-        steamid: json_str(result, "steamid"),
+        steamid,
         message: json_str(result, "message"),
     })
 }
+
+/// Outcome of `resolve`.
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
+pub enum SteamIdResolution {
+    /// The input was a SteamID or a `/profiles/` URL, parsed without calling Steam.
+    Parsed(SteamId),
+    /// The input was a vanity name or a `/id/`/`/groups/` URL Steam resolved.
+    Resolved { vanity_url: String, steam_id: SteamId },
+    /// No profile or group uses the vanity name.
+    NoMatch { vanity_url: String },
+    /// The input is neither a SteamID, a Steam Community URL nor a vanity name.
+    Invalid { input: String, message: String },
+}
+
+impl SteamIdResolution {
+    /// Returns the SteamId, if the input was parsed or resolved.
+    pub fn get_steam_id(&self) -> Option<SteamId> {
+        match self {
+            SteamIdResolution::Parsed(steam_id) => Some(*steam_id),
+            SteamIdResolution::Resolved { steam_id, .. } => Some(*steam_id),
+            _ => None,
+        }
+    }
+}
+
+/// What `resolve` makes of its input before calling Steam.
+#[derive(PartialEq, Clone, Debug)]
+enum ProfileInput {
+    SteamId(SteamId),
+    Vanity(String, Option<u8>),
+    Invalid(String),
+}
+
+/// Resolves whatever users paste as a profile to a SteamID: any format `SteamId::parse`
+/// understands, `steamcommunity.com/id/<vanity>` and `steamcommunity.com/groups/<vanity>` URLs or a
+/// bare vanity name, looked up with `url_type` (`URL_TYPE_INDIVIDUAL` if `None`). Only vanity names
+/// call Steam, through the cache like `get_cached`. Numbers are taken as SteamIDs, not vanity names.
+///
+/// # Examples
+///
+/// ```
+/// use steam_webapi_rust_sdk::SteamId;
+/// use steam_webapi_rust_sdk::isteam_user::resolve_vanity_url::{resolve, SteamIdResolution};
+///
+/// let resolution = resolve("https://steamcommunity.com/profiles/[U:1:22202]/", None).unwrap();
+/// assert_eq!(SteamIdResolution::Parsed(SteamId::from_account_id(22202)), resolution);
+/// ```
+pub fn resolve(input: &str, url_type: Option<u8>) -> Result<SteamIdResolution, SteamApiError> {
+    resolve_with_client(get_default_client().as_ref(), input, url_type)
+}
+
+/// Async version of `resolve`, available with the `async` feature.
+#[cfg(feature = "async")]
+pub async fn resolve_async(input: &str, url_type: Option<u8>) -> Result<SteamIdResolution, SteamApiError> {
+    resolve_async_with_client(get_default_client().as_ref(), input, url_type).await
+}
+
+pub fn resolve_with_client(client: &SteamClient, input: &str, url_type: Option<u8>) -> Result<SteamIdResolution, SteamApiError> {
+    match parse_profile_input(input, url_type) {
+        ProfileInput::SteamId(steam_id) => Ok(SteamIdResolution::Parsed(steam_id)),
+        ProfileInput::Invalid(message) => Ok(SteamIdResolution::Invalid { input: input.to_string(), message }),
+        ProfileInput::Vanity(vanity_url, url_type) => {
+            let boxed_resolution = get_cached_with_client(client, vanity_url.clone(), url_type);
+            if boxed_resolution.is_err() {
+                return Err(boxed_resolution.err().unwrap());
+            }
+
+            to_steam_id_resolution(vanity_url, url_type, boxed_resolution.unwrap())
+        },
+    }
+}
+
+/// Async version of `resolve_with_client`, available with the `async` feature.
+#[cfg(feature = "async")]
+pub async fn resolve_async_with_client(client: &SteamClient, input: &str, url_type: Option<u8>) -> Result<SteamIdResolution, SteamApiError> {
+    match parse_profile_input(input, url_type) {
+        ProfileInput::SteamId(steam_id) => Ok(SteamIdResolution::Parsed(steam_id)),
+        ProfileInput::Invalid(message) => Ok(SteamIdResolution::Invalid { input: input.to_string(), message }),
+        ProfileInput::Vanity(vanity_url, url_type) => {
+            let boxed_resolution = get_cached_async_with_client(client, vanity_url.clone(), url_type).await;
+            if boxed_resolution.is_err() {
+                return Err(boxed_resolution.err().unwrap());
+            }
+
+            to_steam_id_resolution(vanity_url, url_type, boxed_resolution.unwrap())
+        },
+    }
+}
+
+fn parse_profile_input(input: &str, url_type: Option<u8>) -> ProfileInput {
+    let input = input.trim();
+
+    if let Some(position) = input.find(COMMUNITY_HOST) {
+        let path = &input[position + COMMUNITY_HOST.len()..];
+        let segments = path.split(['/', '?', '#']).collect::<Vec<&str>>();
+        let vanity_url = segments.get(1).copied().unwrap_or("");
+
+        let url_type = match segments[0] {
+            "profiles" => {
+                return match SteamId::parse(input) {
+                    Ok(steam_id) => ProfileInput::SteamId(steam_id),
+                    Err(message) => ProfileInput::Invalid(message),
+                };
+            },
+            "id" => Some(URL_TYPE_INDIVIDUAL),
+            "groups" => Some(URL_TYPE_GROUP),
+            _ => return ProfileInput::Invalid(["not a profile or group URL ", input].join("")),
+        };
+
+        if !is_vanity_name(vanity_url) {
+            return ProfileInput::Invalid(["invalid vanity name in ", input].join(""));
+        }
+        return ProfileInput::Vanity(vanity_url.to_string(), url_type);
+    }
+
+    let boxed_steam_id = SteamId::parse(input);
+    if let Ok(steam_id) = boxed_steam_id {
+        return ProfileInput::SteamId(steam_id);
+    }
+
+    if is_vanity_name(input) {
+        return ProfileInput::Vanity(input.to_string(), url_type);
+    }
+
+    ProfileInput::Invalid(boxed_steam_id.err().unwrap())
+}
+
+/// Vanity names are 2 to 32 letters, digits, `_` or `-`; all-digit ones are taken as SteamIDs.
+fn is_vanity_name(name: &str) -> bool {
+    let is_allowed = |c: char| c.is_ascii_alphanumeric() || c == '_' || c == '-';
+
+    (2..=32).contains(&name.len())
+        && name.chars().all(is_allowed)
+        && !name.chars().all(|c| c.is_ascii_digit())
+}
+
+fn to_steam_id_resolution(vanity_url: String, url_type: Option<u8>, resolution: VanityUrlResolution) -> Result<SteamIdResolution, SteamApiError> {
+    if resolution.success == SUCCESS_NO_MATCH {
+        return Ok(SteamIdResolution::NoMatch { vanity_url });
+    }
+
+    if resolution.success != SUCCESS {
+        return Err(SteamApiError::steam_failure(get_endpoint().as_str(), Some(resolution.success as i64), resolution.message.as_str())
+            .with_url(get_api_url(vanity_url, url_type).as_str()));
+    }
+
+    if resolution.steamid.is_none() {
+        return Err(SteamApiError::malformed_response(get_endpoint().as_str(), "response does not contain a steamid")
+            .with_url(get_api_url(vanity_url, url_type).as_str()));
+    }
+
+    Ok(SteamIdResolution::Resolved { vanity_url, steam_id: resolution.steamid.unwrap() })
+}
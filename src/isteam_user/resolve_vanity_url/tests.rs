@@ -1,7 +1,13 @@
+use std::sync::Arc;
 use url_build_parse::parse_url;
 use crate::{get_host, get_scheme};
-use crate::isteam_user::resolve_vanity_url::{get_api_url, parse_response};
-use crate::util::get_steam_web_api_key;
+use crate::cache::MemoryCache;
+use crate::error::SteamApiError;
+use crate::isteam_user::resolve_vanity_url::{get_api_url, get_resource_filepath, parse_profile_input, parse_response, resolve_with_client, ProfileInput, SteamIdResolution, URL_TYPE_GROUP, URL_TYPE_INDIVIDUAL};
+use crate::steam_id::SteamId;
+use crate::client::{get_default_client, SteamClient};
+use crate::transport::FakeTransport;
+use crate::tests::assert_serde_roundtrip;
 
 #[test]
 fn api_url() {
@@ -20,8 +26,14 @@ fn api_url() {
     let boxed_url_type = params.get("url_type");
     assert_eq!("1", boxed_url_type.unwrap().to_string());
 
-    let boxed_key = params.get("key");
-    assert_eq!(get_steam_web_api_key(), boxed_key.unwrap().to_string());
+    assert!(!params.contains_key("key"));
+}
+
+#[test]
+fn resource_filepath() {
+    let filepath = get_resource_filepath("gabe newell".to_string(), None);
+
+    assert_eq!([get_default_client().cache_dir(), "/ISteamUser-ResolveVanityURL-v1-vanityurl=gabe%20newell.json"].join(""), filepath);
 }
 
 #[test]
@@ -33,7 +45,7 @@ fn parse_success() {
 
     let resolution = boxed_parse.unwrap();
     assert_eq!(1, resolution.success);
-    assert_eq!("76561197960287930", resolution.steamid);
+    assert_eq!(Some(SteamId::try_from(76561197960287930).unwrap()), resolution.steamid);
     assert_eq!("", resolution.message);
 }
 
@@ -46,6 +58,76 @@ fn parse_no_match() {
 
     let resolution = boxed_parse.unwrap();
     assert_eq!(42, resolution.success);
-    assert_eq!("", resolution.steamid);
+    assert_eq!(None, resolution.steamid);
     assert_eq!("No match", resolution.message);
 }
+
+#[test]
+fn parse_invalid_steamid() {
+    let response = r#"{"response":{"steamid":"0","success":1}}"#.to_string();
+
+    let boxed_parse = parse_response(response);
+    assert!(matches!(boxed_parse, Err(SteamApiError::MalformedResponse { .. })));
+}
+
+#[test]
+fn profile_input() {
+    let steam_id = SteamId::from_account_id(22202);
+    assert_eq!(ProfileInput::SteamId(steam_id), parse_profile_input("[U:1:22202]", None));
+    assert_eq!(ProfileInput::SteamId(steam_id), parse_profile_input(" 76561197960287930 ", None));
+    assert_eq!(ProfileInput::SteamId(steam_id), parse_profile_input("https://steamcommunity.com/profiles/76561197960287930/", None));
+
+    assert_eq!(ProfileInput::Vanity("gabelogannewell".to_string(), None), parse_profile_input("gabelogannewell", None));
+    assert_eq!(ProfileInput::Vanity("valve".to_string(), Some(URL_TYPE_GROUP)), parse_profile_input("valve", Some(URL_TYPE_GROUP)));
+    assert_eq!(
+        ProfileInput::Vanity("gabelogannewell".to_string(), Some(URL_TYPE_INDIVIDUAL)),
+        parse_profile_input("https://steamcommunity.com/id/gabelogannewell/", Some(URL_TYPE_GROUP)),
+    );
+    assert_eq!(
+        ProfileInput::Vanity("valve".to_string(), Some(URL_TYPE_GROUP)),
+        parse_profile_input("steamcommunity.com/groups/valve?tab=members", None),
+    );
+
+    for input in ["", "a", "gabe newell", "https://steamcommunity.com/id/", "https://steamcommunity.com/market/", "https://steamcommunity.com/profiles/gabe", "STEAM_0:2:1"] {
+        assert!(matches!(parse_profile_input(input, None), ProfileInput::Invalid(_)), "{}", input);
+    }
+}
+
+#[test]
+fn resolve_calls_steam_for_vanity_names_only() {
+    let transport = Arc::new(FakeTransport::new());
+    transport.respond_with(
+        "https://api.steampowered.com/ISteamUser/ResolveVanityURL/v1?vanityurl=gabelogannewell&url_type=1&key=KEY",
+        200,
+        r#"{"response":{"steamid":"76561197960287930","success":1}}"#,
+    );
+    transport.respond_with(
+        "https://api.steampowered.com/ISteamUser/ResolveVanityURL/v1?vanityurl=nobody_here&key=KEY",
+        200,
+        r#"{"response":{"success":42,"message":"No match"}}"#,
+    );
+    let client = SteamClient::builder()
+        .api_key("KEY")
+        .transport(transport.clone())
+        .cache_store(Arc::new(MemoryCache::new(10)))
+        .build();
+
+    let resolution = resolve_with_client(&client, "[U:1:22202]", None).unwrap();
+    assert_eq!(SteamIdResolution::Parsed(SteamId::from_account_id(22202)), resolution);
+    assert_eq!(0, transport.requests().len());
+
+    let resolution = resolve_with_client(&client, "https://steamcommunity.com/id/gabelogannewell/", None).unwrap();
+    assert_eq!(Some(SteamId::from_account_id(22202)), resolution.get_steam_id());
+    assert_eq!(SteamIdResolution::Resolved { vanity_url: "gabelogannewell".to_string(), steam_id: SteamId::from_account_id(22202) }, resolution);
+    assert_eq!(1, transport.requests().len());
+
+    assert_serde_roundtrip(&resolution);
+
+    let resolution = client.resolve_steam_id("nobody_here", None).unwrap();
+    assert_eq!(SteamIdResolution::NoMatch { vanity_url: "nobody_here".to_string() }, resolution);
+    assert_eq!(None, resolution.get_steam_id());
+
+    let resolution = client.resolve_steam_id("gabe newell", None).unwrap();
+    assert!(matches!(resolution, SteamIdResolution::Invalid { .. }));
+    assert_eq!(2, transport.requests().len());
+}
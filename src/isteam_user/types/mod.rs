@@ -0,0 +1,224 @@
+use std::fmt;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+#[cfg(test)]
+mod tests;
+
+/// Online status shown on a profile, the `personastate` of a player summary.
+#[non_exhaustive]
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub enum PersonaState {
+    Offline,
+    Online,
+    Busy,
+    Away,
+    Snooze,
+    LookingToTrade,
+    LookingToPlay,
+    /// A state added after this version of the crate.
+    Unknown(u64),
+}
+
+impl PersonaState {
+    /// Returns the persona state with the given id, `Unknown` if it isn't a known one.
+    pub fn from_id(id: u64) -> PersonaState {
+        match id {
+            0 => PersonaState::Offline,
+            1 => PersonaState::Online,
+            2 => PersonaState::Busy,
+            3 => PersonaState::Away,
+            4 => PersonaState::Snooze,
+            5 => PersonaState::LookingToTrade,
+            6 => PersonaState::LookingToPlay,
+            _ => PersonaState::Unknown(id),
+        }
+    }
+
+    pub fn get_id(&self) -> u64 {
+        match self {
+            PersonaState::Offline => 0,
+            PersonaState::Online => 1,
+            PersonaState::Busy => 2,
+            PersonaState::Away => 3,
+            PersonaState::Snooze => 4,
+            PersonaState::LookingToTrade => 5,
+            PersonaState::LookingToPlay => 6,
+            PersonaState::Unknown(id) => *id,
+        }
+    }
+
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            PersonaState::Offline => "Offline",
+            PersonaState::Online => "Online",
+            PersonaState::Busy => "Busy",
+            PersonaState::Away => "Away",
+            PersonaState::Snooze => "Snooze",
+            PersonaState::LookingToTrade => "Looking to Trade",
+            PersonaState::LookingToPlay => "Looking to Play",
+            PersonaState::Unknown(_) => "Unknown",
+        }
+    }
+
+    /// Whether the player is signed in, whatever their status. Private profiles always read offline.
+    pub fn is_online(&self) -> bool {
+        *self != PersonaState::Offline
+    }
+}
+
+impl TryFrom<u64> for PersonaState {
+    type Error = String;
+
+    fn try_from(id: u64) -> Result<Self, Self::Error> {
+        match PersonaState::from_id(id) {
+            PersonaState::Unknown(_) => Err(format!("unknown persona state {}", id)),
+            persona_state => Ok(persona_state),
+        }
+    }
+}
+
+impl fmt::Display for PersonaState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PersonaState::Unknown(id) => write!(f, "Unknown ({})", id),
+            _ => f.write_str(self.get_name()),
+        }
+    }
+}
+
+impl Serialize for PersonaState {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u64(self.get_id())
+    }
+}
+
+impl<'de> Deserialize<'de> for PersonaState {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        u64::deserialize(deserializer).map(PersonaState::from_id)
+    }
+}
+
+/// Who can see a profile, the `communityvisibilitystate` of a player summary. The Web API reports
+/// friends-only profiles as `Private` unless the key owner is a friend.
+#[non_exhaustive]
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub enum CommunityVisibility {
+    Private,
+    FriendsOnly,
+    Public,
+    /// A visibility added after this version of the crate.
+    Unknown(u64),
+}
+
+impl CommunityVisibility {
+    /// Returns the visibility with the given id, `Unknown` if it isn't a known one.
+    pub fn from_id(id: u64) -> CommunityVisibility {
+        match id {
+            1 => CommunityVisibility::Private,
+            2 => CommunityVisibility::FriendsOnly,
+            3 => CommunityVisibility::Public,
+            _ => CommunityVisibility::Unknown(id),
+        }
+    }
+
+    pub fn get_id(&self) -> u64 {
+        match self {
+            CommunityVisibility::Private => 1,
+            CommunityVisibility::FriendsOnly => 2,
+            CommunityVisibility::Public => 3,
+            CommunityVisibility::Unknown(id) => *id,
+        }
+    }
+
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            CommunityVisibility::Private => "Private",
+            CommunityVisibility::FriendsOnly => "Friends Only",
+            CommunityVisibility::Public => "Public",
+            CommunityVisibility::Unknown(_) => "Unknown",
+        }
+    }
+}
+
+impl TryFrom<u64> for CommunityVisibility {
+    type Error = String;
+
+    fn try_from(id: u64) -> Result<Self, Self::Error> {
+        match CommunityVisibility::from_id(id) {
+            CommunityVisibility::Unknown(_) => Err(format!("unknown community visibility {}", id)),
+            visibility => Ok(visibility),
+        }
+    }
+}
+
+impl fmt::Display for CommunityVisibility {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CommunityVisibility::Unknown(id) => write!(f, "Unknown ({})", id),
+            _ => f.write_str(self.get_name()),
+        }
+    }
+}
+
+impl Serialize for CommunityVisibility {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u64(self.get_id())
+    }
+}
+
+impl<'de> Deserialize<'de> for CommunityVisibility {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        u64::deserialize(deserializer).map(CommunityVisibility::from_id)
+    }
+}
+
+/// Trade ban status, the `EconomyBan` of a player's bans. Steam sends it as a lowercase string.
+#[non_exhaustive]
+#[derive(PartialEq, Eq, Hash, Clone, Debug)]
+pub enum EconomyBan {
+    None,
+    Probation,
+    Banned,
+    /// A status added after this version of the crate.
+    Unknown(String),
+}
+
+impl EconomyBan {
+    /// Returns the economy ban for the given Steam value, `Unknown` if it isn't a known one.
+    pub fn from_name(name: &str) -> EconomyBan {
+        match name {
+            "none" => EconomyBan::None,
+            "probation" => EconomyBan::Probation,
+            "banned" => EconomyBan::Banned,
+            _ => EconomyBan::Unknown(name.to_string()),
+        }
+    }
+
+    /// Returns the value Steam uses for this status, e.g. `probation`.
+    pub fn get_name(&self) -> &str {
+        match self {
+            EconomyBan::None => "none",
+            EconomyBan::Probation => "probation",
+            EconomyBan::Banned => "banned",
+            EconomyBan::Unknown(name) => name.as_str(),
+        }
+    }
+}
+
+impl fmt::Display for EconomyBan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.get_name())
+    }
+}
+
+impl Serialize for EconomyBan {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.get_name())
+    }
+}
+
+impl<'de> Deserialize<'de> for EconomyBan {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer).map(|name| EconomyBan::from_name(name.as_str()))
+    }
+}
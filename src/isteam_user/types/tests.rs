@@ -0,0 +1,53 @@
+use crate::isteam_user::types::{CommunityVisibility, EconomyBan, PersonaState};
+use crate::tests::assert_serde_roundtrip;
+
+#[test]
+fn persona_states() {
+    for id in 0..=6 {
+        let persona_state = PersonaState::try_from(id).unwrap();
+        assert_eq!(id, persona_state.get_id());
+        assert_eq!(persona_state, PersonaState::from_id(id));
+    }
+
+    assert_eq!(PersonaState::LookingToPlay, PersonaState::from_id(6));
+    assert_eq!(PersonaState::Unknown(7), PersonaState::from_id(7));
+    assert!(PersonaState::try_from(7).is_err());
+
+    assert!(!PersonaState::Offline.is_online());
+    assert!(PersonaState::Snooze.is_online());
+    assert_eq!("Looking to Trade", PersonaState::LookingToTrade.to_string());
+    assert_eq!("Unknown (7)", PersonaState::Unknown(7).to_string());
+}
+
+#[test]
+fn community_visibilities() {
+    assert_eq!(CommunityVisibility::Private, CommunityVisibility::try_from(1).unwrap());
+    assert_eq!(CommunityVisibility::FriendsOnly, CommunityVisibility::try_from(2).unwrap());
+    assert_eq!(CommunityVisibility::Public, CommunityVisibility::try_from(3).unwrap());
+    assert!(CommunityVisibility::try_from(0).is_err());
+    assert_eq!(CommunityVisibility::Unknown(0), CommunityVisibility::from_id(0));
+    assert_eq!(3, CommunityVisibility::Public.get_id());
+    assert_eq!("Friends Only", CommunityVisibility::FriendsOnly.to_string());
+}
+
+#[test]
+fn economy_bans() {
+    assert_eq!(EconomyBan::None, EconomyBan::from_name("none"));
+    assert_eq!(EconomyBan::Probation, EconomyBan::from_name("probation"));
+    assert_eq!(EconomyBan::Banned, EconomyBan::from_name("banned"));
+    assert_eq!(EconomyBan::Unknown("suspended".to_string()), EconomyBan::from_name("suspended"));
+    assert_eq!("probation", EconomyBan::Probation.to_string());
+    assert_eq!("suspended", EconomyBan::Unknown("suspended".to_string()).get_name());
+}
+
+#[test]
+fn serde_as_steam_values() {
+    assert_eq!("3", serde_json::to_string(&CommunityVisibility::Public).unwrap());
+    assert_eq!("\"banned\"", serde_json::to_string(&EconomyBan::Banned).unwrap());
+    assert_eq!(PersonaState::Away, serde_json::from_str::<PersonaState>("3").unwrap());
+
+    assert_serde_roundtrip(&PersonaState::Unknown(9));
+    assert_serde_roundtrip(&CommunityVisibility::FriendsOnly);
+    assert_serde_roundtrip(&EconomyBan::Probation);
+    assert_serde_roundtrip(&EconomyBan::Unknown("suspended".to_string()));
+}
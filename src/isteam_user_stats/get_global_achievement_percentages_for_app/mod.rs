@@ -1,12 +1,16 @@
 use std::collections::HashMap;
 use serde_json::Value;
-use crate::{isteam_user_stats, make_api_call};
-use crate::util::{build_steam_api_url, get_steam_web_api_key, json_f64, json_str};
+use serde::Deserialize;
+use serde::Serialize;
+use crate::isteam_user_stats;
+use crate::client::{get_default_client, SteamClient};
+use crate::error::SteamApiError;
+use crate::util::{build_cache_key, build_steam_api_url, json_f64, json_str};
 
 #[cfg(test)]
 mod tests;
 
-#[derive(PartialEq, Clone, Debug)]
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
 pub struct AchievementPercentage {
     pub name: String,
     pub percent: f64,
@@ -20,15 +24,65 @@ pub fn get_version() -> String {
     "v2".to_string()
 }
 
+pub fn get_endpoint() -> String {
+    [isteam_user_stats::get_interface(), "/".to_string(), get_method_name()].join("")
+}
+
 /// Does not require a Steam Web API key.
-pub fn get(gameid: i64) -> Result<Vec<AchievementPercentage>, String> {
+pub fn get(gameid: i64) -> Result<Vec<AchievementPercentage>, SteamApiError> {
+    get_with_client(get_default_client().as_ref(), gameid)
+}
+
+/// Async version of `get`, available with the `async` feature.
+#[cfg(feature = "async")]
+pub async fn get_async(gameid: i64) -> Result<Vec<AchievementPercentage>, SteamApiError> {
+    get_async_with_client(get_default_client().as_ref(), gameid).await
+}
+
+pub fn get_with_client(client: &SteamClient, gameid: i64) -> Result<Vec<AchievementPercentage>, SteamApiError> {
+    let cache_key = get_cache_key(gameid);
     let api_url = get_api_url(gameid);
-    let boxed_response = make_api_call(api_url);
-    if boxed_response.is_err() {
-        return Err(boxed_response.err().unwrap());
-    }
+    client.fetch_and_cache(get_endpoint().as_str(), cache_key.as_str(), api_url, parse_response)
+}
+
+/// Async version of `get_with_client`, available with the `async` feature.
+#[cfg(feature = "async")]
+pub async fn get_async_with_client(client: &SteamClient, gameid: i64) -> Result<Vec<AchievementPercentage>, SteamApiError> {
+    let cache_key = get_cache_key(gameid);
+    let api_url = get_api_url(gameid);
+    client.fetch_and_cache_async(get_endpoint().as_str(), cache_key.as_str(), api_url, parse_response).await
+}
 
-    parse_response(boxed_response.unwrap())
+/// Like `get`, but serves the cached response while it is fresh as per the client's
+/// `cache::CachePolicy`, otherwise calls Steam and caches the response.
+pub fn get_cached(gameid: i64) -> Result<Vec<AchievementPercentage>, SteamApiError> {
+    get_cached_with_client(get_default_client().as_ref(), gameid)
+}
+
+/// Async version of `get_cached`, available with the `async` feature.
+#[cfg(feature = "async")]
+pub async fn get_cached_async(gameid: i64) -> Result<Vec<AchievementPercentage>, SteamApiError> {
+    get_cached_async_with_client(get_default_client().as_ref(), gameid).await
+}
+
+pub fn get_cached_with_client(client: &SteamClient, gameid: i64) -> Result<Vec<AchievementPercentage>, SteamApiError> {
+    client.get_cached_or_fetch(
+        get_endpoint().as_str(),
+        get_cache_key(gameid).as_str(),
+        parse_response,
+        || get_with_client(client, gameid),
+    )
+}
+
+/// Async version of `get_cached_with_client`, available with the `async` feature.
+#[cfg(feature = "async")]
+pub async fn get_cached_async_with_client(client: &SteamClient, gameid: i64) -> Result<Vec<AchievementPercentage>, SteamApiError> {
+    client.get_cached_or_fetch_async(
+        get_endpoint().as_str(),
+        get_cache_key(gameid).as_str(),
+        parse_response,
+        get_async_with_client(client, gameid),
+    ).await
 }
 
 pub fn get_api_url(gameid: i64) -> String {
@@ -38,21 +92,29 @@ pub fn get_api_url(gameid: i64) -> String {
 
     let mut params_map = HashMap::new();
     params_map.insert("gameid".to_string(), gameid.to_string());
-    params_map.insert("key".to_string(), get_steam_web_api_key());
 
     build_steam_api_url(interface.as_str(), method.as_str(), version.as_str(), params_map)
 }
 
-pub fn parse_response(response: String) -> Result<Vec<AchievementPercentage>, String> {
+/// Returns path of the cached resource relative to the cache directory.
+pub fn get_cache_key(gameid: i64) -> String {
+    build_cache_key(isteam_user_stats::get_interface().as_str(), get_method_name().as_str(), get_version().as_str(), vec![("gameid", gameid.to_string())])
+}
+
+pub fn get_resource_filepath(gameid: i64) -> String {
+    get_default_client().get_resource_filepath(get_cache_key(gameid).as_str())
+}
+
+pub fn parse_response(response: String) -> Result<Vec<AchievementPercentage>, SteamApiError> {
     let boxed_initial_parse = serde_json::from_str(&response);
     if boxed_initial_parse.is_err() {
-        return Err(boxed_initial_parse.err().unwrap().to_string());
+        return Err(SteamApiError::malformed_response(get_endpoint().as_str(), boxed_initial_parse.err().unwrap().to_string().as_str()));
     }
     let json: Value = boxed_initial_parse.unwrap();
 
     let boxed_achievements = json.get("achievementpercentages").and_then(|r| r.get("achievements")).and_then(Value::as_array);
     if boxed_achievements.is_none() {
-        return Err("response does not contain achievement percentages".to_string());
+        return Err(SteamApiError::malformed_response(get_endpoint().as_str(), "response does not contain achievement percentages"));
     }
 
     let mut achievements = vec![];
@@ -1,6 +1,8 @@
 use url_build_parse::parse_url;
 use crate::{get_host, get_scheme};
-use crate::isteam_user_stats::get_global_achievement_percentages_for_app::{get_api_url, parse_response};
+use crate::isteam_user_stats::get_global_achievement_percentages_for_app::{get_api_url, get_resource_filepath, parse_response};
+use crate::client::get_default_client;
+use crate::tests::assert_serde_roundtrip;
 
 #[test]
 fn api_url() {
@@ -17,6 +19,13 @@ fn api_url() {
     assert_eq!(440, boxed_gameid.unwrap().parse::<i64>().unwrap());
 }
 
+#[test]
+fn resource_filepath() {
+    let filepath = get_resource_filepath(570);
+
+    assert_eq!([get_default_client().cache_dir(), "/ISteamUserStats-GetGlobalAchievementPercentagesForApp-v2-gameid=570.json"].join(""), filepath);
+}
+
 #[test]
 fn parse() {
     let response = r#"{
@@ -31,6 +40,7 @@ fn parse() {
     assert!(boxed_parse.is_ok());
 
     let achievements = boxed_parse.unwrap();
+    assert_serde_roundtrip(&achievements);
     assert_eq!(1, achievements.len());
 
     let achievement = achievements.get(0).unwrap();
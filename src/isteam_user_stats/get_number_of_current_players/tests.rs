@@ -1,6 +1,7 @@
 use url_build_parse::parse_url;
 use crate::{get_host, get_scheme};
-use crate::isteam_user_stats::get_number_of_current_players::{get_api_url, parse_response};
+use crate::isteam_user_stats::get_number_of_current_players::{get_api_url, get_resource_filepath, parse_response};
+use crate::client::get_default_client;
 
 #[test]
 fn api_url() {
@@ -17,6 +18,13 @@ fn api_url() {
     assert_eq!(570, boxed_appid.unwrap().parse::<i64>().unwrap());
 }
 
+#[test]
+fn resource_filepath() {
+    let filepath = get_resource_filepath(570);
+
+    assert_eq!([get_default_client().cache_dir(), "/ISteamUserStats-GetNumberOfCurrentPlayers-v1-appid=570.json"].join(""), filepath);
+}
+
 #[test]
 fn parse() {
     let response = r#"{"response": {"player_count": 500000, "result": 1}}"#.to_string();
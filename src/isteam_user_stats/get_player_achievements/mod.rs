@@ -1,23 +1,30 @@
 use std::collections::HashMap;
+use std::fmt::Display;
 use serde_json::Value;
-use crate::{isteam_user_stats, make_api_call};
-use crate::util::{build_steam_api_url, get_steam_web_api_key, json_bool, json_str, json_u64};
+use serde::Deserialize;
+use serde::Serialize;
+use crate::isteam_user_stats;
+use crate::client::{get_default_client, SteamClient};
+use crate::error::SteamApiError;
+use crate::steam_id::{json_steam_id, to_steam_id, SteamId};
+use crate::util::{build_cache_key, build_steam_api_url, json_bool, json_opt_str, json_str, json_u64};
 
 #[cfg(test)]
 mod tests;
 
-#[derive(PartialEq, Clone, Debug)]
+/// `name` and `description` are only returned when a language is requested.
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
 pub struct PlayerAchievement {
     pub apiname: String,
     pub achieved: bool,
     pub unlocktime: u64,
-    pub name: String,
-    pub description: String,
+    pub name: Option<String>,
+    pub description: Option<String>,
 }
 
-#[derive(PartialEq, Clone, Debug)]
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
 pub struct PlayerAchievements {
-    pub steamid: String,
+    pub steamid: SteamId,
     pub game_name: String,
     pub achievements: Vec<PlayerAchievement>,
 }
@@ -30,17 +37,89 @@ pub fn get_version() -> String {
     "v1".to_string()
 }
 
-pub fn get(steamid: u64, appid: i64, language: Option<String>) -> Result<PlayerAchievements, String> {
+pub fn get_endpoint() -> String {
+    [isteam_user_stats::get_interface(), "/".to_string(), get_method_name()].join("")
+}
+
+pub fn get(steamid: impl TryInto<SteamId, Error = impl Display>, appid: i64, language: Option<String>) -> Result<PlayerAchievements, SteamApiError> {
+    get_with_client(get_default_client().as_ref(), steamid, appid, language)
+}
+
+/// Async version of `get`, available with the `async` feature.
+#[cfg(feature = "async")]
+pub async fn get_async(steamid: impl TryInto<SteamId, Error = impl Display>, appid: i64, language: Option<String>) -> Result<PlayerAchievements, SteamApiError> {
+    get_async_with_client(get_default_client().as_ref(), steamid, appid, language).await
+}
+
+pub fn get_with_client(client: &SteamClient, steamid: impl TryInto<SteamId, Error = impl Display>, appid: i64, language: Option<String>) -> Result<PlayerAchievements, SteamApiError> {
+    let steamid = to_steam_id(get_endpoint().as_str(), steamid)?;
+    let cache_key = get_cache_key(steamid, appid, language.clone());
     let api_url = get_api_url(steamid, appid, language);
-    let boxed_response = make_api_call(api_url);
-    if boxed_response.is_err() {
-        return Err(boxed_response.err().unwrap());
+    let boxed_result = client.fetch_and_cache(get_endpoint().as_str(), cache_key.as_str(), api_url.clone(), parse_response);
+    // steam answers private profiles with a 403 whose body still explains the failure
+    if let Err(SteamApiError::HttpStatus { body, .. }) = boxed_result.as_ref() {
+        let boxed_parse = parse_response(body.to_string());
+        if boxed_parse.is_err() {
+            return Err(boxed_parse.err().unwrap().with_url(api_url.as_str()));
+        }
     }
 
-    parse_response(boxed_response.unwrap())
+    boxed_result
+}
+
+/// Async version of `get_with_client`, available with the `async` feature.
+#[cfg(feature = "async")]
+pub async fn get_async_with_client(client: &SteamClient, steamid: impl TryInto<SteamId, Error = impl Display>, appid: i64, language: Option<String>) -> Result<PlayerAchievements, SteamApiError> {
+    let steamid = to_steam_id(get_endpoint().as_str(), steamid)?;
+    let cache_key = get_cache_key(steamid, appid, language.clone());
+    let api_url = get_api_url(steamid, appid, language);
+    let boxed_result = client.fetch_and_cache_async(get_endpoint().as_str(), cache_key.as_str(), api_url.clone(), parse_response).await;
+    // steam answers private profiles with a 403 whose body still explains the failure
+    if let Err(SteamApiError::HttpStatus { body, .. }) = boxed_result.as_ref() {
+        let boxed_parse = parse_response(body.to_string());
+        if boxed_parse.is_err() {
+            return Err(boxed_parse.err().unwrap().with_url(api_url.as_str()));
+        }
+    }
+
+    boxed_result
+}
+
+/// Like `get`, but serves the cached response while it is fresh as per the client's
+/// `cache::CachePolicy`, otherwise calls Steam and caches the response.
+pub fn get_cached(steamid: impl TryInto<SteamId, Error = impl Display>, appid: i64, language: Option<String>) -> Result<PlayerAchievements, SteamApiError> {
+    get_cached_with_client(get_default_client().as_ref(), steamid, appid, language)
 }
 
-pub fn get_api_url(steamid: u64, appid: i64, language: Option<String>) -> String {
+/// Async version of `get_cached`, available with the `async` feature.
+#[cfg(feature = "async")]
+pub async fn get_cached_async(steamid: impl TryInto<SteamId, Error = impl Display>, appid: i64, language: Option<String>) -> Result<PlayerAchievements, SteamApiError> {
+    get_cached_async_with_client(get_default_client().as_ref(), steamid, appid, language).await
+}
+
+pub fn get_cached_with_client(client: &SteamClient, steamid: impl TryInto<SteamId, Error = impl Display>, appid: i64, language: Option<String>) -> Result<PlayerAchievements, SteamApiError> {
+    let steamid = to_steam_id(get_endpoint().as_str(), steamid)?;
+    client.get_cached_or_fetch(
+        get_endpoint().as_str(),
+        get_cache_key(steamid, appid, language.clone()).as_str(),
+        parse_response,
+        || get_with_client(client, steamid, appid, language),
+    )
+}
+
+/// Async version of `get_cached_with_client`, available with the `async` feature.
+#[cfg(feature = "async")]
+pub async fn get_cached_async_with_client(client: &SteamClient, steamid: impl TryInto<SteamId, Error = impl Display>, appid: i64, language: Option<String>) -> Result<PlayerAchievements, SteamApiError> {
+    let steamid = to_steam_id(get_endpoint().as_str(), steamid)?;
+    client.get_cached_or_fetch_async(
+        get_endpoint().as_str(),
+        get_cache_key(steamid, appid, language.clone()).as_str(),
+        parse_response,
+        get_async_with_client(client, steamid, appid, language),
+    ).await
+}
+
+pub fn get_api_url(steamid: SteamId, appid: i64, language: Option<String>) -> String {
     let interface = isteam_user_stats::get_interface();
     let method = get_method_name();
     let version = get_version();
@@ -51,28 +130,46 @@ pub fn get_api_url(steamid: u64, appid: i64, language: Option<String>) -> String
     if language.is_some() {
         params_map.insert("l".to_string(), language.unwrap());
     }
-    params_map.insert("key".to_string(), get_steam_web_api_key());
 
     build_steam_api_url(interface.as_str(), method.as_str(), version.as_str(), params_map)
 }
 
-pub fn parse_response(response: String) -> Result<PlayerAchievements, String> {
+const PROFILE_IS_NOT_PUBLIC: &str = "Profile is not public";
+
+/// Returns path of the cached resource relative to the cache directory.
+pub fn get_cache_key(steamid: SteamId, appid: i64, language: Option<String>) -> String {
+    let mut params = vec![("steamid", steamid.to_string()), ("appid", appid.to_string())];
+    if let Some(language) = language {
+        params.push(("l", language));
+    }
+
+    build_cache_key(isteam_user_stats::get_interface().as_str(), get_method_name().as_str(), get_version().as_str(), params)
+}
+
+pub fn get_resource_filepath(steamid: SteamId, appid: i64, language: Option<String>) -> String {
+    get_default_client().get_resource_filepath(get_cache_key(steamid, appid, language).as_str())
+}
+
+pub fn parse_response(response: String) -> Result<PlayerAchievements, SteamApiError> {
     let boxed_initial_parse = serde_json::from_str(&response);
     if boxed_initial_parse.is_err() {
-        return Err(boxed_initial_parse.err().unwrap().to_string());
+        return Err(SteamApiError::malformed_response(get_endpoint().as_str(), boxed_initial_parse.err().unwrap().to_string().as_str()));
     }
     let json: Value = boxed_initial_parse.unwrap();
 
     let boxed_playerstats = json.get("playerstats");
     if boxed_playerstats.is_none() {
-        return Err("response does not contain playerstats".to_string());
+        return Err(SteamApiError::malformed_response(get_endpoint().as_str(), "response does not contain playerstats"));
     }
     let playerstats = boxed_playerstats.unwrap();
 
     let boxed_success = playerstats.get("success").and_then(Value::as_bool);
     if boxed_success == Some(false) {
         let error_message = json_str(playerstats, "error");
-        return Err(if error_message.is_empty() { "steam api returned unsuccessful response".to_string() } else { error_message });
+        if error_message == PROFILE_IS_NOT_PUBLIC {
+            return Err(SteamApiError::private_profile(get_endpoint().as_str()));
+        }
+        return Err(SteamApiError::steam_failure(get_endpoint().as_str(), None, if error_message.is_empty() { "steam api returned unsuccessful response" } else { error_message.as_str() }));
     }
 
     let boxed_achievements = playerstats.get("achievements").and_then(Value::as_array);
@@ -83,14 +180,14 @@ pub fn parse_response(response: String) -> Result<PlayerAchievements, String> {
                 apiname: json_str(achievement, "apiname"),
                 achieved: json_u64(achievement, "achieved") == 1 || json_bool(achievement, "achieved"),
                 unlocktime: json_u64(achievement, "unlocktime"),
-                name: json_str(achievement, "name"),
-                description: json_str(achievement, "description"),
+                name: json_opt_str(achievement, "name"),
+                description: json_opt_str(achievement, "description"),
             });
         }
     }
 
     Ok(PlayerAchievements {
-        steamid: json_str(playerstats, "steamID"),
+        steamid: json_steam_id(get_endpoint().as_str(), playerstats, "steamID")?,
         game_name: json_str(playerstats, "gameName"),
         achievements,
     })
@@ -1,11 +1,14 @@
 use url_build_parse::parse_url;
 use crate::{get_host, get_scheme};
-use crate::isteam_user_stats::get_player_achievements::{get_api_url, parse_response};
-use crate::util::get_steam_web_api_key;
+use crate::isteam_user_stats::get_player_achievements::{get_api_url, get_resource_filepath, parse_response};
+use crate::steam_id::SteamId;
+use crate::error::SteamApiError;
+use crate::client::get_default_client;
+use crate::tests::assert_serde_roundtrip;
 
 #[test]
 fn api_url() {
-    let api_url = get_api_url(76561197960435530, 440, Some("english".to_string()));
+    let api_url = get_api_url(SteamId::try_from(76561197960435530).unwrap(), 440, Some("english".to_string()));
 
     let components = parse_url(api_url.as_str()).unwrap();
 
@@ -23,8 +26,14 @@ fn api_url() {
     let boxed_l = params.get("l");
     assert_eq!("english", boxed_l.unwrap().to_string());
 
-    let boxed_key = params.get("key");
-    assert_eq!(get_steam_web_api_key(), boxed_key.unwrap().to_string());
+    assert!(!params.contains_key("key"));
+}
+
+#[test]
+fn resource_filepath() {
+    let filepath = get_resource_filepath(SteamId::try_from(76561197960435530).unwrap(), 570, Some("en".to_string()));
+
+    assert_eq!([get_default_client().cache_dir(), "/ISteamUserStats-GetPlayerAchievements-v1-steamid=76561197960435530-appid=570-l=en.json"].join(""), filepath);
 }
 
 #[test]
@@ -50,7 +59,8 @@ fn parse() {
     assert!(boxed_parse.is_ok());
 
     let player_achievements = boxed_parse.unwrap();
-    assert_eq!("76561197960435530", player_achievements.steamid);
+    assert_serde_roundtrip(&player_achievements);
+    assert_eq!("76561197960435530", player_achievements.steamid.to_string());
     assert_eq!("Team Fortress 2", player_achievements.game_name);
     assert_eq!(1, player_achievements.achievements.len());
 
@@ -58,7 +68,7 @@ fn parse() {
     assert_eq!("TF_PLAY_GAME_EVERYMAP", achievement.apiname);
     assert_eq!(true, achievement.achieved);
     assert_eq!(1234567890, achievement.unlocktime);
-    assert_eq!("Grand Tourist", achievement.name);
+    assert_eq!(Some("Grand Tourist".to_string()), achievement.name);
 }
 
 #[test]
@@ -67,5 +77,16 @@ fn parse_private_profile() {
 
     let boxed_parse = parse_response(response);
     assert!(boxed_parse.is_err());
-    assert_eq!("Profile is not public", boxed_parse.err().unwrap());
+    assert!(matches!(boxed_parse.err().unwrap(), SteamApiError::PrivateProfile { .. }));
+}
+
+#[test]
+fn parse_unsuccessful_response() {
+    let response = r#"{"playerstats": {"error": "Requested app has no stats", "success": false}}"#.to_string();
+
+    let boxed_parse = parse_response(response);
+    assert!(boxed_parse.is_err());
+
+    let error = boxed_parse.err().unwrap();
+    assert_eq!(SteamApiError::steam_failure("ISteamUserStats/GetPlayerAchievements", None, "Requested app has no stats"), error);
 }
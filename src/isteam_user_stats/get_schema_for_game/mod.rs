@@ -1,30 +1,35 @@
 use std::collections::HashMap;
 use serde_json::Value;
-use crate::{isteam_user_stats, make_api_call};
-use crate::util::{build_steam_api_url, get_steam_web_api_key, json_i64, json_str};
+use serde::Deserialize;
+use serde::Serialize;
+use crate::isteam_user_stats;
+use crate::client::{get_default_client, SteamClient};
+use crate::error::SteamApiError;
+use crate::util::{build_cache_key, build_steam_api_url, json_i64, json_opt_str, json_str};
 
 #[cfg(test)]
 mod tests;
 
-#[derive(PartialEq, Clone, Debug)]
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
 pub struct StatSchema {
     pub name: String,
     pub default_value: i64,
     pub display_name: String,
 }
 
-#[derive(PartialEq, Clone, Debug)]
+/// Hidden achievements usually come without a `description`.
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
 pub struct AchievementSchema {
     pub name: String,
     pub default_value: i64,
     pub display_name: String,
     pub hidden: bool,
-    pub description: String,
+    pub description: Option<String>,
     pub icon: String,
     pub icon_gray: String,
 }
 
-#[derive(PartialEq, Clone, Debug)]
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
 pub struct GameSchema {
     pub game_name: String,
     pub game_version: String,
@@ -40,14 +45,64 @@ pub fn get_version() -> String {
     "v2".to_string()
 }
 
-pub fn get(appid: i64) -> Result<GameSchema, String> {
+pub fn get_endpoint() -> String {
+    [isteam_user_stats::get_interface(), "/".to_string(), get_method_name()].join("")
+}
+
+pub fn get(appid: i64) -> Result<GameSchema, SteamApiError> {
+    get_with_client(get_default_client().as_ref(), appid)
+}
+
+/// Async version of `get`, available with the `async` feature.
+#[cfg(feature = "async")]
+pub async fn get_async(appid: i64) -> Result<GameSchema, SteamApiError> {
+    get_async_with_client(get_default_client().as_ref(), appid).await
+}
+
+pub fn get_with_client(client: &SteamClient, appid: i64) -> Result<GameSchema, SteamApiError> {
+    let cache_key = get_cache_key(appid);
     let api_url = get_api_url(appid);
-    let boxed_response = make_api_call(api_url);
-    if boxed_response.is_err() {
-        return Err(boxed_response.err().unwrap());
-    }
+    client.fetch_and_cache(get_endpoint().as_str(), cache_key.as_str(), api_url, parse_response)
+}
+
+/// Async version of `get_with_client`, available with the `async` feature.
+#[cfg(feature = "async")]
+pub async fn get_async_with_client(client: &SteamClient, appid: i64) -> Result<GameSchema, SteamApiError> {
+    let cache_key = get_cache_key(appid);
+    let api_url = get_api_url(appid);
+    client.fetch_and_cache_async(get_endpoint().as_str(), cache_key.as_str(), api_url, parse_response).await
+}
 
-    parse_response(boxed_response.unwrap())
+/// Like `get`, but serves the cached response while it is fresh as per the client's
+/// `cache::CachePolicy`, otherwise calls Steam and caches the response.
+pub fn get_cached(appid: i64) -> Result<GameSchema, SteamApiError> {
+    get_cached_with_client(get_default_client().as_ref(), appid)
+}
+
+/// Async version of `get_cached`, available with the `async` feature.
+#[cfg(feature = "async")]
+pub async fn get_cached_async(appid: i64) -> Result<GameSchema, SteamApiError> {
+    get_cached_async_with_client(get_default_client().as_ref(), appid).await
+}
+
+pub fn get_cached_with_client(client: &SteamClient, appid: i64) -> Result<GameSchema, SteamApiError> {
+    client.get_cached_or_fetch(
+        get_endpoint().as_str(),
+        get_cache_key(appid).as_str(),
+        parse_response,
+        || get_with_client(client, appid),
+    )
+}
+
+/// Async version of `get_cached_with_client`, available with the `async` feature.
+#[cfg(feature = "async")]
+pub async fn get_cached_async_with_client(client: &SteamClient, appid: i64) -> Result<GameSchema, SteamApiError> {
+    client.get_cached_or_fetch_async(
+        get_endpoint().as_str(),
+        get_cache_key(appid).as_str(),
+        parse_response,
+        get_async_with_client(client, appid),
+    ).await
 }
 
 pub fn get_api_url(appid: i64) -> String {
@@ -57,21 +112,29 @@ pub fn get_api_url(appid: i64) -> String {
 
     let mut params_map = HashMap::new();
     params_map.insert("appid".to_string(), appid.to_string());
-    params_map.insert("key".to_string(), get_steam_web_api_key());
 
     build_steam_api_url(interface.as_str(), method.as_str(), version.as_str(), params_map)
 }
 
-pub fn parse_response(response: String) -> Result<GameSchema, String> {
+/// Returns path of the cached resource relative to the cache directory.
+pub fn get_cache_key(appid: i64) -> String {
+    build_cache_key(isteam_user_stats::get_interface().as_str(), get_method_name().as_str(), get_version().as_str(), vec![("appid", appid.to_string())])
+}
+
+pub fn get_resource_filepath(appid: i64) -> String {
+    get_default_client().get_resource_filepath(get_cache_key(appid).as_str())
+}
+
+pub fn parse_response(response: String) -> Result<GameSchema, SteamApiError> {
     let boxed_initial_parse = serde_json::from_str(&response);
     if boxed_initial_parse.is_err() {
-        return Err(boxed_initial_parse.err().unwrap().to_string());
+        return Err(SteamApiError::malformed_response(get_endpoint().as_str(), boxed_initial_parse.err().unwrap().to_string().as_str()));
     }
     let json: Value = boxed_initial_parse.unwrap();
 
     let boxed_game = json.get("game");
     if boxed_game.is_none() {
-        return Err("response does not contain a game schema".to_string());
+        return Err(SteamApiError::malformed_response(get_endpoint().as_str(), "response does not contain a game schema"));
     }
     let game = boxed_game.unwrap();
 
@@ -101,7 +164,7 @@ pub fn parse_response(response: String) -> Result<GameSchema, String> {
                     default_value: json_i64(achievement, "defaultvalue"),
                     display_name: json_str(achievement, "displayName"),
                     hidden: json_i64(achievement, "hidden") == 1,
-                    description: json_str(achievement, "description"),
+                    description: json_opt_str(achievement, "description"),
                     icon: json_str(achievement, "icon"),
                     icon_gray: json_str(achievement, "icongray"),
                 });
@@ -1,7 +1,8 @@
 use url_build_parse::parse_url;
 use crate::{get_host, get_scheme};
-use crate::isteam_user_stats::get_schema_for_game::{get_api_url, parse_response};
-use crate::util::get_steam_web_api_key;
+use crate::isteam_user_stats::get_schema_for_game::{get_api_url, get_resource_filepath, parse_response};
+use crate::client::get_default_client;
+use crate::tests::assert_serde_roundtrip;
 
 #[test]
 fn api_url() {
@@ -17,8 +18,14 @@ fn api_url() {
     let boxed_appid = params.get("appid");
     assert_eq!(440, boxed_appid.unwrap().parse::<i64>().unwrap());
 
-    let boxed_key = params.get("key");
-    assert_eq!(get_steam_web_api_key(), boxed_key.unwrap().to_string());
+    assert!(!params.contains_key("key"));
+}
+
+#[test]
+fn resource_filepath() {
+    let filepath = get_resource_filepath(570);
+
+    assert_eq!([get_default_client().cache_dir(), "/ISteamUserStats-GetSchemaForGame-v2-appid=570.json"].join(""), filepath);
 }
 
 #[test]
@@ -50,6 +57,7 @@ fn parse() {
     assert!(boxed_parse.is_ok());
 
     let schema = boxed_parse.unwrap();
+    assert_serde_roundtrip(&schema);
     assert_eq!("Team Fortress 2", schema.game_name);
     assert_eq!("3", schema.game_version);
     assert_eq!(1, schema.stats.len());
@@ -60,6 +68,7 @@ fn parse() {
     assert_eq!("TF_SCOUT_LONG_DISTANCE_RUNNER", achievement.name);
     assert_eq!("Long Distance Runner", achievement.display_name);
     assert_eq!(false, achievement.hidden);
+    assert_eq!(Some("Travel 10 miles as the Scout in a single life.".to_string()), achievement.description);
 }
 
 #[test]
@@ -1,11 +1,13 @@
 use url_build_parse::parse_url;
 use crate::{get_host, get_scheme};
-use crate::isteam_user_stats::get_user_stats_for_game::{get_api_url, parse_response};
-use crate::util::get_steam_web_api_key;
+use crate::isteam_user_stats::get_user_stats_for_game::{get_api_url, get_resource_filepath, parse_response};
+use crate::steam_id::SteamId;
+use crate::client::get_default_client;
+use crate::tests::assert_serde_roundtrip;
 
 #[test]
 fn api_url() {
-    let api_url = get_api_url(76561197960435530, 440);
+    let api_url = get_api_url(SteamId::try_from(76561197960435530).unwrap(), 440);
 
     let components = parse_url(api_url.as_str()).unwrap();
 
@@ -20,8 +22,14 @@ fn api_url() {
     let boxed_appid = params.get("appid");
     assert_eq!(440, boxed_appid.unwrap().parse::<i64>().unwrap());
 
-    let boxed_key = params.get("key");
-    assert_eq!(get_steam_web_api_key(), boxed_key.unwrap().to_string());
+    assert!(!params.contains_key("key"));
+}
+
+#[test]
+fn resource_filepath() {
+    let filepath = get_resource_filepath(SteamId::try_from(76561197960435530).unwrap(), 730);
+
+    assert_eq!([get_default_client().cache_dir(), "/ISteamUserStats-GetUserStatsForGame-v2-steamid=76561197960435530-appid=730.json"].join(""), filepath);
 }
 
 #[test]
@@ -43,7 +51,8 @@ fn parse() {
     assert!(boxed_parse.is_ok());
 
     let user_stats = boxed_parse.unwrap();
-    assert_eq!("76561197960435530", user_stats.steamid);
+    assert_serde_roundtrip(&user_stats);
+    assert_eq!("76561197960435530", user_stats.steamid.to_string());
     assert_eq!("Team Fortress 2", user_stats.game_name);
     assert_eq!(1, user_stats.stats.len());
     assert_eq!("Kills", user_stats.stats.get(0).unwrap().name);
@@ -5,24 +5,28 @@
 //! In order to use this library make sure to set STEAM_WEB_API_KEY system environment variable.
 //!
 //! The library itself tries to minimize number of networks calls through the caching relevant
-//! responses to the 'steam-webapi-cache' folder.
+//! responses to the cache directory, see `util::get_cache_dir_path`. It can be set with the
+//! STEAM_WEBAPI_CACHE_DIR environment variable or `SteamClientBuilder::cache_dir`.
 //!
 //! There is already prebuilt cache for all steam apps, in order to use it,
 //! simply clone [steam-webapi-cache](https://github.com/bohdaq/steam-webapi-cache)
 //! into the root folder of your project.
 
+use std::fmt::Display;
 use crate::idota2match_570::get_heroes::Hero;
 use crate::idota2match_570::get_league_listing::League;
 use crate::idota2match_570::get_live_league_games::LiveLeagueGame;
 use crate::idota2match_570::get_match_details::MatchResult;
-use crate::idota2match_570::get_match_history::ResponseMatchHistory;
+use crate::idota2match_570::get_match_history::{MatchHistoryFilter, MatchHistoryPages, ResponseMatchHistory};
 use crate::idota2match_570::get_team_info_by_team_id::TeamInfo;
+use crate::idota2match_570::types::{GameMode, Skill};
+use crate::iecon_dota2_570::get_game_items::GameItem;
 use crate::isteam_apps::get_app_list::SteamApp;
 use crate::isteam_news::get_news_for_app::NewsItem;
 use crate::isteam_user::get_friend_list::Friend;
 use crate::isteam_user::get_player_bans::PlayerBans;
 use crate::isteam_user::get_player_summaries::PlayerSummary;
-use crate::isteam_user::resolve_vanity_url::VanityUrlResolution;
+use crate::isteam_user::resolve_vanity_url::{SteamIdResolution, VanityUrlResolution};
 use crate::isteam_user_stats::get_global_achievement_percentages_for_app::AchievementPercentage;
 use crate::isteam_user_stats::get_player_achievements::PlayerAchievements;
 use crate::isteam_user_stats::get_schema_for_game::GameSchema;
@@ -32,7 +36,17 @@ use crate::iplayer_service::get_owned_games::OwnedGames;
 use crate::iplayer_service::get_recently_played_games::RecentlyPlayedGames;
 use crate::store_steampowered_com::appdetails::SteamAppDetails;
 
+pub use crate::error::SteamApiError;
+pub use crate::steam_id::SteamId;
+
 pub mod util;
+pub mod error;
+pub mod steam_id;
+pub mod client;
+pub mod transport;
+pub mod rate_limit;
+pub mod retry;
+pub mod cache;
 pub mod isteam_apps;
 pub mod isteam_user;
 pub mod isteam_user_stats;
@@ -40,12 +54,14 @@ pub mod isteam_news;
 pub mod iplayer_service;
 pub mod store_steampowered_com;
 pub mod idota2match_570;
+pub mod iecon_dota2_570;
 
 #[cfg(test)]
 mod tests;
 
-/// Retrieves details for the given app id from the local cache. It may return an error
-/// if requested resource is absent, malformed or not readable from local cache.
+/// Retrieves details for the given app id from the local cache, calling Steam if the cached
+/// resource is absent or stale as per the default client's `cache::CachePolicy`. It may return an
+/// error if the resource is malformed or neither cached nor retrievable.
 ///
 /// # Examples
 ///
@@ -62,7 +78,7 @@ mod tests;
 ///
 /// };
 /// ```
-pub fn get_cached_app_details(app_id: i64) -> Result<SteamAppDetails, String> {
+pub fn get_cached_app_details(app_id: i64) -> Result<SteamAppDetails, SteamApiError> {
     let boxed_result = store_steampowered_com::appdetails::get_cached(app_id);
     boxed_result
 }
@@ -70,7 +86,8 @@ pub fn get_cached_app_details(app_id: i64) -> Result<SteamAppDetails, String> {
 /// Retrieves details for the given app id. It will make an API call to Steam and cache response.
 /// It may return an error if API responded with error response. As an example it may be exceeding
 /// the limit of calls from one IP address or if the response contains not valid UTF-8 characters.
-/// Usually Steam API allows 200 requests from single IP address within 5 minutes range.
+/// Usually Steam API allows 200 requests from single IP address within 5 minutes range, so the
+/// default client waits for its `rate_limit::RateLimiter` budget before each request.
 ///
 /// # Examples
 ///
@@ -82,18 +99,18 @@ pub fn get_cached_app_details(app_id: i64) -> Result<SteamAppDetails, String> {
 ///     println!("result is ok for {} app id {}", app_details.name, app_details.app_id);
 ///
 /// } else {
-///     let error_message = boxed_result.err().unwrap();
-///     println!("{} {}", error_message, app_id);
+///     let error = boxed_result.err().unwrap();
+///     println!("{} {}", error, app_id);
 ///
-///     let is_steam_unsuccessful_response = error_message == "steampowered api returned failed response";
-///     let is_invalid_utf8_sequence = error_message == "invalid utf-8 sequence";
-///     let no_response_from_api = error_message == "no response from API";
-///     let exceeded_api_calls_limit = (!is_steam_unsuccessful_response && !is_invalid_utf8_sequence) || no_response_from_api;
+///     let is_steam_unsuccessful_response = matches!(error, steam_webapi_rust_sdk::SteamApiError::SteamFailure { .. });
+///     let exceeded_api_calls_limit = error.http_status_code() == Some(429);
 ///
-///     // you can do a retry or continue execution...
+///     if error.is_retryable() {
+///         // you can do a retry or continue execution...
+///     }
 /// };
 /// ```
-pub fn get_app_details(app_id: i64) -> Result<SteamAppDetails, String> {
+pub fn get_app_details(app_id: i64) -> Result<SteamAppDetails, SteamApiError> {
     let boxed_result = store_steampowered_com::appdetails::get(app_id);
     boxed_result
 }
@@ -103,6 +120,23 @@ pub fn get_app_details(app_id: i64) -> Result<SteamAppDetails, String> {
 /// # Examples
 ///
 /// ```
+/// # use std::sync::Arc;
+/// # use steam_webapi_rust_sdk::client::{set_default_client, SteamClient};
+/// # use steam_webapi_rust_sdk::transport::FakeTransport;
+/// # let transport = FakeTransport::new();
+/// # transport.respond_with(
+/// #     "https://api.steampowered.com/ISteamApps/GetAppList/v2?key=KEY",
+/// #     200,
+/// #     r#"{"applist":{"apps":[{"appid":570,"name":"Dota 2"}]}}"#,
+/// # );
+/// # let cache_dir = std::env::temp_dir().join("steam-webapi-rust-sdk-doctest");
+/// # set_default_client(SteamClient::builder()
+/// #     .api_key("KEY")
+/// #     .cache_enabled(false)
+/// #     .cache_dir(cache_dir.to_str().unwrap())
+/// #     .transport(Arc::new(transport))
+/// #     .build());
+/// #
 /// let steam_app_list = steam_webapi_rust_sdk::get_app_list().unwrap();
 ///
 /// assert!(steam_app_list.len()>0);
@@ -111,7 +145,7 @@ pub fn get_app_details(app_id: i64) -> Result<SteamAppDetails, String> {
 ///
 /// assert!(steam_app.name.len() > 0);
 /// ```
-pub fn get_app_list() -> Result<Vec<SteamApp>, String> {
+pub fn get_app_list() -> Result<Vec<SteamApp>, SteamApiError> {
     let boxed_result = isteam_apps::get_app_list::get();
     boxed_result
 }
@@ -123,6 +157,23 @@ pub fn get_app_list() -> Result<Vec<SteamApp>, String> {
 /// # Examples
 ///
 /// ```
+/// # use std::sync::Arc;
+/// # use steam_webapi_rust_sdk::client::{set_default_client, SteamClient};
+/// # use steam_webapi_rust_sdk::transport::FakeTransport;
+/// # let transport = FakeTransport::new();
+/// # transport.respond_with(
+/// #     "https://api.steampowered.com/ISteamApps/GetAppList/v2?key=KEY",
+/// #     200,
+/// #     r#"{"applist":{"apps":[{"appid":570,"name":"Dota 2"}]}}"#,
+/// # );
+/// # let cache_dir = std::env::temp_dir().join("steam-webapi-rust-sdk-doctest");
+/// # set_default_client(SteamClient::builder()
+/// #     .api_key("KEY")
+/// #     .cache_enabled(false)
+/// #     .cache_dir(cache_dir.to_str().unwrap())
+/// #     .transport(Arc::new(transport))
+/// #     .build());
+/// #
 /// let steam_app_list = steam_webapi_rust_sdk::get_cached_app_list().unwrap();
 ///
 /// assert!(steam_app_list.len()>0);
@@ -131,7 +182,7 @@ pub fn get_app_list() -> Result<Vec<SteamApp>, String> {
 ///
 /// assert!(steam_app.name.len() > 0);
 /// ```
-pub fn get_cached_app_list() -> Result<Vec<SteamApp>, String> {
+pub fn get_cached_app_list() -> Result<Vec<SteamApp>, SteamApiError> {
     let boxed_result = isteam_apps::get_app_list::get_cached();
     boxed_result
 }
@@ -140,9 +191,9 @@ pub fn get_cached_app_list() -> Result<Vec<SteamApp>, String> {
 ///
 /// # Examples
 ///
-/// ```
+/// ```no_run
 /// let boxed_dota2_match_list = steam_webapi_rust_sdk::get_dota2_match_history(
-///     Some(76561197960361544),
+///     Some(steam_webapi_rust_sdk::SteamId::try_from(76561197960361544).unwrap()),
 ///     None,
 ///     None,
 ///     None,
@@ -158,14 +209,14 @@ pub fn get_cached_app_list() -> Result<Vec<SteamApp>, String> {
 /// }
 ///
 /// ```
-pub fn get_dota2_match_history(account_id: Option<i64>,
-                               game_mode: Option<u8>,
-                               skill: Option<u8>,
+pub fn get_dota2_match_history(account_id: Option<SteamId>,
+                               game_mode: Option<GameMode>,
+                               skill: Option<Skill>,
                                min_players: Option<u32>,
                                start_at_match_id: Option<i64>,
                                matches_requested: Option<u32>,
                                tournament_games_only: Option<bool>)
-    -> Result<ResponseMatchHistory, String> {
+    -> Result<ResponseMatchHistory, SteamApiError> {
     idota2match_570::get_dota2_match_history(
         account_id,
         game_mode,
@@ -178,12 +229,48 @@ pub fn get_dota2_match_history(account_id: Option<i64>,
 
 }
 
+/// Iterates over every page of Dota2 match history for the given filter, continuing below the
+/// oldest match of each page until Steam has no results remaining.
+///
+/// # Examples
+///
+/// ```no_run
+/// use steam_webapi_rust_sdk::idota2match_570::get_match_history::MatchHistoryFilter;
+/// use steam_webapi_rust_sdk::idota2match_570::types::GameMode;
+///
+/// let filter = MatchHistoryFilter::new()
+///     .with_account_id(steam_webapi_rust_sdk::SteamId::try_from(76561197960361544).unwrap())
+///     .with_game_mode(GameMode::AllPick);
+///
+/// let mut pages = steam_webapi_rust_sdk::get_dota2_match_history_pages(filter);
+/// while let Some(boxed_page) = pages.next() {
+///     let page = boxed_page.unwrap();
+///     println!("{} matches, next page cursor {:?}", page.matches.len(), pages.get_cursor());
+/// }
+/// ```
+pub fn get_dota2_match_history_pages(filter: MatchHistoryFilter) -> MatchHistoryPages {
+    idota2match_570::get_dota2_match_history_pages(filter)
+}
+
+/// Retrieves public Dota2 matches in the order they were recorded, starting with the given match
+/// sequence number. See `idota2match_570::match_crawler::MatchCrawler` to follow all of them.
+///
+/// # Examples
+///
+/// ```no_run
+/// let boxed_matches = steam_webapi_rust_sdk::get_dota2_match_history_by_sequence_num(Some(6000000000), Some(10));
+/// assert!(boxed_matches.is_ok());
+/// ```
+pub fn get_dota2_match_history_by_sequence_num(start_at_match_seq_num: Option<u64>, matches_requested: Option<u32>) -> Result<Vec<MatchResult>, SteamApiError> {
+    idota2match_570::get_dota2_match_history_by_sequence_num(start_at_match_seq_num, matches_requested)
+}
+
 /// Retrieves match details for the given Dota2 match id. It will make an API call to Steam and
 /// cache the response.
 ///
 /// # Examples
 ///
-/// ```
+/// ```no_run
 /// let match_id = 1461414523;
 /// let boxed_match_result = steam_webapi_rust_sdk::get_dota2_match_details(match_id);
 ///
@@ -193,12 +280,13 @@ pub fn get_dota2_match_history(account_id: Option<i64>,
 ///     assert_eq!(10, match_result.players.len());
 /// }
 /// ```
-pub fn get_dota2_match_details(match_id: u64) -> Result<MatchResult, String> {
+pub fn get_dota2_match_details(match_id: u64) -> Result<MatchResult, SteamApiError> {
     idota2match_570::get_dota2_match_details(match_id)
 }
 
-/// Retrieves match details for the given Dota2 match id from the local cache. It may return an
-/// error if requested resource is absent, malformed or not readable from local cache.
+/// Retrieves match details for the given Dota2 match id from the local cache, calling Steam if
+/// the match hasn't been cached yet. It may return an error if the resource is malformed or
+/// neither cached nor retrievable.
 ///
 /// # Examples
 ///
@@ -215,7 +303,7 @@ pub fn get_dota2_match_details(match_id: u64) -> Result<MatchResult, String> {
 ///
 /// };
 /// ```
-pub fn get_cached_dota2_match_details(match_id: u64) -> Result<MatchResult, String> {
+pub fn get_cached_dota2_match_details(match_id: u64) -> Result<MatchResult, SteamApiError> {
     idota2match_570::get_cached_dota2_match_details(match_id)
 }
 
@@ -227,10 +315,22 @@ pub fn get_cached_dota2_match_details(match_id: u64) -> Result<MatchResult, Stri
 /// let boxed_heroes = steam_webapi_rust_sdk::get_dota2_heroes(Some("en".to_string()));
 /// assert!(boxed_heroes.is_ok());
 /// ```
-pub fn get_dota2_heroes(language: Option<String>) -> Result<Vec<Hero>, String> {
+pub fn get_dota2_heroes(language: Option<String>) -> Result<Vec<Hero>, SteamApiError> {
     idota2match_570::get_dota2_heroes(language)
 }
 
+/// Retrieves the list of all Dota2 items.
+///
+/// # Examples
+///
+/// ```no_run
+/// let boxed_items = steam_webapi_rust_sdk::get_dota2_game_items(Some("en".to_string()));
+/// assert!(boxed_items.is_ok());
+/// ```
+pub fn get_dota2_game_items(language: Option<String>) -> Result<Vec<GameItem>, SteamApiError> {
+    iecon_dota2_570::get_game_items::get(language)
+}
+
 /// Retrieves the list of Dota2 leagues.
 ///
 /// # Examples
@@ -239,7 +339,7 @@ pub fn get_dota2_heroes(language: Option<String>) -> Result<Vec<Hero>, String> {
 /// let boxed_leagues = steam_webapi_rust_sdk::get_dota2_league_listing(None);
 /// assert!(boxed_leagues.is_ok());
 /// ```
-pub fn get_dota2_league_listing(language: Option<String>) -> Result<Vec<League>, String> {
+pub fn get_dota2_league_listing(language: Option<String>) -> Result<Vec<League>, SteamApiError> {
     idota2match_570::get_dota2_league_listing(language)
 }
 
@@ -251,7 +351,7 @@ pub fn get_dota2_league_listing(language: Option<String>) -> Result<Vec<League>,
 /// let boxed_games = steam_webapi_rust_sdk::get_dota2_live_league_games();
 /// assert!(boxed_games.is_ok());
 /// ```
-pub fn get_dota2_live_league_games() -> Result<Vec<LiveLeagueGame>, String> {
+pub fn get_dota2_live_league_games() -> Result<Vec<LiveLeagueGame>, SteamApiError> {
     idota2match_570::get_dota2_live_league_games()
 }
 
@@ -263,7 +363,7 @@ pub fn get_dota2_live_league_games() -> Result<Vec<LiveLeagueGame>, String> {
 /// let boxed_teams = steam_webapi_rust_sdk::get_dota2_team_info_by_team_id(Some(1), Some(100));
 /// assert!(boxed_teams.is_ok());
 /// ```
-pub fn get_dota2_team_info_by_team_id(start_at_team_id: Option<u64>, teams_requested: Option<u32>) -> Result<Vec<TeamInfo>, String> {
+pub fn get_dota2_team_info_by_team_id(start_at_team_id: Option<u64>, teams_requested: Option<u32>) -> Result<Vec<TeamInfo>, SteamApiError> {
     idota2match_570::get_dota2_team_info_by_team_id(start_at_team_id, teams_requested)
 }
 
@@ -275,7 +375,7 @@ pub fn get_dota2_team_info_by_team_id(start_at_team_id: Option<u64>, teams_reque
 /// let boxed_summaries = steam_webapi_rust_sdk::get_player_summaries(vec![76561197960361544]);
 /// assert!(boxed_summaries.is_ok());
 /// ```
-pub fn get_player_summaries(steamids: Vec<u64>) -> Result<Vec<PlayerSummary>, String> {
+pub fn get_player_summaries(steamids: Vec<impl TryInto<SteamId, Error = impl Display>>) -> Result<Vec<PlayerSummary>, SteamApiError> {
     isteam_user::get_player_summaries::get(steamids)
 }
 
@@ -288,7 +388,7 @@ pub fn get_player_summaries(steamids: Vec<u64>) -> Result<Vec<PlayerSummary>, St
 /// let boxed_friends = steam_webapi_rust_sdk::get_friend_list(76561197960361544, None);
 /// assert!(boxed_friends.is_ok());
 /// ```
-pub fn get_friend_list(steamid: u64, relationship: Option<String>) -> Result<Vec<Friend>, String> {
+pub fn get_friend_list(steamid: impl TryInto<SteamId, Error = impl Display>, relationship: Option<String>) -> Result<Vec<Friend>, SteamApiError> {
     isteam_user::get_friend_list::get(steamid, relationship)
 }
 
@@ -300,7 +400,7 @@ pub fn get_friend_list(steamid: u64, relationship: Option<String>) -> Result<Vec
 /// let boxed_bans = steam_webapi_rust_sdk::get_player_bans(vec![76561197960361544]);
 /// assert!(boxed_bans.is_ok());
 /// ```
-pub fn get_player_bans(steamids: Vec<u64>) -> Result<Vec<PlayerBans>, String> {
+pub fn get_player_bans(steamids: Vec<impl TryInto<SteamId, Error = impl Display>>) -> Result<Vec<PlayerBans>, SteamApiError> {
     isteam_user::get_player_bans::get(steamids)
 }
 
@@ -312,10 +412,23 @@ pub fn get_player_bans(steamids: Vec<u64>) -> Result<Vec<PlayerBans>, String> {
 /// let boxed_resolution = steam_webapi_rust_sdk::resolve_vanity_url("gabelogannewell".to_string(), None);
 /// assert!(boxed_resolution.is_ok());
 /// ```
-pub fn resolve_vanity_url(vanity_url: String, url_type: Option<u8>) -> Result<VanityUrlResolution, String> {
+pub fn resolve_vanity_url(vanity_url: String, url_type: Option<u8>) -> Result<VanityUrlResolution, SteamApiError> {
     isteam_user::resolve_vanity_url::get(vanity_url, url_type)
 }
 
+/// Resolves a profile as users paste it, a SteamID in any format, a profile/group URL or a vanity
+/// name, to a SteamID. Calls Steam for vanity names only.
+///
+/// # Examples
+///
+/// ```no_run
+/// let boxed_resolution = steam_webapi_rust_sdk::resolve_steam_id("https://steamcommunity.com/id/gabelogannewell/", None);
+/// assert!(boxed_resolution.unwrap().get_steam_id().is_some());
+/// ```
+pub fn resolve_steam_id(input: &str, url_type: Option<u8>) -> Result<SteamIdResolution, SteamApiError> {
+    isteam_user::resolve_vanity_url::resolve(input, url_type)
+}
+
 /// Retrieves the games a Steam account owns, and optionally their playtime.
 ///
 /// # Examples
@@ -324,7 +437,7 @@ pub fn resolve_vanity_url(vanity_url: String, url_type: Option<u8>) -> Result<Va
 /// let boxed_games = steam_webapi_rust_sdk::get_owned_games(76561197960361544, Some(true), Some(true));
 /// assert!(boxed_games.is_ok());
 /// ```
-pub fn get_owned_games(steamid: u64, include_appinfo: Option<bool>, include_played_free_games: Option<bool>) -> Result<OwnedGames, String> {
+pub fn get_owned_games(steamid: impl TryInto<SteamId, Error = impl Display>, include_appinfo: Option<bool>, include_played_free_games: Option<bool>) -> Result<OwnedGames, SteamApiError> {
     iplayer_service::get_owned_games::get(steamid, include_appinfo, include_played_free_games)
 }
 
@@ -336,7 +449,7 @@ pub fn get_owned_games(steamid: u64, include_appinfo: Option<bool>, include_play
 /// let boxed_games = steam_webapi_rust_sdk::get_recently_played_games(76561197960361544, None);
 /// assert!(boxed_games.is_ok());
 /// ```
-pub fn get_recently_played_games(steamid: u64, count: Option<u32>) -> Result<RecentlyPlayedGames, String> {
+pub fn get_recently_played_games(steamid: impl TryInto<SteamId, Error = impl Display>, count: Option<u32>) -> Result<RecentlyPlayedGames, SteamApiError> {
     iplayer_service::get_recently_played_games::get(steamid, count)
 }
 
@@ -348,7 +461,7 @@ pub fn get_recently_played_games(steamid: u64, count: Option<u32>) -> Result<Rec
 /// let boxed_level = steam_webapi_rust_sdk::get_steam_level(76561197960361544);
 /// assert!(boxed_level.is_ok());
 /// ```
-pub fn get_steam_level(steamid: u64) -> Result<u64, String> {
+pub fn get_steam_level(steamid: impl TryInto<SteamId, Error = impl Display>) -> Result<u64, SteamApiError> {
     iplayer_service::get_steam_level::get(steamid)
 }
 
@@ -360,7 +473,7 @@ pub fn get_steam_level(steamid: u64) -> Result<u64, String> {
 /// let boxed_badges = steam_webapi_rust_sdk::get_badges(76561197960361544);
 /// assert!(boxed_badges.is_ok());
 /// ```
-pub fn get_badges(steamid: u64) -> Result<Badges, String> {
+pub fn get_badges(steamid: impl TryInto<SteamId, Error = impl Display>) -> Result<Badges, SteamApiError> {
     iplayer_service::get_badges::get(steamid)
 }
 
@@ -373,7 +486,7 @@ pub fn get_badges(steamid: u64) -> Result<Badges, String> {
 /// let boxed_achievements = steam_webapi_rust_sdk::get_player_achievements(76561197960361544, 440, Some("english".to_string()));
 /// assert!(boxed_achievements.is_ok());
 /// ```
-pub fn get_player_achievements(steamid: u64, appid: i64, language: Option<String>) -> Result<PlayerAchievements, String> {
+pub fn get_player_achievements(steamid: impl TryInto<SteamId, Error = impl Display>, appid: i64, language: Option<String>) -> Result<PlayerAchievements, SteamApiError> {
     isteam_user_stats::get_player_achievements::get(steamid, appid, language)
 }
 
@@ -385,7 +498,7 @@ pub fn get_player_achievements(steamid: u64, appid: i64, language: Option<String
 /// let boxed_stats = steam_webapi_rust_sdk::get_user_stats_for_game(76561197960361544, 440);
 /// assert!(boxed_stats.is_ok());
 /// ```
-pub fn get_user_stats_for_game(steamid: u64, appid: i64) -> Result<UserStatsForGame, String> {
+pub fn get_user_stats_for_game(steamid: impl TryInto<SteamId, Error = impl Display>, appid: i64) -> Result<UserStatsForGame, SteamApiError> {
     isteam_user_stats::get_user_stats_for_game::get(steamid, appid)
 }
 
@@ -397,7 +510,7 @@ pub fn get_user_stats_for_game(steamid: u64, appid: i64) -> Result<UserStatsForG
 /// let boxed_schema = steam_webapi_rust_sdk::get_schema_for_game(440);
 /// assert!(boxed_schema.is_ok());
 /// ```
-pub fn get_schema_for_game(appid: i64) -> Result<GameSchema, String> {
+pub fn get_schema_for_game(appid: i64) -> Result<GameSchema, SteamApiError> {
     isteam_user_stats::get_schema_for_game::get(appid)
 }
 
@@ -410,7 +523,7 @@ pub fn get_schema_for_game(appid: i64) -> Result<GameSchema, String> {
 /// let boxed_percentages = steam_webapi_rust_sdk::get_global_achievement_percentages_for_app(440);
 /// assert!(boxed_percentages.is_ok());
 /// ```
-pub fn get_global_achievement_percentages_for_app(appid: i64) -> Result<Vec<AchievementPercentage>, String> {
+pub fn get_global_achievement_percentages_for_app(appid: i64) -> Result<Vec<AchievementPercentage>, SteamApiError> {
     isteam_user_stats::get_global_achievement_percentages_for_app::get(appid)
 }
 
@@ -423,7 +536,7 @@ pub fn get_global_achievement_percentages_for_app(appid: i64) -> Result<Vec<Achi
 /// let boxed_count = steam_webapi_rust_sdk::get_number_of_current_players(570);
 /// assert!(boxed_count.is_ok());
 /// ```
-pub fn get_number_of_current_players(appid: i64) -> Result<i64, String> {
+pub fn get_number_of_current_players(appid: i64) -> Result<i64, SteamApiError> {
     isteam_user_stats::get_number_of_current_players::get(appid)
 }
 
@@ -435,11 +548,12 @@ pub fn get_number_of_current_players(appid: i64) -> Result<i64, String> {
 /// let boxed_news = steam_webapi_rust_sdk::get_news_for_app(440, Some(3), Some(300));
 /// assert!(boxed_news.is_ok());
 /// ```
-pub fn get_news_for_app(appid: i64, count: Option<u32>, maxlength: Option<u32>) -> Result<Vec<NewsItem>, String> {
+pub fn get_news_for_app(appid: i64, count: Option<u32>, maxlength: Option<u32>) -> Result<Vec<NewsItem>, SteamApiError> {
     isteam_news::get_news_for_app::get(appid, count, maxlength)
 }
 
-/// Converts given 32 bit Steam account id to 64 bit
+/// Converts given 32 bit Steam account id to 64 bit. Doesn't check its input, see `SteamId` for
+/// validated conversions between every id format.
 ///
 /// # Examples
 ///
@@ -461,7 +575,8 @@ pub fn convert_32bit_account_id_to_64bit(account_id_32bit: i64) -> i64 {
 }
 
 
-/// Converts given 64 bit Steam account id to 32 bit
+/// Converts given 64 bit Steam account id to 32 bit. Doesn't check its input, see `SteamId` for
+/// validated conversions between every id format.
 ///
 /// # Examples
 ///
@@ -491,25 +606,3 @@ pub(crate) fn get_host() -> String {
     let host = "api.steampowered.com".to_string();
     host
 }
-
-pub(crate) fn make_api_call(url: String) -> Result<String, String> {
-
-    let boxed_response = minreq::get(url).send();
-    if boxed_response.is_err() {
-        return Err("Operation timed out (API call)".to_string());
-    }
-
-    let raw_response : Vec<u8> = boxed_response.unwrap().into_bytes();
-
-    let response_string_boxed = String::from_utf8(raw_response);
-    if response_string_boxed.is_err() {
-        let error_message = response_string_boxed.err().unwrap().to_string();
-        if error_message == "invalid utf-8 sequence of 1 bytes from index 1" {
-            return Err("no response from API".to_string());
-        }
-        return Err("invalid utf-8 sequence".to_string());
-    }
-    let response_string: String = response_string_boxed.unwrap();
-
-    Ok(response_string)
-}
\ No newline at end of file
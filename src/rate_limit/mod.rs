@@ -0,0 +1,177 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+#[cfg(test)]
+mod tests;
+
+/// Steam store throttles at roughly 200 requests per IP address within 5 minutes.
+pub const DEFAULT_STORE_CAPACITY: u32 = 200;
+pub const DEFAULT_STORE_PERIOD: Duration = Duration::from_secs(5 * 60);
+
+/// Steam Web API terms of use allow 100 000 calls per key per day.
+pub const DEFAULT_API_CAPACITY: u32 = 100_000;
+pub const DEFAULT_API_PERIOD: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// What to do when a request would exceed the budget of its host.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum RateLimitMode {
+    /// Wait until a token is available, then send the request.
+    Block,
+    /// Fail right away with `SteamApiError::RateLimited`, carrying the time until a token is available.
+    Error,
+}
+
+/// Budget of a host: up to `capacity` requests per `period`, refilled continuously.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct RateLimit {
+    pub capacity: u32,
+    pub period: Duration,
+}
+
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+/// Token bucket rate limiter with a budget per host. Share one limiter between clients (and
+/// threads) through an `Arc` so they all draw from the same budget.
+///
+/// Hosts are matched against the default Steam urls (`api.steampowered.com`,
+/// `store.steampowered.com`), even if the client points its base urls elsewhere. Hosts without a
+/// budget are not limited.
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::Arc;
+/// use std::time::Duration;
+/// use steam_webapi_rust_sdk::client::SteamClient;
+/// use steam_webapi_rust_sdk::rate_limit::{RateLimiter, RateLimitMode};
+///
+/// let rate_limiter = RateLimiter::new(RateLimitMode::Error)
+///     .with_limit("store.steampowered.com", 100, Duration::from_secs(5 * 60));
+///
+/// let client = SteamClient::builder()
+///     .rate_limiter(Arc::new(rate_limiter))
+///     .build();
+/// ```
+#[derive(Debug)]
+pub struct RateLimiter {
+    mode: RateLimitMode,
+    limits: HashMap<String, RateLimit>,
+    buckets: Mutex<HashMap<String, Bucket>>,
+}
+
+static SHARED_RATE_LIMITER: Mutex<Option<Arc<RateLimiter>>> = Mutex::new(None);
+
+/// Returns the limiter of clients built without `SteamClientBuilder::rate_limiter`: one
+/// `RateLimiter::default()` for the whole process, so the budgets hold however many clients are
+/// built.
+pub fn get_shared_rate_limiter() -> Arc<RateLimiter> {
+    let mut shared_rate_limiter = SHARED_RATE_LIMITER.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    if shared_rate_limiter.is_none() {
+        *shared_rate_limiter = Some(Arc::new(RateLimiter::default()));
+    }
+    shared_rate_limiter.as_ref().unwrap().clone()
+}
+
+impl Default for RateLimiter {
+    /// Blocking limiter with the default budgets of `store.steampowered.com` and `api.steampowered.com`.
+    fn default() -> RateLimiter {
+        RateLimiter::new(RateLimitMode::Block)
+            .with_limit("store.steampowered.com", DEFAULT_STORE_CAPACITY, DEFAULT_STORE_PERIOD)
+            .with_limit("api.steampowered.com", DEFAULT_API_CAPACITY, DEFAULT_API_PERIOD)
+    }
+}
+
+impl RateLimiter {
+    /// Creates a limiter without any budgets, see `with_limit`.
+    pub fn new(mode: RateLimitMode) -> RateLimiter {
+        RateLimiter {
+            mode,
+            limits: HashMap::new(),
+            buckets: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Sets the budget of `host` to `capacity` requests per `period`. The bucket starts full. A
+    /// `capacity` of 0 denies every request to `host`, in `Block` mode too, as no token ever comes.
+    pub fn with_limit(mut self, host: &str, capacity: u32, period: Duration) -> RateLimiter {
+        self.limits.insert(host.to_string(), RateLimit { capacity, period });
+        self
+    }
+
+    pub fn mode(&self) -> RateLimitMode {
+        self.mode
+    }
+
+    pub fn get_limit(&self, host: &str) -> Option<RateLimit> {
+        self.limits.get(host).copied()
+    }
+
+    /// Takes a token for a request to `host` if one is available. Otherwise leaves the bucket as is
+    /// and returns how long until the next token.
+    pub fn try_acquire(&self, host: &str) -> Result<(), Duration> {
+        let boxed_limit = self.limits.get(host);
+        if boxed_limit.is_none() {
+            return Ok(());
+        }
+        let limit = boxed_limit.unwrap();
+        if limit.capacity == 0 {
+            return Err(limit.period);
+        }
+
+        let mut buckets = self.buckets.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let now = Instant::now();
+        let bucket = buckets.entry(host.to_string()).or_insert(Bucket {
+            tokens: limit.capacity as f64,
+            refilled_at: now,
+        });
+
+        let tokens_per_second = limit.capacity as f64 / limit.period.as_secs_f64();
+        let elapsed = now.duration_since(bucket.refilled_at).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * tokens_per_second).min(limit.capacity as f64);
+        bucket.refilled_at = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            return Ok(());
+        }
+
+        let missing_tokens = 1.0 - bucket.tokens;
+        Err(Duration::from_secs_f64(missing_tokens / tokens_per_second))
+    }
+
+    /// Takes a token for a request to `host`. In `Block` mode sleeps until one is available, in
+    /// `Error` mode returns how long until the next token. Hosts with a capacity of 0 return the
+    /// error in both modes.
+    pub fn acquire(&self, host: &str) -> Result<(), Duration> {
+        loop {
+            let boxed_acquire = self.try_acquire(host);
+            if boxed_acquire.is_ok() || self.mode == RateLimitMode::Error || self.is_denied(host) {
+                return boxed_acquire;
+            }
+            thread::sleep(boxed_acquire.err().unwrap());
+        }
+    }
+
+    /// Async version of `acquire`, available with the `async` feature.
+    #[cfg(feature = "async")]
+    pub async fn acquire_async(&self, host: &str) -> Result<(), Duration> {
+        loop {
+            let boxed_acquire = self.try_acquire(host);
+            if boxed_acquire.is_ok() || self.mode == RateLimitMode::Error || self.is_denied(host) {
+                return boxed_acquire;
+            }
+            tokio::time::sleep(boxed_acquire.err().unwrap()).await;
+        }
+    }
+
+    /// Whether the budget of `host` never allows a request, so waiting for a token is pointless.
+    fn is_denied(&self, host: &str) -> bool {
+        self.limits.get(host).map(|limit| limit.capacity == 0).unwrap_or(false)
+    }
+}
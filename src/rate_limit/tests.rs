@@ -0,0 +1,114 @@
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+use crate::client::SteamClient;
+use crate::error::SteamApiError;
+use crate::rate_limit::{RateLimiter, RateLimitMode, DEFAULT_STORE_CAPACITY, DEFAULT_STORE_PERIOD};
+use crate::transport::FakeTransport;
+
+#[test]
+fn default_limits() {
+    let rate_limiter = RateLimiter::default();
+
+    assert_eq!(RateLimitMode::Block, rate_limiter.mode());
+    let store_limit = rate_limiter.get_limit("store.steampowered.com").unwrap();
+    assert_eq!(DEFAULT_STORE_CAPACITY, store_limit.capacity);
+    assert_eq!(DEFAULT_STORE_PERIOD, store_limit.period);
+    assert!(rate_limiter.get_limit("api.steampowered.com").is_some());
+    assert!(rate_limiter.get_limit("steamcommunity.com").is_none());
+}
+
+#[test]
+fn try_acquire_drains_bucket() {
+    let rate_limiter = RateLimiter::new(RateLimitMode::Error)
+        .with_limit("store.steampowered.com", 3, Duration::from_secs(300));
+
+    assert!(rate_limiter.try_acquire("store.steampowered.com").is_ok());
+    assert!(rate_limiter.try_acquire("store.steampowered.com").is_ok());
+    assert!(rate_limiter.try_acquire("store.steampowered.com").is_ok());
+
+    let retry_after = rate_limiter.try_acquire("store.steampowered.com").err().unwrap();
+    assert!(retry_after > Duration::from_secs(90));
+    assert!(retry_after <= Duration::from_secs(100));
+}
+
+#[test]
+fn hosts_have_separate_budgets() {
+    let rate_limiter = RateLimiter::new(RateLimitMode::Error)
+        .with_limit("store.steampowered.com", 1, Duration::from_secs(300))
+        .with_limit("api.steampowered.com", 1, Duration::from_secs(300));
+
+    assert!(rate_limiter.acquire("store.steampowered.com").is_ok());
+    assert!(rate_limiter.acquire("store.steampowered.com").is_err());
+    assert!(rate_limiter.acquire("api.steampowered.com").is_ok());
+
+    for _ in 0..10 {
+        assert!(rate_limiter.acquire("steamcommunity.com").is_ok());
+    }
+}
+
+#[test]
+fn block_mode_waits_for_token() {
+    let rate_limiter = RateLimiter::new(RateLimitMode::Block)
+        .with_limit("store.steampowered.com", 1, Duration::from_millis(50));
+
+    let started_at = Instant::now();
+    assert!(rate_limiter.acquire("store.steampowered.com").is_ok());
+    assert!(rate_limiter.acquire("store.steampowered.com").is_ok());
+
+    assert!(started_at.elapsed() >= Duration::from_millis(40));
+}
+
+#[test]
+fn zero_capacity_denies_in_block_mode() {
+    let rate_limiter = RateLimiter::new(RateLimitMode::Block)
+        .with_limit("store.steampowered.com", 0, Duration::from_secs(300));
+
+    assert_eq!(Err(Duration::from_secs(300)), rate_limiter.acquire("store.steampowered.com"));
+    assert!(rate_limiter.acquire("api.steampowered.com").is_ok());
+}
+
+#[test]
+fn budget_is_shared_across_threads() {
+    let rate_limiter = Arc::new(RateLimiter::new(RateLimitMode::Error)
+        .with_limit("store.steampowered.com", 5, Duration::from_secs(300)));
+
+    let handles : Vec<_> = (0..10).map(|_| {
+        let rate_limiter = rate_limiter.clone();
+        thread::spawn(move || rate_limiter.acquire("store.steampowered.com").is_ok())
+    }).collect();
+
+    let acquired = handles.into_iter().map(|handle| handle.join().unwrap()).filter(|is_ok| *is_ok).count();
+    assert_eq!(5, acquired);
+}
+
+#[test]
+fn client_returns_rate_limited_error() {
+    let transport = Arc::new(FakeTransport::new());
+    transport.respond_with(
+        "https://store.steampowered.com/api/appdetails?appids=440&lang=en",
+        200,
+        r#"{"440":{"success":true,"data":{"name":"Team Fortress 2"}}}"#,
+    );
+
+    let rate_limiter = Arc::new(RateLimiter::new(RateLimitMode::Error)
+        .with_limit("store.steampowered.com", 1, Duration::from_secs(300)));
+
+    let client = SteamClient::builder()
+        .cache_enabled(false)
+        .transport(transport.clone())
+        .rate_limiter(rate_limiter.clone())
+        .build();
+    let other_client = SteamClient::builder()
+        .cache_enabled(false)
+        .transport(transport.clone())
+        .rate_limiter(rate_limiter)
+        .build();
+
+    assert!(client.get_app_details(440).is_ok());
+
+    let error = other_client.get_app_details(440).err().unwrap();
+    assert!(matches!(error, SteamApiError::RateLimited { .. }));
+    assert!(error.is_retryable());
+    assert_eq!(1, transport.requests().len());
+}
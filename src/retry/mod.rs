@@ -0,0 +1,170 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use crate::error::SteamApiError;
+
+#[cfg(test)]
+mod tests;
+
+/// How `SteamClient` retries requests that failed with a transport error (timeout, connection
+/// reset, DNS failure), a 429 or a 5xx response. Other failures, such as a 403 for a bad key, are
+/// never retried.
+///
+/// Retries wait `base_delay * 2^attempt`, capped at `max_delay`, with jitter drawing the actual wait
+/// between half and all of it. A `Retry-After` header on the response takes precedence; if it asks
+/// for more than `max_delay` the error is returned right away instead of sleeping that long.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use steam_webapi_rust_sdk::client::SteamClient;
+/// use steam_webapi_rust_sdk::retry::RetryPolicy;
+///
+/// let client = SteamClient::builder()
+///     .retry_policy(RetryPolicy {
+///         max_retries: 5,
+///         base_delay: Duration::from_secs(1),
+///         max_delay: Duration::from_secs(60),
+///         jitter: true,
+///     })
+///     .build();
+///
+/// assert_eq!(5, client.retry_policy().max_retries);
+/// ```
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct RetryPolicy {
+    /// Number of retries after the first attempt, 0 disables retries.
+    pub max_retries: u32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+    pub jitter: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> RetryPolicy {
+        RetryPolicy {
+            max_retries: 3,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+            jitter: true,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Policy that never retries.
+    pub fn none() -> RetryPolicy {
+        RetryPolicy {
+            max_retries: 0,
+            ..RetryPolicy::default()
+        }
+    }
+
+    /// Returns the exponential backoff delay before the retry following `attempt` (0 for the first
+    /// request), jittered if enabled.
+    pub fn get_backoff_delay(&self, attempt: u32) -> Duration {
+        let multiplier = 2u32.saturating_pow(attempt);
+        let delay = self.base_delay.saturating_mul(multiplier).min(self.max_delay);
+        if !self.jitter {
+            return delay;
+        }
+
+        let half_delay = delay / 2;
+        let jitter_range = (delay - half_delay).as_nanos() as u64;
+        if jitter_range == 0 {
+            return delay;
+        }
+        half_delay + Duration::from_nanos(get_random_u64() % (jitter_range + 1))
+    }
+
+    /// Returns how long to wait before retrying the request that failed with `error` after
+    /// `attempt` retries, or `None` if it shouldn't be retried. `retry_after` is the parsed
+    /// `Retry-After` header of the response, if any.
+    pub fn get_retry_delay(&self, attempt: u32, error: &SteamApiError, retry_after: Option<Duration>) -> Option<Duration> {
+        if attempt >= self.max_retries {
+            return None;
+        }
+
+        let is_retryable = match error {
+            SteamApiError::Transport { .. } => true,
+            SteamApiError::HttpStatus { .. } => error.is_retryable(),
+            _ => false,
+        };
+        if !is_retryable {
+            return None;
+        }
+
+        if let Some(retry_after) = retry_after {
+            if retry_after > self.max_delay {
+                return None;
+            }
+            return Some(retry_after);
+        }
+
+        Some(self.get_backoff_delay(attempt))
+    }
+}
+
+/// Parses a `Retry-After` header value, either a number of seconds or an HTTP date such as
+/// `Wed, 21 Oct 2015 07:28:00 GMT`. Dates in the past yield a zero delay.
+pub fn parse_retry_after(value: &str) -> Option<Duration> {
+    let value = value.trim();
+
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let timestamp = parse_http_date(value)?;
+
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+    Some(Duration::from_secs(timestamp.saturating_sub(now)))
+}
+
+/// Parses an IMF-fixdate (`Wed, 21 Oct 2015 07:28:00 GMT`) into a unix timestamp.
+fn parse_http_date(value: &str) -> Option<u64> {
+    let parts : Vec<&str> = value.split_whitespace().collect();
+    if parts.len() != 6 || parts[5] != "GMT" {
+        return None;
+    }
+
+    let day : u64 = parts[1].parse().ok()?;
+    let months = ["Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec"];
+    let month = months.iter().position(|month| *month == parts[2])? as u64 + 1;
+    let year : u64 = parts[3].parse().ok()?;
+
+    let time : Vec<&str> = parts[4].split(':').collect();
+    if time.len() != 3 {
+        return None;
+    }
+    let hours : u64 = time[0].parse().ok()?;
+    let minutes : u64 = time[1].parse().ok()?;
+    let seconds : u64 = time[2].parse().ok()?;
+
+    if year < 1970 || day == 0 || day > 31 || hours > 23 || minutes > 59 || seconds > 60 {
+        return None;
+    }
+
+    Some(get_days_since_epoch(year, month, day) * 86400 + hours * 3600 + minutes * 60 + seconds)
+}
+
+/// Days between 1970-01-01 and the given date of the proleptic Gregorian calendar.
+fn get_days_since_epoch(year: u64, month: u64, day: u64) -> u64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year / 400;
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+static RANDOM_STATE: AtomicU64 = AtomicU64::new(0);
+
+/// Cheap xorshift random number seeded from the clock, good enough to spread out retries.
+fn get_random_u64() -> u64 {
+    let nanos = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().subsec_nanos() as u64;
+    let mut x = RANDOM_STATE.fetch_add(0x9E37_79B9_7F4A_7C15, Ordering::Relaxed) ^ nanos;
+    x ^= x << 13;
+    x ^= x >> 7;
+    x ^= x << 17;
+    x
+}
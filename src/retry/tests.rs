@@ -0,0 +1,151 @@
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use crate::client::SteamClient;
+use crate::error::SteamApiError;
+use crate::retry::{parse_http_date, parse_retry_after, RetryPolicy};
+use crate::transport::{FakeTransport, HttpResponse};
+
+const PLAYER_BANS_URL: &str = "https://api.steampowered.com/ISteamUser/GetPlayerBans/v1?key=KEY&steamids=76561197960434622";
+const PLAYER_BANS_RESPONSE: &str = r#"{"players":[{"SteamId":"76561197960434622","CommunityBanned":false,"VACBanned":false,"NumberOfVACBans":0,"DaysSinceLastBan":0,"NumberOfGameBans":0,"EconomyBan":"none"}]}"#;
+
+fn get_fast_retry_policy() -> RetryPolicy {
+    RetryPolicy {
+        max_retries: 3,
+        base_delay: Duration::from_millis(1),
+        max_delay: Duration::from_millis(50),
+        jitter: true,
+    }
+}
+
+fn get_client(transport: Arc<FakeTransport>) -> SteamClient {
+    SteamClient::builder()
+        .api_key("KEY")
+        .transport(transport)
+        .retry_policy(get_fast_retry_policy())
+        .build()
+}
+
+#[test]
+fn backoff_doubles_up_to_max_delay() {
+    let retry_policy = RetryPolicy {
+        max_retries: 10,
+        base_delay: Duration::from_millis(500),
+        max_delay: Duration::from_secs(5),
+        jitter: false,
+    };
+
+    assert_eq!(Duration::from_millis(500), retry_policy.get_backoff_delay(0));
+    assert_eq!(Duration::from_millis(1000), retry_policy.get_backoff_delay(1));
+    assert_eq!(Duration::from_millis(2000), retry_policy.get_backoff_delay(2));
+    assert_eq!(Duration::from_millis(4000), retry_policy.get_backoff_delay(3));
+    assert_eq!(Duration::from_secs(5), retry_policy.get_backoff_delay(4));
+    assert_eq!(Duration::from_secs(5), retry_policy.get_backoff_delay(40));
+}
+
+#[test]
+fn jitter_stays_within_half_and_full_delay() {
+    let retry_policy = RetryPolicy::default();
+
+    for _ in 0..100 {
+        let delay = retry_policy.get_backoff_delay(2);
+        assert!(delay >= Duration::from_millis(1000));
+        assert!(delay <= Duration::from_millis(2000));
+    }
+}
+
+#[test]
+fn retry_delay_only_for_retryable_errors() {
+    let retry_policy = RetryPolicy::default();
+    let endpoint = "ISteamUser/GetPlayerBans";
+
+    assert!(retry_policy.get_retry_delay(0, &SteamApiError::transport(endpoint, PLAYER_BANS_URL, "timed out"), None).is_some());
+    assert!(retry_policy.get_retry_delay(0, &SteamApiError::http_status(endpoint, PLAYER_BANS_URL, 429, ""), None).is_some());
+    assert!(retry_policy.get_retry_delay(0, &SteamApiError::http_status(endpoint, PLAYER_BANS_URL, 502, ""), None).is_some());
+
+    assert!(retry_policy.get_retry_delay(0, &SteamApiError::http_status(endpoint, PLAYER_BANS_URL, 403, ""), None).is_none());
+    assert!(retry_policy.get_retry_delay(0, &SteamApiError::http_status(endpoint, PLAYER_BANS_URL, 404, ""), None).is_none());
+    assert!(retry_policy.get_retry_delay(0, &SteamApiError::rate_limited(endpoint, PLAYER_BANS_URL, Duration::from_secs(1)), None).is_none());
+    assert!(retry_policy.get_retry_delay(0, &SteamApiError::malformed_response(endpoint, "expected value"), None).is_none());
+
+    assert!(retry_policy.get_retry_delay(3, &SteamApiError::transport(endpoint, PLAYER_BANS_URL, "timed out"), None).is_none());
+    assert!(RetryPolicy::none().get_retry_delay(0, &SteamApiError::transport(endpoint, PLAYER_BANS_URL, "timed out"), None).is_none());
+}
+
+#[test]
+fn retry_delay_honors_retry_after() {
+    let retry_policy = RetryPolicy::default();
+    let error = SteamApiError::http_status("ISteamUser/GetPlayerBans", PLAYER_BANS_URL, 429, "");
+
+    assert_eq!(Some(Duration::from_secs(7)), retry_policy.get_retry_delay(0, &error, Some(Duration::from_secs(7))));
+    assert_eq!(None, retry_policy.get_retry_delay(0, &error, Some(Duration::from_secs(3600))));
+}
+
+#[test]
+fn parse_retry_after_seconds() {
+    assert_eq!(Some(Duration::from_secs(120)), parse_retry_after("120"));
+    assert_eq!(Some(Duration::from_secs(0)), parse_retry_after(" 0 "));
+    assert_eq!(None, parse_retry_after("soon"));
+}
+
+#[test]
+fn parse_retry_after_http_date() {
+    assert_eq!(Some(1445412480), parse_http_date("Wed, 21 Oct 2015 07:28:00 GMT"));
+    assert_eq!(Some(951782400), parse_http_date("Tue, 29 Feb 2000 00:00:00 GMT"));
+    assert_eq!(None, parse_http_date("Wed, 21 Oct 2015 07:28:00 PST"));
+
+    assert_eq!(Some(Duration::from_secs(0)), parse_retry_after("Wed, 21 Oct 2015 07:28:00 GMT"));
+
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+    let retry_after = parse_retry_after("Fri, 01 Jan 2100 00:00:00 GMT").unwrap();
+    assert!(retry_after.as_secs() > 4102444800 - now - 5);
+}
+
+#[test]
+fn client_retries_server_errors() {
+    let transport = Arc::new(FakeTransport::new());
+    transport.respond_with(PLAYER_BANS_URL, 200, PLAYER_BANS_RESPONSE);
+    transport.enqueue(PLAYER_BANS_URL, HttpResponse::new(503, ""));
+    transport.enqueue(PLAYER_BANS_URL, HttpResponse::new(429, "").with_header("Retry-After", "0"));
+
+    let client = get_client(transport.clone());
+
+    let player_bans = client.get_player_bans(vec![76561197960434622]).unwrap();
+    assert_eq!(1, player_bans.len());
+    assert_eq!(3, transport.requests().len());
+}
+
+#[test]
+fn client_gives_up_after_max_retries() {
+    let transport = Arc::new(FakeTransport::new());
+    transport.respond_with(PLAYER_BANS_URL, 500, "");
+
+    let client = get_client(transport.clone());
+
+    let error = client.get_player_bans(vec![76561197960434622]).err().unwrap();
+    assert_eq!(Some(500), error.http_status_code());
+    assert_eq!(4, transport.requests().len());
+}
+
+#[test]
+fn client_does_not_retry_client_errors() {
+    let transport = Arc::new(FakeTransport::new());
+    transport.respond_with(PLAYER_BANS_URL, 403, "<html><body>Forbidden</body></html>");
+
+    let client = get_client(transport.clone());
+
+    let error = client.get_player_bans(vec![76561197960434622]).err().unwrap();
+    assert_eq!(Some(403), error.http_status_code());
+    assert_eq!(1, transport.requests().len());
+}
+
+#[test]
+fn client_does_not_sleep_for_long_retry_after() {
+    let transport = Arc::new(FakeTransport::new());
+    transport.insert(PLAYER_BANS_URL, HttpResponse::new(429, "").with_header("Retry-After", "3600"));
+
+    let client = get_client(transport.clone());
+
+    let error = client.get_player_bans(vec![76561197960434622]).err().unwrap();
+    assert_eq!(Some(429), error.http_status_code());
+    assert_eq!(1, transport.requests().len());
+}
@@ -0,0 +1,433 @@
+use std::fmt;
+use std::str::FromStr;
+use serde::de::{self, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value;
+use crate::error::SteamApiError;
+use crate::util::json_str;
+
+#[cfg(test)]
+mod tests;
+
+const ACCOUNT_ID_MASK: u64 = 0xFFFF_FFFF;
+const INSTANCE_MASK: u64 = 0xF_FFFF;
+const INSTANCE_SHIFT: u64 = 32;
+const ACCOUNT_TYPE_SHIFT: u64 = 52;
+const UNIVERSE_SHIFT: u64 = 56;
+
+/// Instance of individual accounts logged in from the desktop client, the only one profiles use.
+const DESKTOP_INSTANCE: u32 = 1;
+
+const PROFILES_URL_PATH: &str = "steamcommunity.com/profiles/";
+
+/// Steam universe an account belongs to, bits 56-63 of a SteamID64.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub enum Universe {
+    Invalid = 0,
+    Public = 1,
+    Beta = 2,
+    Internal = 3,
+    Dev = 4,
+}
+
+impl Universe {
+    pub fn from_id(id: u8) -> Option<Universe> {
+        match id {
+            0 => Some(Universe::Invalid),
+            1 => Some(Universe::Public),
+            2 => Some(Universe::Beta),
+            3 => Some(Universe::Internal),
+            4 => Some(Universe::Dev),
+            _ => None,
+        }
+    }
+
+    pub fn get_id(&self) -> u8 {
+        *self as u8
+    }
+}
+
+/// Kind of account, bits 52-55 of a SteamID64.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub enum AccountType {
+    Invalid = 0,
+    Individual = 1,
+    Multiseat = 2,
+    GameServer = 3,
+    AnonGameServer = 4,
+    Pending = 5,
+    ContentServer = 6,
+    Clan = 7,
+    Chat = 8,
+    ConsoleUser = 9,
+    AnonUser = 10,
+}
+
+impl AccountType {
+    pub fn from_id(id: u8) -> Option<AccountType> {
+        match id {
+            0 => Some(AccountType::Invalid),
+            1 => Some(AccountType::Individual),
+            2 => Some(AccountType::Multiseat),
+            3 => Some(AccountType::GameServer),
+            4 => Some(AccountType::AnonGameServer),
+            5 => Some(AccountType::Pending),
+            6 => Some(AccountType::ContentServer),
+            7 => Some(AccountType::Clan),
+            8 => Some(AccountType::Chat),
+            9 => Some(AccountType::ConsoleUser),
+            10 => Some(AccountType::AnonUser),
+            _ => None,
+        }
+    }
+
+    pub fn get_id(&self) -> u8 {
+        *self as u8
+    }
+
+    /// Returns the letter identifying the account type in a SteamID3, e.g. `U` in `[U:1:95816]`.
+    /// Valve defines no letter for console users; like other tools, `i` is used for them.
+    pub fn get_letter(&self) -> char {
+        match self {
+            AccountType::Invalid => 'I',
+            AccountType::Individual => 'U',
+            AccountType::Multiseat => 'M',
+            AccountType::GameServer => 'G',
+            AccountType::AnonGameServer => 'A',
+            AccountType::Pending => 'P',
+            AccountType::ContentServer => 'C',
+            AccountType::Clan => 'g',
+            AccountType::Chat => 'T',
+            AccountType::ConsoleUser => 'i',
+            AccountType::AnonUser => 'a',
+        }
+    }
+
+    /// Inverse of `get_letter`. Chat ids are also written as `L` (lobby) or `c` (clan chat).
+    pub fn from_letter(letter: char) -> Option<AccountType> {
+        match letter {
+            'I' => Some(AccountType::Invalid),
+            'U' => Some(AccountType::Individual),
+            'M' => Some(AccountType::Multiseat),
+            'G' => Some(AccountType::GameServer),
+            'A' => Some(AccountType::AnonGameServer),
+            'P' => Some(AccountType::Pending),
+            'C' => Some(AccountType::ContentServer),
+            'g' => Some(AccountType::Clan),
+            'T' | 'L' | 'c' => Some(AccountType::Chat),
+            'i' => Some(AccountType::ConsoleUser),
+            'a' => Some(AccountType::AnonUser),
+            _ => None,
+        }
+    }
+}
+
+/// A Steam account id, stored as its SteamID64 and accepted by every endpoint taking one.
+///
+/// Parses (`str::parse`/`SteamId::parse`) and renders every common format:
+///
+/// - SteamID64, `76561197960361544`, also what `Display` renders;
+/// - 32-bit account id, `95816`, as used by Dota2;
+/// - SteamID2, `STEAM_0:0:47908`;
+/// - SteamID3, `[U:1:95816]`;
+/// - profile URL, `https://steamcommunity.com/profiles/76561197960361544`.
+///
+/// Vanity URLs (`steamcommunity.com/id/<name>`) need a call to Steam, see
+/// `isteam_user::resolve_vanity_url`.
+///
+/// # Examples
+///
+/// ```
+/// use steam_webapi_rust_sdk::SteamId;
+///
+/// let steam_id: SteamId = "STEAM_0:0:47908".parse().unwrap();
+///
+/// assert_eq!(76561197960361544, steam_id.get_steam_id64());
+/// assert_eq!(95816, steam_id.get_account_id());
+/// assert_eq!("[U:1:95816]", steam_id.to_steam_id3());
+/// assert_eq!(steam_id, SteamId::parse("https://steamcommunity.com/profiles/76561197960361544").unwrap());
+/// assert!(SteamId::parse("STEAM_0:2:47908").is_err());
+/// ```
+#[derive(PartialEq, Eq, Hash, PartialOrd, Ord, Clone, Copy, Debug)]
+pub struct SteamId(u64);
+
+impl SteamId {
+    /// Builds the SteamID64 of the given parts.
+    pub fn new(universe: Universe, account_type: AccountType, instance: u32, account_id: u32) -> SteamId {
+        SteamId(
+            (universe.get_id() as u64) << UNIVERSE_SHIFT
+                | (account_type.get_id() as u64) << ACCOUNT_TYPE_SHIFT
+                | (instance as u64 & INSTANCE_MASK) << INSTANCE_SHIFT
+                | account_id as u64
+        )
+    }
+
+    /// Returns the SteamId of the individual account with the given 32-bit account id, in the public
+    /// universe.
+    pub fn from_account_id(account_id: u32) -> SteamId {
+        SteamId::new(Universe::Public, AccountType::Individual, DESKTOP_INSTANCE, account_id)
+    }
+
+    /// Checks that the SteamID64 has a known universe and account type.
+    pub fn from_steam_id64(steam_id64: u64) -> Result<SteamId, String> {
+        let universe_id = (steam_id64 >> UNIVERSE_SHIFT) as u8;
+        if universe_id == 0 || Universe::from_id(universe_id).is_none() {
+            return Err(["invalid universe in SteamID64 ", steam_id64.to_string().as_str()].join(""));
+        }
+
+        let account_type_id = ((steam_id64 >> ACCOUNT_TYPE_SHIFT) & 0xF) as u8;
+        if account_type_id == 0 || AccountType::from_id(account_type_id).is_none() {
+            return Err(["invalid account type in SteamID64 ", steam_id64.to_string().as_str()].join(""));
+        }
+
+        Ok(SteamId(steam_id64))
+    }
+
+    /// Parses any of the formats listed on `SteamId`. Numbers up to `u32::MAX` are taken as 32-bit
+    /// account ids, larger ones as SteamID64s.
+    pub fn parse(id: &str) -> Result<SteamId, String> {
+        let id = id.trim();
+
+        if let Some(position) = id.find(PROFILES_URL_PATH) {
+            let path = &id[position + PROFILES_URL_PATH.len()..];
+            let profile_id = path.split(['/', '?', '#']).next().unwrap_or("");
+            return SteamId::parse(profile_id)
+                .map_err(|error| ["invalid profile URL ", id, ": ", error.as_str()].join(""));
+        }
+
+        if id.starts_with("STEAM_") {
+            return parse_steam_id2(id);
+        }
+
+        if id.starts_with('[') || id.contains(':') {
+            return parse_steam_id3(id);
+        }
+
+        let boxed_number = id.parse::<u64>();
+        if boxed_number.is_err() {
+            return Err(["unrecognized SteamID ", id].join(""));
+        }
+
+        SteamId::from_number(boxed_number.unwrap())
+    }
+
+    /// Takes numbers up to `u32::MAX` as 32-bit account ids, larger ones as SteamID64s.
+    fn from_number(number: u64) -> Result<SteamId, String> {
+        if number <= u32::MAX as u64 {
+            if number == 0 {
+                return Err("account id 0 is not a valid SteamID".to_string());
+            }
+            return Ok(SteamId::from_account_id(number as u32));
+        }
+
+        SteamId::from_steam_id64(number)
+    }
+
+    pub fn get_steam_id64(&self) -> u64 {
+        self.0
+    }
+
+    /// Returns the 32-bit account id, the lower half of the SteamID64.
+    pub fn get_account_id(&self) -> u32 {
+        (self.0 & ACCOUNT_ID_MASK) as u32
+    }
+
+    pub fn get_instance(&self) -> u32 {
+        ((self.0 >> INSTANCE_SHIFT) & INSTANCE_MASK) as u32
+    }
+
+    /// `None` for universes the SDK doesn't know, only possible through `SteamId::new`.
+    pub fn get_universe(&self) -> Option<Universe> {
+        Universe::from_id((self.0 >> UNIVERSE_SHIFT) as u8)
+    }
+
+    /// `None` for account types the SDK doesn't know, only possible through `SteamId::new`.
+    pub fn get_account_type(&self) -> Option<AccountType> {
+        AccountType::from_id(((self.0 >> ACCOUNT_TYPE_SHIFT) & 0xF) as u8)
+    }
+
+    /// Renders the id as `STEAM_X:Y:Z`, with `X` as 0 for the public universe like most tools do.
+    pub fn to_steam_id2(&self) -> String {
+        let universe_id = match self.get_universe() {
+            Some(Universe::Public) => 0,
+            _ => (self.0 >> UNIVERSE_SHIFT) as u8,
+        };
+        let account_id = self.get_account_id();
+
+        format!("STEAM_{}:{}:{}", universe_id, account_id & 1, account_id >> 1)
+    }
+
+    /// Renders the id as `[U:1:95816]`; the instance is appended when it isn't the usual one for
+    /// the account type, e.g. `[A:1:123:456]`.
+    pub fn to_steam_id3(&self) -> String {
+        let account_type = self.get_account_type().unwrap_or(AccountType::Invalid);
+        let universe_id = (self.0 >> UNIVERSE_SHIFT) as u8;
+        let instance = self.get_instance();
+
+        let default_instance = match account_type {
+            AccountType::Individual => DESKTOP_INSTANCE,
+            _ => 0,
+        };
+        if instance == default_instance && account_type != AccountType::AnonGameServer {
+            return format!("[{}:{}:{}]", account_type.get_letter(), universe_id, self.get_account_id());
+        }
+
+        format!("[{}:{}:{}:{}]", account_type.get_letter(), universe_id, self.get_account_id(), instance)
+    }
+
+    /// Returns `https://steamcommunity.com/profiles/<SteamID64>`.
+    pub fn get_profile_url(&self) -> String {
+        ["https://", PROFILES_URL_PATH, self.0.to_string().as_str()].join("")
+    }
+}
+
+fn parse_steam_id2(id: &str) -> Result<SteamId, String> {
+    let invalid = || ["invalid SteamID2 ", id].join("");
+
+    let parts = id["STEAM_".len()..].split(':').collect::<Vec<&str>>();
+    if parts.len() != 3 {
+        return Err(invalid());
+    }
+
+    let boxed_universe_id = parts[0].parse::<u8>();
+    let boxed_y = parts[1].parse::<u32>();
+    let boxed_z = parts[2].parse::<u32>();
+    if boxed_universe_id.is_err() || boxed_y.is_err() || boxed_z.is_err() {
+        return Err(invalid());
+    }
+
+    let y = boxed_y.unwrap();
+    let z = boxed_z.unwrap();
+    if y > 1 || z > u32::MAX >> 1 {
+        return Err(invalid());
+    }
+
+    // STEAM_0 is how older games render the public universe
+    let universe = match boxed_universe_id.unwrap() {
+        0 => Some(Universe::Public),
+        universe_id => Universe::from_id(universe_id),
+    };
+    if universe.is_none() {
+        return Err(invalid());
+    }
+
+    Ok(SteamId::new(universe.unwrap(), AccountType::Individual, DESKTOP_INSTANCE, z << 1 | y))
+}
+
+fn parse_steam_id3(id: &str) -> Result<SteamId, String> {
+    let invalid = || ["invalid SteamID3 ", id].join("");
+
+    let inner = id.strip_prefix('[').and_then(|inner| inner.strip_suffix(']')).unwrap_or(id);
+    let parts = inner.split(':').collect::<Vec<&str>>();
+    if parts.len() != 3 && parts.len() != 4 {
+        return Err(invalid());
+    }
+
+    let mut letters = parts[0].chars();
+    let account_type = letters.next().and_then(AccountType::from_letter);
+    if account_type.is_none() || letters.next().is_some() {
+        return Err(invalid());
+    }
+    let account_type = account_type.unwrap();
+
+    let universe = parts[1].parse::<u8>().ok().and_then(Universe::from_id);
+    let boxed_account_id = parts[2].parse::<u32>();
+    if universe.is_none() || universe == Some(Universe::Invalid) || boxed_account_id.is_err() {
+        return Err(invalid());
+    }
+
+    let instance = if parts.len() == 4 {
+        match parts[3].parse::<u32>() {
+            Ok(instance) if instance as u64 <= INSTANCE_MASK => instance,
+            _ => return Err(invalid()),
+        }
+    } else if account_type == AccountType::Individual {
+        DESKTOP_INSTANCE
+    } else {
+        0
+    };
+
+    Ok(SteamId::new(universe.unwrap(), account_type, instance, boxed_account_id.unwrap()))
+}
+
+/// Same rules as `SteamId::parse`: numbers up to `u32::MAX` are 32-bit account ids, larger ones
+/// are checked SteamID64s.
+impl TryFrom<u64> for SteamId {
+    type Error = String;
+
+    fn try_from(id: u64) -> Result<SteamId, String> {
+        SteamId::from_number(id)
+    }
+}
+
+impl From<SteamId> for u64 {
+    fn from(steam_id: SteamId) -> u64 {
+        steam_id.0
+    }
+}
+
+impl FromStr for SteamId {
+    type Err = String;
+
+    fn from_str(id: &str) -> Result<SteamId, String> {
+        SteamId::parse(id)
+    }
+}
+
+/// Converts an id passed to an endpoint, a `SteamId` or a number, reporting an invalid one as
+/// `SteamApiError::InvalidInput`.
+pub(crate) fn to_steam_id(endpoint: &str, id: impl TryInto<SteamId, Error = impl fmt::Display>) -> Result<SteamId, SteamApiError> {
+    id.try_into().map_err(|error| SteamApiError::invalid_input(endpoint, error.to_string().as_str()))
+}
+
+/// `to_steam_id` for every id of a list.
+pub(crate) fn to_steam_ids(endpoint: &str, ids: Vec<impl TryInto<SteamId, Error = impl fmt::Display>>) -> Result<Vec<SteamId>, SteamApiError> {
+    ids.into_iter().map(|id| to_steam_id(endpoint, id)).collect()
+}
+
+/// Reads the SteamID field of a response object, reporting an absent or invalid one as
+/// `SteamApiError::MalformedResponse`.
+pub(crate) fn json_steam_id(endpoint: &str, value: &Value, key: &str) -> Result<SteamId, SteamApiError> {
+    SteamId::parse(json_str(value, key).as_str())
+        .map_err(|message| SteamApiError::malformed_response(endpoint, [key, ": ", message.as_str()].join("").as_str()))
+}
+
+/// Renders the SteamID64, the format the Web API expects.
+impl fmt::Display for SteamId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Serializes the SteamID64 as a string, like Steam does, since JavaScript numbers can't hold it.
+impl Serialize for SteamId {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+/// Deserializes a number or any string `SteamId::parse` accepts. Numbers follow the same rules as
+/// `SteamId::parse`: up to `u32::MAX` they are 32-bit account ids, larger ones are checked SteamID64s.
+impl<'de> Deserialize<'de> for SteamId {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<SteamId, D::Error> {
+        deserializer.deserialize_any(SteamIdVisitor)
+    }
+}
+
+struct SteamIdVisitor;
+
+impl<'de> Visitor<'de> for SteamIdVisitor {
+    type Value = SteamId;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a SteamID64 or account id number, or a SteamID string")
+    }
+
+    fn visit_u64<E: de::Error>(self, id: u64) -> Result<SteamId, E> {
+        SteamId::from_number(id).map_err(E::custom)
+    }
+
+    fn visit_str<E: de::Error>(self, id: &str) -> Result<SteamId, E> {
+        SteamId::parse(id).map_err(E::custom)
+    }
+}
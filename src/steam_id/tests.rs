@@ -0,0 +1,101 @@
+use crate::steam_id::{AccountType, SteamId, Universe};
+use crate::tests::assert_serde_roundtrip;
+
+#[test]
+fn parses_every_format() {
+    let steam_id = SteamId::try_from(76561197960361544).unwrap();
+
+    assert_eq!(steam_id, SteamId::parse("76561197960361544").unwrap());
+    assert_eq!(steam_id, SteamId::parse("95816").unwrap());
+    assert_eq!(steam_id, SteamId::parse("STEAM_0:0:47908").unwrap());
+    assert_eq!(steam_id, SteamId::parse("STEAM_1:0:47908").unwrap());
+    assert_eq!(steam_id, SteamId::parse("[U:1:95816]").unwrap());
+    assert_eq!(steam_id, SteamId::parse("U:1:95816").unwrap());
+    assert_eq!(steam_id, SteamId::parse(" https://steamcommunity.com/profiles/76561197960361544/ ").unwrap());
+    assert_eq!(steam_id, SteamId::parse("steamcommunity.com/profiles/76561197960361544?l=english").unwrap());
+    assert_eq!(steam_id, SteamId::parse("https://steamcommunity.com/profiles/[U:1:95816]").unwrap());
+    assert_eq!(steam_id, "76561197960361544".parse::<SteamId>().unwrap());
+}
+
+#[test]
+fn renders_every_format() {
+    let steam_id = SteamId::from_account_id(95817);
+
+    assert_eq!(76561197960361545, steam_id.get_steam_id64());
+    assert_eq!(76561197960361545, u64::from(steam_id));
+    assert_eq!("76561197960361545", steam_id.to_string());
+    assert_eq!("STEAM_0:1:47908", steam_id.to_steam_id2());
+    assert_eq!("[U:1:95817]", steam_id.to_steam_id3());
+    assert_eq!("https://steamcommunity.com/profiles/76561197960361545", steam_id.get_profile_url());
+}
+
+#[test]
+fn exposes_parts() {
+    let steam_id = SteamId::try_from(76561197960361544).unwrap();
+    assert_eq!(95816, steam_id.get_account_id());
+    assert_eq!(1, steam_id.get_instance());
+    assert_eq!(Some(Universe::Public), steam_id.get_universe());
+    assert_eq!(Some(AccountType::Individual), steam_id.get_account_type());
+
+    let clan = SteamId::parse("[g:1:4]").unwrap();
+    assert_eq!(103582791429521412, clan.get_steam_id64());
+    assert_eq!(0, clan.get_instance());
+    assert_eq!(Some(AccountType::Clan), clan.get_account_type());
+    assert_eq!("[g:1:4]", clan.to_steam_id3());
+
+    let anon_game_server = SteamId::new(Universe::Public, AccountType::AnonGameServer, 456, 123);
+    assert_eq!(anon_game_server, SteamId::parse("[A:1:123:456]").unwrap());
+    assert_eq!("[A:1:123:456]", anon_game_server.to_steam_id3());
+
+    let console_user = SteamId::new(Universe::Public, AccountType::ConsoleUser, 0, 95816);
+    assert_eq!("[i:1:95816]", console_user.to_steam_id3());
+    assert_eq!(console_user, SteamId::parse("[i:1:95816]").unwrap());
+    assert_ne!(AccountType::Invalid.get_letter(), AccountType::ConsoleUser.get_letter());
+}
+
+#[test]
+fn rejects_invalid_ids() {
+    assert!(SteamId::parse("").is_err());
+    assert!(SteamId::parse("0").is_err());
+    assert!(SteamId::parse("gabelogannewell").is_err());
+    assert!(SteamId::parse("-95816").is_err());
+    assert!(SteamId::parse("STEAM_0:2:47908").is_err());
+    assert!(SteamId::parse("STEAM_0:0").is_err());
+    assert!(SteamId::parse("STEAM_9:0:47908").is_err());
+    assert!(SteamId::parse("[X:1:95816]").is_err());
+    assert!(SteamId::parse("[U:0:95816]").is_err());
+    assert!(SteamId::parse("[U:1:95816:2000000]").is_err());
+    assert!(SteamId::parse("https://steamcommunity.com/profiles/").is_err());
+    assert!(SteamId::parse("https://steamcommunity.com/id/gabelogannewell").is_err());
+
+    // universe 0 and account type 0 are the high bits of a plain number above u32::MAX
+    assert!(SteamId::parse("4294967296").is_err());
+    assert!(SteamId::from_steam_id64(0x0010_0001_0001_7648).is_err());
+    assert!(SteamId::from_steam_id64(0x0100_0001_0001_7648).is_err());
+    assert!(SteamId::from_steam_id64(76561197960361544).is_ok());
+}
+
+#[test]
+fn converts_numbers_like_parse() {
+    assert_eq!(SteamId::parse("95816").unwrap(), SteamId::try_from(95816u64).unwrap());
+    assert_eq!(SteamId::parse("76561197960361544").unwrap(), SteamId::try_from(76561197960361544u64).unwrap());
+    assert!(SteamId::try_from(0u64).is_err());
+    assert!(SteamId::try_from(4294967296u64).is_err());
+}
+
+#[test]
+fn serde() {
+    let steam_id = SteamId::try_from(76561197960361544).unwrap();
+    assert_serde_roundtrip(&steam_id);
+    assert_eq!("\"76561197960361544\"", serde_json::to_string(&steam_id).unwrap());
+
+    assert_eq!(steam_id, serde_json::from_str::<SteamId>("76561197960361544").unwrap());
+    assert_eq!(steam_id, serde_json::from_str::<SteamId>("\"[U:1:95816]\"").unwrap());
+    assert!(serde_json::from_str::<SteamId>("\"gabelogannewell\"").is_err());
+
+    // numbers follow the same rules as SteamId::parse
+    assert_eq!(steam_id, serde_json::from_str::<SteamId>("95816").unwrap());
+    assert_eq!(SteamId::parse("95816").unwrap(), serde_json::from_str::<SteamId>("\"95816\"").unwrap());
+    assert!(serde_json::from_str::<SteamId>("0").is_err());
+    assert!(serde_json::from_str::<SteamId>("4294967296").is_err());
+}
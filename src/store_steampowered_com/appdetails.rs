@@ -1,12 +1,12 @@
-use std::fs;
-use std::fs::{File, read_to_string};
-use std::path::Path;
-use std::io::Write;
 use serde_json::Value;
 use serde::Deserialize;
-use crate::util::get_json_filetype;
+use serde::Serialize;
+use crate::client::{get_default_client, SteamClient};
+use crate::error::SteamApiError;
+use crate::util::{get_json_filetype, json_opt_i64, json_opt_str};
 
-#[derive(Deserialize, Debug)]
+/// Sections the store leaves out for some apps, such as `price_overview` on free games, are `None`.
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
 pub struct SteamAppDetails {
     pub app_id: i64,
     pub name: String,
@@ -15,66 +15,66 @@ pub struct SteamAppDetails {
     pub support_info: SupportInfo,
     pub short_description: String,
     pub screenshots: Vec<Screenshot>,
-    pub reviews: String,
+    pub reviews: Option<String>,
     pub required_age: i64,
     pub release_date: ReleaseDate,
-    pub recommendations: Recommendations,
-    pub price_overview: PriceOverview,
+    pub recommendations: Option<Recommendations>,
+    pub price_overview: Option<PriceOverview>,
     pub platforms: Platforms,
     pub pc_requirements: PcRequirements,
     pub mac_requirements: MacRequirements,
     pub linux_requirements: LinuxRequirements,
     pub package_groups: Vec<PackageGroup>,
     pub movies: Vec<Movie>,
-    pub metacritic: Metacritic,
-    pub legal_notice: String,
+    pub metacritic: Option<Metacritic>,
+    pub legal_notice: Option<String>,
     pub is_free: bool,
     pub genres: Vec<Genre>,
-    pub fullgame: FullGame,
-    pub ext_user_account_notice: String,
-    pub drm_notice: String,
+    pub fullgame: Option<FullGame>,
+    pub ext_user_account_notice: Option<String>,
+    pub drm_notice: Option<String>,
     pub detailed_description: String,
     pub header_image: String,
     pub demos: Vec<Demo>,
-    pub controller_support: String,
+    pub controller_support: Option<String>,
     pub content_descriptors: ContentDescriptors,
     pub categories: Vec<Category>,
-    pub website: String,
+    pub website: Option<String>,
     pub background_raw: String,
     pub background: String,
-    pub alternate_appid: String,
+    pub alternate_appid: Option<String>,
     pub about_the_game: String,
-    pub achievements: Achievement,
+    pub achievements: Option<Achievement>,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
 pub struct SupportInfo {
     pub url: String,
     pub email: String,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
 pub struct Screenshot {
     pub path_thumbnail: String,
     pub path_full: String,
     pub id: i64,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
 pub struct ReleaseDate {
     pub date: String,
     pub coming_soon: bool,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
 pub struct Recommendations {
     pub total: i64,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
 pub struct PriceOverview {
-    pub recurring_sub_desc: String,
-    pub recurring_sub: i64,
+    pub recurring_sub_desc: Option<String>,
+    pub recurring_sub: Option<i64>,
     pub initial_formatted: String,
     pub initial: i64,
     pub final_formatted: String,
@@ -83,32 +83,32 @@ pub struct PriceOverview {
     pub currency: String,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
 pub struct Platforms {
     pub windows: bool,
     pub mac: bool,
     pub linux: bool,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
 pub struct PcRequirements {
     pub recommended: String,
     pub minimum: String,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
 pub struct MacRequirements {
     pub recommended: String,
     pub minimum: String,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
 pub struct LinuxRequirements {
     pub recommended: String,
     pub minimum: String,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
 pub struct PackageGroup {
     pub title: String,
     pub selection_text: String,
@@ -120,7 +120,7 @@ pub struct PackageGroup {
     pub subs: Vec<Sub>,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
 pub struct Sub {
     pub price_in_cents_with_discount: i64,
     pub percent_savings_text: String,
@@ -132,7 +132,7 @@ pub struct Sub {
     pub can_get_free_license: String,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
 pub struct Movie {
     pub thumbnail: String,
     pub name: String,
@@ -142,110 +142,153 @@ pub struct Movie {
     pub mp4: Mp4,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
 pub struct Mp4 {
     pub max: String,
     pub _480: String,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
 pub struct Webm {
     pub max: String,
     pub dash: String,
     pub _480: String,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
 pub struct Metacritic {
     pub url: String,
     pub score: i64,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
 pub struct Genre {
     pub id: String,
     pub description: String
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
 pub struct FullGame {
     pub name: String,
     pub appid: String
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
 pub struct Demo {
     pub description: String,
     pub appid: i64
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
 pub struct ContentDescriptors {
-    pub notes: String,
+    pub notes: Option<String>,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
 pub struct Category {
     pub id: i64,
     pub description: String,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
 pub struct Achievement {
     pub total: i64,
     pub highlighted: Vec<Highlight>,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
 pub struct Highlight {
     pub path: String,
     pub name: String,
 }
 
-pub fn get(app_id: i64) -> Result<SteamAppDetails, String> {
-    let api_response_boxed = make_api_call(app_id);
-    if api_response_boxed.is_err() {
-        return Err(api_response_boxed.err().unwrap().to_string());
-    } else {
-        parse_api_call_result(api_response_boxed.unwrap(), app_id)
-    }
+pub fn get(app_id: i64) -> Result<SteamAppDetails, SteamApiError> {
+    get_with_client(get_default_client().as_ref(), app_id)
 }
 
-pub fn get_cached(app_id: i64) -> Result<SteamAppDetails, String> {
-    let filepath = get_resource_filepath(app_id);
+/// Async version of `get`, available with the `async` feature.
+#[cfg(feature = "async")]
+pub async fn get_async(app_id: i64) -> Result<SteamAppDetails, SteamApiError> {
+    get_async_with_client(get_default_client().as_ref(), app_id).await
+}
 
-    let boxed_read = read_to_string(filepath);
-    let is_readable = boxed_read.is_ok();
-    if is_readable {
-        let cached_api_response = boxed_read.unwrap();
-        parse_api_call_result(cached_api_response, app_id)
-    } else {
-        Err("Cached resource not readable. Consider use get call to retrieve data from steam api".to_string())
-    }
+pub fn get_with_client(client: &SteamClient, app_id: i64) -> Result<SteamAppDetails, SteamApiError> {
+    client.fetch_and_cache(
+        get_endpoint().as_str(),
+        get_cache_key(app_id).as_str(),
+        get_api_url(app_id),
+        |response| parse_api_call_result(response, app_id),
+    )
+}
 
+/// Async version of `get_with_client`, available with the `async` feature.
+#[cfg(feature = "async")]
+pub async fn get_async_with_client(client: &SteamClient, app_id: i64) -> Result<SteamAppDetails, SteamApiError> {
+    client.fetch_and_cache_async(
+        get_endpoint().as_str(),
+        get_cache_key(app_id).as_str(),
+        get_api_url(app_id),
+        |response| parse_api_call_result(response, app_id),
+    ).await
 }
 
-pub fn make_api_call(app_id: i64) -> Result<String, String> {
-    let url = get_api_url(app_id);
+/// Retrieves details for the given app id from the local cache, calling Steam when the entry is
+/// missing or older than a week. See `cache::CachePolicy` to change the time to live or the read
+/// mode, e.g. `CacheMode::CacheOnly` never calls Steam.
+pub fn get_cached(app_id: i64) -> Result<SteamAppDetails, SteamApiError> {
+    get_cached_with_client(get_default_client().as_ref(), app_id)
+}
 
-    let boxed_response = minreq::get(url).send();
-    if boxed_response.is_err() {
-        return Err("Operation timed out (API call)".to_string());
-    }
+/// Async version of `get_cached`, available with the `async` feature.
+#[cfg(feature = "async")]
+pub async fn get_cached_async(app_id: i64) -> Result<SteamAppDetails, SteamApiError> {
+    get_cached_async_with_client(get_default_client().as_ref(), app_id).await
+}
 
-    let raw_response : Vec<u8> = boxed_response.unwrap().into_bytes();
+pub fn get_cached_with_client(client: &SteamClient, app_id: i64) -> Result<SteamAppDetails, SteamApiError> {
+    client.get_cached_or_fetch(
+        get_endpoint().as_str(),
+        get_cache_key(app_id).as_str(),
+        |response| parse_api_call_result(response, app_id),
+        || get_with_client(client, app_id),
+    )
+}
 
-    let response_string_boxed = String::from_utf8(raw_response);
-    if response_string_boxed.is_err() {
-        let error_message = response_string_boxed.err().unwrap().to_string();
-        if error_message == "invalid utf-8 sequence of 1 bytes from index 1" {
-            return Err("no response from API".to_string());
-        }
-        return Err("invalid utf-8 sequence".to_string());
-    }
-    let response_string: String = response_string_boxed.unwrap();
+/// Async version of `get_cached_with_client`, available with the `async` feature.
+#[cfg(feature = "async")]
+pub async fn get_cached_async_with_client(client: &SteamClient, app_id: i64) -> Result<SteamAppDetails, SteamApiError> {
+    client.get_cached_or_fetch_async(
+        get_endpoint().as_str(),
+        get_cache_key(app_id).as_str(),
+        |response| parse_api_call_result(response, app_id),
+        get_async_with_client(client, app_id),
+    ).await
+}
+
+pub fn get_endpoint() -> String {
+    "steampowered/appdetails".to_string()
+}
+
+pub fn make_api_call(app_id: i64) -> Result<String, SteamApiError> {
+    make_api_call_with_client(get_default_client().as_ref(), app_id)
+}
 
-    Ok(response_string)
+/// Async version of `make_api_call`, available with the `async` feature.
+#[cfg(feature = "async")]
+pub async fn make_api_call_async(app_id: i64) -> Result<String, SteamApiError> {
+    make_api_call_async_with_client(get_default_client().as_ref(), app_id).await
+}
+
+pub fn make_api_call_with_client(client: &SteamClient, app_id: i64) -> Result<String, SteamApiError> {
+    let url = get_api_url(app_id);
+    client.make_api_call(get_endpoint().as_str(), url)
+}
+
+/// Async version of `make_api_call_with_client`, available with the `async` feature.
+#[cfg(feature = "async")]
+pub async fn make_api_call_async_with_client(client: &SteamClient, app_id: i64) -> Result<String, SteamApiError> {
+    let url = get_api_url(app_id);
+    client.make_api_call_async(get_endpoint().as_str(), url).await
 }
 
 pub fn get_api_url(app_id: i64) -> String {
@@ -253,19 +296,22 @@ pub fn get_api_url(app_id: i64) -> String {
     api_url
 }
 
-pub fn get_resource_filepath(app_id: i64) -> String {
-    let cache_dir = get_cache_dir_path(app_id);
-    let filepath = [
-        cache_dir,
+/// Returns path of the cached resource relative to the cache directory.
+pub fn get_cache_key(app_id: i64) -> String {
+    [
+        get_cache_subdir_path(app_id),
         app_id.to_string(),
         ".".to_string(),
         get_json_filetype(),
-    ].join("");
-    filepath
+    ].join("")
+}
+
+pub fn get_resource_filepath(app_id: i64) -> String {
+    get_default_client().get_resource_filepath(get_cache_key(app_id).as_str())
 }
 
 
-pub fn parse_api_call_result(response_string: String, app_id: i64) -> Result<SteamAppDetails, String> {
+pub fn parse_api_call_result(response_string: String, app_id: i64) -> Result<SteamAppDetails, SteamApiError> {
     let mut steam_app_details = SteamAppDetails {
         app_id: app_id,
         name: "".to_string(),
@@ -278,34 +324,23 @@ pub fn parse_api_call_result(response_string: String, app_id: i64) -> Result<Ste
         short_description: "".to_string(),
         screenshots: vec![],
         detailed_description: "".to_string(),
-        reviews: "".to_string(),
+        reviews: None,
         header_image: "".to_string(),
         demos: vec![],
-        controller_support: "".to_string(),
-        content_descriptors: ContentDescriptors { notes: "".to_string() },
+        controller_support: None,
+        content_descriptors: ContentDescriptors { notes: None },
         categories: vec![],
-        website: "".to_string(),
+        website: None,
         background_raw: "".to_string(),
         background: "".to_string(),
-        alternate_appid: "".to_string(),
+        alternate_appid: None,
         required_age: 0,
         release_date: ReleaseDate {
             date: "".to_string(),
             coming_soon: false
         },
-        recommendations: Recommendations {
-            total: 0
-        },
-        price_overview: PriceOverview {
-            recurring_sub_desc: "".to_string(),
-            recurring_sub: 0,
-            initial_formatted: "".to_string(),
-            initial: 0,
-            final_formatted: "".to_string(),
-            final_price: 0,
-            discount_percent: 0,
-            currency: "".to_string()
-        },
+        recommendations: None,
+        price_overview: None,
         platforms: Platforms {
             windows: false,
             mac: false,
@@ -325,46 +360,34 @@ pub fn parse_api_call_result(response_string: String, app_id: i64) -> Result<Ste
         },
         package_groups: vec![],
         movies: vec![],
-        metacritic: Metacritic {
-            url: "".to_string(),
-            score: 0
-        },
-        legal_notice: "".to_string(),
+        metacritic: None,
+        legal_notice: None,
         is_free: false,
         genres: vec![],
-        fullgame: FullGame {
-            name: "".to_string(),
-            appid: "".to_string()
-        },
-        ext_user_account_notice: "".to_string(),
-        drm_notice: "".to_string(),
+        fullgame: None,
+        ext_user_account_notice: None,
+        drm_notice: None,
         about_the_game: "".to_string(),
-        achievements: Achievement {
-            total: 0, highlighted:
-            vec![]
-        },
+        achievements: None,
     };
 
     if response_string.len() > 0 {
         let boxed_initial_parse = serde_json::from_str(&response_string);
         if boxed_initial_parse.is_err() {
-            return Err(boxed_initial_parse.err().unwrap().to_string());
+            return Err(SteamApiError::malformed_response(get_endpoint().as_str(), boxed_initial_parse.err().unwrap().to_string().as_str()));
         }
         let mut json: Value = boxed_initial_parse.unwrap();
 
         let mut app_details_wrapped = json[app_id.to_string()].take();
 
         let mut is_success = app_details_wrapped["success".to_string()].take();
-        if is_success.take().as_bool().unwrap() == false {
-            return Err("steampowered api returned failed response".to_string());
+        if is_success.take().as_bool() != Some(true) {
+            return Err(SteamApiError::steam_failure(get_endpoint().as_str(), None, "steampowered api returned failed response"));
         }
 
         let mut app_details : Value = app_details_wrapped["data"].take();
 
-        let boxed_website = app_details["website"].take();
-        if boxed_website.as_str().is_some() {
-            steam_app_details.website = boxed_website.as_str().unwrap().to_string();
-        }
+        steam_app_details.website = json_opt_str(&app_details, "website");
 
         let boxed_type = app_details["type"].take();
         if boxed_type.as_str().is_some() {
@@ -398,8 +421,8 @@ pub fn parse_api_call_result(response_string: String, app_id: i64) -> Result<Ste
 
 
             let mut price_overview = PriceOverview {
-                recurring_sub_desc: "".to_string(),
-                recurring_sub: 0,
+                recurring_sub_desc: json_opt_str(&boxed_price_overview, "recurring_sub_desc"),
+                recurring_sub: json_opt_i64(&boxed_price_overview, "recurring_sub"),
                 initial_formatted: "".to_string(),
                 initial: 0,
                 final_formatted: "".to_string(),
@@ -409,12 +432,6 @@ pub fn parse_api_call_result(response_string: String, app_id: i64) -> Result<Ste
             };
 
 
-            let boxed_recurring_sub_desc = price_overview_map.get("recurring_sub_desc");
-            if boxed_recurring_sub_desc.is_some() {
-                price_overview.recurring_sub_desc = boxed_recurring_sub_desc.unwrap().as_str().unwrap().to_string();
-            }
-
-
             let boxed_initial_formatted = price_overview_map.get("initial_formatted");
             if boxed_initial_formatted.is_some() {
                 price_overview.initial_formatted =  boxed_initial_formatted.unwrap().as_str().unwrap().to_string();
@@ -427,12 +444,6 @@ pub fn parse_api_call_result(response_string: String, app_id: i64) -> Result<Ste
             }
 
 
-            let boxed_recurring_sub = price_overview_map.get("recurring_sub");
-            if boxed_recurring_sub.is_some() {
-                price_overview.recurring_sub = boxed_recurring_sub.unwrap().as_i64().unwrap();
-            }
-
-
             let boxed_initial = price_overview_map.get("initial");
             if boxed_initial.is_some() {
                 price_overview.initial = boxed_initial.unwrap().as_i64().unwrap();
@@ -455,7 +466,7 @@ pub fn parse_api_call_result(response_string: String, app_id: i64) -> Result<Ste
                 price_overview.currency = boxed_currency.unwrap().as_str().unwrap().to_string();
             }
 
-            steam_app_details.price_overview = price_overview;
+            steam_app_details.price_overview = Some(price_overview);
         }
 
         let boxed_platforms = app_details["platforms"].take();
@@ -571,7 +582,7 @@ pub fn parse_api_call_result(response_string: String, app_id: i64) -> Result<Ste
                 total
             };
 
-            steam_app_details.recommendations = recommendations;
+            steam_app_details.recommendations = Some(recommendations);
         }
 
         let boxed_release_date = app_details["release_date"].take();
@@ -625,10 +636,7 @@ pub fn parse_api_call_result(response_string: String, app_id: i64) -> Result<Ste
             steam_app_details.screenshots = screenshoot_parsed_list;
         }
 
-        let boxed_reviews = app_details["reviews"].take();
-        if boxed_reviews.as_str().is_some() {
-            steam_app_details.reviews = boxed_reviews.as_str().unwrap().to_string();
-        }
+        steam_app_details.reviews = json_opt_str(&app_details, "reviews");
 
         let boxed_name = app_details["name"].take();
         if boxed_name.as_str().is_some() {
@@ -669,13 +677,10 @@ pub fn parse_api_call_result(response_string: String, app_id: i64) -> Result<Ste
                 metacritic.score = boxed_score.unwrap().as_i64().unwrap();
             }
 
-            steam_app_details.metacritic = metacritic;
+            steam_app_details.metacritic = Some(metacritic);
         }
 
-        let boxed_legal_notice = app_details["legal_notice"].take();
-        if boxed_legal_notice.as_str().is_some() {
-            steam_app_details.legal_notice = boxed_legal_notice.as_str().unwrap().to_string();
-        }
+        steam_app_details.legal_notice = json_opt_str(&app_details, "legal_notice");
 
         let boxed_is_free = app_details["is_free"].take();
         if boxed_is_free.as_bool().is_some() {
@@ -726,18 +731,11 @@ pub fn parse_api_call_result(response_string: String, app_id: i64) -> Result<Ste
                 fullgame.appid = boxed_appid.unwrap().as_str().unwrap().to_string();
             }
 
-            steam_app_details.fullgame = fullgame;
+            steam_app_details.fullgame = Some(fullgame);
         }
 
-        let boxed_ext_user_account_notice = app_details["ext_user_account_notice"].take();
-        if boxed_ext_user_account_notice.as_str().is_some() {
-            steam_app_details.ext_user_account_notice = boxed_ext_user_account_notice.as_str().unwrap().to_string();
-        }
-
-        let boxed_drm_notice = app_details["drm_notice"].take();
-        if boxed_drm_notice.as_str().is_some() {
-            steam_app_details.drm_notice = boxed_drm_notice.as_str().unwrap().to_string();
-        }
+        steam_app_details.ext_user_account_notice = json_opt_str(&app_details, "ext_user_account_notice");
+        steam_app_details.drm_notice = json_opt_str(&app_details, "drm_notice");
 
         let boxed_demos = app_details["demos"].take();
         if boxed_demos.as_array().is_some() {
@@ -766,24 +764,15 @@ pub fn parse_api_call_result(response_string: String, app_id: i64) -> Result<Ste
 
         }
 
-        let boxed_controller_support = app_details["controller_support"].take();
-        if boxed_controller_support.as_str().is_some() {
-            steam_app_details.controller_support = boxed_controller_support.as_str().unwrap().to_string();
-        }
+        steam_app_details.controller_support = json_opt_str(&app_details, "controller_support");
 
         let boxed_content_descriptors = app_details["content_descriptors"].take();
         if boxed_content_descriptors.as_object().is_some() {
             let content_descriptors_json = boxed_content_descriptors.as_object().unwrap();
 
-            let mut content_descriptors = ContentDescriptors{ notes: "".to_string() };
-
-            let boxed_notes = content_descriptors_json.get("notes");
-            if boxed_notes.is_some() {
-                let unwrapped_notes = boxed_notes.unwrap();
-                if unwrapped_notes.as_str().is_some() {
-                    content_descriptors.notes = unwrapped_notes.as_str().unwrap().to_string();
-                }
-            }
+            let content_descriptors = ContentDescriptors {
+                notes: content_descriptors_json.get("notes").and_then(Value::as_str).map(str::to_string)
+            };
             steam_app_details.content_descriptors = content_descriptors;
         }
 
@@ -818,10 +807,7 @@ pub fn parse_api_call_result(response_string: String, app_id: i64) -> Result<Ste
             steam_app_details.background = boxed_background.as_str().unwrap().to_string();
         }
 
-        let boxed_alternate_appid = app_details["alternate_appid"].take();
-        if boxed_alternate_appid.as_str().is_some() {
-            steam_app_details.alternate_appid = boxed_alternate_appid.as_str().unwrap().to_string();
-        }
+        steam_app_details.alternate_appid = json_opt_str(&app_details, "alternate_appid");
 
         let boxed_about_the_game = app_details["about_the_game"].take();
         if boxed_about_the_game.as_str().is_some() {
@@ -829,23 +815,12 @@ pub fn parse_api_call_result(response_string: String, app_id: i64) -> Result<Ste
         }
 
         let boxed_achievements = app_details["achievements"].take();
-        steam_app_details.achievements = parse_achievements(boxed_achievements);
-
-    }
-
-    let filepath = get_resource_filepath(app_id);
+        if boxed_achievements.as_object().is_some() {
+            steam_app_details.achievements = Some(parse_achievements(boxed_achievements));
+        }
 
-    let mut file: File;
-    let directory_exists = Path::new(get_cache_dir_path(app_id).as_str()).is_dir();
-    if !directory_exists {
-        fs::create_dir_all(get_cache_dir_path(app_id)).unwrap();
-        file = File::create(filepath).unwrap();
-    } else {
-        file = File::create(filepath).unwrap();
     }
 
-    file.write_all(response_string.as_ref()).unwrap();
-
     Ok(steam_app_details)
 }
 
@@ -1107,15 +1082,13 @@ pub fn parse_package_groups_subs(package_group: &Value) -> Vec<Sub> {
     sub_list
 }
 
-pub fn get_cache_dir_path(app_id: i64) -> String {
+fn get_cache_subdir_path(app_id: i64) -> String {
     let  interface = "steampowered";
     let  method = "appdetails";
     let number_of_entries_per_bucket = 10000;
     let bucket = app_id / number_of_entries_per_bucket;
 
     [
-        "steam-webapi-cache".to_string(),
-        "/".to_string(),
         interface.to_string(),
         "/".to_string(),
         method.to_string(),
@@ -1125,4 +1098,10 @@ pub fn get_cache_dir_path(app_id: i64) -> String {
         app_id.to_string(),
         "/".to_string()
     ].join("")
-}
\ No newline at end of file
+}
+
+/// Returns the directory holding the cached details of the app inside the default client's cache
+/// directory.
+pub fn get_cache_dir_path(app_id: i64) -> String {
+    [get_default_client().cache_dir().to_string(), "/".to_string(), get_cache_subdir_path(app_id)].join("")
+}
@@ -1,19 +1,41 @@
+use std::fs::{read_dir, read_to_string};
+use std::sync::Arc;
 use crate::store_steampowered_com;
-#[cfg(test)]
+use crate::cache::MemoryCache;
+use crate::client::{get_default_client, SteamClient};
+use crate::transport::FakeTransport;
+
+/// Client answering appdetails requests with the responses in `test/store_steampowered_com/appdetails/`.
+fn get_fake_client() -> SteamClient {
+    let transport = FakeTransport::new();
+
+    for entry in read_dir("test/store_steampowered_com/appdetails").unwrap() {
+        let filepath = entry.unwrap().path();
+        let app_id = filepath.file_stem().unwrap().to_str().unwrap().to_string();
+        let app_details = read_to_string(&filepath).unwrap();
+        let url = ["https://store.steampowered.com/api/appdetails?appids=", app_id.as_str(), "&lang=en"].join("");
+        transport.respond_with(url.as_str(), 200, app_details.as_str());
+    }
+
+    SteamClient::builder()
+        .transport(Arc::new(transport))
+        .cache_store(Arc::new(MemoryCache::new(10)))
+        .build()
+}
 
 #[test]
 fn test_get() {
     let app_id = 570;
-    let app = store_steampowered_com::appdetails::get(app_id).unwrap();
+    let app = store_steampowered_com::appdetails::get_with_client(&get_fake_client(), app_id).unwrap();
 
     assert_eq!(app.name, "Dota 2".to_string());
-    assert_eq!(app.reviews, "“A modern multiplayer masterpiece.”<br>9.5/10 – <a href=\"https://www.destructoid.com/review-dota-2-258506.phtml\" target=\"_blank\" rel=\"noreferrer\"  >Destructoid</a><br><br>“Once you start to learn its secrets, there’s a wild and exciting variety of play here that’s unmatched, even by its peers.”<br>9.4/10 – <a href=\"http://www.ign.com/articles/2013/07/24/dota-2-review\" target=\"_blank\" rel=\"noreferrer\"  >IGN</a><br><br>“Dota 2 is possibly the only competitive free-to-play game that is totally uncompromised by its business model.”<br>90/100 – <a href=\"http://www.pcgamer.com/dota-2-review-2/\" target=\"_blank\" rel=\"noreferrer\"  >PC Gamer</a><br>".to_string());
+    assert_eq!(app.reviews.unwrap(), "“A modern multiplayer masterpiece.”<br>9.5/10 – <a href=\"https://www.destructoid.com/review-dota-2-258506.phtml\" target=\"_blank\" rel=\"noreferrer\"  >Destructoid</a><br><br>“Once you start to learn its secrets, there’s a wild and exciting variety of play here that’s unmatched, even by its peers.”<br>9.4/10 – <a href=\"http://www.ign.com/articles/2013/07/24/dota-2-review\" target=\"_blank\" rel=\"noreferrer\"  >IGN</a><br><br>“Dota 2 is possibly the only competitive free-to-play game that is totally uncompromised by its business model.”<br>90/100 – <a href=\"http://www.pcgamer.com/dota-2-review-2/\" target=\"_blank\" rel=\"noreferrer\"  >PC Gamer</a><br>".to_string());
 }
 
 #[test]
 fn test_get_2210() {
     let app_id = 2210;
-    let app = store_steampowered_com::appdetails::get(app_id).unwrap();
+    let app = store_steampowered_com::appdetails::get_with_client(&get_fake_client(), app_id).unwrap();
 
     assert_eq!(app.required_age, 18);
 }
@@ -21,7 +43,7 @@ fn test_get_2210() {
 #[test]
 fn test_get_220() {
     let app_id = 220;
-    let app = store_steampowered_com::appdetails::get(app_id).unwrap();
+    let app = store_steampowered_com::appdetails::get_with_client(&get_fake_client(), app_id).unwrap();
 
     assert_eq!(app.demos.len(), 1);
     assert_eq!(app.demos.get(0).unwrap().appid, 219);
@@ -31,39 +53,40 @@ fn test_get_220() {
 #[test]
 fn test_get_1070410() {
     let app_id = 1070410;
-    let app = store_steampowered_com::appdetails::get(app_id).unwrap();
-
-    assert_eq!(app.price_overview.recurring_sub_desc, "279₴ at checkout, auto-renewed every 1 month(s) at 169₴.");
-    assert_eq!(app.price_overview.recurring_sub, 414304);
-    assert_eq!(app.price_overview.currency, "UAH");
-    assert_eq!(app.price_overview.final_price, 29900);
-    assert_eq!(app.price_overview.discount_percent, 0);
-    assert_eq!(app.price_overview.initial, 29900);
-    assert_eq!(app.price_overview.initial_formatted, "");
+    let app = store_steampowered_com::appdetails::get_with_client(&get_fake_client(), app_id).unwrap();
+
+    let price_overview = app.price_overview.unwrap();
+    assert_eq!(price_overview.recurring_sub_desc.unwrap(), "279₴ at checkout, auto-renewed every 1 month(s) at 169₴.");
+    assert_eq!(price_overview.recurring_sub, Some(414304));
+    assert_eq!(price_overview.currency, "UAH");
+    assert_eq!(price_overview.final_price, 29900);
+    assert_eq!(price_overview.discount_percent, 0);
+    assert_eq!(price_overview.initial, 29900);
+    assert_eq!(price_overview.initial_formatted, "");
 }
 
 #[test]
 fn test_get_1900() {
     let app_id = 1900;
-    let app = store_steampowered_com::appdetails::get(app_id).unwrap();
+    let app = store_steampowered_com::appdetails::get_with_client(&get_fake_client(), app_id).unwrap();
 
-    assert_eq!(app.price_overview.initial_formatted, "");
+    assert_eq!(app.price_overview.unwrap().initial_formatted, "");
 }
 
 #[test]
 fn test_get_550() {
     let app_id = 550;
-    let app = store_steampowered_com::appdetails::get(app_id).unwrap();
+    let app = store_steampowered_com::appdetails::get_with_client(&get_fake_client(), app_id).unwrap();
 
     assert_eq!(app.pc_requirements.recommended, "<strong>Recommended:</strong><br><ul class=\"bb_ul\"><li><strong>OS:</strong> Windows® 7 32/64-bit / Vista 32/64 / XP<br></li><li><strong>Processor:</strong> Intel core 2 duo 2.4GHz<br></li><li><strong>Memory:</strong> 2 GB RAM<br></li><li><strong>Graphics:</strong> Video Card Shader model 3.0. NVidia 7600, ATI X1600 or better<br></li><li><strong>DirectX:</strong> Version 9.0c<br></li><li><strong>Storage:</strong> 13 GB available space<br></li><li><strong>Sound Card:</strong> DirectX 9.0c compatible sound card</li></ul>");
     assert_eq!(app.pc_requirements.minimum, "<strong>Minimum:</strong><br><ul class=\"bb_ul\"><li><strong>OS:</strong> Windows® 7 32/64-bit / Vista 32/64 / XP<br></li><li><strong>Processor:</strong> Pentium 4 3.0GHz<br></li><li><strong>Memory:</strong> 2 GB RAM<br></li><li><strong>Graphics:</strong> Video card with 128 MB, Shader model 2.0. ATI X800, NVidia 6600 or better<br></li><li><strong>DirectX:</strong> Version 9.0c<br></li><li><strong>Storage:</strong> 13 GB available space<br></li><li><strong>Sound Card:</strong> DirectX 9.0c compatible sound card</li></ul>");
-    assert_eq!(app.content_descriptors.notes, "Left 4 Dead 2 features intense violence, blood and gore (including decapitation or dismemberment of zombie-like creatures) and some strong language.");
+    assert_eq!(app.content_descriptors.notes.unwrap(), "Left 4 Dead 2 features intense violence, blood and gore (including decapitation or dismemberment of zombie-like creatures) and some strong language.");
 }
 
 #[test]
 fn test_get_320() {
     let app_id = 320;
-    let app = store_steampowered_com::appdetails::get(app_id).unwrap();
+    let app = store_steampowered_com::appdetails::get_with_client(&get_fake_client(), app_id).unwrap();
 
     assert_eq!(app.mac_requirements.recommended, "");
     assert_eq!(app.mac_requirements.minimum, "<strong>Minimum: </strong>OS X version Leopard 10.5.8, Snow Leopard 10.6.3, 1GB RAM, NVIDIA GeForce 8 or higher, ATI X1600 or higher, or Intel HD 3000 or higher Mouse, Keyboard, Internet Connection");
@@ -72,7 +95,7 @@ fn test_get_320() {
 #[test]
 fn test_get_cached_730() {
     let app_id = 730;
-    let app = store_steampowered_com::appdetails::get_cached(app_id).unwrap();
+    let app = store_steampowered_com::appdetails::get_cached_with_client(&get_fake_client(), app_id).unwrap();
 
     assert_eq!(app.name, "Counter-Strike: Global Offensive".to_string());
     assert_eq!(app.app_type, "game".to_string());
@@ -94,7 +117,7 @@ fn test_get_cached_730() {
     assert_eq!(app.required_age, 0);
     assert_eq!(app.release_date.coming_soon, false);
     assert_eq!(app.release_date.date, "21 Aug, 2012");
-    assert_eq!(app.recommendations.total, 3458723);
+    assert_eq!(app.recommendations.unwrap().total, 3458723);
     assert_eq!(app.platforms.windows, true);
     assert_eq!(app.platforms.mac, true);
     assert_eq!(app.platforms.linux, true);
@@ -134,8 +157,9 @@ fn test_get_cached_730() {
     assert_eq!(app.movies.get(0).unwrap().mp4.max, "http://cdn.akamai.steamstatic.com/steam/apps/81958/movie_max.mp4?t=1554409259");
 
 
-    assert_eq!(app.metacritic.score, 83);
-    assert_eq!(app.metacritic.url, "https://www.metacritic.com/game/pc/counter-strike-global-offensive?ftag=MCD-06-10aaa1f");
+    let metacritic = app.metacritic.unwrap();
+    assert_eq!(metacritic.score, 83);
+    assert_eq!(metacritic.url, "https://www.metacritic.com/game/pc/counter-strike-global-offensive?ftag=MCD-06-10aaa1f");
 
 
     assert_eq!(app.is_free, true);
@@ -148,7 +172,7 @@ fn test_get_cached_730() {
 
     assert_eq!(app.genres.len(), 2);
 
-    assert_eq!(app.controller_support, "full");
+    assert_eq!(app.controller_support.unwrap(), "full");
 
     assert_eq!(app.categories.get(0).unwrap().description, "Multi-player");
     assert_eq!(app.categories.get(0).unwrap().id, 1);
@@ -160,18 +184,19 @@ fn test_get_cached_730() {
     assert_eq!(app.background_raw, "https://cdn.akamai.steamstatic.com/steam/apps/730/page_bg_generated.jpg?t=1641233427");
     assert_eq!(app.background, "https://cdn.akamai.steamstatic.com/steam/apps/730/page_bg_generated_v6b.jpg?t=1641233427");
 
-    assert_eq!(app.achievements.total, 167);
-    assert_eq!(app.achievements.highlighted.len(), 10);
-    assert_eq!(app.achievements.highlighted.get(0).unwrap().path, "https://cdn.akamai.steamstatic.com/steamcommunity/public/images/apps/730/9f60ea3c56b4ab248ab598bbd62568b953116301.jpg");
-    assert_eq!(app.achievements.highlighted.get(0).unwrap().name, "Someone Set Up Us The Bomb");
-    assert_eq!(app.achievements.highlighted.get(9).unwrap().path, "https://cdn.akamai.steamstatic.com/steamcommunity/public/images/apps/730/648550738f4845f12aa686a1b4ee8c4a51ec2348.jpg");
-    assert_eq!(app.achievements.highlighted.get(9).unwrap().name, "Short Fuse");
+    let achievements = app.achievements.unwrap();
+    assert_eq!(achievements.total, 167);
+    assert_eq!(achievements.highlighted.len(), 10);
+    assert_eq!(achievements.highlighted.first().unwrap().path, "https://cdn.akamai.steamstatic.com/steamcommunity/public/images/apps/730/9f60ea3c56b4ab248ab598bbd62568b953116301.jpg");
+    assert_eq!(achievements.highlighted.first().unwrap().name, "Someone Set Up Us The Bomb");
+    assert_eq!(achievements.highlighted.get(9).unwrap().path, "https://cdn.akamai.steamstatic.com/steamcommunity/public/images/apps/730/648550738f4845f12aa686a1b4ee8c4a51ec2348.jpg");
+    assert_eq!(achievements.highlighted.get(9).unwrap().name, "Short Fuse");
 }
 
 #[test]
 fn test_get_cached_214150() {
     let app_id = 214150;
-    let app = store_steampowered_com::appdetails::get_cached(app_id).unwrap();
+    let app = store_steampowered_com::appdetails::get_cached_with_client(&get_fake_client(), app_id).unwrap();
     assert_eq!(app.package_groups.get(0).unwrap().display_type, "default");
     assert_eq!(app.package_groups.get(0).unwrap().description, "");
 }
@@ -179,37 +204,38 @@ fn test_get_cached_214150() {
 #[test]
 fn test_get_cached_21979() {
     let app_id = 21979;
-    let app = store_steampowered_com::appdetails::get_cached(app_id).unwrap();
-    assert_eq!(app.alternate_appid, "21970");
+    let app = store_steampowered_com::appdetails::get_cached_with_client(&get_fake_client(), app_id).unwrap();
+    assert_eq!(app.alternate_appid.unwrap(), "21970");
 }
 
 #[test]
 fn test_get_cached_219() {
     let app_id = 219;
-    let app = store_steampowered_com::appdetails::get_cached(app_id).unwrap();
-    assert_eq!(app.fullgame.name, "Half-Life 2");
-    assert_eq!(app.fullgame.appid, "220");
+    let app = store_steampowered_com::appdetails::get_cached_with_client(&get_fake_client(), app_id).unwrap();
+    let fullgame = app.fullgame.unwrap();
+    assert_eq!(fullgame.name, "Half-Life 2");
+    assert_eq!(fullgame.appid, "220");
 }
 
 #[test]
 fn test_get_cached_1313() {
     let app_id = 1313;
-    let app = store_steampowered_com::appdetails::get_cached(app_id).unwrap();
-    assert_eq!(app.legal_notice, "SiN: Gold ©2020 Night Dive Studios, Inc.");
+    let app = store_steampowered_com::appdetails::get_cached_with_client(&get_fake_client(), app_id).unwrap();
+    assert_eq!(app.legal_notice.unwrap(), "SiN: Gold ©2020 Night Dive Studios, Inc.");
 }
 
 #[test]
 fn test_get_cached_12210() {
     let app_id = 12210;
-    let app = store_steampowered_com::appdetails::get_cached(app_id).unwrap();
-    assert_eq!(app.ext_user_account_notice, "Rockstar Games Social Club ");
+    let app = store_steampowered_com::appdetails::get_cached_with_client(&get_fake_client(), app_id).unwrap();
+    assert_eq!(app.ext_user_account_notice.unwrap(), "Rockstar Games Social Club ");
 }
 
 #[test]
 fn test_get_cached_7530() {
     let app_id = 7530;
-    let app = store_steampowered_com::appdetails::get_cached(app_id).unwrap();
-    assert_eq!(app.drm_notice, "Reality Pump<br>no machine activation limit");
+    let app = store_steampowered_com::appdetails::get_cached_with_client(&get_fake_client(), app_id).unwrap();
+    assert_eq!(app.drm_notice.unwrap(), "Reality Pump<br>no machine activation limit");
 }
 
 #[test]
@@ -217,7 +243,7 @@ fn test_get_cache_dir_path() {
     let app_id = 730;
     let cache_dir_path = store_steampowered_com::appdetails::get_cache_dir_path(app_id);
 
-    assert_eq!(cache_dir_path, "steam-webapi-cache/steampowered/appdetails/0/730/".to_string());
+    assert_eq!(cache_dir_path, [get_default_client().cache_dir(), "/steampowered/appdetails/0/730/"].join(""));
 }
 
 
@@ -226,7 +252,7 @@ fn test_get_resource_filepath() {
     let app_id = 730;
     let resource_filepath = store_steampowered_com::appdetails::get_resource_filepath(app_id);
 
-    assert_eq!(resource_filepath, "steam-webapi-cache/steampowered/appdetails/0/730/730.json".to_string());
+    assert_eq!(resource_filepath, [get_default_client().cache_dir(), "/steampowered/appdetails/0/730/730.json"].join(""));
 }
 
 #[test]
@@ -234,7 +260,7 @@ fn test_get_resource_filepath_long_number() {
     let app_id = 147730;
     let resource_filepath = store_steampowered_com::appdetails::get_resource_filepath(app_id);
 
-    assert_eq!(resource_filepath, "steam-webapi-cache/steampowered/appdetails/14/147730/147730.json".to_string());
+    assert_eq!(resource_filepath, [get_default_client().cache_dir(), "/steampowered/appdetails/14/147730/147730.json"].join(""));
 }
 
 #[test]
@@ -249,8 +275,8 @@ fn test_get_api_url() {
 
 #[test]
 fn test_make_api_call() {
-    let app_id = 147730;
-    let result = store_steampowered_com::appdetails::make_api_call(app_id);
+    let app_id = 570;
+    let result = store_steampowered_com::appdetails::make_api_call_with_client(&get_fake_client(), app_id);
 
     assert!(result.is_ok());
 
@@ -261,7 +287,7 @@ fn test_make_api_call() {
 #[test]
 fn test_parse_api_call_result() {
     let app_id = 730;
-    let result = store_steampowered_com::appdetails::make_api_call(app_id);
+    let result = store_steampowered_com::appdetails::make_api_call_with_client(&get_fake_client(), app_id);
 
     assert!(result.is_ok());
 
@@ -274,9 +300,9 @@ fn test_parse_api_call_result() {
     let steam_app = boxed_result.unwrap();
     assert_eq!(steam_app.app_id, app_id);
     assert_eq!(steam_app.name, "Counter-Strike: Global Offensive");
-    assert_eq!(steam_app.reviews, "");
+    assert_eq!(steam_app.reviews, None);
     assert_eq!(steam_app.header_image, "https://cdn.akamai.steamstatic.com/steam/apps/730/header.jpg?t=1641233427");
-    assert_eq!(steam_app.website, "http://blog.counter-strike.net/");
+    assert_eq!(steam_app.website.unwrap(), "http://blog.counter-strike.net/");
 
     let description = "Counter-Strike: Global Offensive (CS: GO) expands upon the team-based action gameplay that it pioneered when it was launched 19 years ago.<br />\r\n<br />\r\nCS: GO features new maps, characters, weapons, and game modes, and delivers updated versions of the classic CS content (de_dust2, etc.).<br />\r\n<br />\r\n&quot;Counter-Strike took the gaming industry by surprise when the unlikely MOD became the most played online PC action game in the world almost immediately after its release in August 1999,&quot; said Doug Lombardi at Valve. &quot;For the past 12 years, it has continued to be one of the most-played games in the world, headline competitive gaming tournaments and selling over 25 million units worldwide across the franchise. CS: GO promises to expand on CS' award-winning gameplay and deliver it to gamers on the PC as well as the next gen consoles and the Mac.&quot;";
     assert_eq!(steam_app.detailed_description, description);
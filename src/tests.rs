@@ -1,26 +1,85 @@
-use crate::{convert_32bit_account_id_to_64bit, convert_64bit_account_id_to_32bit, get_app_details, get_app_list, get_cached_app_details, get_cached_app_list};
+use std::fmt::Debug;
+use std::fs::read_to_string;
+use std::sync::Arc;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use crate::{convert_32bit_account_id_to_64bit, convert_64bit_account_id_to_32bit};
+use crate::client::SteamClient;
+use crate::transport::FakeTransport;
+
+fn get_fake_client(test_name: &str) -> SteamClient {
+    let transport = FakeTransport::new();
+
+    let app_list = read_to_string("test/isteam_apps/get_app_list/applist.json").unwrap();
+    transport.respond_with("https://api.steampowered.com/ISteamApps/GetAppList/v2?key=KEY", 200, app_list.as_str());
+
+    for app_id in [440, 570, 730] {
+        let filepath = ["test/store_steampowered_com/appdetails/", app_id.to_string().as_str(), ".json"].join("");
+        let app_details = read_to_string(filepath).unwrap();
+        let url = ["https://store.steampowered.com/api/appdetails?appids=", app_id.to_string().as_str(), "&lang=en"].join("");
+        transport.respond_with(url.as_str(), 200, app_details.as_str());
+    }
+
+    let cache_dir = std::env::temp_dir()
+        .join(["steam-webapi-rust-sdk-", test_name, "-", std::process::id().to_string().as_str()].join(""));
+
+    SteamClient::builder()
+        .api_key("KEY")
+        .cache_dir(cache_dir.to_str().unwrap())
+        .transport(Arc::new(transport))
+        .build()
+}
+
+/// Asserts that `value` comes back unchanged from a serde round trip through JSON.
+pub(crate) fn assert_serde_roundtrip<T: Serialize + DeserializeOwned + PartialEq + Debug>(value: &T) {
+    let json = serde_json::to_string(value).unwrap();
+    let deserialized : T = serde_json::from_str(json.as_str()).unwrap();
+    assert_eq!(value, &deserialized);
+}
+
+/// Builds a `GetMatchHistoryBySequenceNum` response out of the `GetMatchDetails` fixture, one
+/// match per sequence number with `match_id` set to the sequence number plus 10.
+pub(crate) fn get_match_sequence_response(match_seq_nums: Vec<u64>) -> String {
+    let details = read_to_string("test/idota2match_570/get_match_details/1461414523.json").unwrap();
+    let details : serde_json::Value = serde_json::from_str(details.as_str()).unwrap();
+
+    let matches = match_seq_nums.iter()
+        .map(|match_seq_num| {
+            let mut match_value = details["result"].clone();
+            match_value["match_id"] = serde_json::Value::from(match_seq_num + 10);
+            match_value["match_seq_num"] = serde_json::Value::from(*match_seq_num);
+            match_value.to_string()
+        })
+        .collect::<Vec<String>>()
+        .join(",");
+    ["{\"result\":{\"status\":1,\"matches\":[", matches.as_str(), "]}}"].join("")
+}
 
 #[cfg(test)]
 
 #[test]
 fn test_get_app_details() {
+    let client = get_fake_client("test_get_app_details");
     let app_id = 570;
-    let app = get_app_details(app_id).unwrap();
+    let app = client.get_app_details(app_id).unwrap();
 
     assert_eq!(app.name, "Dota 2".to_string());
 }
 
 #[test]
 fn test_get_cached_app_details() {
+    let client = get_fake_client("test_get_cached_app_details");
     let app_id = 730;
-    let app = get_cached_app_details(app_id).unwrap();
+    client.get_app_details(app_id).unwrap();
+    let app = client.get_cached_app_details(app_id).unwrap();
 
     assert_eq!(app.name, "Counter-Strike: Global Offensive".to_string());
 }
 
 #[test]
 fn test_get_app_list() {
-    let boxed_steam_app_list = get_app_list();
+    let client = get_fake_client("test_get_app_list");
+    let boxed_steam_app_list = client.get_app_list();
     assert!(boxed_steam_app_list.is_ok());
 
     let steam_app_list = boxed_steam_app_list.unwrap();
@@ -33,7 +92,8 @@ fn test_get_app_list() {
 
 #[test]
 fn test_get_cached_app_list() {
-    let boxed_steam_app_list = get_cached_app_list();
+    let client = get_fake_client("test_get_cached_app_list");
+    let boxed_steam_app_list = client.get_cached_app_list();
     assert!(boxed_steam_app_list.is_ok());
 
 
@@ -48,14 +108,15 @@ fn test_get_cached_app_list() {
 
 #[test]
 fn test_details_on_cached_resources() {
-    let boxed_steam_app_list = get_cached_app_list();
+    let client = get_fake_client("test_details_on_cached_resources");
+    let boxed_steam_app_list = client.get_cached_app_list();
     assert!(boxed_steam_app_list.is_ok());
 
     let steam_app_list = boxed_steam_app_list.unwrap();
     for steam_app in steam_app_list {
         let app_id = steam_app.appid;
 
-        let boxed_result = get_cached_app_details(app_id);
+        let boxed_result = client.get_cached_app_details(app_id);
         if boxed_result.is_ok() {
             let app_details = boxed_result.unwrap();
             println!("result is ok for {} app id {}", app_details.name, app_details.app_id);
@@ -97,4 +158,24 @@ fn test_steam_64bit_to_32bit_id_conversion_v2() {
 
     let expected_id = 38101599;
     assert_eq!(expected_id, converted);
-}
\ No newline at end of file
+}
+#[test]
+fn test_app_details_serde_roundtrip() {
+    let app_details = read_to_string("test/store_steampowered_com/appdetails/730.json").unwrap();
+    let app = crate::store_steampowered_com::appdetails::parse_api_call_result(app_details, 730).unwrap();
+
+    assert_serde_roundtrip(&app);
+}
+
+#[test]
+fn test_app_details_missing_sections_are_none() {
+    let app_details = read_to_string("test/store_steampowered_com/appdetails/1313.json").unwrap();
+    let app = crate::store_steampowered_com::appdetails::parse_api_call_result(app_details, 1313).unwrap();
+
+    assert!(app.legal_notice.is_some());
+    assert_eq!(None, app.price_overview);
+    assert_eq!(None, app.metacritic);
+    assert_eq!(None, app.fullgame);
+    assert_eq!(None, app.reviews);
+    assert_eq!(None, app.website);
+}
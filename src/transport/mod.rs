@@ -0,0 +1,309 @@
+use std::collections::{HashMap, VecDeque};
+use std::fmt::Debug;
+#[cfg(feature = "async")]
+use std::future::Future;
+#[cfg(feature = "async")]
+use std::pin::Pin;
+use std::sync::Mutex;
+use std::time::Duration;
+use url_build_parse::{build_url, parse_url};
+
+#[cfg(test)]
+mod tests;
+
+/// HTTP GET request sent by the SDK. Steam Web API and store endpoints are all plain GETs.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct HttpRequest {
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    pub timeout: Option<Duration>,
+}
+
+/// HTTP response handed back to the SDK. Header names are lowercased.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct HttpResponse {
+    pub status: u16,
+    pub headers: HashMap<String, String>,
+    pub body: Vec<u8>,
+}
+
+impl HttpRequest {
+    pub fn get(url: &str) -> HttpRequest {
+        HttpRequest {
+            url: url.to_string(),
+            headers: vec![],
+            timeout: None,
+        }
+    }
+
+    /// Returns the value of the header, compared case-insensitively.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.iter()
+            .find(|(header_name, _)| header_name.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+impl HttpResponse {
+    pub fn new(status: u16, body: &str) -> HttpResponse {
+        HttpResponse {
+            status,
+            headers: HashMap::new(),
+            body: body.as_bytes().to_vec(),
+        }
+    }
+
+    pub fn with_header(mut self, name: &str, value: &str) -> HttpResponse {
+        self.headers.insert(name.to_lowercase(), value.to_string());
+        self
+    }
+
+    /// Returns the value of the header, compared case-insensitively.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(name.to_lowercase().as_str()).map(|value| value.as_str())
+    }
+}
+
+/// Sends requests on behalf of `SteamClient`. Every endpoint goes through the client's transport,
+/// so replacing it (see `SteamClientBuilder::transport`) swaps out the network for all of them.
+///
+/// An `Err` means no HTTP response was received at all (DNS failure, refused connection, timeout,
+/// TLS error); non-success statuses are returned as regular responses.
+pub trait Transport: Send + Sync + Debug {
+    fn send(&self, request: &HttpRequest) -> Result<HttpResponse, String>;
+}
+
+/// Default transport, backed by `minreq`. `minreq` takes whole seconds, so timeouts are rounded up
+/// to the next second, and sub-second ones to 1 second.
+#[derive(Clone, Debug, Default)]
+pub struct MinreqTransport;
+
+impl Transport for MinreqTransport {
+    fn send(&self, request: &HttpRequest) -> Result<HttpResponse, String> {
+        let mut minreq_request = minreq::get(request.url.as_str());
+        for (name, value) in request.headers.iter() {
+            minreq_request = minreq_request.with_header(name.as_str(), value.as_str());
+        }
+        if let Some(timeout) = request.timeout {
+            minreq_request = minreq_request.with_timeout(get_timeout_secs(timeout));
+        }
+
+        let boxed_response = minreq_request.send();
+        if boxed_response.is_err() {
+            return Err(boxed_response.err().unwrap().to_string());
+        }
+
+        let response = boxed_response.unwrap();
+        let status = response.status_code as u16;
+        let headers : HashMap<String, String> = response.headers.iter()
+            .map(|(name, value)| (name.to_lowercase(), value.to_string()))
+            .collect();
+
+        Ok(HttpResponse {
+            status,
+            headers,
+            body: response.into_bytes(),
+        })
+    }
+}
+
+/// Rounds `timeout` up to whole seconds, at least 1, so a short timeout never turns into none.
+pub(crate) fn get_timeout_secs(timeout: Duration) -> u64 {
+    let secs = timeout.as_secs() + u64::from(timeout.subsec_nanos() > 0);
+    secs.max(1)
+}
+
+/// Future returned by `AsyncTransport::send`.
+#[cfg(feature = "async")]
+pub type SendFuture<'a> = Pin<Box<dyn Future<Output = Result<HttpResponse, String>> + Send + 'a>>;
+
+/// Async counterpart of `Transport`, used by the `get_async` functions of the `async` feature.
+#[cfg(feature = "async")]
+pub trait AsyncTransport: Send + Sync + Debug {
+    fn send<'a>(&'a self, request: &'a HttpRequest) -> SendFuture<'a>;
+}
+
+/// Default async transport, backed by `reqwest`.
+#[cfg(feature = "async")]
+#[derive(Clone, Debug, Default)]
+pub struct ReqwestTransport {
+    client: reqwest::Client,
+}
+
+#[cfg(feature = "async")]
+impl ReqwestTransport {
+    pub fn new() -> ReqwestTransport {
+        ReqwestTransport::default()
+    }
+
+    /// Uses the given `reqwest::Client`, e.g. to share its connection pool with the application.
+    pub fn with_client(client: reqwest::Client) -> ReqwestTransport {
+        ReqwestTransport { client }
+    }
+}
+
+#[cfg(feature = "async")]
+impl AsyncTransport for ReqwestTransport {
+    fn send<'a>(&'a self, request: &'a HttpRequest) -> SendFuture<'a> {
+        Box::pin(async move {
+            let mut reqwest_request = self.client.get(request.url.as_str());
+            for (name, value) in request.headers.iter() {
+                reqwest_request = reqwest_request.header(name.as_str(), value.as_str());
+            }
+            if let Some(timeout) = request.timeout {
+                reqwest_request = reqwest_request.timeout(timeout);
+            }
+
+            let boxed_response = reqwest_request.send().await;
+            if boxed_response.is_err() {
+                return Err(boxed_response.err().unwrap().to_string());
+            }
+
+            let response = boxed_response.unwrap();
+            let status = response.status().as_u16();
+            let headers : HashMap<String, String> = response.headers().iter()
+                .filter_map(|(name, value)| value.to_str().ok().map(|value| (name.as_str().to_lowercase(), value.to_string())))
+                .collect();
+
+            let boxed_body = response.bytes().await;
+            if boxed_body.is_err() {
+                return Err(boxed_body.err().unwrap().to_string());
+            }
+
+            Ok(HttpResponse {
+                status,
+                headers,
+                body: boxed_body.unwrap().to_vec(),
+            })
+        })
+    }
+}
+
+/// In-memory transport returning canned responses keyed by url, for tests that can't reach the
+/// network. Query parameter order doesn't matter when matching urls. Requests without a canned
+/// response fail as transport errors. Every request sent is recorded and available via `requests`.
+/// Implements `AsyncTransport` as well, under the `async` feature.
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::Arc;
+/// use steam_webapi_rust_sdk::client::SteamClient;
+/// use steam_webapi_rust_sdk::transport::FakeTransport;
+///
+/// let transport = Arc::new(FakeTransport::new());
+/// transport.respond_with(
+///     "https://api.steampowered.com/ISteamApps/GetAppList/v2?key=KEY",
+///     200,
+///     r#"{"applist":{"apps":[{"appid":570,"name":"Dota 2"}]}}"#,
+/// );
+///
+/// let client = SteamClient::builder()
+///     .api_key("KEY")
+///     .cache_enabled(false)
+///     .transport(transport.clone())
+///     .build();
+///
+/// let steam_app_list = client.get_app_list().unwrap();
+/// assert_eq!("Dota 2", steam_app_list.get(0).unwrap().name);
+/// assert_eq!(1, transport.requests().len());
+/// ```
+#[derive(Debug, Default)]
+pub struct FakeTransport {
+    responses: Mutex<HashMap<String, HttpResponse>>,
+    queued_responses: Mutex<HashMap<String, VecDeque<HttpResponse>>>,
+    requests: Mutex<Vec<HttpRequest>>,
+}
+
+impl FakeTransport {
+    pub fn new() -> FakeTransport {
+        FakeTransport::default()
+    }
+
+    /// Registers the response returned for every request to `url`.
+    pub fn insert(&self, url: &str, response: HttpResponse) {
+        let mut responses = self.responses.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        responses.insert(normalize_url(url), response);
+    }
+
+    /// Registers a response with the given status and body for every request to `url`.
+    pub fn respond_with(&self, url: &str, status: u16, body: &str) {
+        self.insert(url, HttpResponse::new(status, body));
+    }
+
+    /// Queues a response returned once for a request to `url`. Queued responses are returned in
+    /// order before falling back to the one registered with `insert`.
+    pub fn enqueue(&self, url: &str, response: HttpResponse) {
+        let mut queued_responses = self.queued_responses.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        queued_responses.entry(normalize_url(url)).or_default().push_back(response);
+    }
+
+    /// Returns the requests sent so far, oldest first.
+    pub fn requests(&self) -> Vec<HttpRequest> {
+        let requests = self.requests.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        requests.clone()
+    }
+}
+
+impl Transport for FakeTransport {
+    fn send(&self, request: &HttpRequest) -> Result<HttpResponse, String> {
+        {
+            let mut requests = self.requests.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+            requests.push(request.clone());
+        }
+
+        let url = normalize_url(request.url.as_str());
+        {
+            let mut queued_responses = self.queued_responses.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+            let boxed_queued_response = queued_responses.get_mut(url.as_str()).and_then(|queue| queue.pop_front());
+            if let Some(queued_response) = boxed_queued_response {
+                return Ok(queued_response);
+            }
+        }
+
+        let responses = self.responses.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let boxed_response = responses.get(url.as_str());
+        if boxed_response.is_none() {
+            return Err(["no canned response for ", request.url.as_str()].join(""));
+        }
+
+        Ok(boxed_response.unwrap().clone())
+    }
+}
+
+#[cfg(feature = "async")]
+impl AsyncTransport for FakeTransport {
+    fn send<'a>(&'a self, request: &'a HttpRequest) -> SendFuture<'a> {
+        Box::pin(async move { Transport::send(self, request) })
+    }
+}
+
+/// Rebuilds the url with its query parameters sorted by name, so urls built from a `HashMap` of
+/// parameters compare equal regardless of iteration order.
+pub fn normalize_url(url: &str) -> String {
+    let boxed_components = parse_url(url);
+    if boxed_components.is_err() {
+        return url.to_string();
+    }
+    let mut components = boxed_components.unwrap();
+
+    let query = components.query.take();
+    let boxed_url = build_url(components);
+    if boxed_url.is_err() {
+        return url.to_string();
+    }
+    let url_without_query = boxed_url.unwrap();
+
+    if query.is_none() {
+        return url_without_query;
+    }
+
+    let mut params : Vec<(String, String)> = query.unwrap().into_iter().collect();
+    params.sort();
+    let query_string = params.iter()
+        .map(|(name, value)| [name.as_str(), "=", value.as_str()].join(""))
+        .collect::<Vec<String>>()
+        .join("&");
+
+    [url_without_query, "?".to_string(), query_string].join("")
+}
@@ -0,0 +1,128 @@
+use std::sync::Arc;
+use std::time::Duration;
+use crate::client::SteamClient;
+use crate::error::SteamApiError;
+use crate::retry::RetryPolicy;
+use crate::transport::{get_timeout_secs, normalize_url, FakeTransport, HttpRequest, HttpResponse, Transport};
+
+#[test]
+fn normalize_url_sorts_query_params() {
+    let url = normalize_url("https://api.steampowered.com/ISteamUser/GetFriendList/v1?steamid=76561197960434622&relationship=friend&key=KEY");
+
+    assert_eq!("https://api.steampowered.com/ISteamUser/GetFriendList/v1?key=KEY&relationship=friend&steamid=76561197960434622", url);
+}
+
+#[test]
+fn normalize_url_without_query() {
+    let url = normalize_url("https://store.steampowered.com/api/appdetails");
+
+    assert_eq!("https://store.steampowered.com/api/appdetails", url);
+}
+
+#[test]
+fn minreq_timeout_rounded_up_to_seconds() {
+    assert_eq!(1, get_timeout_secs(Duration::from_millis(1)));
+    assert_eq!(1, get_timeout_secs(Duration::from_millis(500)));
+    assert_eq!(1, get_timeout_secs(Duration::ZERO));
+    assert_eq!(5, get_timeout_secs(Duration::from_secs(5)));
+    assert_eq!(6, get_timeout_secs(Duration::from_millis(5_001)));
+}
+
+#[test]
+fn fake_transport_returns_canned_response() {
+    let transport = FakeTransport::new();
+    transport.insert(
+        "https://store.steampowered.com/api/appdetails?appids=570&lang=en",
+        HttpResponse::new(200, "{}").with_header("Content-Type", "application/json"),
+    );
+
+    let mut request = HttpRequest::get("https://store.steampowered.com/api/appdetails?lang=en&appids=570");
+    request.headers.push(("User-Agent".to_string(), "my-app/1.0".to_string()));
+    let response = transport.send(&request).unwrap();
+
+    assert_eq!(200, response.status);
+    assert_eq!("{}".as_bytes(), response.body.as_slice());
+    assert_eq!(Some("application/json"), response.header("content-type"));
+
+    let requests = transport.requests();
+    assert_eq!(1, requests.len());
+    assert_eq!(Some("my-app/1.0"), requests.first().unwrap().header("user-agent"));
+}
+
+#[test]
+fn fake_transport_fails_unknown_url() {
+    let transport = FakeTransport::new();
+
+    let boxed_response = transport.send(&HttpRequest::get("https://store.steampowered.com/api/appdetails?appids=570"));
+
+    assert_eq!("no canned response for https://store.steampowered.com/api/appdetails?appids=570", boxed_response.err().unwrap());
+}
+
+#[test]
+fn client_sends_through_transport() {
+    let transport = Arc::new(FakeTransport::new());
+    transport.respond_with(
+        "https://api.steampowered.com/ISteamUser/GetPlayerBans/v1?key=KEY&steamids=76561197960434622",
+        200,
+        r#"{"players":[{"SteamId":"76561197960434622","CommunityBanned":false,"VACBanned":true,"NumberOfVACBans":1,"DaysSinceLastBan":1000,"NumberOfGameBans":0,"EconomyBan":"none"}]}"#,
+    );
+
+    let client = SteamClient::builder()
+        .api_key("KEY")
+        .user_agent("my-app/1.0")
+        .timeout(Duration::from_secs(5))
+        .transport(transport.clone())
+        .build();
+
+    let player_bans = client.get_player_bans(vec![76561197960434622]).unwrap();
+    assert_eq!(1, player_bans.len());
+
+    let requests = transport.requests();
+    let request = requests.first().unwrap();
+    assert_eq!(Some("my-app/1.0"), request.header("User-Agent"));
+    assert_eq!(Some(Duration::from_secs(5)), request.timeout);
+}
+
+#[test]
+fn client_reports_http_status() {
+    let transport = Arc::new(FakeTransport::new());
+    transport.respond_with(
+        "https://api.steampowered.com/ISteamUser/GetPlayerBans/v1?key=KEY&steamids=76561197960434622",
+        403,
+        "<html><body>Forbidden</body></html>",
+    );
+
+    let client = SteamClient::builder()
+        .api_key("KEY")
+        .transport(transport)
+        .build();
+
+    let error = client.get_player_bans(vec![76561197960434622]).err().unwrap();
+    assert_eq!(Some(403), error.http_status_code());
+}
+
+#[test]
+fn client_reports_transport_failure() {
+    let transport = Arc::new(FakeTransport::new());
+    let client = SteamClient::builder()
+        .api_key("KEY")
+        .transport(transport.clone())
+        .retry_policy(RetryPolicy { max_retries: 2, base_delay: Duration::from_millis(1), max_delay: Duration::from_millis(10), jitter: true })
+        .build();
+
+    let error = client.get_player_bans(vec![76561197960434622]).err().unwrap();
+    assert!(matches!(error, SteamApiError::Transport { .. }));
+    assert_eq!(3, transport.requests().len());
+}
+
+#[test]
+fn fake_transport_returns_queued_responses_first() {
+    let transport = FakeTransport::new();
+    let url = "https://store.steampowered.com/api/appdetails?appids=570&lang=en";
+    transport.respond_with(url, 200, "{}");
+    transport.enqueue(url, HttpResponse::new(503, ""));
+
+    assert_eq!(503, transport.send(&HttpRequest::get(url)).unwrap().status);
+    assert_eq!(200, transport.send(&HttpRequest::get(url)).unwrap().status);
+    assert_eq!(200, transport.send(&HttpRequest::get(url)).unwrap().status);
+}
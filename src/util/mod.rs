@@ -1,38 +1,143 @@
 use std::collections::HashMap;
 use std::env;
+use std::path::Path;
 use std::time::{SystemTime, UNIX_EPOCH};
 use serde_json::Value;
 use url_build_parse::{build_url, UrlAuthority, UrlComponents};
 
-pub fn get_steam_web_api_key() -> String {
-    let boxed_steam_web_api_key = env::var("STEAM_WEBAPI_KEY");
-    if boxed_steam_web_api_key.is_err() {
-        println!("To use this SDK please specify STEAM_WEBAPI_KEY environment variable");
-        return "".to_string();
-    }
-    let steam_web_api_key = boxed_steam_web_api_key.unwrap();
-    let _key = ["STEAM_WEB_API_KEY is ", &steam_web_api_key].join("");
+#[cfg(test)]
+mod tests;
 
-    return steam_web_api_key;
+/// Returns the key from the `STEAM_WEBAPI_KEY` environment variable, or an empty string if it isn't set.
+pub fn get_steam_web_api_key() -> String {
+    env::var("STEAM_WEBAPI_KEY").unwrap_or_default()
 }
 
+/// Builds a `https://api.steampowered.com/<interface>/<method>/<version>` URL. The key is added by
+/// `SteamClient` when the request is sent.
 pub fn build_api_url(interface: &str, method: &str, version: &str, _parameters: HashMap<String, String>) -> String {
     let slash_separator = "/";
-    let parameters_start = "?";
-    let parameter_equals = "=";
-    let key_parameter = "key";
 
     let steam_api_url = "https://api.steampowered.com";
 
-    let steam_web_api_key = get_steam_web_api_key();
-
-    let url = [steam_api_url, slash_separator, interface, slash_separator, method, slash_separator, version, parameters_start, key_parameter, parameter_equals, &steam_web_api_key].join("");
+    let url = [steam_api_url, slash_separator, interface, slash_separator, method, slash_separator, version].join("");
 
     return url
 }
 
+/// Environment variable overriding the cache directory.
+pub const CACHE_DIR_ENV_VAR: &str = "STEAM_WEBAPI_CACHE_DIR";
+/// Name of the cache directory the SDK used to create in the working directory.
+pub const LEGACY_CACHE_DIR_NAME: &str = "steam-webapi-cache";
+/// Name of the cache directory inside the user cache directory, e.g. `~/.cache/steam-webapi-rust-sdk`.
+pub const USER_CACHE_DIR_NAME: &str = "steam-webapi-rust-sdk";
+
+/// Returns the cache directory used unless `SteamClientBuilder::cache_dir` says otherwise:
+///
+/// 1. the `STEAM_WEBAPI_CACHE_DIR` environment variable, if set;
+/// 2. `steam-webapi-cache` in the working directory, if it already exists, so caches created by
+///    earlier versions (or the extracted prebuilt cache) keep working;
+/// 3. `steam-webapi-rust-sdk` in the user cache directory: `$XDG_CACHE_HOME` or `~/.cache` on
+///    Linux, `~/Library/Caches` on macOS, `%LOCALAPPDATA%` on Windows;
+/// 4. `steam-webapi-cache` in the working directory if there is no user cache directory either.
 pub fn get_cache_dir_path() -> String {
-    ["steam-webapi-cache".to_string()].join("/")
+    resolve_cache_dir_path(
+        env::var(CACHE_DIR_ENV_VAR).ok(),
+        Path::new(LEGACY_CACHE_DIR_NAME).is_dir(),
+        get_user_cache_dir_path(),
+    )
+}
+
+pub(crate) fn resolve_cache_dir_path(cache_dir: Option<String>, is_legacy_cache_dir_present: bool, user_cache_dir: Option<String>) -> String {
+    if let Some(cache_dir) = cache_dir.filter(|cache_dir| !cache_dir.is_empty()) {
+        return cache_dir;
+    }
+
+    if is_legacy_cache_dir_present {
+        return LEGACY_CACHE_DIR_NAME.to_string();
+    }
+
+    if let Some(user_cache_dir) = user_cache_dir {
+        return [user_cache_dir.as_str(), "/", USER_CACHE_DIR_NAME].join("");
+    }
+
+    LEGACY_CACHE_DIR_NAME.to_string()
+}
+
+fn get_absolute_env_var(name: &str) -> Option<String> {
+    env::var(name).ok().filter(|value| Path::new(value).is_absolute())
+}
+
+#[cfg(target_os = "windows")]
+fn get_user_cache_dir_path() -> Option<String> {
+    get_absolute_env_var("LOCALAPPDATA")
+}
+
+#[cfg(target_os = "macos")]
+fn get_user_cache_dir_path() -> Option<String> {
+    get_absolute_env_var("HOME").map(|home| [home.as_str(), "/Library/Caches"].join(""))
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+fn get_user_cache_dir_path() -> Option<String> {
+    get_absolute_env_var("XDG_CACHE_HOME")
+        .or_else(|| get_absolute_env_var("HOME").map(|home| [home.as_str(), "/.cache"].join("")))
+}
+
+/// Longest parameter part of a cache key kept verbatim; longer ones, e.g. a hundred steam ids,
+/// are replaced by their hash to stay within filename limits.
+pub const MAX_CACHE_KEY_PARAMETERS_LENGTH: usize = 128;
+
+/// Builds the key of a cached Web API response, `<interface>-<method>-<version>-<name>=<value>....json`,
+/// e.g. `IDOTA2Match_570-GetHeroes-v1-language=en.json`. Parameters are named as in the query
+/// string and given in a fixed order; characters that are not safe in a filename are
+/// percent-encoded.
+///
+/// # Examples
+///
+/// ```
+/// use steam_webapi_rust_sdk::util::build_cache_key;
+///
+/// let cache_key = build_cache_key("ISteamUser", "GetFriendList", "v1", vec![
+///     ("steamid", "76561197960435530".to_string()),
+///     ("relationship", "friend".to_string()),
+/// ]);
+///
+/// assert_eq!("ISteamUser-GetFriendList-v1-steamid=76561197960435530-relationship=friend.json", cache_key);
+/// ```
+pub fn build_cache_key(interface: &str, method: &str, version: &str, params: Vec<(&str, String)>) -> String {
+    let mut parameters = params.iter()
+        .map(|(name, value)| ["-", name, "=", encode_cache_key_value(value).as_str()].join(""))
+        .collect::<Vec<String>>()
+        .join("");
+
+    if parameters.len() > MAX_CACHE_KEY_PARAMETERS_LENGTH {
+        parameters = format!("-{:016x}", get_fnv1a_hash(parameters.as_bytes()));
+    }
+
+    [interface, "-", method, "-", version, parameters.as_str(), ".", get_json_filetype().as_str()].join("")
+}
+
+fn encode_cache_key_value(value: &str) -> String {
+    let mut encoded = String::new();
+    for byte in value.bytes() {
+        if byte.is_ascii_alphanumeric() || byte == b'_' || byte == b'.' || byte == b',' {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(format!("%{:02X}", byte).as_str());
+        }
+    }
+    encoded
+}
+
+/// 64-bit FNV-1a, stable across Rust releases unlike `DefaultHasher`, so cache keys are too.
+fn get_fnv1a_hash(bytes: &[u8]) -> u64 {
+    let mut hash : u64 = 0xcbf29ce484222325;
+    for byte in bytes {
+        hash ^= *byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    hash
 }
 
 pub fn as_unix_timestamp(system_time: SystemTime) -> u64 {
@@ -45,7 +150,8 @@ pub fn get_json_filetype() -> String {
     "json".to_string()
 }
 
-/// Builds a `https://api.steampowered.com/<interface>/<method>/<version>?<params>` URL.
+/// Builds a `https://api.steampowered.com/<interface>/<method>/<version>?<params>` URL. The key is
+/// added by `SteamClient` when the request is sent.
 pub fn build_steam_api_url(interface: &str, method: &str, version: &str, params_map: HashMap<String, String>) -> String {
     let path = ["/".to_string(), interface.to_string(), "/".to_string(), method.to_string(), "/".to_string(), version.to_string()].join("");
 
@@ -57,13 +163,33 @@ pub fn build_steam_api_url(interface: &str, method: &str, version: &str, params_
             port: None
         }),
         path,
-        query: Some(params_map),
+        query: if params_map.is_empty() { None } else { Some(params_map) },
         fragment: None
     };
 
     build_url(url_builder).unwrap()
 }
 
+/// Removes the `key` query parameter from a url, so it can be kept in errors and logs without
+/// exposing the Steam Web API key. Other parameters are left untouched.
+pub fn strip_api_key(url: &str) -> String {
+    let query_start = url.find('?');
+    if query_start.is_none() {
+        return url.to_string();
+    }
+    let (path, query) = url.split_at(query_start.unwrap());
+
+    let params: Vec<&str> = query[1..]
+        .split('&')
+        .filter(|param| *param != "key" && !param.starts_with("key="))
+        .collect();
+
+    if params.is_empty() {
+        return path.to_string();
+    }
+    [path, "?", &params.join("&")].join("")
+}
+
 /// Reads an unsigned integer field from a JSON object, defaulting to 0 if absent or the wrong type.
 pub fn json_u64(value: &Value, key: &str) -> u64 {
     value.get(key).and_then(Value::as_u64).unwrap_or(0)
@@ -87,4 +213,31 @@ pub fn json_str(value: &Value, key: &str) -> String {
 /// Reads a boolean field from a JSON object, defaulting to false if absent or the wrong type.
 pub fn json_bool(value: &Value, key: &str) -> bool {
     value.get(key).and_then(Value::as_bool).unwrap_or(false)
-}
\ No newline at end of file
+}
+
+/// Reads an optional unsigned integer field from a JSON object, `None` if absent or the wrong type.
+pub fn json_opt_u64(value: &Value, key: &str) -> Option<u64> {
+    value.get(key).and_then(Value::as_u64)
+}
+
+/// Reads an optional signed integer field from a JSON object, `None` if absent or the wrong type.
+pub fn json_opt_i64(value: &Value, key: &str) -> Option<i64> {
+    value.get(key).and_then(Value::as_i64)
+}
+
+/// Reads an optional string field from a JSON object, `None` if absent or the wrong type.
+pub fn json_opt_str(value: &Value, key: &str) -> Option<String> {
+    value.get(key).and_then(Value::as_str).map(str::to_string)
+}
+
+/// Reads an optional array field from a JSON object, `None` if absent or the wrong type.
+pub fn json_opt_array<'a>(value: &'a Value, key: &str) -> Option<&'a Vec<Value>> {
+    value.get(key).and_then(Value::as_array)
+}
+
+/// Reads an optional boolean field from a JSON object, `None` if absent or the wrong type.
+/// Steam sends some flags as `0`/`1`, so integers are accepted as well.
+pub fn json_opt_bool(value: &Value, key: &str) -> Option<bool> {
+    let field = value.get(key)?;
+    field.as_bool().or_else(|| field.as_u64().map(|flag| flag == 1))
+}
@@ -0,0 +1,67 @@
+use serde_json::json;
+use crate::util::{json_opt_array, json_opt_bool, json_opt_str, json_opt_u64, json_u64, resolve_cache_dir_path, strip_api_key, LEGACY_CACHE_DIR_NAME};
+
+#[test]
+fn cache_dir_from_env_var() {
+    let cache_dir = resolve_cache_dir_path(Some("/srv/steam-cache".to_string()), true, Some("/home/steam/.cache".to_string()));
+
+    assert_eq!("/srv/steam-cache", cache_dir);
+}
+
+#[test]
+fn cache_dir_ignores_empty_env_var() {
+    let cache_dir = resolve_cache_dir_path(Some("".to_string()), false, Some("/home/steam/.cache".to_string()));
+
+    assert_eq!("/home/steam/.cache/steam-webapi-rust-sdk", cache_dir);
+}
+
+#[test]
+fn cache_dir_keeps_existing_legacy_dir() {
+    let cache_dir = resolve_cache_dir_path(None, true, Some("/home/steam/.cache".to_string()));
+
+    assert_eq!(LEGACY_CACHE_DIR_NAME, cache_dir);
+}
+
+#[test]
+fn cache_dir_in_user_cache_dir() {
+    let cache_dir = resolve_cache_dir_path(None, false, Some("/home/steam/.cache".to_string()));
+
+    assert_eq!("/home/steam/.cache/steam-webapi-rust-sdk", cache_dir);
+}
+
+#[test]
+fn cache_dir_without_user_cache_dir() {
+    let cache_dir = resolve_cache_dir_path(None, false, None);
+
+    assert_eq!("steam-webapi-cache", cache_dir);
+}
+
+#[test]
+fn optional_json_fields() {
+    let value = json!({"count": 0, "name": "", "flag": 1, "public": false, "wrong": "1"});
+
+    assert_eq!(Some(0), json_opt_u64(&value, "count"));
+    assert_eq!(None, json_opt_u64(&value, "missing"));
+    assert_eq!(None, json_opt_u64(&value, "wrong"));
+    assert_eq!(0, json_u64(&value, "missing"));
+
+    assert_eq!(Some("".to_string()), json_opt_str(&value, "name"));
+    assert_eq!(None, json_opt_str(&value, "count"));
+
+    assert_eq!(Some(true), json_opt_bool(&value, "flag"));
+    assert_eq!(Some(false), json_opt_bool(&value, "public"));
+    assert_eq!(None, json_opt_bool(&value, "missing"));
+
+    let value = json!({"players": [{"hero_id": 1}], "count": 0});
+    assert_eq!(1, json_opt_array(&value, "players").unwrap().len());
+    assert_eq!(None, json_opt_array(&value, "count"));
+    assert_eq!(None, json_opt_array(&value, "missing"));
+}
+
+#[test]
+fn api_key_stripped_from_url() {
+    assert_eq!("https://api.steampowered.com/ISteamApps/GetAppList/v2", strip_api_key("https://api.steampowered.com/ISteamApps/GetAppList/v2?key=SECRET"));
+    assert_eq!("https://api.steampowered.com/ISteamUser/GetFriendList/v1?steamid=1&relationship=friend", strip_api_key("https://api.steampowered.com/ISteamUser/GetFriendList/v1?steamid=1&key=SECRET&relationship=friend"));
+    assert_eq!("https://store.steampowered.com/api/appdetails?appids=730&keywords=a", strip_api_key("https://store.steampowered.com/api/appdetails?appids=730&keywords=a"));
+    assert_eq!("steam-webapi-cache/a.json", strip_api_key("steam-webapi-cache/a.json"));
+}